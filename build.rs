@@ -0,0 +1,30 @@
+//! Generates the lookup tables used by the evaluator. See `src/lookups/generator.rs`.
+
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+
+#[path = "src/lookups/generator.rs"]
+mod generator;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/lookups/generator.rs");
+
+    let out_dir = env::var_os("OUT_DIR").expect("OUT_DIR is set by cargo");
+    let out_dir = Path::new(&out_dir);
+    let (products, values) = generator::products_and_values();
+
+    write_table(out_dir, "flushes.rs", &generator::flushes());
+    write_table(out_dir, "unique5.rs", &generator::unique5());
+    write_table(out_dir, "products.rs", &products);
+    write_table(out_dir, "values.rs", &values);
+    write_table(out_dir, "primes.rs", &generator::PRIMES);
+}
+
+fn write_table<T: Display>(out_dir: &Path, name: &str, table: &[T]) {
+    let entries: Vec<String> = table.iter().map(ToString::to_string).collect();
+    let contents = format!("[{}]\n", entries.join(", "));
+    fs::write(out_dir.join(name), contents).expect("unable to write lookup table");
+}
@@ -0,0 +1,45 @@
+/* Declarations for the C ABI of ckc-rs, exported with the ckc_ffi feature. */
+/* Regenerate with: cbindgen --config cbindgen.toml --output include/ckc.h */
+
+#ifndef CKC_H
+#define CKC_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Returns the `HandRankValue` of five cards, or zero if they aren't five valid, distinct cards.
+ *
+ * # Safety
+ *
+ * `cards` must be null or point to five readable `u32`s.
+ */
+uint16_t ckc_eval5(const uint32_t *cards);
+
+/**
+ * Returns the `HandRankValue` of the best five of seven cards, or zero if they aren't seven
+ * valid, distinct cards.
+ *
+ * # Safety
+ *
+ * `cards` must be null or point to seven readable `u32`s.
+ */
+uint16_t ckc_eval7(const uint32_t *cards);
+
+/**
+ * Returns the card for an index such as `"AS"` or `"A♠"`, or zero if it isn't one.
+ *
+ * # Safety
+ *
+ * `index` must be null or point to a nul terminated string.
+ */
+uint32_t ckc_parse_card(const char *index);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* CKC_H */
@@ -0,0 +1,191 @@
+use crate::analysis::equity::Tally;
+use crate::analysis::range::Range;
+use crate::cards::binary_card::{BinaryCard, BC64};
+use crate::cards::five::Five;
+use crate::cards::seven::Seven;
+use crate::cards::two::Two;
+use crate::cards::{HandRanker, HandValidator};
+use crate::HandError;
+use alloc::collections::BTreeSet;
+
+/// What calling a river bet with a bluff catcher is worth.
+///
+/// * `value_combos` and `bluff_combos` are the villain's combos in each range that are live
+///   against hero's hand and the board.
+/// * `equity` is hero's share of the pot at showdown against all of them.
+/// * `pot_odds` is the equity that hero needs for calling to break even.
+/// * `ev` is the expected value of calling, in the same units as the bet and the pot, where
+///   folding is worth zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BluffCatch {
+    pub value_combos: usize,
+    pub bluff_combos: usize,
+    pub equity: f64,
+    pub pot_odds: f64,
+    pub ev: f64,
+}
+
+impl BluffCatch {
+    /// Returns the share of the villain's live combos that are bluffs.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn bluff_frequency(&self) -> f64 {
+        let combos = self.value_combos + self.bluff_combos;
+        if combos == 0 {
+            return 0.0;
+        }
+        self.bluff_combos as f64 / combos as f64
+    }
+
+    #[must_use]
+    pub fn is_profitable(&self) -> bool {
+        self.ev > 0.0
+    }
+}
+
+/// Returns the value of calling a river `bet` into a `pot` with hero's hand, when the villain
+/// bets with every combo in `villain_value` and `villain_bluffs`. The `pot` is its size before
+/// the bet, and each live combo is weighted equally. A combo in both ranges is only counted
+/// once, as value.
+///
+/// # Errors
+///
+/// Returns `HandError::Incomplete`, `HandError::BlankCard`, `HandError::InvalidCard` or
+/// `HandError::DuplicateCard` if hero's hand and the board aren't seven valid, distinct cards, and
+/// `HandError::NotEnoughCards` if neither of the villain's ranges has a live combo.
+#[allow(clippy::cast_precision_loss)]
+pub fn bluff_catch_value(
+    hero: Two,
+    board: Five,
+    villain_value: &Range,
+    villain_bluffs: &Range,
+    bet: f64,
+    pot: f64,
+) -> Result<BluffCatch, HandError> {
+    let hand = Seven::from_two_and_five(hero, board)?;
+    let hero_value = hand.hand_rank_value();
+    let dead = BinaryCard::from_seven(hand);
+
+    let mut tally = Tally::default();
+    let mut seen = BTreeSet::new();
+    let mut live = |range: &Range| {
+        range
+            .live(dead)
+            .filter(|combo| seen.insert(combo.id()))
+            .fold(0, |combos, combo| {
+                tally.record(hero_value, Seven::new(*combo, board).hand_rank_value());
+                combos + 1
+            })
+    };
+    let value_combos = live(villain_value);
+    let bluff_combos = live(villain_bluffs);
+
+    if tally.total == 0 {
+        return Err(HandError::NotEnoughCards);
+    }
+
+    let total = tally.total as f64;
+    let wins = tally.wins as f64 / total;
+    let ties = tally.ties as f64 / total;
+    let losses = 1.0 - wins - ties;
+
+    Ok(BluffCatch {
+        value_combos,
+        bluff_combos,
+        equity: tally.equity(),
+        pot_odds: bet / (pot + 2.0 * bet),
+        // A chop returns hero's call and half of the pot.
+        ev: wins * (pot + bet) + ties * pot / 2.0 - losses * bet,
+    })
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__bluff_tests {
+    use super::*;
+
+    fn range(combos: &[&'static str]) -> Range {
+        combos.iter().map(|combo| Two::try_from(*combo).unwrap()).collect()
+    }
+
+    #[test]
+    fn bluff_catch_value() {
+        let hero = Two::try_from("K♠ J♦").unwrap();
+        let board = Five::try_from("K♥ 8♣ 4♦ 2♠ 9♥").unwrap();
+        let value = range(&["A♠ K♦", "9♠ 9♣"]);
+        let bluffs = range(&["Q♥ J♥", "7♠ 6♠"]);
+
+        let catch = super::bluff_catch_value(hero, board, &value, &bluffs, 50.0, 100.0).unwrap();
+
+        assert_eq!(2, catch.value_combos);
+        assert_eq!(2, catch.bluff_combos);
+        assert!((catch.equity - 0.5).abs() < f64::EPSILON);
+        assert!((catch.pot_odds - 0.25).abs() < f64::EPSILON);
+        assert!((catch.ev - 50.0).abs() < f64::EPSILON);
+        assert!((catch.bluff_frequency() - 0.5).abs() < f64::EPSILON);
+        assert!(catch.is_profitable());
+    }
+
+    #[test]
+    fn bluff_catch_value__value_heavy() {
+        let hero = Two::try_from("K♠ J♦").unwrap();
+        let board = Five::try_from("K♥ 8♣ 4♦ 2♠ 9♥").unwrap();
+        let value = range(&["A♠ K♦", "9♠ 9♣", "8♠ 8♦", "4♠ 4♥"]);
+        let bluffs = range(&["Q♥ J♥"]);
+
+        let catch = super::bluff_catch_value(hero, board, &value, &bluffs, 100.0, 100.0).unwrap();
+
+        assert!((catch.ev - -40.0).abs() < f64::EPSILON);
+        assert!(!catch.is_profitable());
+    }
+
+    #[test]
+    fn bluff_catch_value__chop() {
+        let hero = Two::try_from("K♠ J♦").unwrap();
+        let board = Five::try_from("K♥ 8♣ 4♦ 2♠ 9♥").unwrap();
+        let value = range(&["K♣ J♠"]);
+
+        let catch = super::bluff_catch_value(hero, board, &value, &Range::new(), 50.0, 100.0).unwrap();
+
+        assert!((catch.equity - 0.5).abs() < f64::EPSILON);
+        assert!((catch.ev - 50.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn bluff_catch_value__in_both_ranges() {
+        let hero = Two::try_from("K♠ J♦").unwrap();
+        let board = Five::try_from("K♥ 8♣ 4♦ 2♠ 9♥").unwrap();
+        let value = range(&["A♠ K♦", "9♠ 9♣"]);
+        let bluffs = range(&["K♦ A♠", "Q♥ J♥", "7♠ 6♠"]);
+
+        let catch = super::bluff_catch_value(hero, board, &value, &bluffs, 50.0, 100.0).unwrap();
+
+        assert_eq!(2, catch.value_combos);
+        assert_eq!(2, catch.bluff_combos);
+        assert!((catch.equity - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn bluff_catch_value__dead_combos() {
+        let hero = Two::try_from("K♠ J♦").unwrap();
+        let board = Five::try_from("K♥ 8♣ 4♦ 2♠ 9♥").unwrap();
+        let value = range(&["K♠ K♦"]);
+
+        assert_eq!(
+            HandError::NotEnoughCards,
+            super::bluff_catch_value(hero, board, &value, &Range::new(), 50.0, 100.0).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn bluff_catch_value__duplicate() {
+        let hero = Two::try_from("K♥ J♦").unwrap();
+        let board = Five::try_from("K♥ 8♣ 4♦ 2♠ 9♥").unwrap();
+        let value = range(&["A♠ K♦"]);
+
+        assert_eq!(
+            HandError::DuplicateCard,
+            super::bluff_catch_value(hero, board, &value, &Range::new(), 50.0, 100.0).unwrap_err()
+        );
+    }
+}
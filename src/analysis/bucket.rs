@@ -0,0 +1,264 @@
+use crate::analysis::range::Range;
+use crate::cards::binary_card::{BinaryCard, BC64};
+use crate::cards::two::Two;
+use crate::cards::HandValidator;
+use crate::evaluate::best_five_of;
+use crate::hand_rank::HandRankValue;
+use crate::{CKCNumber, HandError};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// How `bucket_values()` divides values into buckets.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Bucketing {
+    /// Buckets of as close to equal size as possible, without splitting equal values.
+    Percentile,
+    /// One dimensional k-means clustering, stopping after the passed in number of iterations if
+    /// it hasn't already settled.
+    KMeans(usize),
+}
+
+/// A hole card combination with its hand strength on the board and the bucket it falls into.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HandBucket {
+    pub two: Two,
+    pub strength: f64,
+    pub bucket: usize,
+}
+
+/// Assigns each value to one of `k` buckets, numbered from the lowest values (0) to the highest
+/// (`k - 1`). Equal values always share a bucket, so fewer than `k` buckets may be used.
+#[must_use]
+pub fn bucket_values(values: &[f64], k: usize, bucketing: Bucketing) -> Vec<usize> {
+    if values.is_empty() || k == 0 {
+        return alloc::vec![0; values.len()];
+    }
+    match bucketing {
+        Bucketing::Percentile => percentile(values, k),
+        Bucketing::KMeans(iterations) => k_means(values, k, iterations),
+    }
+}
+
+/// Buckets every hole card combination that doesn't conflict with the board by its hand
+/// strength: the share of the other live combinations that it beats on the board as it stands,
+/// counting ties as half. Suit isomorphic combinations always have the same strength, so they
+/// always share a bucket.
+///
+/// # Errors
+///
+/// Returns `HandError::NotEnoughCards` or `HandError::TooManyCards` if the board doesn't have
+/// three to five cards, `HandError::InvalidCard` if any of them are invalid, and
+/// `HandError::DuplicateCard` if any of them repeat.
+pub fn bucket_hands(board: &[CKCNumber], k: usize, bucketing: Bucketing) -> Result<Vec<HandBucket>, HandError> {
+    match board.len() {
+        0..=2 => return Err(HandError::NotEnoughCards),
+        3..=5 => {},
+        _ => return Err(HandError::TooManyCards),
+    }
+
+    let mut dead = BinaryCard::BLANK;
+    for card in board {
+        let bc = BinaryCard::from_ckc(*card);
+        if bc == BinaryCard::BLANK {
+            return Err(HandError::InvalidCard);
+        }
+        if dead.has(bc) {
+            return Err(HandError::DuplicateCard);
+        }
+        dead = dead.fold_in(bc);
+    }
+
+    let mut combos: Vec<(Two, BinaryCard, HandRankValue)> = Vec::with_capacity(Range::ALL_COMBOS);
+    let mut hand = board.to_vec();
+    for two in Range::all().live(dead) {
+        hand.truncate(board.len());
+        hand.extend(two.iter());
+        let (rank, _) = best_five_of(&hand)?;
+        combos.push((*two, BinaryCard::from_two(*two), rank.value));
+    }
+
+    let strengths: Vec<f64> = combos
+        .iter()
+        .map(|(_, hero_bits, hero_value)| {
+            let mut points = 0u32;
+            let mut total = 0u32;
+            for (_, villain_bits, villain_value) in &combos {
+                if hero_bits & villain_bits != 0 {
+                    continue;
+                }
+                total += 2;
+                points += match hero_value.cmp(villain_value) {
+                    Ordering::Less => 2,
+                    Ordering::Equal => 1,
+                    Ordering::Greater => 0,
+                };
+            }
+            f64::from(points) / f64::from(total)
+        })
+        .collect();
+
+    let buckets = bucket_values(&strengths, k, bucketing);
+
+    Ok(combos
+        .iter()
+        .zip(strengths)
+        .zip(buckets)
+        .map(|(((two, _, _), strength), bucket)| HandBucket {
+            two: *two,
+            strength,
+            bucket,
+        })
+        .collect())
+}
+
+fn sorted(values: &[f64]) -> Vec<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    sorted
+}
+
+fn percentile(values: &[f64], k: usize) -> Vec<usize> {
+    let sorted = sorted(values);
+    values
+        .iter()
+        .map(|value| {
+            // Use the first position of the value so that equal values share a bucket.
+            let position = sorted.partition_point(|v| v < value);
+            (position * k / sorted.len()).min(k - 1)
+        })
+        .collect()
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn k_means(values: &[f64], k: usize, iterations: usize) -> Vec<usize> {
+    let sorted = sorted(values);
+    let k = k.min(sorted.len());
+
+    // Seed the centroids at evenly spaced quantiles.
+    let mut centroids: Vec<f64> = (0..k).map(|i| sorted[(2 * i + 1) * sorted.len() / (2 * k)]).collect();
+    let mut assignments = assign(values, &centroids);
+
+    for _ in 0..iterations {
+        let mut sums = alloc::vec![0.0; k];
+        let mut counts = alloc::vec![0usize; k];
+        for (value, bucket) in values.iter().zip(&assignments) {
+            sums[*bucket] += value;
+            counts[*bucket] += 1;
+        }
+        for (i, centroid) in centroids.iter_mut().enumerate() {
+            if counts[i] > 0 {
+                *centroid = sums[i] / counts[i] as f64;
+            }
+        }
+
+        let next = assign(values, &centroids);
+        if next == assignments {
+            break;
+        }
+        assignments = next;
+    }
+    assignments
+}
+
+/// Assigns each value to its nearest centroid. The centroids stay in ascending order, so the
+/// buckets do too.
+fn assign(values: &[f64], centroids: &[f64]) -> Vec<usize> {
+    values
+        .iter()
+        .map(|value| {
+            let mut best = 0;
+            for (i, centroid) in centroids.iter().enumerate() {
+                let distance = (value - centroid) * (value - centroid);
+                let best_distance = (value - centroids[best]) * (value - centroids[best]);
+                if distance < best_distance {
+                    best = i;
+                }
+            }
+            best
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__bucket_tests {
+    use super::*;
+    use crate::cards::five::Five;
+    use crate::CardNumber;
+
+    #[test]
+    fn bucket_values__percentile() {
+        let values = [0.9, 0.1, 0.5, 0.3, 0.7, 0.5];
+
+        assert_eq!(
+            alloc::vec![2, 0, 1, 0, 2, 1],
+            bucket_values(&values, 3, Bucketing::Percentile)
+        );
+    }
+
+    #[test]
+    fn bucket_values__k_means() {
+        let values = [0.11, 0.9, 0.1, 0.52, 0.88, 0.12, 0.5];
+
+        assert_eq!(
+            alloc::vec![0, 2, 0, 1, 2, 0, 1],
+            bucket_values(&values, 3, Bucketing::KMeans(10))
+        );
+    }
+
+    #[test]
+    fn bucket_values__empty() {
+        assert!(bucket_values(&[], 3, Bucketing::Percentile).is_empty());
+        assert_eq!(alloc::vec![0, 0], bucket_values(&[0.1, 0.2], 0, Bucketing::KMeans(1)));
+    }
+
+    #[test]
+    fn bucket_hands() {
+        let board = Five::try_from("A♠ K♠ Q♠ 7♦ 2♣").unwrap();
+
+        let buckets = super::bucket_hands(&board.to_arr(), 5, Bucketing::Percentile).unwrap();
+
+        assert_eq!(1081, buckets.len());
+        let royal = buckets
+            .iter()
+            .find(|hand| hand.two.sort() == Two::try_from("J♠ T♠").unwrap().sort())
+            .unwrap();
+        assert!((royal.strength - 1.0).abs() < f64::EPSILON);
+        assert_eq!(4, royal.bucket);
+        assert!(buckets.iter().any(|hand| hand.bucket == 0));
+    }
+
+    #[test]
+    fn bucket_hands__isomorphic() {
+        let board = [
+            CardNumber::ACE_SPADES,
+            CardNumber::KING_HEARTS,
+            CardNumber::SEVEN_DIAMONDS,
+        ];
+
+        let buckets = super::bucket_hands(&board, 8, Bucketing::KMeans(20)).unwrap();
+        let bucket_of = |index: &'static str| {
+            let two = Two::try_from(index).unwrap().sort();
+            buckets.iter().find(|hand| hand.two.sort() == two).unwrap().bucket
+        };
+
+        assert_eq!(bucket_of("Q♣ J♣"), bucket_of("Q♥ J♥"));
+    }
+
+    #[test]
+    fn bucket_hands__errors() {
+        assert_eq!(
+            HandError::NotEnoughCards,
+            super::bucket_hands(&[CardNumber::ACE_SPADES], 2, Bucketing::Percentile).unwrap_err()
+        );
+        assert_eq!(
+            HandError::DuplicateCard,
+            super::bucket_hands(
+                &[CardNumber::ACE_SPADES, CardNumber::ACE_SPADES, CardNumber::KING_SPADES],
+                2,
+                Bucketing::Percentile
+            )
+            .unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use crate::cards::binary_card::{BinaryCard, BC64};
+use crate::{CKCNumber, CardSuit, PokerCard};
+use alloc::vec::Vec;
+
+/// Every ordering of the four suits, as the new position of spades, hearts, diamonds and clubs.
+#[rustfmt::skip]
+pub(crate) const SUIT_PERMUTATIONS: [[u32; 4]; 24] = [
+    [0, 1, 2, 3], [0, 1, 3, 2], [0, 2, 1, 3], [0, 2, 3, 1], [0, 3, 1, 2], [0, 3, 2, 1],
+    [1, 0, 2, 3], [1, 0, 3, 2], [1, 2, 0, 3], [1, 2, 3, 0], [1, 3, 0, 2], [1, 3, 2, 0],
+    [2, 0, 1, 3], [2, 0, 3, 1], [2, 1, 0, 3], [2, 1, 3, 0], [2, 3, 0, 1], [2, 3, 1, 0],
+    [3, 0, 1, 2], [3, 0, 2, 1], [3, 1, 0, 2], [3, 1, 2, 0], [3, 2, 0, 1], [3, 2, 1, 0],
+];
+
+/// Moves each suit's 13 bits of a `BinaryCard` to the position given by the permutation, where
+/// spades are 0 and clubs are 3.
+pub(crate) fn permute_suits(bits: BinaryCard, permutation: [u32; 4]) -> BinaryCard {
+    permutation.iter().enumerate().fold(0, |permuted, (from, to)| {
+        let field = (bits >> (39 - 13 * from)) & 0x1fff;
+        permuted | field << (39 - 13 * to)
+    })
+}
+
+/// Relabels the suits of the cards in the order that they first appear, so that the first suit
+/// seen becomes spades, the second hearts, and so on. Hands that only differ by a permutation of
+/// their suits, such as `A♠ K♠` and `A♦ K♦`, have the same canonical form, which makes it a
+/// good key for caching results that don't depend on the actual suits.
+///
+/// Invalid cards are left as they are.
+#[must_use]
+pub fn canonical_suits(cards: &[CKCNumber]) -> Vec<CKCNumber> {
+    let mut seen: Vec<CardSuit> = Vec::with_capacity(4);
+    let suits = CardSuit::all();
+
+    cards
+        .iter()
+        .map(|card| {
+            let suit = card.get_card_suit();
+            if suit == CardSuit::BLANK {
+                return *card;
+            }
+            let position = seen.iter().position(|s| *s == suit).unwrap_or_else(|| {
+                seen.push(suit);
+                seen.len() - 1
+            });
+            CKCNumber::create(card.get_card_rank(), suits[position])
+        })
+        .collect()
+}
+
+/// Cards in their canonical form, from `canonical_suits()`.
+///
+/// ```
+/// use ckc_rs::analysis::canonical::Canonical;
+/// use ckc_rs::CardNumber;
+///
+/// let suited = Canonical::new(&[CardNumber::ACE_DIAMONDS, CardNumber::KING_DIAMONDS]);
+/// assert_eq!(&[CardNumber::ACE_SPADES, CardNumber::KING_SPADES], suited.cards());
+///
+/// // A♠ K♠, A♥ K♥, A♦ K♦ and A♣ K♣.
+/// assert_eq!(4, suited.expand().len());
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Canonical(Vec<CKCNumber>);
+
+/// One of the hands that a `Canonical` stands for, from `Canonical::expand()`.
+///
+/// `multiplicity` is the number of the 24 orderings of the suits that turn the canonical cards
+/// into these ones, so the multiplicities of a full expansion add up to 24. Each hand gets the
+/// same number unless cards of the same rank can swap suits, as with the pair in `A♠ A♥`, where
+/// relabeling spades as hearts and hearts as spades gives back the same hand.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Expansion {
+    pub cards: Vec<CKCNumber>,
+    pub multiplicity: usize,
+}
+
+impl Canonical {
+    #[must_use]
+    pub fn new(cards: &[CKCNumber]) -> Self {
+        Canonical(canonical_suits(cards))
+    }
+
+    #[must_use]
+    pub fn cards(&self) -> &[CKCNumber] {
+        &self.0
+    }
+
+    /// Every hand with the same canonical form, which is every way of giving the canonical
+    /// suits different real ones. It's the inverse of `canonical_suits()`, for spreading results
+    /// worked out once per canonical form back over the hands they stand for.
+    ///
+    /// The hands keep the order of the canonical cards, and each one only appears once, however
+    /// its cards are ordered. Invalid cards are left as they are in every hand.
+    ///
+    /// ```
+    /// use ckc_rs::analysis::canonical::Canonical;
+    /// use ckc_rs::cards::three::Three;
+    /// use ckc_rs::cards::HandValidator;
+    ///
+    /// let monotone = Canonical::new(&Three::try_from("A♠ 7♠ 2♠").unwrap().to_arr());
+    /// let two_tone = Canonical::new(&Three::try_from("A♠ 7♠ 2♥").unwrap().to_arr());
+    /// let rainbow = Canonical::new(&Three::try_from("A♠ 7♥ 2♦").unwrap().to_arr());
+    ///
+    /// assert_eq!(4, monotone.expand().len());
+    /// assert_eq!(12, two_tone.expand().len());
+    /// assert_eq!(24, rainbow.expand().len());
+    /// ```
+    #[must_use]
+    pub fn expand(&self) -> Vec<Expansion> {
+        let suits = CardSuit::all();
+        let mut expansions: Vec<(BinaryCard, Expansion)> = Vec::new();
+
+        for permutation in SUIT_PERMUTATIONS {
+            let cards: Vec<CKCNumber> = self
+                .0
+                .iter()
+                .map(
+                    |card| match suits.iter().position(|suit| *suit == card.get_card_suit()) {
+                        Some(from) => CKCNumber::create(card.get_card_rank(), suits[permutation[from] as usize]),
+                        None => *card,
+                    },
+                )
+                .collect();
+            let bits = cards
+                .iter()
+                .fold(BinaryCard::BLANK, |bits, card| bits | BinaryCard::from_ckc(*card));
+
+            match expansions.iter_mut().find(|(seen, _)| *seen == bits) {
+                Some((_, expansion)) => expansion.multiplicity += 1,
+                None => expansions.push((bits, Expansion { cards, multiplicity: 1 })),
+            }
+        }
+        expansions.into_iter().map(|(_, expansion)| expansion).collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__canonical_tests {
+    use super::*;
+    use crate::cards::binary_card::{BinaryCard, BC64};
+    use crate::CardNumber;
+    use rstest::rstest;
+
+    #[test]
+    fn permute_suits() {
+        let bits = BinaryCard::ACE_SPADES | BinaryCard::KING_HEARTS | BinaryCard::DEUCE_CLUBS;
+
+        assert_eq!(bits, super::permute_suits(bits, [0, 1, 2, 3]));
+        assert_eq!(
+            BinaryCard::ACE_CLUBS | BinaryCard::KING_DIAMONDS | BinaryCard::DEUCE_SPADES,
+            super::permute_suits(bits, [3, 2, 1, 0])
+        );
+        for permutation in super::SUIT_PERMUTATIONS {
+            assert_eq!(3, super::permute_suits(bits, permutation).count_ones());
+        }
+    }
+
+    #[test]
+    fn canonical_suits() {
+        let cards = [
+            CardNumber::ACE_DIAMONDS,
+            CardNumber::KING_DIAMONDS,
+            CardNumber::SEVEN_CLUBS,
+            CardNumber::DEUCE_DIAMONDS,
+        ];
+
+        let expected = alloc::vec![
+            CardNumber::ACE_SPADES,
+            CardNumber::KING_SPADES,
+            CardNumber::SEVEN_HEARTS,
+            CardNumber::DEUCE_SPADES,
+        ];
+
+        assert_eq!(expected, super::canonical_suits(&cards));
+    }
+
+    #[test]
+    fn canonical_suits__blank() {
+        let cards = [CardNumber::BLANK, CardNumber::ACE_CLUBS];
+
+        assert_eq!(
+            alloc::vec![CardNumber::BLANK, CardNumber::ACE_SPADES],
+            super::canonical_suits(&cards)
+        );
+    }
+
+    #[rstest]
+    #[case("A♠ K♠", 4, 6)]
+    #[case("A♠ K♥", 12, 2)]
+    #[case("A♠ A♥", 6, 4)]
+    #[case("A♠ 7♠ 2♠", 4, 6)]
+    #[case("A♠ 7♠ 2♥", 12, 2)]
+    #[case("A♠ 7♥ 2♦", 24, 1)]
+    #[case("A♠ A♥ A♦ A♣", 1, 24)]
+    #[case("A♠ K♥ Q♦ J♣", 24, 1)]
+    fn expand(#[case] index: &'static str, #[case] hands: usize, #[case] multiplicity: usize) {
+        let cards: Vec<CKCNumber> = index.split(' ').map(CKCNumber::from_index).collect();
+
+        let expansions = Canonical::new(&cards).expand();
+
+        assert_eq!(hands, expansions.len());
+        assert!(expansions
+            .iter()
+            .all(|expansion| expansion.multiplicity == multiplicity));
+        assert!(expansions
+            .iter()
+            .all(|expansion| Canonical::new(&expansion.cards) == Canonical::new(&cards)));
+    }
+
+    #[test]
+    fn expand__order() {
+        let canonical = Canonical::new(&[CardNumber::KING_CLUBS, CardNumber::ACE_CLUBS]);
+
+        let expansions = canonical.expand();
+
+        assert_eq!(&[CardNumber::KING_SPADES, CardNumber::ACE_SPADES], canonical.cards());
+        assert_eq!(
+            alloc::vec![CardNumber::KING_SPADES, CardNumber::ACE_SPADES],
+            expansions[0].cards
+        );
+        assert!(expansions
+            .iter()
+            .any(|expansion| expansion.cards == alloc::vec![CardNumber::KING_CLUBS, CardNumber::ACE_CLUBS]));
+    }
+
+    #[test]
+    fn expand__blank() {
+        let expansions = Canonical::new(&[CardNumber::BLANK, CardNumber::ACE_CLUBS]).expand();
+
+        assert_eq!(4, expansions.len());
+        assert!(expansions
+            .iter()
+            .all(|expansion| expansion.cards[0] == CardNumber::BLANK));
+        assert_eq!(1, Canonical::default().expand().len());
+    }
+}
@@ -0,0 +1,220 @@
+use crate::analysis::flops::{FlopPairing, FlopSuits, FlopTexture};
+use crate::cards::three::Three;
+use crate::cards::HandValidator;
+use crate::hand_rank::HandRankName;
+use crate::sim::{SimHand, Simulator, Visitor};
+use crate::{HandError, PokerCard};
+use rand::Rng;
+
+/// A made hand losing at showdown to a better hand of the same kind.
+///
+/// * `SetOverSet` - two players flopped, turned or rivered a set with a pocket pair, whatever
+///   they ended up with.
+/// * The others - the winner and at least one other player both ended up with that kind of hand,
+///   and the other player's was worse. Players that chop the pot by playing the board don't
+///   count.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Cooler {
+    SetOverSet,
+    StraightOverStraight,
+    FlushOverFlush,
+    FullHouseOverFullHouse,
+}
+
+impl Cooler {
+    pub const ALL: [Cooler; 4] = [
+        Cooler::SetOverSet,
+        Cooler::StraightOverStraight,
+        Cooler::FlushOverFlush,
+        Cooler::FullHouseOverFullHouse,
+    ];
+
+    /// Returns true if the simulated hand had this cooler.
+    #[must_use]
+    pub fn is_in(self, hand: &SimHand) -> bool {
+        let name = match self {
+            Cooler::SetOverSet => return Cooler::sets(hand) >= 2,
+            Cooler::StraightOverStraight => HandRankName::Straight,
+            Cooler::FlushOverFlush => HandRankName::Flush,
+            Cooler::FullHouseOverFullHouse => HandRankName::FullHouse,
+        };
+        let Some(best) = hand.winners.first().map(|winner| hand.ranks[*winner]) else {
+            return false;
+        };
+        best.name == name
+            && hand
+                .ranks
+                .iter()
+                .any(|rank| rank.name == name && rank.value > best.value)
+    }
+
+    /// The number of seats with a pocket pair that matches exactly one card on the board.
+    fn sets(hand: &SimHand) -> usize {
+        hand.seats
+            .iter()
+            .filter(|hole| hole.is_pocket_pair())
+            .filter(|hole| {
+                let rank = hole.first().get_card_rank();
+                hand.board.iter().filter(|card| card.get_card_rank() == rank).count() == 1
+            })
+            .count()
+    }
+}
+
+/// The flops that `cooler_frequencies()` counts, by their `FlopTexture`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BoardType {
+    Any,
+    Suits(FlopSuits),
+    Pairing(FlopPairing),
+}
+
+impl BoardType {
+    #[must_use]
+    pub fn matches(self, flop: Three) -> bool {
+        let texture = FlopTexture::new(flop);
+        match self {
+            BoardType::Any => true,
+            BoardType::Suits(suits) => texture.suits == suits,
+            BoardType::Pairing(pairing) => texture.pairing == pairing,
+        }
+    }
+}
+
+/// How often each `Cooler` came up in the simulated hands whose flop matched the `BoardType`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CoolerFrequencies {
+    /// The hands that were dealt, including the ones with flops that didn't match.
+    pub dealt: usize,
+    /// The hands with flops that matched, which are the only ones that coolers are counted in.
+    pub boards: usize,
+    counts: [usize; 4],
+}
+
+impl CoolerFrequencies {
+    #[must_use]
+    pub fn count(&self, cooler: Cooler) -> usize {
+        self.counts[cooler as usize]
+    }
+
+    /// The share of the matching boards with the cooler.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn frequency(&self, cooler: Cooler) -> f64 {
+        match self.boards {
+            0 => 0.0,
+            boards => self.count(cooler) as f64 / boards as f64,
+        }
+    }
+}
+
+impl Visitor for CoolerFrequencies {
+    fn visit(&mut self, hand: &SimHand) {
+        self.dealt += 1;
+        self.boards += 1;
+        for cooler in Cooler::ALL {
+            if cooler.is_in(hand) {
+                self.counts[cooler as usize] += 1;
+            }
+        }
+    }
+}
+
+/// Deals hands to the number of players, with everyone going to showdown, and counts how often
+/// each `Cooler` comes up on the boards whose flop matches the `BoardType`.
+///
+/// # Errors
+///
+/// Same as `Simulator::new()`.
+pub fn cooler_frequencies<R: Rng + ?Sized>(
+    board_type: BoardType,
+    num_players: usize,
+    num_deals: usize,
+    rng: &mut R,
+) -> Result<CoolerFrequencies, HandError> {
+    let mut frequencies = CoolerFrequencies::default();
+    Simulator::new(num_players)?.run(num_deals, rng, &mut |hand: &SimHand| {
+        let board = hand.board.to_arr();
+        if board_type.matches(Three::from([board[0], board[1], board[2]])) {
+            frequencies.visit(hand);
+        } else {
+            frequencies.dealt += 1;
+        }
+    });
+    Ok(frequencies)
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__cooler_tests {
+    use super::*;
+    use crate::cards::five::Five;
+    use crate::cards::seven::Seven;
+    use crate::cards::two::Two;
+    use crate::cards::HandRanker;
+    use crate::hand_rank::HandRank;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+    use rstest::rstest;
+
+    fn hand(seats: &[&'static str], board: &'static str) -> SimHand {
+        let seats: Vec<Two> = seats.iter().map(|index| Two::try_from(*index).unwrap()).collect();
+        let board = Five::try_from(board).unwrap();
+        let ranks: Vec<HandRank> = seats.iter().map(|hole| Seven::new(*hole, board).hand_rank()).collect();
+        let best = ranks.iter().map(|rank| rank.value).min().unwrap();
+        SimHand {
+            winners: (0..ranks.len()).filter(|seat| ranks[*seat].value == best).collect(),
+            seats,
+            board,
+            ranks,
+            equities: Vec::new(),
+        }
+    }
+
+    #[rstest]
+    #[case(&["K♠ K♥", "9♠ 9♥"], "K♦ 9♦ 2♣ 5♠ 7♥", Cooler::SetOverSet)]
+    #[case(&["K♠ Q♥", "8♠ 7♥"], "J♦ T♦ 9♣ 2♠ 3♥", Cooler::StraightOverStraight)]
+    #[case(&["A♦ 2♥", "K♦ Q♦"], "J♦ 9♦ 7♣ 2♦ 3♦", Cooler::FlushOverFlush)]
+    #[case(&["K♠ K♥", "9♠ 9♥"], "K♦ 9♦ 2♣ 2♠ 7♥", Cooler::FullHouseOverFullHouse)]
+    fn is_in(#[case] seats: &[&'static str], #[case] board: &'static str, #[case] cooler: Cooler) {
+        let hand = hand(seats, board);
+
+        for other in Cooler::ALL {
+            if other == cooler {
+                assert!(cooler.is_in(&hand), "{cooler:?}");
+            } else if other != Cooler::SetOverSet || cooler != Cooler::FullHouseOverFullHouse {
+                assert!(!other.is_in(&hand), "{other:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn is_in__board() {
+        let chop = hand(&["2♠ 3♥", "2♦ 4♥"], "A♦ K♦ Q♦ J♦ T♦");
+        let set_over_overpair = hand(&["K♠ K♥", "A♠ A♥"], "K♦ 9♦ 2♣ 5♠ 7♥");
+
+        assert!(!Cooler::FlushOverFlush.is_in(&chop));
+        assert!(!Cooler::SetOverSet.is_in(&set_over_overpair));
+    }
+
+    #[test]
+    fn cooler_frequencies() {
+        let mut rng = SmallRng::seed_from_u64(42);
+
+        let all = super::cooler_frequencies(BoardType::Any, 9, 5_000, &mut rng).unwrap();
+        let monotone = super::cooler_frequencies(BoardType::Suits(FlopSuits::Monotone), 9, 5_000, &mut rng).unwrap();
+
+        assert_eq!(5_000, all.dealt);
+        assert_eq!(5_000, all.boards);
+        // About one flop in 19 is monotone.
+        assert!(monotone.boards > 150 && monotone.boards < 400);
+        assert!(monotone.frequency(Cooler::FlushOverFlush) > all.frequency(Cooler::FlushOverFlush));
+        for cooler in Cooler::ALL {
+            assert!(all.count(cooler) > 0, "{cooler:?}");
+        }
+        assert_eq!(
+            HandError::NotEnoughCards,
+            super::cooler_frequencies(BoardType::Any, 24, 1, &mut rng).unwrap_err()
+        );
+    }
+}
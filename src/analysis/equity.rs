@@ -0,0 +1,886 @@
+use crate::analysis::canonical::{permute_suits, SUIT_PERMUTATIONS};
+use crate::analysis::preflop::PreflopClass;
+use crate::cards::binary_card::{BinaryCard, BC64};
+use crate::cards::five::Five;
+use crate::cards::seven::Seven;
+use crate::cards::two::Two;
+use crate::cards::{HandRanker, HandValidator};
+use crate::deck::Deck;
+use crate::evaluate::Outcome;
+use crate::hand_rank::{HandRank, HandRankValue};
+use crate::math::next_combination;
+#[cfg(feature = "rand")]
+use crate::math::sqrt;
+use crate::{CKCNumber, HandError};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+#[cfg(feature = "rand")]
+use rand::rngs::SmallRng;
+#[cfg(feature = "rand")]
+use rand::{Rng, SeedableRng};
+
+/// The number of deals sampled when there are too many runouts to enumerate.
+pub const SAMPLES: usize = 10_000;
+
+/// The seed that `Sampling::default()` draws its deals with.
+#[cfg(feature = "rand")]
+const SEED: u64 = 0x2545_F491_4F6C_DD1D;
+
+/// Counts hero's wins and ties against every villain hand for every runout.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct Tally {
+    pub(crate) wins: u64,
+    pub(crate) ties: u64,
+    pub(crate) total: u64,
+}
+
+impl Tally {
+    pub(crate) fn record(&mut self, hero: HandRankValue, villain: HandRankValue) {
+        self.total += 1;
+        // Lower hand rank values are stronger hands.
+        match hero.cmp(&villain) {
+            Ordering::Less => self.wins += 1,
+            Ordering::Equal => self.ties += 1,
+            Ordering::Greater => {},
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn equity(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        (self.wins as f64 + self.ties as f64 / 2.0) / self.total as f64
+    }
+}
+
+/// Memoizes the results of `equity_vs_random()`. Results are keyed by the canonical form of the
+/// hero's hand and the board, so `A♠ K♠` on `Q♠ J♠ 2♥` shares an entry with `A♦ K♦` on
+/// `Q♦ J♦ 2♣`.
+#[derive(Clone, Debug, Default)]
+pub struct EquityCache {
+    entries: BTreeMap<(BinaryCard, BinaryCard), f64>,
+}
+
+impl EquityCache {
+    #[must_use]
+    pub fn new() -> Self {
+        EquityCache::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the hero's equity against a random hand, calculating it only if it hasn't been
+    /// calculated for an equivalent hand and board before.
+    ///
+    /// # Errors
+    ///
+    /// See `equity_vs_random()`.
+    pub fn equity_vs_random(&mut self, hero: Two, board: &[CKCNumber]) -> Result<f64, HandError> {
+        validate(hero, board)?;
+
+        let key = canonical_key(hero, board);
+        if let Some(equity) = self.entries.get(&key) {
+            return Ok(*equity);
+        }
+
+        let equity = equity_vs_random(hero, board)?;
+        self.entries.insert(key, equity);
+        Ok(equity)
+    }
+}
+
+/// Returns the hero's share of the pot against a single random hand, given zero to five board
+/// cards.
+///
+/// From the flop on, every possible villain hand and runout is enumerated, so the result is
+/// exact. Preflop there are too many deals for that, so `sample_equity_vs_random()` draws the
+/// default `Sampling` of `SAMPLES` deals with a fixed seed instead, which makes the result
+/// approximate but repeatable. Without the `rand` feature, an empty board is read from
+/// `PreflopClass::equity_vs_random()` and one or two board cards are enumerated, which is slow.
+///
+/// # Errors
+///
+/// Returns `HandError::InvalidCard` if any card is invalid, `HandError::DuplicateCard` if any
+/// card appears more than once, and `HandError::TooManyCards` if the board has more than five
+/// cards.
+pub fn equity_vs_random(hero: Two, board: &[CKCNumber]) -> Result<f64, HandError> {
+    validate(hero, board)?;
+
+    #[cfg(feature = "rand")]
+    if board.len() < 3 {
+        return sample_equity_vs_random(hero, board, Sampling::default()).map(|estimate| estimate.equity);
+    }
+    #[cfg(not(feature = "rand"))]
+    if board.is_empty() {
+        return PreflopClass::try_from(hero).map(|class| f64::from(class.equity_vs_random()));
+    }
+
+    let dead = BinaryCard::from_two(hero) | board_bits(board);
+    Ok(enumerate_vs_random(hero, board, &Deck::live(dead)).equity())
+}
+
+fn enumerate_vs_random(hero: Two, board: &[CKCNumber], live: &[CKCNumber]) -> Tally {
+    let mut tally = Tally::default();
+
+    for runout in runouts(live, 5 - board.len()) {
+        let mut cards = [CKCNumber::default(); 5];
+        for (i, card) in board.iter().chain(runout.iter()).enumerate() {
+            cards[i] = *card;
+        }
+        let full = Five::from(cards);
+        let hero_value = Seven::new(hero, full).hand_rank_value();
+        let runout_bits = board_bits(&runout);
+
+        for (i, first) in live.iter().enumerate() {
+            if BinaryCard::from_ckc(*first) & runout_bits != 0 {
+                continue;
+            }
+            for second in &live[i + 1..] {
+                if BinaryCard::from_ckc(*second) & runout_bits != 0 {
+                    continue;
+                }
+                let villain = Seven::new(Two::new(*first, *second), full);
+                tally.record(hero_value, villain.hand_rank_value());
+            }
+        }
+    }
+    tally
+}
+
+/// One runout from `enumerate()`: the full board, and how each hand did on it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RunoutResult {
+    pub board: Five,
+    pub hero: HandRank,
+    pub villain: HandRank,
+    /// From the hero's point of view.
+    pub outcome: Outcome,
+}
+
+impl RunoutResult {
+    fn new(hero: Two, villain: Two, board: Five) -> Self {
+        let hero = Seven::new(hero, board).hand_rank();
+        let villain = Seven::new(villain, board).hand_rank();
+        RunoutResult {
+            board,
+            hero,
+            villain,
+            outcome: Outcome::from(hero.cmp(&villain)),
+        }
+    }
+
+    /// The hero's share of the pot on this runout: 1 for a win, 0.5 for a tie and 0 for a loss.
+    #[must_use]
+    pub fn equity(&self) -> f64 {
+        match self.outcome {
+            Outcome::Win => 1.0,
+            Outcome::Tie => 0.5,
+            Outcome::Loss => 0.0,
+        }
+    }
+}
+
+/// Walks every runout of the board, yielding the result of each one, so that statistics other
+/// than the overall equity can be collected in a single pass. The runouts are lazy, so a preflop
+/// enumeration of all 1,712,304 boards doesn't hold them in memory.
+///
+/// ```
+/// use ckc_rs::analysis::equity::enumerate;
+/// use ckc_rs::cards::three::Three;
+/// use ckc_rs::cards::two::Two;
+/// use ckc_rs::cards::HandValidator;
+/// use ckc_rs::{CardSuit, PokerCard};
+///
+/// let hero = Two::try_from("A♠ A♥").unwrap();
+/// let villain = Two::try_from("K♦ Q♦").unwrap();
+/// let flop = Three::try_from("J♦ T♦ 2♣").unwrap().to_arr();
+///
+/// // The hero's equity on the runouts where a diamond comes.
+/// let diamonds: Vec<f64> = enumerate(hero, villain, &flop)
+///     .unwrap()
+///     .filter(|runout| runout.board.iter().skip(3).any(|card| card.get_card_suit() == CardSuit::DIAMONDS))
+///     .map(|runout| runout.equity())
+///     .collect();
+///
+/// assert!(diamonds.iter().sum::<f64>() / (diamonds.len() as f64) < 0.1);
+/// ```
+///
+/// # Errors
+///
+/// Returns `HandError::InvalidCard` if any card is invalid, `HandError::DuplicateCard` if any
+/// card appears more than once, and `HandError::TooManyCards` if the board has more than five
+/// cards.
+pub fn enumerate(
+    hero: Two,
+    villain: Two,
+    board: &[CKCNumber],
+) -> Result<impl Iterator<Item = RunoutResult>, HandError> {
+    validate(hero, board)?;
+    if !villain.is_valid() {
+        return Err(HandError::InvalidCard);
+    }
+    let dead = BinaryCard::from_two(hero) | board_bits(board);
+    if dead & BinaryCard::from_two(villain) != BinaryCard::BLANK {
+        return Err(HandError::DuplicateCard);
+    }
+
+    let live = Deck::live(dead | BinaryCard::from_two(villain));
+    let known = board.len();
+    let mut cards = [CKCNumber::default(); 5];
+    cards[..known].copy_from_slice(board);
+    let first: [usize; 5] = core::array::from_fn(|i| i);
+    let len = live.len();
+
+    let runouts = core::iter::successors(Some(first), move |indexes| {
+        let mut next = *indexes;
+        next_combination(&mut next[..5 - known], len).then_some(next)
+    });
+    Ok(runouts.map(move |indexes| {
+        let mut cards = cards;
+        for (card, index) in cards[known..].iter_mut().zip(indexes) {
+            *card = live[index];
+        }
+        RunoutResult::new(hero, villain, Five::from(cards))
+    }))
+}
+
+/// An equity worked out from a sample of deals, along with how far it's likely to be from the
+/// exact one.
+///
+/// ```
+/// use ckc_rs::analysis::equity::EquityEstimate;
+///
+/// let estimate = EquityEstimate::new(0.6, 0.01, 10_000);
+/// let (low, high) = estimate.confidence_interval();
+///
+/// assert!((low - 0.5804).abs() < 1e-4 && (high - 0.6196).abs() < 1e-4);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EquityEstimate {
+    pub equity: f64,
+    pub standard_error: f64,
+    /// The number of deals that the estimate is from.
+    pub samples: usize,
+}
+
+impl EquityEstimate {
+    /// The number of standard errors either side of the estimate for a 95% confidence interval.
+    pub const Z_95: f64 = 1.959_964;
+
+    #[must_use]
+    pub fn new(equity: f64, standard_error: f64, samples: usize) -> Self {
+        EquityEstimate {
+            equity,
+            standard_error,
+            samples,
+        }
+    }
+
+    /// The 95% confidence interval around the estimate, kept between 0 and 1.
+    #[must_use]
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        let margin = EquityEstimate::Z_95 * self.standard_error;
+        ((self.equity - margin).max(0.0), (self.equity + margin).min(1.0))
+    }
+
+    /// The full width of the 95% confidence interval, before it's kept between 0 and 1.
+    #[must_use]
+    pub fn width(&self) -> f64 {
+        2.0 * EquityEstimate::Z_95 * self.standard_error
+    }
+}
+
+/// How `sample_equity_vs_random()` draws its deals. The default is `SAMPLES` plain deals, the
+/// same as `equity_vs_random()` preflop.
+///
+/// * `antithetic` - plays every deal a second time with its mirror image, where each of the
+///   villain's and the runout's cards is swapped for the live card the same distance from the
+///   other end of the deck, ordered by rank and then suit. Aces become deuces, spades become
+///   clubs, and a strong villain hand becomes a weak one, so the two results tend to cancel out.
+/// * `control_variate` - uses the villain hand's `PreflopClass::equity_vs_random()`, whose
+///   average over the live cards is known exactly, to correct for deals that happened to give
+///   the villain better or worse hands than usual.
+///
+/// Neither one helps much preflop, since the board decides most showdowns. The control variate
+/// takes a few percent off the variance at most. The mirror image deals help a little for hands
+/// such as `J♥ T♥`, but add to the variance for pairs such as `A♠ A♥`, where a low villain
+/// hand is just as likely to be drawing dead as a high one.
+#[cfg(feature = "rand")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sampling {
+    /// The number of deals, with each half of an antithetic pair counting as one. With a
+    /// `target_width`, this is the most that will be dealt.
+    pub samples: usize,
+    pub seed: u64,
+    pub antithetic: bool,
+    pub control_variate: bool,
+    /// Stops as soon as the 95% confidence interval is this wide or narrower, checking after
+    /// every `Sampling::BATCH` deals, or pairs of deals with `antithetic`.
+    pub target_width: Option<f64>,
+}
+
+#[cfg(feature = "rand")]
+impl Sampling {
+    /// How often a `target_width` is checked.
+    pub const BATCH: usize = 500;
+
+    #[must_use]
+    pub fn new(samples: usize) -> Self {
+        Sampling {
+            samples,
+            ..Sampling::default()
+        }
+    }
+
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    #[must_use]
+    pub fn with_antithetic(mut self) -> Self {
+        self.antithetic = true;
+        self
+    }
+
+    #[must_use]
+    pub fn with_control_variate(mut self) -> Self {
+        self.control_variate = true;
+        self
+    }
+
+    /// Deals until the 95% confidence interval is no wider than the width, such as `0.01` for
+    /// plus or minus half a percent, or until `samples` have been dealt.
+    #[must_use]
+    pub fn until_width(mut self, width: f64) -> Self {
+        self.target_width = Some(width);
+        self
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Default for Sampling {
+    fn default() -> Self {
+        Sampling {
+            samples: SAMPLES,
+            seed: SEED,
+            antithetic: false,
+            control_variate: false,
+            target_width: None,
+        }
+    }
+}
+
+/// Returns the hero's share of the pot against a single random hand, from deals drawn the way
+/// the `Sampling` says, given zero to five board cards. Sampling is most useful preflop and on
+/// the flop, where enumerating is slow.
+///
+/// The standard error takes the variance reduction into account, so it shrinks faster with
+/// it switched on.
+///
+/// ```
+/// use ckc_rs::analysis::equity::{sample_equity_vs_random, Sampling};
+/// use ckc_rs::cards::two::Two;
+///
+/// let sampling = Sampling::new(100_000).with_antithetic().until_width(0.02);
+/// let estimate = sample_equity_vs_random(Two::try_from("A♠ A♥").unwrap(), &[], sampling).unwrap();
+///
+/// assert!(estimate.width() <= 0.02 && estimate.samples < 100_000);
+/// assert!((estimate.equity - 0.852).abs() < 0.02);
+/// ```
+///
+/// # Errors
+///
+/// The same as `equity_vs_random()`.
+#[cfg(feature = "rand")]
+#[allow(clippy::cast_precision_loss)]
+pub fn sample_equity_vs_random(
+    hero: Two,
+    board: &[CKCNumber],
+    sampling: Sampling,
+) -> Result<EquityEstimate, HandError> {
+    validate(hero, board)?;
+
+    let dead = BinaryCard::from_two(hero) | board_bits(board);
+    // Ordered by rank and then suit, so that index `i` mirrors index `len - 1 - i`.
+    let mut live = Deck::live(dead).to_vec();
+    live.sort_unstable();
+    let len = live.len();
+    let needed = 7 - board.len();
+
+    let mut rng = SmallRng::seed_from_u64(sampling.seed);
+    let mut indexes: Vec<usize> = (0..len).collect();
+    let mut mirror = [0usize; 7];
+    let mut moments = Moments::default();
+    let play = |indexes: &[usize]| {
+        let mut cards = [CKCNumber::default(); 5];
+        for (i, card) in board
+            .iter()
+            .copied()
+            .chain(indexes[2..needed].iter().map(|i| live[*i]))
+            .enumerate()
+        {
+            cards[i] = card;
+        }
+        let villain = Two::new(live[indexes[0]], live[indexes[1]]);
+        (
+            RunoutResult::new(hero, villain, Five::from(cards)).equity(),
+            villain_equity(villain),
+        )
+    };
+
+    // The average of the control over every villain hand that can be dealt.
+    let expected_control = sampling.control_variate.then(|| {
+        let mut total = 0.0;
+        let mut combos = 0usize;
+        for (i, first) in live.iter().enumerate() {
+            for second in &live[i + 1..] {
+                total += villain_equity(Two::new(*first, *second));
+                combos += 1;
+            }
+        }
+        total / combos as f64
+    });
+    let per_draw = if sampling.antithetic { 2 } else { 1 };
+
+    let mut samples = 0;
+    while samples < sampling.samples {
+        for i in 0..needed {
+            let j = rng.gen_range(i..len);
+            indexes.swap(i, j);
+        }
+        let (mut equity, mut control) = play(&indexes[..needed]);
+        if sampling.antithetic {
+            for (mirrored, i) in mirror.iter_mut().zip(&indexes[..needed]) {
+                *mirrored = len - 1 - i;
+            }
+            let (mirror_equity, mirror_control) = play(&mirror[..needed]);
+            equity = (equity + mirror_equity) / 2.0;
+            control = (control + mirror_control) / 2.0;
+        }
+        moments.record(equity, control);
+        samples += per_draw;
+
+        if let Some(width) = sampling.target_width {
+            if moments.n % Sampling::BATCH == 0 && moments.estimate(expected_control, samples).width() <= width {
+                break;
+            }
+        }
+    }
+    Ok(moments.estimate(expected_control, samples))
+}
+
+#[cfg(feature = "rand")]
+fn villain_equity(villain: Two) -> f64 {
+    PreflopClass::try_from(villain).map_or(0.5, |class| f64::from(class.equity_vs_random()))
+}
+
+/// Running sums of the sampled equities and their control variates.
+#[cfg(feature = "rand")]
+#[derive(Clone, Copy, Debug, Default)]
+struct Moments {
+    n: usize,
+    sum_x: f64,
+    sum_xx: f64,
+    sum_c: f64,
+    sum_cc: f64,
+    sum_xc: f64,
+}
+
+#[cfg(feature = "rand")]
+impl Moments {
+    fn record(&mut self, x: f64, c: f64) {
+        self.n += 1;
+        self.sum_x += x;
+        self.sum_xx += x * x;
+        self.sum_c += c;
+        self.sum_cc += c * c;
+        self.sum_xc += x * c;
+    }
+
+    /// The mean of the samples and its standard error. With a control, the mean is corrected by
+    /// how far the mean of the controls was from their expected value, scaled by the least
+    /// squares slope of the samples against the controls, and the standard error comes from
+    /// what's left of the variance after the correction.
+    #[allow(clippy::cast_precision_loss)]
+    fn estimate(&self, expected_control: Option<f64>, samples: usize) -> EquityEstimate {
+        if self.n < 2 {
+            let mean = if self.n == 1 { self.sum_x } else { 0.0 };
+            return EquityEstimate::new(mean, 0.0, samples);
+        }
+        let n = self.n as f64;
+        let mean_x = self.sum_x / n;
+        let mut mean = mean_x;
+        let mut variance = self.sum_xx / n - mean_x * mean_x;
+        if let Some(expected) = expected_control {
+            let mean_c = self.sum_c / n;
+            let var_c = self.sum_cc / n - mean_c * mean_c;
+            if var_c > f64::EPSILON {
+                let covariance = self.sum_xc / n - mean_x * mean_c;
+                mean -= covariance / var_c * (mean_c - expected);
+                variance -= covariance * covariance / var_c;
+            }
+        }
+        EquityEstimate::new(mean.clamp(0.0, 1.0), sqrt(variance / (n - 1.0)), samples)
+    }
+}
+
+/// Every combination of `count` cards.
+fn runouts(live: &[CKCNumber], count: usize) -> Vec<Vec<CKCNumber>> {
+    let mut runouts = Vec::new();
+    if count > live.len() {
+        return runouts;
+    }
+    let mut indexes: Vec<usize> = (0..count).collect();
+    loop {
+        runouts.push(indexes.iter().map(|i| live[*i]).collect());
+        if !next_combination(&mut indexes, live.len()) {
+            return runouts;
+        }
+    }
+}
+
+fn validate(hero: Two, board: &[CKCNumber]) -> Result<(), HandError> {
+    if board.len() > 5 {
+        return Err(HandError::TooManyCards);
+    }
+    if !hero.is_valid()
+        || board
+            .iter()
+            .any(|card| BinaryCard::from_ckc(*card) == BinaryCard::BLANK)
+    {
+        return Err(HandError::InvalidCard);
+    }
+    let known = BinaryCard::from_two(hero) | board_bits(board);
+    if known.number_of_cards() as usize != board.len() + 2 {
+        return Err(HandError::DuplicateCard);
+    }
+    Ok(())
+}
+
+fn board_bits(board: &[CKCNumber]) -> BinaryCard {
+    board
+        .iter()
+        .fold(BinaryCard::BLANK, |bits, card| bits | BinaryCard::from_ckc(*card))
+}
+
+/// The smallest of the keys under every relabelling of the suits, so that every hand and board
+/// that only differ by their suits share a key.
+fn canonical_key(hero: Two, board: &[CKCNumber]) -> (BinaryCard, BinaryCard) {
+    let hero = BinaryCard::from_two(hero);
+    let board = board_bits(board);
+
+    SUIT_PERMUTATIONS
+        .iter()
+        .map(|permutation| (permute_suits(hero, *permutation), permute_suits(board, *permutation)))
+        .min()
+        .unwrap_or((hero, board))
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__equity_tests {
+    use super::*;
+    use crate::analysis::range::Range;
+    use crate::analysis::street::equity_by_street;
+    use crate::cards::three::Three;
+    use crate::CardNumber;
+
+    fn board(index: &'static str) -> Vec<CKCNumber> {
+        let three = Three::try_from(index).unwrap();
+        three.to_arr().to_vec()
+    }
+
+    #[test]
+    fn equity_vs_random__river() {
+        let hero = Two::try_from("A♠ K♠").unwrap();
+        let mut board = board("Q♠ J♠ T♠");
+        board.push(CardNumber::DEUCE_HEARTS);
+        board.push(CardNumber::TREY_DIAMONDS);
+
+        let equity = super::equity_vs_random(hero, &board).unwrap();
+
+        assert!((equity - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn equity_vs_random__turn() {
+        let hero = Two::try_from("7♠ 2♦").unwrap();
+        let mut board = board("A♥ K♣ Q♦");
+        board.push(CardNumber::NINE_CLUBS);
+
+        let equity = super::equity_vs_random(hero, &board).unwrap();
+
+        assert!(equity > 0.15 && equity < 0.2);
+    }
+
+    #[test]
+    fn equity_vs_random__preflop() {
+        let aces = super::equity_vs_random(Two::try_from("A♠ A♥").unwrap(), &[]).unwrap();
+        let trash = super::equity_vs_random(Two::try_from("7♠ 2♦").unwrap(), &[]).unwrap();
+
+        // AA is about 85% against a random hand, and 72o is about 35%.
+        assert!((aces - 0.85).abs() < 0.02);
+        assert!((trash - 0.35).abs() < 0.02);
+    }
+
+    #[test]
+    #[cfg(not(feature = "rand"))]
+    fn equity_vs_random__preflop_table() {
+        let hero = Two::try_from("A♠ A♥").unwrap();
+
+        let equity = super::equity_vs_random(hero, &[]).unwrap();
+
+        assert!((f64::from(PreflopClass::AA.equity_vs_random()) - equity).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn equity_vs_random__errors() {
+        let hero = Two::try_from("A♠ A♥").unwrap();
+
+        assert_eq!(
+            HandError::DuplicateCard,
+            super::equity_vs_random(hero, &[CardNumber::ACE_SPADES]).unwrap_err()
+        );
+        assert_eq!(
+            HandError::InvalidCard,
+            super::equity_vs_random(hero, &[CardNumber::BLANK]).unwrap_err()
+        );
+        assert_eq!(
+            HandError::InvalidCard,
+            super::equity_vs_random(Two::default(), &[]).unwrap_err()
+        );
+        assert_eq!(
+            HandError::TooManyCards,
+            super::equity_vs_random(hero, &Two::AK.map(|two| two.first())[..6]).unwrap_err()
+        );
+    }
+
+    /// The variance of the estimates from a hundred seeds.
+    #[cfg(feature = "rand")]
+    fn spread(hero: Two, sampling: Sampling) -> f64 {
+        let estimates: Vec<f64> = (1..=100)
+            .map(|seed| {
+                super::sample_equity_vs_random(hero, &[], sampling.with_seed(seed))
+                    .unwrap()
+                    .equity
+            })
+            .collect();
+        let mean = estimates.iter().sum::<f64>() / 100.0;
+        estimates.iter().map(|estimate| (estimate - mean).powi(2)).sum::<f64>() / 100.0
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn sample_equity_vs_random() {
+        let hero = Two::try_from("K♠ Q♠").unwrap();
+        let expected = f64::from(PreflopClass::try_from(hero).unwrap().equity_vs_random());
+
+        for sampling in [
+            Sampling::new(4_000),
+            Sampling::new(4_000).with_antithetic(),
+            Sampling::new(4_000).with_control_variate(),
+            Sampling::new(4_000).with_antithetic().with_control_variate(),
+        ] {
+            let estimate = super::sample_equity_vs_random(hero, &[], sampling).unwrap();
+
+            assert_eq!(4_000, estimate.samples);
+            assert!((estimate.equity - expected).abs() < 0.03, "{sampling:?} {estimate:?}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn sample_equity_vs_random__variance_reduction() {
+        let hero = Two::try_from("K♠ Q♠").unwrap();
+        let plain = spread(hero, Sampling::new(500));
+
+        // The same deals with the control variate can only have less variance left over.
+        for seed in 1..=10 {
+            let sampling = Sampling::new(500).with_seed(seed);
+            let without = super::sample_equity_vs_random(hero, &[], sampling).unwrap();
+            let with = super::sample_equity_vs_random(hero, &[], sampling.with_control_variate()).unwrap();
+
+            assert!(with.standard_error <= without.standard_error);
+        }
+        for sampling in [
+            Sampling::new(500).with_antithetic(),
+            Sampling::new(500).with_control_variate(),
+            Sampling::new(500).with_antithetic().with_control_variate(),
+        ] {
+            let spread = spread(hero, sampling);
+
+            assert!(
+                spread < plain * 1.5 && spread > plain / 1.5,
+                "{sampling:?} {spread} {plain}"
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn sample_equity_vs_random__confidence_interval() {
+        let hero = Two::try_from("9♣ 9♦").unwrap();
+        let expected = f64::from(PreflopClass::try_from(hero).unwrap().equity_vs_random());
+
+        let plain = super::sample_equity_vs_random(hero, &[], Sampling::new(10_000)).unwrap();
+        let (low, high) = plain.confidence_interval();
+
+        assert!(low < expected && expected < high, "{plain:?}");
+        // The standard error of a plain sample is about sqrt(p * (1 - p) / n).
+        assert!((plain.standard_error - sqrt(0.72 * 0.28 / 10_000.0)).abs() < 0.0005);
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn sample_equity_vs_random__until_width() {
+        let hero = Two::try_from("9♣ 9♦").unwrap();
+
+        let coarse = super::sample_equity_vs_random(hero, &[], Sampling::new(100_000).until_width(0.04)).unwrap();
+        let fine = super::sample_equity_vs_random(hero, &[], Sampling::new(100_000).until_width(0.02)).unwrap();
+        let capped = super::sample_equity_vs_random(hero, &[], Sampling::new(1_000).until_width(0.001)).unwrap();
+
+        assert!(coarse.width() <= 0.04 && coarse.samples < fine.samples);
+        assert!(fine.width() <= 0.02 && fine.samples < 100_000);
+        assert_eq!(0, fine.samples % Sampling::BATCH);
+        assert_eq!(1_000, capped.samples);
+        assert!(capped.width() > 0.001);
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn sample_equity_vs_random__board() {
+        let hero = Two::try_from("7♠ 2♦").unwrap();
+        let mut board = board("A♥ K♣ Q♦");
+        board.push(CardNumber::NINE_CLUBS);
+        let exact = super::equity_vs_random(hero, &board).unwrap();
+
+        let sampled = super::sample_equity_vs_random(hero, &board, Sampling::default().with_antithetic()).unwrap();
+
+        assert!((exact - sampled.equity).abs() < 0.02);
+        assert_eq!(
+            HandError::DuplicateCard,
+            super::sample_equity_vs_random(hero, &[CardNumber::SEVEN_SPADES], Sampling::default()).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn enumerate() {
+        let hero = Two::try_from("A♠ A♥").unwrap();
+        let villain = Two::try_from("K♠ K♥").unwrap();
+        let flop = Three::try_from("2♣ 7♦ 9♥").unwrap();
+
+        let runouts: Vec<RunoutResult> = super::enumerate(hero, villain, &flop.to_arr()).unwrap().collect();
+        let equity = runouts.iter().map(RunoutResult::equity).sum::<f64>() / 990.0;
+        let expected = equity_by_street(hero, &Range::from(vec![villain]), flop).unwrap().river;
+
+        assert_eq!(990, runouts.len());
+        assert!((expected - equity).abs() < 1e-12);
+        assert!(runouts.iter().all(|runout| runout.board.is_valid()));
+        assert_eq!(
+            Outcome::Loss,
+            runouts
+                .iter()
+                .find(|runout| runout.board.to_arr()[3..].contains(&CardNumber::KING_DIAMONDS))
+                .unwrap()
+                .outcome
+        );
+    }
+
+    #[test]
+    fn enumerate__river() {
+        let hero = Two::try_from("A♠ K♥").unwrap();
+        let villain = Two::try_from("A♦ K♣").unwrap();
+        let mut board = board("Q♠ J♠ T♠");
+        board.push(CardNumber::DEUCE_HEARTS);
+        board.push(CardNumber::TREY_DIAMONDS);
+
+        let runouts: Vec<RunoutResult> = super::enumerate(hero, villain, &board).unwrap().collect();
+
+        assert_eq!(1, runouts.len());
+        assert_eq!(Outcome::Tie, runouts[0].outcome);
+        assert_eq!(runouts[0].hero, runouts[0].villain);
+        assert!((0.5 - runouts[0].equity()).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn enumerate__turn() {
+        let hero = Two::try_from("7♠ 2♦").unwrap();
+        let villain = Two::try_from("A♠ A♦").unwrap();
+        let mut board = board("A♥ K♣ Q♦");
+        board.push(CardNumber::NINE_CLUBS);
+
+        assert_eq!(44, super::enumerate(hero, villain, &board).unwrap().count());
+    }
+
+    #[test]
+    fn enumerate__errors() {
+        let hero = Two::try_from("A♠ A♥").unwrap();
+        let flop = board("2♣ 7♦ 9♥");
+
+        assert_eq!(
+            Some(HandError::DuplicateCard),
+            super::enumerate(hero, Two::try_from("A♠ K♥").unwrap(), &flop).err()
+        );
+        assert_eq!(
+            Some(HandError::DuplicateCard),
+            super::enumerate(hero, Two::try_from("9♥ K♥").unwrap(), &flop).err()
+        );
+        assert_eq!(
+            Some(HandError::InvalidCard),
+            super::enumerate(hero, Two::default(), &flop).err()
+        );
+    }
+
+    #[test]
+    fn canonical_key__every_suit_permutation() {
+        let hero = Two::try_from("A♠ A♥").unwrap();
+        let flop = board("A♦ K♠ 2♣");
+        let key = canonical_key(hero, &flop);
+
+        for permutation in SUIT_PERMUTATIONS {
+            let hero = permute_suits(BinaryCard::from_two(hero), permutation).to_vec_ckc();
+            let flop = permute_suits(board_bits(&flop), permutation).to_vec_ckc();
+
+            assert_eq!(key, canonical_key(Two::new(hero[0], hero[1]), &flop), "{permutation:?}");
+        }
+        assert_ne!(key, canonical_key(hero, &board("A♦ K♦ 2♣")));
+    }
+
+    #[test]
+    fn equity_cache() {
+        let mut cache = EquityCache::new();
+        let mut spades = board("Q♠ J♠ 2♥");
+        spades.push(CardNumber::NINE_CLUBS);
+        let mut diamonds = board("Q♦ J♦ 2♣");
+        diamonds.push(CardNumber::NINE_HEARTS);
+
+        let first = cache
+            .equity_vs_random(Two::try_from("A♠ K♠").unwrap(), &spades)
+            .unwrap();
+        let second = cache
+            .equity_vs_random(Two::try_from("K♦ A♦").unwrap(), &diamonds)
+            .unwrap();
+
+        assert_eq!(1, cache.len());
+        assert!((first - second).abs() < f64::EPSILON);
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,323 @@
+use crate::analysis::canonical::{permute_suits, SUIT_PERMUTATIONS};
+use crate::analysis::range::Range;
+use crate::cards::binary_card::{BinaryCard, BC64};
+use crate::{CKCNumber, HandError};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+/// The number of `u64` words needed for one bit per combo.
+pub(crate) const RANGE_WORDS: usize = 21;
+
+/// The bytes at the start of every saved `EquityTable`.
+const MAGIC: [u8; 4] = *b"CKCE";
+
+/// The version of the binary format written by `EquityTable::to_bytes()`.
+pub const EQUITY_TABLE_VERSION: u8 = 1;
+
+/// The length of one saved entry: the board, both ranges, and the equity.
+const ENTRY_LEN: usize = 8 + 2 * RANGE_WORDS * 8 + 8;
+
+/// One bit for each of the 1,326 combos.
+pub(crate) type RangeBits = [u64; RANGE_WORDS];
+
+/// The board and both ranges, relabeled to the suit permutation that gives the smallest key.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+struct SpotKey {
+    board: BinaryCard,
+    hero: RangeBits,
+    villain: RangeBits,
+}
+
+impl SpotKey {
+    fn new(hero: &Range, villain: &Range, board: &[CKCNumber]) -> Self {
+        let board = board
+            .iter()
+            .fold(BinaryCard::BLANK, |bits, card| bits | BinaryCard::from_ckc(*card));
+        let combos =
+            |range: &Range| -> Vec<BinaryCard> { range.iter().map(|two| BinaryCard::from_two(*two)).collect() };
+        let hero = combos(hero);
+        let villain = combos(villain);
+
+        SUIT_PERMUTATIONS
+            .iter()
+            .map(|permutation| SpotKey {
+                board: permute_suits(board, *permutation),
+                hero: range_bits(&hero, *permutation),
+                villain: range_bits(&villain, *permutation),
+            })
+            .min()
+            .unwrap_or(SpotKey {
+                board,
+                hero: [0; RANGE_WORDS],
+                villain: [0; RANGE_WORDS],
+            })
+    }
+}
+
+pub(crate) fn range_bits(combos: &[BinaryCard], permutation: [u32; 4]) -> RangeBits {
+    let mut bits = [0; RANGE_WORDS];
+    for combo in combos {
+        let permuted = permute_suits(*combo, permutation);
+        if permuted.count_ones() != 2 {
+            continue;
+        }
+        // Colex order of the two card positions, from 0 for the two lowest cards to 1,325.
+        let low = permuted.trailing_zeros() as usize;
+        let high = 63 - permuted.leading_zeros() as usize;
+        let index = high * (high - 1) / 2 + low;
+        bits[index / 64] |= 1 << (index % 64);
+    }
+    bits
+}
+
+/// Remembers the equity of one range against another on a board, so that it only has to be
+/// calculated once. Spots that only differ by a permutation of their suits, such as `A♠ A♥`
+/// against `K♠ K♥` on `2♣ 7♦ 9♥` and `A♦ A♣` against `K♦ K♣` on `2♠ 7♥ 9♦`, share an entry.
+///
+/// The table can be written to bytes and read back, and with the `std` feature saved to and
+/// loaded from a file, so that results carry over between sessions.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EquityTable {
+    entries: BTreeMap<SpotKey, f64>,
+}
+
+impl EquityTable {
+    #[must_use]
+    pub fn new() -> Self {
+        EquityTable::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the hero's equity for the spot, if it, or a suit permutation of it, has been
+    /// stored.
+    #[must_use]
+    pub fn get(&self, hero: &Range, villain: &Range, board: &[CKCNumber]) -> Option<f64> {
+        self.entries.get(&SpotKey::new(hero, villain, board)).copied()
+    }
+
+    pub fn insert(&mut self, hero: &Range, villain: &Range, board: &[CKCNumber], equity: f64) {
+        self.entries.insert(SpotKey::new(hero, villain, board), equity);
+    }
+
+    /// Returns the stored equity for the spot, or calculates it with `f` and stores it.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from `f`, in which case nothing is stored.
+    pub fn get_or_insert_with<F>(
+        &mut self,
+        hero: &Range,
+        villain: &Range,
+        board: &[CKCNumber],
+        f: F,
+    ) -> Result<f64, HandError>
+    where
+        F: FnOnce() -> Result<f64, HandError>,
+    {
+        let key = SpotKey::new(hero, villain, board);
+        if let Some(equity) = self.entries.get(&key) {
+            return Ok(*equity);
+        }
+        let equity = f()?;
+        self.entries.insert(key, equity);
+        Ok(equity)
+    }
+
+    /// Writes the table as `CKCE`, the `EQUITY_TABLE_VERSION`, the number of entries as a little
+    /// endian `u32`, and then each entry's board, hero range, villain range and equity, all little
+    /// endian.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(9 + self.entries.len() * ENTRY_LEN);
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(EQUITY_TABLE_VERSION);
+        bytes.extend_from_slice(&(self.entries.len() as u32).to_le_bytes());
+        for (key, equity) in &self.entries {
+            bytes.extend_from_slice(&key.board.to_le_bytes());
+            for word in key.hero.iter().chain(key.villain.iter()) {
+                bytes.extend_from_slice(&word.to_le_bytes());
+            }
+            bytes.extend_from_slice(&equity.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Reads a table written by `EquityTable::to_bytes()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HandError::InvalidBinaryFormat` if the bytes don't start with `CKCE` and the
+    /// current `EQUITY_TABLE_VERSION`, or aren't the right length for the number of entries.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, HandError> {
+        if bytes.len() < 9 || bytes[..4] != MAGIC || bytes[4] != EQUITY_TABLE_VERSION {
+            return Err(HandError::InvalidBinaryFormat);
+        }
+        let len = u32::from_le_bytes([bytes[5], bytes[6], bytes[7], bytes[8]]) as usize;
+        let body = &bytes[9..];
+        if len.checked_mul(ENTRY_LEN) != Some(body.len()) {
+            return Err(HandError::InvalidBinaryFormat);
+        }
+
+        let mut entries = BTreeMap::new();
+        for entry in body.chunks_exact(ENTRY_LEN) {
+            let mut words = entry.chunks_exact(8).map(|word| {
+                let mut buf = [0u8; 8];
+                buf.copy_from_slice(word);
+                u64::from_le_bytes(buf)
+            });
+            let board = words.next().unwrap_or_default();
+            let mut hero = [0; RANGE_WORDS];
+            let mut villain = [0; RANGE_WORDS];
+            for word in hero.iter_mut().chain(villain.iter_mut()) {
+                *word = words.next().unwrap_or_default();
+            }
+            let equity = f64::from_bits(words.next().unwrap_or_default());
+            entries.insert(SpotKey { board, hero, villain }, equity);
+        }
+        Ok(EquityTable { entries })
+    }
+
+    /// Saves the table to a file, in the format of `EquityTable::to_bytes()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from writing the file.
+    #[cfg(feature = "std")]
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_bytes())
+    }
+
+    /// Loads a table saved by `EquityTable::save()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from reading the file, and an `InvalidData` error if it isn't a saved
+    /// `EquityTable`.
+    #[cfg(feature = "std")]
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        let bytes = std::fs::read(path)?;
+        EquityTable::from_bytes(&bytes)
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidData, "not a saved EquityTable"))
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__equity_table_tests {
+    use super::*;
+    use crate::cards::two::Two;
+    use crate::CardNumber;
+
+    fn range(index: &'static str) -> Range {
+        Range::from(alloc::vec![Two::try_from(index).unwrap()])
+    }
+
+    #[test]
+    fn get() {
+        let mut table = EquityTable::new();
+        let board = [
+            CardNumber::DEUCE_CLUBS,
+            CardNumber::SEVEN_DIAMONDS,
+            CardNumber::NINE_HEARTS,
+        ];
+        table.insert(&range("A♠ A♥"), &range("K♠ K♥"), &board, 0.8);
+
+        let permuted = [
+            CardNumber::DEUCE_SPADES,
+            CardNumber::SEVEN_HEARTS,
+            CardNumber::NINE_DIAMONDS,
+        ];
+
+        assert_eq!(Some(0.8), table.get(&range("A♦ A♣"), &range("K♦ K♣"), &permuted));
+        assert_eq!(None, table.get(&range("A♦ A♣"), &range("K♠ K♥"), &permuted));
+        assert_eq!(None, table.get(&range("K♦ K♣"), &range("A♦ A♣"), &permuted));
+        assert_eq!(1, table.len());
+    }
+
+    #[test]
+    fn get_or_insert_with() {
+        let mut table = EquityTable::new();
+        let hero = range("A♠ K♠");
+        let villain = Range::all();
+
+        assert_eq!(
+            HandError::NotEnoughCards,
+            table
+                .get_or_insert_with(&hero, &villain, &[], || Err(HandError::NotEnoughCards))
+                .unwrap_err()
+        );
+        assert!(table.is_empty());
+        assert_eq!(Ok(0.67), table.get_or_insert_with(&hero, &villain, &[], || Ok(0.67)));
+        assert_eq!(
+            Ok(0.67),
+            table.get_or_insert_with(&range("A♥ K♥"), &villain, &[], || Ok(0.0))
+        );
+    }
+
+    #[test]
+    fn to_bytes() {
+        let mut table = EquityTable::new();
+        table.insert(&range("A♠ A♥"), &Range::all(), &[CardNumber::DEUCE_CLUBS], 0.8);
+        table.insert(&range("7♠ 2♥"), &range("A♠ K♠"), &[], 0.3);
+
+        let bytes = table.to_bytes();
+
+        assert_eq!(9 + 2 * ENTRY_LEN, bytes.len());
+        assert_eq!(table, EquityTable::from_bytes(&bytes).unwrap());
+    }
+
+    #[test]
+    fn from_bytes__invalid() {
+        let bytes = EquityTable::new().to_bytes();
+        let mut wrong_version = bytes.clone();
+        wrong_version[4] = 0;
+
+        assert!(EquityTable::from_bytes(&bytes).unwrap().is_empty());
+        assert_eq!(
+            HandError::InvalidBinaryFormat,
+            EquityTable::from_bytes(&wrong_version).unwrap_err()
+        );
+        assert_eq!(
+            HandError::InvalidBinaryFormat,
+            EquityTable::from_bytes(&bytes[..8]).unwrap_err()
+        );
+        assert_eq!(
+            HandError::InvalidBinaryFormat,
+            EquityTable::from_bytes(&[bytes.clone(), alloc::vec![0]].concat()).unwrap_err()
+        );
+        let mut too_long = bytes;
+        too_long[5..9].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            HandError::InvalidBinaryFormat,
+            EquityTable::from_bytes(&too_long).unwrap_err()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn save() {
+        let mut table = EquityTable::new();
+        table.insert(&range("A♠ A♥"), &range("K♠ K♥"), &[], 0.82);
+        let path = std::env::temp_dir().join(alloc::format!("ckc-equity-table-{}.bin", std::process::id()));
+
+        table.save(&path).unwrap();
+        let loaded = EquityTable::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(table, loaded);
+    }
+}
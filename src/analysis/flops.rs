@@ -0,0 +1,321 @@
+use crate::analysis::equity::Tally;
+use crate::analysis::range::Range;
+use crate::cancel::Cancellation;
+use crate::cards::binary_card::{BinaryCard, BC64};
+use crate::cards::seven::seven;
+use crate::cards::three::Three;
+use crate::cards::{HandRanker, HandValidator, RankBits};
+use crate::deck::{Deck, POKER_DECK};
+use crate::hand_rank::HandRankValue;
+use crate::{CKCNumber, CardRank, CardSuit, PokerCard};
+use alloc::vec::Vec;
+
+/// How many suits a flop has.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FlopSuits {
+    Rainbow,
+    TwoTone,
+    Monotone,
+}
+
+/// How many of a flop's cards share a rank.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FlopPairing {
+    Unpaired,
+    Paired,
+    Trips,
+}
+
+/// The broad shape of a flop, as used to group flops in aggregate reports.
+///
+/// * `high` is the rank of the flop's highest card.
+/// * `straight_possible` is true if some two hole cards make a straight with the flop.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct FlopTexture {
+    pub suits: FlopSuits,
+    pub pairing: FlopPairing,
+    pub high: CardRank,
+    pub straight_possible: bool,
+}
+
+impl FlopTexture {
+    #[must_use]
+    pub fn new(flop: Three) -> Self {
+        let suits = match flop.suit_counts().iter().max() {
+            Some(3) => FlopSuits::Monotone,
+            Some(2) => FlopSuits::TwoTone,
+            _ => FlopSuits::Rainbow,
+        };
+        let pairing = match flop.rank_counts().iter().max() {
+            Some(3) => FlopPairing::Trips,
+            Some(2) => FlopPairing::Paired,
+            _ => FlopPairing::Unpaired,
+        };
+
+        // Shift the ranks up one so that the ace can also play low, as bit 0.
+        let mask = flop.rank_mask();
+        let ranks = (mask << 1) | (mask >> 12);
+        let straight_possible = (0..10).any(|shift| ((ranks >> shift) & 0b1_1111).count_ones() >= 3);
+
+        FlopTexture {
+            suits,
+            pairing,
+            high: flop.sort().first().get_card_rank(),
+            straight_possible,
+        }
+    }
+}
+
+/// One strategically distinct flop in `aggregate_over_flops()`.
+///
+/// * `weight` is how many of the 22,100 flops the report stands for, since flops that only
+///   differ by a permutation of their suits are only visited once.
+/// * `equity` is the first range's all in equity against the second on this flop, over every
+///   live pair of combos and every turn and river.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FlopReport {
+    pub flop: Three,
+    pub weight: usize,
+    pub equity: f64,
+    pub texture: FlopTexture,
+}
+
+/// Visits each of the 1,755 strategically distinct flops, calling `f` with the first range's
+/// equity against the second on it. Flops where no combo in one range is live against a combo
+/// in the other are skipped.
+///
+/// Each flop stands in for every flop that it's a suit permutation of, so the weighted results
+/// are only exact for ranges that don't favour any suit, such as ones made of whole
+/// `PreflopClasses`. Every turn and river is enumerated for every flop, so this is slow for
+/// wide ranges.
+pub fn aggregate_over_flops(range_a: &Range, range_b: &Range, f: impl FnMut(FlopReport)) {
+    aggregate(range_a, range_b, canonical_flops(), Cancellation::NEVER, f);
+}
+
+/// The same as `aggregate_over_flops()`, except that it checks the `Cancellation` before each
+/// turn of each flop, and stops if it's set. The flop it was working on is dropped, so `f` has
+/// only been called with finished flops.
+///
+/// Returns true if every flop was visited, and false if it was cancelled.
+pub fn aggregate_over_flops_cancellable(
+    range_a: &Range,
+    range_b: &Range,
+    cancellation: Cancellation,
+    f: impl FnMut(FlopReport),
+) -> bool {
+    aggregate(range_a, range_b, canonical_flops(), cancellation, f)
+}
+
+fn aggregate(
+    range_a: &Range,
+    range_b: &Range,
+    flops: Vec<(Three, usize)>,
+    cancellation: Cancellation,
+    mut f: impl FnMut(FlopReport),
+) -> bool {
+    for (flop, weight) in flops {
+        let Some(tally) = flop_tally(range_a, range_b, flop, cancellation) else {
+            return false;
+        };
+        if tally.total == 0 {
+            continue;
+        }
+        f(FlopReport {
+            flop,
+            weight,
+            equity: tally.equity(),
+            texture: FlopTexture::new(flop),
+        });
+    }
+    true
+}
+
+/// Every flop whose suits are in canonical order, along with how many flops it stands for.
+///
+/// A `BinaryCard` holds each suit in its own 13 bits, so permuting the suits of a flop permutes
+/// those fields. The flop is canonical when the fields are in descending order, and its weight
+/// is the number of distinct ways to order them.
+fn canonical_flops() -> Vec<(Three, usize)> {
+    let mut flops = Vec::new();
+    for (i, first) in POKER_DECK.iter().enumerate() {
+        for (j, second) in POKER_DECK.iter().enumerate().skip(i + 1) {
+            for third in &POKER_DECK[j + 1..] {
+                let bits = BinaryCard::from_ckc(*first) | BinaryCard::from_ckc(*second) | BinaryCard::from_ckc(*third);
+                let fields = suit_fields(bits);
+                if fields.windows(2).all(|pair| pair[0] >= pair[1]) {
+                    flops.push((Three::from([*first, *second, *third]), orderings(fields)));
+                }
+            }
+        }
+    }
+    flops
+}
+
+/// The spades, hearts, diamonds and clubs fields of a `BinaryCard`.
+fn suit_fields(bits: BinaryCard) -> [u16; 4] {
+    CardSuit::all().map(|suit| bits.suit_mask(suit))
+}
+
+/// The number of distinct orderings of four sorted fields, which is 24 divided by the
+/// factorial of the size of each run of equal fields.
+fn orderings(fields: [u16; 4]) -> usize {
+    let mut orderings = 24;
+    let mut run = 1;
+    for i in 1..4 {
+        if fields[i] == fields[i - 1] {
+            run += 1;
+            orderings /= run;
+        } else {
+            run = 1;
+        }
+    }
+    orderings
+}
+
+/// Returns `None` if the `Cancellation` was set before every turn and river was tallied.
+fn flop_tally(range_a: &Range, range_b: &Range, flop: Three, cancellation: Cancellation) -> Option<Tally> {
+    let mut tally = Tally::default();
+    let flop_bits = BinaryCard::from_three(flop);
+    let matchup = range_a
+        .live(flop_bits)
+        .any(|a| range_b.live(flop_bits | BinaryCard::from_two(*a)).next().is_some());
+    if !matchup {
+        return Some(tally);
+    }
+    let live = Deck::live(flop_bits);
+
+    for (i, turn) in live.iter().enumerate() {
+        if cancellation.is_cancelled() {
+            return None;
+        }
+        for river in &live[i + 1..] {
+            let board = flop_bits | BinaryCard::from_ckc(*turn) | BinaryCard::from_ckc(*river);
+            let a = ranked(range_a, board, flop, *turn, *river);
+            let b = ranked(range_b, board, flop, *turn, *river);
+            for (a_bits, a_value) in &a {
+                for (b_bits, b_value) in &b {
+                    if a_bits & b_bits == 0 {
+                        tally.record(*a_value, *b_value);
+                    }
+                }
+            }
+        }
+    }
+    Some(tally)
+}
+
+/// Ranks every combo in the `Range` that doesn't use a board card.
+fn ranked(
+    range: &Range,
+    board: BinaryCard,
+    flop: Three,
+    turn: CKCNumber,
+    river: CKCNumber,
+) -> Vec<(BinaryCard, HandRankValue)> {
+    range
+        .live(board)
+        .map(|combo| {
+            (
+                BinaryCard::from_two(*combo),
+                seven(*combo, flop, turn, river).hand_rank_value(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__flops_tests {
+    use super::*;
+    use crate::cards::two::Two;
+    use core::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn canonical_flops() {
+        let flops = super::canonical_flops();
+
+        assert_eq!(1755, flops.len());
+        assert_eq!(22100, flops.iter().map(|(_, weight)| weight).sum::<usize>());
+    }
+
+    #[test]
+    fn flop_texture() {
+        let texture = FlopTexture::new(Three::try_from("K♠ 7♥ 2♦").unwrap());
+
+        assert_eq!(FlopSuits::Rainbow, texture.suits);
+        assert_eq!(FlopPairing::Unpaired, texture.pairing);
+        assert_eq!(CardRank::KING, texture.high);
+        assert!(!texture.straight_possible);
+    }
+
+    #[test]
+    fn flop_texture__wheel() {
+        let texture = FlopTexture::new(Three::try_from("3♠ A♠ 5♠").unwrap());
+
+        assert_eq!(FlopSuits::Monotone, texture.suits);
+        assert_eq!(CardRank::ACE, texture.high);
+        assert!(texture.straight_possible);
+    }
+
+    #[test]
+    fn flop_texture__paired() {
+        let texture = FlopTexture::new(Three::try_from("9♠ 9♥ T♠").unwrap());
+
+        assert_eq!(FlopSuits::TwoTone, texture.suits);
+        assert_eq!(FlopPairing::Paired, texture.pairing);
+        assert!(!texture.straight_possible);
+    }
+
+    #[test]
+    fn aggregate() {
+        let aces = Range::from(alloc::vec![Two::try_from("A♦ A♣").unwrap()]);
+        let kings = Range::from(alloc::vec![Two::try_from("K♦ K♣").unwrap()]);
+        let set = Three::try_from("K♠ 7♥ 2♦").unwrap();
+        let dead = Three::try_from("A♦ 7♥ 2♠").unwrap();
+        let overpair = Three::try_from("Q♠ Q♥ 2♠").unwrap();
+        let mut reports = Vec::new();
+
+        super::aggregate(
+            &aces,
+            &kings,
+            alloc::vec![(set, 24), (dead, 24), (overpair, 12)],
+            Cancellation::NEVER,
+            |report| reports.push(report),
+        );
+
+        // The flop with the A♦ is skipped.
+        assert_eq!(2, reports.len());
+        assert_eq!(set, reports[0].flop);
+        assert_eq!(24, reports[0].weight);
+        assert_eq!(FlopTexture::new(set), reports[0].texture);
+        // The kings flopped a set, so the aces need one of the two aces left, or runner runner.
+        assert!(reports[0].equity < 0.15);
+        assert!(reports[1].equity > 0.8);
+    }
+
+    #[test]
+    fn aggregate_over_flops__dead() {
+        let aces = Range::from(alloc::vec![Two::try_from("A♠ A♥").unwrap()]);
+        let mut reports = 0;
+
+        super::aggregate_over_flops(&aces, &aces, |_| reports += 1);
+
+        assert_eq!(0, reports);
+    }
+
+    #[test]
+    fn aggregate_over_flops_cancellable() {
+        let aces = Range::from(alloc::vec![Two::try_from("A♦ A♣").unwrap()]);
+        let kings = Range::from(alloc::vec![Two::try_from("K♦ K♣").unwrap()]);
+        let flag = AtomicBool::new(false);
+        let mut reports = 0;
+
+        let complete = super::aggregate_over_flops_cancellable(&aces, &kings, Cancellation::from(&flag), |_| {
+            reports += 1;
+            flag.store(reports == 3, Ordering::Relaxed);
+        });
+
+        assert!(!complete);
+        assert_eq!(3, reports);
+    }
+}
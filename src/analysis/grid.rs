@@ -0,0 +1,124 @@
+use crate::analysis::preflop::{PreflopClass, PreflopMatrix, RANK_CHARS};
+use crate::analysis::range::Range;
+use alloc::string::{String, ToString};
+use core::fmt::Write;
+
+/// How `render_grid()` lays out a chart.
+///
+/// * `Ascii` - a 13x13 grid with the hands that are in shown by name, and the rest as `--`.
+/// * `Unicode` - the same grid, with the hands that are out shown as `··`.
+/// * `Csv` - a header row of ranks, then a row per rank with the names of the hands that are in
+///   and empty cells for the rest.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum GridFormat {
+    Ascii,
+    Unicode,
+    Csv,
+}
+
+/// Renders a chart of `PreflopClasses` with the rows and columns running from aces down to
+/// deuces, suited hands above the diagonal and offsuit hands below it. Every row ends with a
+/// newline.
+#[must_use]
+pub fn render_grid(matrix: &PreflopMatrix<bool>, format: GridFormat) -> String {
+    let mut grid = String::new();
+
+    if format == GridFormat::Csv {
+        for c in RANK_CHARS {
+            grid.push(',');
+            grid.push(c);
+        }
+        grid.push('\n');
+    }
+
+    for (row, cells) in matrix.rows().enumerate() {
+        let mut line = String::new();
+        if format == GridFormat::Csv {
+            line.push(RANK_CHARS[row]);
+        }
+        for (col, included) in cells.iter().enumerate() {
+            let name = PreflopClass::from_grid(row, col)
+                .map(|class| class.to_string())
+                .unwrap_or_default();
+            // Writing to a String can't fail.
+            let _ = match (format, included) {
+                (GridFormat::Csv, true) => write!(line, ",{name}"),
+                (GridFormat::Csv, false) => write!(line, ","),
+                (_, true) => write!(line, "{name:<4}"),
+                (GridFormat::Ascii, false) => write!(line, "{:<4}", "--"),
+                (GridFormat::Unicode, false) => write!(line, "{:<4}", "··"),
+            };
+        }
+        grid.push_str(line.trim_end());
+        grid.push('\n');
+    }
+    grid
+}
+
+/// Renders the `PreflopClasses` that have at least one combo in the `Range`. See
+/// `render_grid()`.
+#[must_use]
+pub fn render_range(range: &Range, format: GridFormat) -> String {
+    render_grid(&range.to_matrix(), format)
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__grid_tests {
+    use super::*;
+    use crate::cards::two::Two;
+
+    fn matrix() -> PreflopMatrix<bool> {
+        let mut matrix = PreflopMatrix::new(false);
+        matrix[PreflopClass::AA] = true;
+        matrix[PreflopClass::AKs] = true;
+        matrix[PreflopClass::AKo] = true;
+        matrix[PreflopClass::_22] = true;
+        matrix
+    }
+
+    #[test]
+    fn render_grid__ascii() {
+        let grid = render_grid(&matrix(), GridFormat::Ascii);
+        let lines: alloc::vec::Vec<&str> = grid.lines().collect();
+
+        assert_eq!(13, lines.len());
+        assert_eq!("AA  AKs --  --  --  --  --  --  --  --  --  --  --", lines[0]);
+        assert_eq!("AKo --  --  --  --  --  --  --  --  --  --  --  --", lines[1]);
+        assert_eq!("--  --  --  --  --  --  --  --  --  --  --  --  22", lines[12]);
+    }
+
+    #[test]
+    fn render_grid__unicode() {
+        let grid = render_grid(&matrix(), GridFormat::Unicode);
+
+        assert!(grid.starts_with("AA  AKs ··  ··"));
+        assert!(grid.ends_with("··  22\n"));
+    }
+
+    #[test]
+    fn render_grid__csv() {
+        let grid = render_grid(&matrix(), GridFormat::Csv);
+        let lines: alloc::vec::Vec<&str> = grid.lines().collect();
+
+        assert_eq!(14, lines.len());
+        assert_eq!(",A,K,Q,J,T,9,8,7,6,5,4,3,2", lines[0]);
+        assert_eq!("A,AA,AKs,,,,,,,,,,,", lines[1]);
+        assert_eq!("2,,,,,,,,,,,,,22", lines[13]);
+    }
+
+    #[test]
+    fn render_range() {
+        let range = Range::from(alloc::vec![
+            Two::try_from("A♠ A♥").unwrap(),
+            Two::try_from("A♠ K♠").unwrap(),
+            Two::try_from("A♦ K♣").unwrap(),
+            Two::try_from("2♠ 2♥").unwrap(),
+        ]);
+
+        assert_eq!(
+            render_grid(&matrix(), GridFormat::Ascii),
+            super::render_range(&range, GridFormat::Ascii)
+        );
+    }
+}
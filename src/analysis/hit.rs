@@ -0,0 +1,242 @@
+use crate::cards::five::Five;
+use crate::cards::three::Three;
+use crate::cards::two::Two;
+use crate::cards::{HandRanker, HandValidator, StraightDraw};
+use crate::hand_rank::HandRankName;
+use crate::{CKCNumber, PokerCard};
+
+/// The best hand that hole cards make with a flop, strongest first. Hands that only come from
+/// the flop, such as a pair on the board, don't count, so `A♠ K♥` on `7♦ 7♣ 2♠` is `Nothing`.
+///
+/// * `Set` - a pocket pair that matches a card on the flop.
+/// * `Trips` - one hole card that matches a pair on the flop.
+/// * `Overpair` - a pocket pair above every card on the flop.
+/// * `Underpair` - a pocket pair below the flop's top card.
+/// * `TopPair`, `MiddlePair` and `WeakPair` - one hole card that matches the flop's highest,
+///   middle or lowest rank.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum MadeHand {
+    StraightFlush,
+    Quads,
+    FullHouse,
+    Flush,
+    Straight,
+    Set,
+    Trips,
+    TwoPair,
+    Overpair,
+    TopPair,
+    MiddlePair,
+    WeakPair,
+    Underpair,
+    Nothing,
+}
+
+/// How two hole cards hit a flop: the hand they make, along with any draws to a better one.
+///
+/// * `flush_draw` is true if the cards have four of a suit, with at least one of them in the
+///   hole, and no flush yet.
+/// * `straight_draw` is the draw to a straight, if the cards haven't made one yet.
+/// * `overcards` is true if both hole cards are above every card on the flop.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct FlopHit {
+    pub made: MadeHand,
+    pub flush_draw: bool,
+    pub straight_draw: StraightDraw,
+    pub overcards: bool,
+}
+
+impl FlopHit {
+    /// True for an open ended straight draw or a double gutshot, which both have eight outs.
+    #[must_use]
+    pub fn is_oesd(&self) -> bool {
+        matches!(
+            self.straight_draw,
+            StraightDraw::OpenEnded | StraightDraw::DoubleGutshot
+        )
+    }
+
+    #[must_use]
+    pub fn is_gutshot(&self) -> bool {
+        self.straight_draw == StraightDraw::Gutshot
+    }
+
+    /// True for a flush draw and a straight draw at the same time.
+    #[must_use]
+    pub fn is_combo_draw(&self) -> bool {
+        self.flush_draw && self.straight_draw != StraightDraw::None
+    }
+
+    /// True if the cards made a pair or better with the flop.
+    #[must_use]
+    pub fn is_made(&self) -> bool {
+        self.made != MadeHand::Nothing
+    }
+
+    /// True if the cards missed the flop completely: no made hand, no draw and no overcards.
+    #[must_use]
+    pub fn is_air(&self) -> bool {
+        !self.is_made() && !self.flush_draw && self.straight_draw == StraightDraw::None && !self.overcards
+    }
+}
+
+/// Classifies how the hole cards hit the flop. Invalid cards are classified as air.
+///
+/// ```
+/// use ckc_rs::analysis::hit::{hit_type, MadeHand};
+/// use ckc_rs::cards::three::Three;
+/// use ckc_rs::cards::two::Two;
+///
+/// let hit = hit_type(Two::try_from("A♥ T♥").unwrap(), Three::try_from("T♠ 9♥ 2♥").unwrap());
+///
+/// assert_eq!(MadeHand::TopPair, hit.made);
+/// assert!(hit.flush_draw);
+/// ```
+#[must_use]
+pub fn hit_type(two: Two, flop: Three) -> FlopHit {
+    let five = Five::from([two.first(), two.second(), flop.first(), flop.second(), flop.third()]);
+    if !five.is_valid() {
+        return FlopHit {
+            made: MadeHand::Nothing,
+            flush_draw: false,
+            straight_draw: StraightDraw::None,
+            overcards: false,
+        };
+    }
+
+    let made = match five.hand_rank().name {
+        HandRankName::StraightFlush => MadeHand::StraightFlush,
+        HandRankName::FourOfAKind => MadeHand::Quads,
+        HandRankName::FullHouse => MadeHand::FullHouse,
+        HandRankName::Flush => MadeHand::Flush,
+        HandRankName::Straight => MadeHand::Straight,
+        _ => paired(two, flop),
+    };
+    let flush_draw = made > MadeHand::Flush
+        && five
+            .suit_counts()
+            .iter()
+            .zip(two.suit_counts())
+            .any(|(all, hole)| *all == 4 && hole > 0);
+    let straight_draw = if made > MadeHand::Straight {
+        five.straight_draw_type()
+    } else {
+        StraightDraw::None
+    };
+    let top = flop.iter().map(|card| rank(*card)).max().unwrap_or_default();
+
+    FlopHit {
+        made,
+        flush_draw,
+        straight_draw,
+        overcards: two.iter().all(|card| rank(*card) > top),
+    }
+}
+
+/// Deuces are 0 and aces are 12.
+fn rank(card: CKCNumber) -> u32 {
+    card.get_rank_bit().trailing_zeros()
+}
+
+/// The made hand for anything less than a straight, counting only the ranks that use a hole card.
+fn paired(two: Two, flop: Three) -> MadeHand {
+    let counts = flop.rank_counts();
+    let high = rank(two.first()).max(rank(two.second()));
+    let low = rank(two.first()).min(rank(two.second()));
+
+    // The distinct ranks on the flop, from highest to lowest.
+    let mut ranks = [0; 3];
+    let mut len = 0;
+    for flop_rank in (0..13).rev().filter(|flop_rank| counts[*flop_rank as usize] > 0) {
+        ranks[len] = flop_rank;
+        len += 1;
+    }
+    let pair = |hole_rank: u32| match ranks[..len].iter().position(|flop_rank| *flop_rank == hole_rank) {
+        Some(0) => MadeHand::TopPair,
+        Some(position) if position == len - 1 => MadeHand::WeakPair,
+        _ => MadeHand::MiddlePair,
+    };
+
+    if high == low {
+        return match counts[high as usize] {
+            0 if high > ranks[0] => MadeHand::Overpair,
+            0 => MadeHand::Underpair,
+            _ => MadeHand::Set,
+        };
+    }
+    match (counts[high as usize], counts[low as usize]) {
+        (2.., _) | (_, 2..) => MadeHand::Trips,
+        (1, 1) => MadeHand::TwoPair,
+        (1, _) => pair(high),
+        (_, 1) => pair(low),
+        _ => MadeHand::Nothing,
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__hit_tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn hit(two: &'static str, flop: &'static str) -> FlopHit {
+        hit_type(Two::try_from(two).unwrap(), Three::try_from(flop).unwrap())
+    }
+
+    #[rstest]
+    #[case("9♠ 8♠", "7♠ 6♠ 5♠", MadeHand::StraightFlush)]
+    #[case("7♥ 7♦", "7♠ 7♣ 2♠", MadeHand::Quads)]
+    #[case("K♥ 7♦", "K♠ K♣ 7♠", MadeHand::FullHouse)]
+    #[case("A♠ 2♠", "K♠ 7♠ 5♠", MadeHand::Flush)]
+    #[case("9♠ 8♥", "7♦ 6♣ 5♠", MadeHand::Straight)]
+    #[case("7♥ 7♦", "7♠ K♣ 2♠", MadeHand::Set)]
+    #[case("A♥ 7♦", "7♠ 7♣ 2♠", MadeHand::Trips)]
+    #[case("K♥ 7♦", "K♠ 7♣ 2♠", MadeHand::TwoPair)]
+    #[case("Q♥ Q♦", "J♠ 7♣ 2♠", MadeHand::Overpair)]
+    #[case("A♥ J♦", "J♠ 7♣ 2♠", MadeHand::TopPair)]
+    #[case("A♥ 7♦", "J♠ 7♣ 2♠", MadeHand::MiddlePair)]
+    #[case("A♥ 2♦", "J♠ 7♣ 2♠", MadeHand::WeakPair)]
+    #[case("A♥ 2♦", "J♠ J♣ 2♠", MadeHand::WeakPair)]
+    #[case("8♥ 8♦", "J♠ 7♣ 2♠", MadeHand::Underpair)]
+    #[case("3♥ 3♦", "J♠ 7♣ 4♠", MadeHand::Underpair)]
+    #[case("Q♥ Q♦", "J♠ J♣ 2♠", MadeHand::Overpair)]
+    #[case("A♥ K♦", "7♠ 7♣ 2♠", MadeHand::Nothing)]
+    #[case("A♥ K♦", "J♠ 7♣ 2♠", MadeHand::Nothing)]
+    fn hit_type__made(#[case] two: &'static str, #[case] flop: &'static str, #[case] expected: MadeHand) {
+        assert_eq!(expected, hit(two, flop).made);
+    }
+
+    #[test]
+    fn hit_type__draws() {
+        let combo = hit("9♥ 8♥", "T♥ 7♥ 2♠");
+        let gutshot = hit("Q♠ T♦", "J♥ 8♣ 2♠");
+        let overcards = hit("A♠ K♦", "J♥ 8♣ 2♠");
+        let monotone = hit("A♠ K♦", "J♥ 8♥ 2♥");
+        let air = hit("5♠ 3♦", "J♥ 9♣ T♠");
+
+        assert_eq!(MadeHand::Nothing, combo.made);
+        assert!(combo.flush_draw && combo.is_oesd() && combo.is_combo_draw());
+        assert!(gutshot.is_gutshot() && !gutshot.is_oesd());
+        assert!(overcards.overcards && !overcards.is_air());
+        assert!(!monotone.flush_draw);
+        assert!(air.is_air());
+    }
+
+    #[test]
+    fn hit_type__made_hands_dont_draw() {
+        let flush = hit("A♠ 2♠", "K♠ Q♠ J♠");
+        let straight = hit("9♠ 8♥", "7♥ 6♥ 5♣");
+
+        assert!(!flush.flush_draw);
+        assert_eq!(StraightDraw::None, flush.straight_draw);
+        assert_eq!(StraightDraw::None, straight.straight_draw);
+        assert!(!straight.flush_draw);
+    }
+
+    #[test]
+    fn hit_type__invalid() {
+        let hit = hit_type(Two::default(), Three::try_from("J♠ 7♣ 2♠").unwrap());
+
+        assert!(hit.is_air());
+    }
+}
@@ -0,0 +1,50 @@
+//! Tools for analysing hands against ranges and across streets.
+
+pub mod bluff;
+pub mod bucket;
+pub mod canonical;
+#[cfg(feature = "rand")]
+pub mod cooler;
+pub mod equity;
+pub mod equity_table;
+pub mod flops;
+pub mod grid;
+pub mod hit;
+pub mod nuts;
+pub mod omaha_range;
+pub mod pattern;
+pub mod preflop;
+pub mod range;
+pub mod realization;
+pub mod river_table;
+pub mod runout;
+pub mod street;
+pub mod tree;
+pub mod variance;
+
+pub use bluff::{bluff_catch_value, BluffCatch};
+pub use bucket::{bucket_hands, bucket_values, Bucketing, HandBucket};
+#[cfg(feature = "rand")]
+pub use cooler::{cooler_frequencies, BoardType, Cooler, CoolerFrequencies};
+pub use equity::{equity_vs_random, EquityCache, EquityEstimate};
+#[cfg(feature = "rand")]
+pub use equity::{sample_equity_vs_random, Sampling};
+pub use equity_table::{EquityTable, EQUITY_TABLE_VERSION};
+pub use flops::{
+    aggregate_over_flops, aggregate_over_flops_cancellable, FlopPairing, FlopReport, FlopSuits, FlopTexture,
+};
+pub use grid::{render_grid, render_range, GridFormat};
+pub use hit::{hit_type, FlopHit, MadeHand};
+pub use nuts::{nut_analysis, NutAnalysis, NutHand};
+pub use omaha_range::{OmahaRange, OmahaSuits};
+pub use pattern::{Pattern, PatternSuits};
+pub use preflop::{PreflopClass, PreflopMatrix, StartingHandTier, PREFLOP_EQUITY, SKLANSKY_GROUPS};
+pub use range::{Range, StartingHandMetric};
+pub use realization::{realization_factor, RealizationTable, POSITION_REALIZATION, REALIZATION_ON_BUTTON};
+pub use river_table::{canonical_rivers, river_equities, RiverTable, CANONICAL_RIVERS, RIVER_TABLE_VERSION};
+pub use runout::{runout_outcomes, OutcomeTable, RiverCard, RiverOutcome};
+pub use street::{
+    equity_by_street, equity_by_street_cancellable, river_delta, street_delta, RiverDelta, StreetDelta, StreetEquity,
+};
+pub use tree::{RunoutNode, RunoutTree};
+pub use variance::WinRate;
@@ -0,0 +1,133 @@
+use crate::analysis::range::Range;
+use crate::cards::binary_card::{BinaryCard, BC64};
+use crate::cards::two::Two;
+use crate::cards::HandValidator;
+use crate::evaluate::best_five_of;
+use crate::hand_rank::HandRank;
+use crate::{CKCNumber, HandError};
+use alloc::vec::Vec;
+
+/// A hole card combination and the hand it makes on the board.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct NutHand {
+    pub two: Two,
+    pub rank: HandRank,
+}
+
+/// The strongest hands that can be made on a board.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct NutAnalysis {
+    /// The best possible `HandRank` on the board.
+    pub nuts: HandRank,
+    /// How many hole card combinations make the nuts.
+    pub nut_combos: usize,
+    /// The strongest hole card combinations, strongest first.
+    pub hands: Vec<NutHand>,
+}
+
+/// Ranks every hole card combination that doesn't share a card with the board, returning the
+/// nuts and the `top` strongest combinations. The board can be a flop, turn or river, and hands
+/// are ranked by the best five cards available so far.
+///
+/// # Errors
+///
+/// Returns `HandError::NotEnoughCards` or `HandError::TooManyCards` if the board doesn't have
+/// three to five cards, `HandError::InvalidCard` if any of them are invalid, and
+/// `HandError::DuplicateCard` if any of them repeat.
+pub fn nut_analysis<B: HandValidator>(board: &B, top: usize) -> Result<NutAnalysis, HandError> {
+    let cards: Vec<CKCNumber> = board.iter().copied().collect();
+    match cards.len() {
+        0..=2 => return Err(HandError::NotEnoughCards),
+        3..=5 => {},
+        _ => return Err(HandError::TooManyCards),
+    }
+    if board.is_corrupt() {
+        return Err(HandError::InvalidCard);
+    }
+    if !board.are_unique() {
+        return Err(HandError::DuplicateCard);
+    }
+
+    let dead = cards
+        .iter()
+        .fold(BinaryCard::BLANK, |bits, card| bits | BinaryCard::from_ckc(*card));
+
+    let mut hands = Vec::with_capacity(Range::ALL_COMBOS);
+    let mut hand = cards.clone();
+    for two in Range::all().live(dead) {
+        hand.truncate(cards.len());
+        hand.extend(two.iter());
+        let (rank, _) = best_five_of(&hand)?;
+        hands.push(NutHand { two: *two, rank });
+    }
+    hands.sort_by_key(|hand| core::cmp::Reverse(hand.rank));
+
+    let nuts = hands.first().map(|hand| hand.rank).unwrap_or_default();
+    let nut_combos = hands.iter().take_while(|hand| hand.rank == nuts).count();
+    hands.truncate(top);
+
+    Ok(NutAnalysis {
+        nuts,
+        nut_combos,
+        hands,
+    })
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__nuts_tests {
+    use super::*;
+    use crate::cards::five::Five;
+    use crate::cards::four::Four;
+    use crate::cards::three::Three;
+    use crate::hand_rank::{HandRankClass, HandRankName};
+
+    #[test]
+    fn nut_analysis__flop() {
+        let board = Three::try_from("A♠ K♠ Q♠").unwrap();
+
+        let analysis = nut_analysis(&board, 5).unwrap();
+
+        assert_eq!(HandRankClass::RoyalFlush, analysis.nuts.class);
+        assert_eq!(1, analysis.nut_combos);
+        assert_eq!(Two::try_from("J♠ T♠").unwrap().sort(), analysis.hands[0].two.sort());
+        assert_eq!(5, analysis.hands.len());
+        // Two hole cards can't make quads or a full house on this board, so the next best is a flush.
+        assert_eq!(HandRankName::Flush, analysis.hands[1].rank.name);
+    }
+
+    #[test]
+    fn nut_analysis__turn() {
+        let board = Four::try_from("A♥ A♦ 7♣ 2♠").unwrap();
+
+        let analysis = nut_analysis(&board, 1).unwrap();
+
+        assert_eq!(HandRankClass::FourAces, analysis.nuts.class);
+        assert_eq!(1, analysis.nut_combos);
+        assert_eq!(1, analysis.hands.len());
+    }
+
+    #[test]
+    fn nut_analysis__river() {
+        // The board plays for everyone.
+        let board = Five::try_from("A♠ K♠ Q♠ J♠ T♠").unwrap();
+
+        let analysis = nut_analysis(&board, 3).unwrap();
+
+        assert_eq!(1, analysis.nuts.value);
+        assert_eq!(1081, analysis.nut_combos);
+    }
+
+    #[test]
+    fn nut_analysis__errors() {
+        assert_eq!(
+            HandError::NotEnoughCards,
+            nut_analysis(&Two::try_from("A♠ K♠").unwrap(), 1).unwrap_err()
+        );
+        assert_eq!(HandError::InvalidCard, nut_analysis(&Three::default(), 1).unwrap_err());
+        assert_eq!(
+            HandError::DuplicateCard,
+            nut_analysis(&Three::try_from("A♠ A♠ K♠").unwrap(), 1).unwrap_err()
+        );
+    }
+}
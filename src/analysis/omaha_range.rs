@@ -0,0 +1,296 @@
+use crate::cards::binary_card::{conflicts, BinaryCard};
+use crate::cards::four::Four;
+use crate::cards::HandValidator;
+use crate::deck::POKER_DECK;
+use crate::math::Combinations;
+use crate::{CardRank, HandError};
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::slice::Iter;
+
+/// How the suits of an Omaha hand are spread.
+///
+/// * `DoubleSuited` - two cards of one suit and two of another, written `ds`.
+/// * `SingleSuited` - exactly two cards of one suit, and the other two of different suits,
+///   written `ss`.
+/// * `Rainbow` - four different suits, written `r`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum OmahaSuits {
+    DoubleSuited,
+    SingleSuited,
+    Rainbow,
+}
+
+impl OmahaSuits {
+    #[must_use]
+    pub fn matches(self, four: &Four) -> bool {
+        let mut counts = four.suit_counts();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        match self {
+            OmahaSuits::DoubleSuited => counts == [2, 2, 0, 0],
+            OmahaSuits::SingleSuited => counts == [2, 1, 1, 0],
+            OmahaSuits::Rainbow => counts == [1, 1, 1, 1],
+        }
+    }
+
+    fn from_qualifier(qualifier: &str) -> Result<Option<OmahaSuits>, HandError> {
+        match qualifier.to_ascii_lowercase().as_str() {
+            "" => Ok(None),
+            "ds" => Ok(Some(OmahaSuits::DoubleSuited)),
+            "ss" => Ok(Some(OmahaSuits::SingleSuited)),
+            "r" => Ok(Some(OmahaSuits::Rainbow)),
+            _ => Err(HandError::InvalidIndex),
+        }
+    }
+}
+
+/// One term of an Omaha range, such as `AAxx` or `T987ss`: the ranks that the hand must hold,
+/// and how its suits must be spread, if it matters.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct OmahaPattern {
+    /// How many cards of each rank the hand needs, from deuce (0) to ace (12).
+    ranks: [u8; 13],
+    suits: Option<OmahaSuits>,
+}
+
+impl OmahaPattern {
+    fn matches(&self, four: &Four) -> bool {
+        let counts = four.rank_counts();
+        self.ranks.iter().zip(counts).all(|(needed, count)| *needed <= count)
+            && self.suits.map_or(true, |suits| suits.matches(four))
+    }
+}
+
+impl TryFrom<&str> for OmahaPattern {
+    type Error = HandError;
+
+    fn try_from(term: &str) -> Result<Self, Self::Error> {
+        let mut ranks = [0u8; 13];
+        let mut positions = 0;
+        let mut qualifier = "";
+        for (i, c) in term.char_indices() {
+            if positions == 4 {
+                qualifier = &term[i..];
+                break;
+            }
+            if !matches!(c, 'x' | 'X' | '*') {
+                let rank = CardRank::from_char(c);
+                if rank == CardRank::BLANK {
+                    return Err(HandError::InvalidIndex);
+                }
+                ranks[rank as usize - 2] += 1;
+            }
+            positions += 1;
+        }
+        if positions < 4 {
+            return Err(HandError::NotEnoughCards);
+        }
+        Ok(OmahaPattern {
+            ranks,
+            suits: OmahaSuits::from_qualifier(qualifier)?,
+        })
+    }
+}
+
+/// A set of four card Omaha hands, parsed from the usual range notation.
+///
+/// A range is a comma separated list of terms, and holds every hand that matches any of them.
+/// Each term is four positions followed by an optional suit qualifier:
+///
+/// * A rank, `A` to `2`, which the hand must hold. Repeating a rank, as in `AAxx`, needs that
+///   many cards of it.
+/// * `x` or `*`, which matches any card.
+/// * `ds` for double suited, `ss` for single suited, and `r` for rainbow, from `OmahaSuits`.
+///
+/// ```
+/// use ckc_rs::analysis::omaha_range::OmahaRange;
+///
+/// let aces = OmahaRange::parse("AAxx").unwrap();
+/// let double_suited = OmahaRange::parse("A*K*ds").unwrap();
+/// let rundowns = OmahaRange::parse("T987ss, 9876ss").unwrap();
+///
+/// // 6,768 hands with two aces, 192 with three and one with all four.
+/// assert_eq!(6_961, aces.len());
+/// assert!(double_suited.contains(&"A♠ K♥ 7♠ 2♥".try_into().unwrap()));
+/// assert_eq!(2 * 144, rundowns.len());
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct OmahaRange {
+    hands: Vec<Four>,
+    /// The `id()` of every hand, so that checking for one doesn't scan them all.
+    ids: BTreeSet<BinaryCard>,
+}
+
+impl OmahaRange {
+    /// The number of distinct four card combinations in a 52 card deck.
+    pub const ALL_COMBOS: usize = 270_725;
+
+    #[must_use]
+    pub fn new() -> Self {
+        OmahaRange::default()
+    }
+
+    /// Parses a range such as `AAxx, KKQQds`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HandError::InvalidIndex` for a character that isn't a rank, a wildcard or a
+    /// suit qualifier, and `HandError::NotEnoughCards` for a term with fewer than four positions.
+    pub fn parse(index: &str) -> Result<Self, HandError> {
+        let patterns = index
+            .split(',')
+            .map(str::trim)
+            .filter(|term| !term.is_empty())
+            .map(OmahaPattern::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let hands: Vec<Four> = Combinations::<4>::new(POKER_DECK.len())
+            .map(|indexes| Four::from(indexes.map(|i| POKER_DECK[i])))
+            .filter(|four| patterns.iter().any(|pattern| pattern.matches(four)))
+            .collect();
+        let ids = hands.iter().map(HandValidator::id).collect();
+        Ok(OmahaRange { hands, ids })
+    }
+
+    /// Adds a hand to the `OmahaRange` unless it's invalid or already present.
+    pub fn push(&mut self, four: Four) {
+        if four.is_valid() && self.ids.insert(four.id()) {
+            self.hands.push(four);
+        }
+    }
+
+    #[must_use]
+    pub fn contains(&self, four: &Four) -> bool {
+        self.ids.contains(&four.id())
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.hands.is_empty()
+    }
+
+    pub fn iter(&self) -> Iter<'_, Four> {
+        self.hands.iter()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.hands.len()
+    }
+
+    /// Returns the hands in the `OmahaRange` that don't contain any of the dead cards.
+    pub fn live(&self, dead: BinaryCard) -> impl Iterator<Item = &Four> {
+        self.hands
+            .iter()
+            .filter(move |four| !conflicts(BinaryCard::from(**four), dead))
+    }
+
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<Four> {
+        self.hands.clone()
+    }
+}
+
+impl TryFrom<&str> for OmahaRange {
+    type Error = HandError;
+
+    fn try_from(index: &str) -> Result<Self, Self::Error> {
+        OmahaRange::parse(index)
+    }
+}
+
+impl<'a> IntoIterator for &'a OmahaRange {
+    type Item = &'a Four;
+    type IntoIter = Iter<'a, Four>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Four> for OmahaRange {
+    fn from_iter<I: IntoIterator<Item = Four>>(iter: I) -> Self {
+        let mut range = OmahaRange::new();
+        for four in iter {
+            range.push(four);
+        }
+        range
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__omaha_range_tests {
+    use super::*;
+    use crate::cards::binary_card::BC64;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("AAxx", 6_961)]
+    #[case("aa**", 6_961)]
+    #[case("AAAA", 1)]
+    #[case("xxxx", OmahaRange::ALL_COMBOS)]
+    #[case("KKQQ", 36)]
+    #[case("KKQQds", 6)]
+    #[case("T987", 256)]
+    #[case("T987ss", 144)]
+    #[case("T987r", 24)]
+    #[case("T987ds", 36)]
+    #[case("AAxx, KKxx", 6_961 * 2 - 36)]
+    #[case("T987ss,T987ss", 144)]
+    #[case("", 0)]
+    fn parse(#[case] index: &str, #[case] expected: usize) {
+        assert_eq!(expected, OmahaRange::parse(index).unwrap().len());
+    }
+
+    #[rstest]
+    #[case("AAx", HandError::NotEnoughCards)]
+    #[case("AAxy", HandError::InvalidIndex)]
+    #[case("AAxxss2", HandError::InvalidIndex)]
+    #[case("AAxxdd", HandError::InvalidIndex)]
+    fn parse__errors(#[case] index: &str, #[case] expected: HandError) {
+        assert_eq!(expected, OmahaRange::parse(index).unwrap_err());
+    }
+
+    #[test]
+    fn parse__suits() {
+        let range = OmahaRange::try_from("A*K*ds").unwrap();
+
+        assert!(range.contains(&Four::try_from("A♠ K♥ 7♠ 2♥").unwrap()));
+        assert!(range.contains(&Four::try_from("A♠ A♥ K♠ K♥").unwrap()));
+        assert!(!range.contains(&Four::try_from("A♠ K♠ 7♠ 2♥").unwrap()));
+        assert!(!range.contains(&Four::try_from("Q♠ J♥ 7♠ 2♥").unwrap()));
+        assert!(range.iter().all(|four| OmahaSuits::DoubleSuited.matches(four)));
+    }
+
+    #[test]
+    fn live() {
+        let range = OmahaRange::parse("AAAx").unwrap();
+
+        assert_eq!(4 * 48 + 1, range.len());
+        assert_eq!(48, range.live(BinaryCard::from_index("A♠")).count());
+        assert_eq!(0, range.live(BinaryCard::from_index("A♠ A♥")).count());
+    }
+
+    #[test]
+    fn from_iter() {
+        let four = Four::try_from("A♠ K♥ 7♠ 2♥").unwrap();
+
+        let range: OmahaRange = [four, four, Four::default()].into_iter().collect();
+
+        assert_eq!(1, range.len());
+        assert!(range.contains(&Four::try_from("2♥ 7♠ K♥ A♠").unwrap()));
+    }
+
+    #[test]
+    fn push() {
+        let mut range = OmahaRange::parse("AAAA").unwrap();
+
+        range.push(Four::try_from("A♥ A♠ A♣ A♦").unwrap());
+        range.push(Four::try_from("K♠ K♥ K♦ K♣").unwrap());
+        range.push(Four::try_from("K♣ K♦ K♥ K♠").unwrap());
+
+        assert_eq!(2, range.len());
+        assert_eq!(range, OmahaRange::parse("AAAA, KKKK").unwrap());
+    }
+}
@@ -0,0 +1,308 @@
+//! Matching hands and boards against rank patterns, whatever their suits, such as `A A x y z` for
+//! a board with exactly one pair of aces, or `K x suited` for a suited king.
+//!
+//! ```
+//! use ckc_rs::analysis::pattern::Pattern;
+//! use ckc_rs::analysis::range::Range;
+//! use ckc_rs::cards::five::Five;
+//!
+//! let paired_aces = Pattern::parse("A A x y z").unwrap();
+//!
+//! assert!(paired_aces.matches(&Five::try_from("A♠ 7♦ A♥ 2♣ K♦").unwrap()));
+//! assert!(!paired_aces.matches(&Five::try_from("A♠ 7♦ A♥ 7♣ K♦").unwrap()));
+//! assert!(!paired_aces.matches(&Five::try_from("A♠ 7♦ A♥ A♣ K♦").unwrap()));
+//!
+//! // Filtering the suited kings out of every starting hand.
+//! let suited_kings = Pattern::parse("Kxs").unwrap();
+//!
+//! assert_eq!(48, Range::all().iter().filter(|two| suited_kings.matches(*two)).count());
+//! ```
+
+use crate::cards::HandValidator;
+use crate::{CardRank, HandError};
+use alloc::vec::Vec;
+
+/// How the suits of a hand or board matching a `Pattern` must be spread.
+///
+/// * `Suited` - every card is of the same suit, written `suited` or `s`.
+/// * `Offsuit` - at least two suits, written `offsuit` or `o`.
+/// * `Rainbow` - no two cards of the same suit, written `rainbow` or `r`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PatternSuits {
+    Suited,
+    Offsuit,
+    Rainbow,
+}
+
+impl PatternSuits {
+    #[must_use]
+    pub fn matches<H: HandValidator>(self, hand: &H) -> bool {
+        let suits = hand.suit_counts().iter().filter(|count| **count > 0).count();
+        match self {
+            PatternSuits::Suited => suits == 1,
+            PatternSuits::Offsuit => suits > 1,
+            PatternSuits::Rainbow => hand.suit_counts().iter().all(|count| *count <= 1),
+        }
+    }
+
+    fn from_qualifier(qualifier: &str) -> Result<PatternSuits, HandError> {
+        match qualifier.to_ascii_lowercase().as_str() {
+            "suited" | "s" => Ok(PatternSuits::Suited),
+            "offsuit" | "o" => Ok(PatternSuits::Offsuit),
+            "rainbow" | "r" => Ok(PatternSuits::Rainbow),
+            _ => Err(HandError::InvalidIndex),
+        }
+    }
+}
+
+/// A pattern of ranks that a hand or board of the same number of cards can match, with an
+/// optional constraint on its suits.
+///
+/// A pattern is a list of positions, one per card, which can be separated by spaces or not,
+/// followed by an optional suit qualifier from `PatternSuits`:
+///
+/// * A rank, `A` to `2`, which needs a card of that rank. Repeating a rank, as in `A A`, needs
+///   that many cards of it, and no more unless a `*` makes up the difference.
+/// * A rank variable, `u` to `z`, which stands for a rank that isn't spelled out anywhere else
+///   in the pattern. Repeating a variable needs that many cards of the same rank, and different
+///   variables stand for different ranks, so `x x y` is a pair and a kicker, but never trips.
+/// * `*`, which matches any card at all.
+///
+/// Unlike the `x` of `OmahaRange`, which matches any card, a variable here pins the rank down,
+/// so that `A A x y z` is a board with exactly one pair of aces, while `A A * * *` is any board
+/// with at least two of them.
+///
+/// ```
+/// use ckc_rs::analysis::pattern::Pattern;
+/// use ckc_rs::cards::three::Three;
+/// use ckc_rs::cards::two::Two;
+///
+/// let monotone = Pattern::parse("x y z suited").unwrap();
+/// let paired = Pattern::parse("x x *").unwrap();
+///
+/// assert!(monotone.matches(&Three::try_from("9♥ 6♥ 2♥").unwrap()));
+/// assert!(!monotone.matches(&Three::try_from("9♥ 9♦ 2♥").unwrap()));
+/// assert!(paired.matches(&Three::try_from("9♥ 9♦ 9♣").unwrap()));
+/// assert!(Pattern::parse("A K o").unwrap().matches(&Two::try_from("K♣ A♦").unwrap()));
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Pattern {
+    /// How many cards of each spelled out rank the hand needs, from deuce (0) to ace (12).
+    ranks: [u8; 13],
+    /// How many cards each rank variable needs, in the order they first appear.
+    variables: Vec<u8>,
+    positions: usize,
+    suits: Option<PatternSuits>,
+}
+
+impl Pattern {
+    /// The most cards that a pattern can match, as in a `Seven`.
+    pub const MAX_POSITIONS: usize = 7;
+
+    /// Parses a pattern such as `A A x y z`, `AAxyz` or `K x suited`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HandError::InvalidIndex` for a character that isn't a rank, a variable, a `*` or
+    /// a suit qualifier, or for anything after the qualifier, `HandError::NotEnoughCards` for a
+    /// pattern without any positions, and `HandError::TooManyCards` for one with more than
+    /// `Pattern::MAX_POSITIONS`.
+    pub fn parse(index: &str) -> Result<Self, HandError> {
+        let mut ranks = [0u8; 13];
+        let mut names: Vec<char> = Vec::new();
+        let mut variables: Vec<u8> = Vec::new();
+        let mut positions = 0;
+        let mut suits = None;
+
+        for token in index.split_whitespace() {
+            if suits.is_some() {
+                return Err(HandError::InvalidIndex);
+            }
+            for (i, c) in token.char_indices() {
+                match c.to_ascii_lowercase() {
+                    '*' => {},
+                    name @ 'u'..='z' => {
+                        if let Some(variable) = names.iter().position(|n| *n == name) {
+                            variables[variable] += 1;
+                        } else {
+                            names.push(name);
+                            variables.push(1);
+                        }
+                    },
+                    _ => {
+                        let rank = CardRank::from_char(c);
+                        if rank == CardRank::BLANK {
+                            suits = Some(PatternSuits::from_qualifier(&token[i..])?);
+                            break;
+                        }
+                        ranks[rank as usize - 2] += 1;
+                    },
+                }
+                positions += 1;
+            }
+        }
+
+        if positions == 0 {
+            return Err(HandError::NotEnoughCards);
+        }
+        if positions > Pattern::MAX_POSITIONS {
+            return Err(HandError::TooManyCards);
+        }
+        Ok(Pattern {
+            ranks,
+            variables,
+            positions,
+            suits,
+        })
+    }
+
+    /// Returns true if the hand has as many cards as the pattern has positions, and its ranks
+    /// and suits fit the pattern. A hand with blank or invalid cards never matches.
+    #[must_use]
+    pub fn matches<H: HandValidator>(&self, hand: &H) -> bool {
+        let mut counts = hand.rank_counts();
+        if hand.iter().len() != self.positions
+            || counts.iter().map(|count| usize::from(*count)).sum::<usize>() != self.positions
+        {
+            return false;
+        }
+        if !self.suits.map_or(true, |suits| suits.matches(hand)) {
+            return false;
+        }
+
+        let mut spelled_out = 0u16;
+        for (rank, needed) in self.ranks.iter().enumerate() {
+            if *needed > 0 {
+                if counts[rank] < *needed {
+                    return false;
+                }
+                counts[rank] -= needed;
+                spelled_out |= 1 << rank;
+            }
+        }
+        // Whatever is left over after the variables have their ranks goes to the `*`s, which
+        // there are exactly enough of, since the hand and the pattern are the same size.
+        Pattern::assign(&self.variables, &mut counts, spelled_out)
+    }
+
+    /// Gives each variable a rank of its own, which isn't one of the `taken` ranks, and has
+    /// enough cards left in `counts`, backtracking until every variable has one.
+    fn assign(variables: &[u8], counts: &mut [u8; 13], taken: u16) -> bool {
+        let Some((needed, rest)) = variables.split_first() else {
+            return true;
+        };
+        for rank in 0..13 {
+            if taken & (1 << rank) == 0 && counts[rank] >= *needed {
+                counts[rank] -= needed;
+                let assigned = Pattern::assign(rest, counts, taken | (1 << rank));
+                counts[rank] += needed;
+                if assigned {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+impl TryFrom<&str> for Pattern {
+    type Error = HandError;
+
+    fn try_from(index: &str) -> Result<Self, Self::Error> {
+        Pattern::parse(index)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__pattern_tests {
+    use super::*;
+    use crate::cards::five::Five;
+    use crate::cards::seven::Seven;
+    use crate::cards::three::Three;
+    use crate::cards::two::Two;
+    use crate::CardNumber;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("A A x y z", "A♠ 7♦ A♥ 2♣ K♦", true)]
+    #[case("AAxyz", "A♠ 7♦ A♥ 2♣ K♦", true)]
+    #[case("A A x y z", "A♠ 7♦ A♥ 7♣ K♦", false)]
+    #[case("A A x y z", "A♠ 7♦ A♥ A♣ K♦", false)]
+    #[case("A A * * *", "A♠ 7♦ A♥ A♣ K♦", true)]
+    #[case("x x y y z", "A♠ 7♦ A♥ 7♣ K♦", true)]
+    #[case("x x y y z", "A♠ 7♦ A♥ 7♣ 7♥", false)]
+    #[case("x x x y y", "A♠ 7♦ A♥ 7♣ 7♥", true)]
+    #[case("v w x y z", "A♠ K♠ Q♠ J♠ T♠", true)]
+    #[case("v w x y z suited", "A♠ K♠ Q♠ J♠ T♠", true)]
+    #[case("v w x y z offsuit", "A♠ K♠ Q♠ J♠ T♠", false)]
+    #[case("A x y z *", "A♠ K♠ Q♠ J♠ T♠", true)]
+    #[case("K x y z *", "A♠ K♠ Q♠ J♠ K♦", true)]
+    #[case("K x y z w", "A♠ K♠ Q♠ J♠ K♦", false)]
+    #[case("x y z", "A♠ K♠ Q♠ J♠ T♠", false)]
+    fn matches(#[case] pattern: &str, #[case] index: &'static str, #[case] expected: bool) {
+        let pattern = Pattern::parse(pattern).unwrap();
+
+        assert_eq!(expected, pattern.matches(&Five::try_from(index).unwrap()));
+    }
+
+    #[rstest]
+    #[case("K x suited", "K♥ 4♥", true)]
+    #[case("K x suited", "K♥ 4♣", false)]
+    #[case("Kxs", "4♥ K♥", true)]
+    #[case("Kxo", "4♥ K♥", false)]
+    #[case("Kxo", "4♦ K♥", true)]
+    #[case("K x", "K♦ K♥", false)]
+    #[case("x x", "K♦ K♥", true)]
+    #[case("* *", "K♦ 2♥", true)]
+    fn matches__two(#[case] pattern: &str, #[case] index: &'static str, #[case] expected: bool) {
+        let pattern = Pattern::parse(pattern).unwrap();
+
+        assert_eq!(expected, pattern.matches(&Two::try_from(index).unwrap()));
+    }
+
+    #[test]
+    fn matches__rainbow() {
+        let pattern = Pattern::parse("x y z rainbow").unwrap();
+
+        assert!(pattern.matches(&Three::try_from("K♦ 8♥ 2♣").unwrap()));
+        assert!(!pattern.matches(&Three::try_from("K♦ 8♦ 2♣").unwrap()));
+        assert!(!pattern.matches(&Three::try_from("K♦ K♥ 2♣").unwrap()));
+    }
+
+    #[test]
+    fn matches__blank() {
+        let pattern = Pattern::parse("A x").unwrap();
+
+        assert!(!pattern.matches(&Two::new(CardNumber::ACE_DIAMONDS, CardNumber::BLANK)));
+        assert!(!pattern.matches(&Two::EMPTY));
+    }
+
+    #[test]
+    fn matches__seven() {
+        let pattern = Pattern::parse("A A K K x y z").unwrap();
+
+        assert!(pattern.matches(&Seven::try_from("A♠ A♥ K♦ K♣ 9♠ 5♥ 2♦").unwrap()));
+        assert!(!pattern.matches(&Seven::try_from("A♠ A♥ K♦ K♣ 9♠ 9♥ 2♦").unwrap()));
+    }
+
+    #[rstest]
+    #[case("", HandError::NotEnoughCards)]
+    #[case("suited", HandError::NotEnoughCards)]
+    #[case("A K P", HandError::InvalidIndex)]
+    #[case("A K suited x", HandError::InvalidIndex)]
+    #[case("A K s o", HandError::InvalidIndex)]
+    #[case("A K monotone", HandError::InvalidIndex)]
+    #[case("x x x x y y y y", HandError::TooManyCards)]
+    fn parse__invalid(#[case] index: &str, #[case] expected: HandError) {
+        assert_eq!(expected, Pattern::parse(index).unwrap_err());
+    }
+
+    #[test]
+    fn try_from() {
+        assert_eq!(
+            Pattern::parse("A A x y z").unwrap(),
+            Pattern::try_from("aaXYZ").unwrap()
+        );
+    }
+}
@@ -0,0 +1,934 @@
+use crate::analysis::range::Range;
+use crate::cards::two::Two;
+use crate::cards::HandValidator;
+use crate::deck::POKER_DECK;
+use crate::{CardRank, HandError, PokerCard};
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Index, IndexMut};
+#[cfg(feature = "serde")]
+use serde::de::Error;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub(crate) const RANK_CHARS: [char; 13] = ['A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2'];
+
+/// The Sklansky-Malmuth group of every `PreflopClass`, in chart order. Groups run from 1, the
+/// strongest hands, to 8, with 9 for the hands that aren't in any group.
+#[rustfmt::skip]
+pub const SKLANSKY_GROUPS: [u8; PreflopClass::COUNT] = [
+    1, 1, 2, 2, 3, 5, 5, 5, 5, 5, 5, 5, 5,
+    2, 1, 2, 3, 4, 6, 7, 7, 7, 7, 7, 7, 7,
+    3, 4, 1, 3, 4, 5, 7, 9, 9, 9, 9, 9, 9,
+    4, 5, 5, 1, 3, 4, 6, 8, 9, 9, 9, 9, 9,
+    6, 6, 6, 5, 2, 4, 5, 7, 9, 9, 9, 9, 9,
+    8, 8, 8, 7, 7, 3, 4, 5, 8, 9, 9, 9, 9,
+    9, 9, 9, 8, 8, 7, 4, 5, 6, 8, 9, 9, 9,
+    9, 9, 9, 9, 9, 9, 8, 5, 5, 6, 8, 9, 9,
+    9, 9, 9, 9, 9, 9, 9, 8, 6, 5, 7, 9, 9,
+    9, 9, 9, 9, 9, 9, 9, 9, 8, 6, 6, 7, 9,
+    9, 9, 9, 9, 9, 9, 9, 9, 9, 8, 7, 7, 8,
+    9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 7, 8,
+    9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 7,
+];
+
+/// The all in equity of every `PreflopClass` against one random hand, in chart order, from a
+/// million deals each. Ties count as half.
+#[rustfmt::skip]
+pub const PREFLOP_EQUITY: [f32; PreflopClass::COUNT] = [
+    0.852, 0.669, 0.662, 0.654, 0.647, 0.629, 0.619, 0.610, 0.600, 0.598, 0.590, 0.581, 0.574,
+    0.653, 0.824, 0.634, 0.626, 0.618, 0.600, 0.583, 0.575, 0.566, 0.558, 0.549, 0.541, 0.533,
+    0.644, 0.614, 0.799, 0.603, 0.595, 0.577, 0.560, 0.544, 0.536, 0.528, 0.520, 0.510, 0.502,
+    0.636, 0.604, 0.581, 0.775, 0.576, 0.556, 0.540, 0.524, 0.507, 0.500, 0.491, 0.482, 0.474,
+    0.628, 0.598, 0.573, 0.552, 0.751, 0.541, 0.523, 0.507, 0.490, 0.472, 0.465, 0.457, 0.448,
+    0.607, 0.579, 0.554, 0.532, 0.515, 0.721, 0.509, 0.492, 0.474, 0.457, 0.438, 0.433, 0.425,
+    0.599, 0.560, 0.536, 0.516, 0.497, 0.481, 0.691, 0.480, 0.462, 0.446, 0.428, 0.407, 0.403,
+    0.589, 0.552, 0.518, 0.496, 0.479, 0.463, 0.449, 0.662, 0.453, 0.437, 0.419, 0.401, 0.382,
+    0.577, 0.542, 0.510, 0.479, 0.461, 0.444, 0.432, 0.423, 0.633, 0.432, 0.413, 0.396, 0.377,
+    0.577, 0.533, 0.500, 0.472, 0.442, 0.428, 0.414, 0.406, 0.399, 0.604, 0.414, 0.397, 0.378,
+    0.567, 0.523, 0.492, 0.462, 0.435, 0.407, 0.394, 0.386, 0.380, 0.381, 0.570, 0.387, 0.368,
+    0.558, 0.514, 0.482, 0.452, 0.426, 0.401, 0.375, 0.366, 0.361, 0.363, 0.352, 0.537, 0.360,
+    0.549, 0.506, 0.473, 0.444, 0.416, 0.390, 0.368, 0.346, 0.341, 0.342, 0.332, 0.323, 0.502,
+];
+
+/// A starting hand's standing in one of the popular preflop tier systems.
+///
+/// * `Chen` - the points from Bill Chen's formula, from -1 for `72o` up to 20 for `AA`.
+/// * `Sklansky` - the Sklansky-Malmuth group, from 1 for the premium hands down to 9.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum StartingHandTier {
+    Chen(i8),
+    Sklansky(u8),
+}
+
+/// One of the 169 canonical starting hands in hold'em, ignoring specific suits.
+///
+/// The variants are in chart order: the rows and columns of the standard 13x13 grid run from
+/// aces down to deuces, with pairs on the diagonal, suited hands above it and offsuit hands
+/// below it. So the first row is `AA AKs AQs ... A2s` and the second is `AKo KK KQs ... K2s`.
+///
+/// Identifiers can't start with a digit, so hands without a broadway card are prefixed with
+/// an underscore: `PreflopClass::_72o`.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum PreflopClass {
+    AA,
+    AKs,
+    AQs,
+    AJs,
+    ATs,
+    A9s,
+    A8s,
+    A7s,
+    A6s,
+    A5s,
+    A4s,
+    A3s,
+    A2s,
+    AKo,
+    KK,
+    KQs,
+    KJs,
+    KTs,
+    K9s,
+    K8s,
+    K7s,
+    K6s,
+    K5s,
+    K4s,
+    K3s,
+    K2s,
+    AQo,
+    KQo,
+    QQ,
+    QJs,
+    QTs,
+    Q9s,
+    Q8s,
+    Q7s,
+    Q6s,
+    Q5s,
+    Q4s,
+    Q3s,
+    Q2s,
+    AJo,
+    KJo,
+    QJo,
+    JJ,
+    JTs,
+    J9s,
+    J8s,
+    J7s,
+    J6s,
+    J5s,
+    J4s,
+    J3s,
+    J2s,
+    ATo,
+    KTo,
+    QTo,
+    JTo,
+    TT,
+    T9s,
+    T8s,
+    T7s,
+    T6s,
+    T5s,
+    T4s,
+    T3s,
+    T2s,
+    A9o,
+    K9o,
+    Q9o,
+    J9o,
+    T9o,
+    _99,
+    _98s,
+    _97s,
+    _96s,
+    _95s,
+    _94s,
+    _93s,
+    _92s,
+    A8o,
+    K8o,
+    Q8o,
+    J8o,
+    T8o,
+    _98o,
+    _88,
+    _87s,
+    _86s,
+    _85s,
+    _84s,
+    _83s,
+    _82s,
+    A7o,
+    K7o,
+    Q7o,
+    J7o,
+    T7o,
+    _97o,
+    _87o,
+    _77,
+    _76s,
+    _75s,
+    _74s,
+    _73s,
+    _72s,
+    A6o,
+    K6o,
+    Q6o,
+    J6o,
+    T6o,
+    _96o,
+    _86o,
+    _76o,
+    _66,
+    _65s,
+    _64s,
+    _63s,
+    _62s,
+    A5o,
+    K5o,
+    Q5o,
+    J5o,
+    T5o,
+    _95o,
+    _85o,
+    _75o,
+    _65o,
+    _55,
+    _54s,
+    _53s,
+    _52s,
+    A4o,
+    K4o,
+    Q4o,
+    J4o,
+    T4o,
+    _94o,
+    _84o,
+    _74o,
+    _64o,
+    _54o,
+    _44,
+    _43s,
+    _42s,
+    A3o,
+    K3o,
+    Q3o,
+    J3o,
+    T3o,
+    _93o,
+    _83o,
+    _73o,
+    _63o,
+    _53o,
+    _43o,
+    _33,
+    _32s,
+    A2o,
+    K2o,
+    Q2o,
+    J2o,
+    T2o,
+    _92o,
+    _82o,
+    _72o,
+    _62o,
+    _52o,
+    _42o,
+    _32o,
+    _22,
+}
+
+impl PreflopClass {
+    pub const COUNT: usize = 169;
+    pub const GRID_SIZE: usize = 13;
+
+    /// Every `PreflopClass` in chart order.
+    pub const ALL: [PreflopClass; PreflopClass::COUNT] = [
+        PreflopClass::AA,
+        PreflopClass::AKs,
+        PreflopClass::AQs,
+        PreflopClass::AJs,
+        PreflopClass::ATs,
+        PreflopClass::A9s,
+        PreflopClass::A8s,
+        PreflopClass::A7s,
+        PreflopClass::A6s,
+        PreflopClass::A5s,
+        PreflopClass::A4s,
+        PreflopClass::A3s,
+        PreflopClass::A2s,
+        PreflopClass::AKo,
+        PreflopClass::KK,
+        PreflopClass::KQs,
+        PreflopClass::KJs,
+        PreflopClass::KTs,
+        PreflopClass::K9s,
+        PreflopClass::K8s,
+        PreflopClass::K7s,
+        PreflopClass::K6s,
+        PreflopClass::K5s,
+        PreflopClass::K4s,
+        PreflopClass::K3s,
+        PreflopClass::K2s,
+        PreflopClass::AQo,
+        PreflopClass::KQo,
+        PreflopClass::QQ,
+        PreflopClass::QJs,
+        PreflopClass::QTs,
+        PreflopClass::Q9s,
+        PreflopClass::Q8s,
+        PreflopClass::Q7s,
+        PreflopClass::Q6s,
+        PreflopClass::Q5s,
+        PreflopClass::Q4s,
+        PreflopClass::Q3s,
+        PreflopClass::Q2s,
+        PreflopClass::AJo,
+        PreflopClass::KJo,
+        PreflopClass::QJo,
+        PreflopClass::JJ,
+        PreflopClass::JTs,
+        PreflopClass::J9s,
+        PreflopClass::J8s,
+        PreflopClass::J7s,
+        PreflopClass::J6s,
+        PreflopClass::J5s,
+        PreflopClass::J4s,
+        PreflopClass::J3s,
+        PreflopClass::J2s,
+        PreflopClass::ATo,
+        PreflopClass::KTo,
+        PreflopClass::QTo,
+        PreflopClass::JTo,
+        PreflopClass::TT,
+        PreflopClass::T9s,
+        PreflopClass::T8s,
+        PreflopClass::T7s,
+        PreflopClass::T6s,
+        PreflopClass::T5s,
+        PreflopClass::T4s,
+        PreflopClass::T3s,
+        PreflopClass::T2s,
+        PreflopClass::A9o,
+        PreflopClass::K9o,
+        PreflopClass::Q9o,
+        PreflopClass::J9o,
+        PreflopClass::T9o,
+        PreflopClass::_99,
+        PreflopClass::_98s,
+        PreflopClass::_97s,
+        PreflopClass::_96s,
+        PreflopClass::_95s,
+        PreflopClass::_94s,
+        PreflopClass::_93s,
+        PreflopClass::_92s,
+        PreflopClass::A8o,
+        PreflopClass::K8o,
+        PreflopClass::Q8o,
+        PreflopClass::J8o,
+        PreflopClass::T8o,
+        PreflopClass::_98o,
+        PreflopClass::_88,
+        PreflopClass::_87s,
+        PreflopClass::_86s,
+        PreflopClass::_85s,
+        PreflopClass::_84s,
+        PreflopClass::_83s,
+        PreflopClass::_82s,
+        PreflopClass::A7o,
+        PreflopClass::K7o,
+        PreflopClass::Q7o,
+        PreflopClass::J7o,
+        PreflopClass::T7o,
+        PreflopClass::_97o,
+        PreflopClass::_87o,
+        PreflopClass::_77,
+        PreflopClass::_76s,
+        PreflopClass::_75s,
+        PreflopClass::_74s,
+        PreflopClass::_73s,
+        PreflopClass::_72s,
+        PreflopClass::A6o,
+        PreflopClass::K6o,
+        PreflopClass::Q6o,
+        PreflopClass::J6o,
+        PreflopClass::T6o,
+        PreflopClass::_96o,
+        PreflopClass::_86o,
+        PreflopClass::_76o,
+        PreflopClass::_66,
+        PreflopClass::_65s,
+        PreflopClass::_64s,
+        PreflopClass::_63s,
+        PreflopClass::_62s,
+        PreflopClass::A5o,
+        PreflopClass::K5o,
+        PreflopClass::Q5o,
+        PreflopClass::J5o,
+        PreflopClass::T5o,
+        PreflopClass::_95o,
+        PreflopClass::_85o,
+        PreflopClass::_75o,
+        PreflopClass::_65o,
+        PreflopClass::_55,
+        PreflopClass::_54s,
+        PreflopClass::_53s,
+        PreflopClass::_52s,
+        PreflopClass::A4o,
+        PreflopClass::K4o,
+        PreflopClass::Q4o,
+        PreflopClass::J4o,
+        PreflopClass::T4o,
+        PreflopClass::_94o,
+        PreflopClass::_84o,
+        PreflopClass::_74o,
+        PreflopClass::_64o,
+        PreflopClass::_54o,
+        PreflopClass::_44,
+        PreflopClass::_43s,
+        PreflopClass::_42s,
+        PreflopClass::A3o,
+        PreflopClass::K3o,
+        PreflopClass::Q3o,
+        PreflopClass::J3o,
+        PreflopClass::T3o,
+        PreflopClass::_93o,
+        PreflopClass::_83o,
+        PreflopClass::_73o,
+        PreflopClass::_63o,
+        PreflopClass::_53o,
+        PreflopClass::_43o,
+        PreflopClass::_33,
+        PreflopClass::_32s,
+        PreflopClass::A2o,
+        PreflopClass::K2o,
+        PreflopClass::Q2o,
+        PreflopClass::J2o,
+        PreflopClass::T2o,
+        PreflopClass::_92o,
+        PreflopClass::_82o,
+        PreflopClass::_72o,
+        PreflopClass::_62o,
+        PreflopClass::_52o,
+        PreflopClass::_42o,
+        PreflopClass::_32o,
+        PreflopClass::_22,
+    ];
+
+    #[must_use]
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    #[must_use]
+    pub fn from_index(index: usize) -> Option<PreflopClass> {
+        PreflopClass::ALL.get(index).copied()
+    }
+
+    #[must_use]
+    pub fn from_grid(row: usize, col: usize) -> Option<PreflopClass> {
+        if row < PreflopClass::GRID_SIZE && col < PreflopClass::GRID_SIZE {
+            PreflopClass::from_index(row * PreflopClass::GRID_SIZE + col)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the `PreflopClass` for two ranks, in either order. The suited flag is ignored
+    /// for pairs.
+    #[must_use]
+    pub fn from_ranks(first: CardRank, second: CardRank, suited: bool) -> Option<PreflopClass> {
+        let first = CardRank::ALL.iter().position(|rank| *rank == first)?;
+        let second = CardRank::ALL.iter().position(|rank| *rank == second)?;
+        let (high, low) = (first.min(second), first.max(second));
+        if suited {
+            PreflopClass::from_grid(high, low)
+        } else {
+            PreflopClass::from_grid(low, high)
+        }
+    }
+
+    #[must_use]
+    pub fn row(self) -> usize {
+        self.index() / PreflopClass::GRID_SIZE
+    }
+
+    #[must_use]
+    pub fn col(self) -> usize {
+        self.index() % PreflopClass::GRID_SIZE
+    }
+
+    #[must_use]
+    pub fn high_rank(self) -> CardRank {
+        CardRank::ALL[self.row().min(self.col())]
+    }
+
+    #[must_use]
+    pub fn low_rank(self) -> CardRank {
+        CardRank::ALL[self.row().max(self.col())]
+    }
+
+    #[must_use]
+    pub fn is_pair(self) -> bool {
+        self.row() == self.col()
+    }
+
+    #[must_use]
+    pub fn is_suited(self) -> bool {
+        self.col() > self.row()
+    }
+
+    #[must_use]
+    pub fn is_offsuit(self) -> bool {
+        self.col() < self.row()
+    }
+
+    /// Returns the hand's all in equity against one random hand, from `PREFLOP_EQUITY`.
+    #[must_use]
+    pub fn equity_vs_random(self) -> f32 {
+        PREFLOP_EQUITY[self.index()]
+    }
+
+    /// Returns the points from Bill Chen's formula, which are the same for every combo of the
+    /// class.
+    #[must_use]
+    pub fn chen_formula(self) -> i8 {
+        self.combos()[0].chen_formula()
+    }
+
+    /// Returns the hand's group from `SKLANSKY_GROUPS`.
+    #[must_use]
+    pub fn sklansky_group(self) -> u8 {
+        SKLANSKY_GROUPS[self.index()]
+    }
+
+    /// Every combination of two cards in the class: 6 for a pair, 4 for a suited hand and 12
+    /// for an offsuit hand. They're in the same order as the hand constants on `Two`, so
+    /// `PreflopClass::AKo.combos()` is the same as `Two::AKo`.
+    #[must_use]
+    pub fn combos(self) -> &'static [Two] {
+        &COMBOS[COMBO_OFFSETS[self.index()]..COMBO_OFFSETS[self.index() + 1]]
+    }
+
+    /// The suited combinations of the class's two ranks, whether the class itself is suited or
+    /// offsuit, so `PreflopClass::AKo.suited_combos()` is the same as `Two::AKs`. Pairs have
+    /// none.
+    #[must_use]
+    pub fn suited_combos(self) -> &'static [Two] {
+        if self.is_pair() {
+            &[]
+        } else {
+            PreflopClass::from_grid(self.row().min(self.col()), self.row().max(self.col()))
+                .map_or(&[], PreflopClass::combos)
+        }
+    }
+}
+
+/// Every combination of two cards, grouped by `PreflopClass` in chart order.
+const COMBOS: [Two; Range::ALL_COMBOS] = combos();
+
+/// Where each `PreflopClass` starts in `COMBOS`, plus where the last one ends.
+const COMBO_OFFSETS: [usize; PreflopClass::COUNT + 1] = combo_offsets();
+
+const fn combo_offsets() -> [usize; PreflopClass::COUNT + 1] {
+    let mut offsets = [0; PreflopClass::COUNT + 1];
+    let mut i = 0;
+    while i < PreflopClass::COUNT {
+        let (row, col) = (i / PreflopClass::GRID_SIZE, i % PreflopClass::GRID_SIZE);
+        offsets[i + 1] = offsets[i]
+            + if row == col {
+                6
+            } else if col > row {
+                4
+            } else {
+                12
+            };
+        i += 1;
+    }
+    offsets
+}
+
+const fn combos() -> [Two; Range::ALL_COMBOS] {
+    // The `POKER_DECK` runs through every rank of one suit before the next suit.
+    const fn card(rank: usize, suit: usize) -> crate::CKCNumber {
+        POKER_DECK[suit * PreflopClass::GRID_SIZE + rank]
+    }
+
+    let mut combos = [Two::new(0, 0); Range::ALL_COMBOS];
+    let mut next = 0;
+    let mut i = 0;
+    while i < PreflopClass::COUNT {
+        let (row, col) = (i / PreflopClass::GRID_SIZE, i % PreflopClass::GRID_SIZE);
+        let (high, low) = if row < col { (row, col) } else { (col, row) };
+        let mut first = 0;
+        while first < 4 {
+            let mut second = 0;
+            while second < 4 {
+                let include = if row == col {
+                    second > first
+                } else if col > row {
+                    second == first
+                } else {
+                    second != first
+                };
+                if include {
+                    combos[next] = Two::new(card(high, first), card(low, second));
+                    next += 1;
+                }
+                second += 1;
+            }
+            first += 1;
+        }
+        i += 1;
+    }
+    combos
+}
+
+impl fmt::Display for PreflopClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let high = RANK_CHARS[self.row().min(self.col())];
+        let low = RANK_CHARS[self.row().max(self.col())];
+        if self.is_suited() {
+            write!(f, "{high}{low}s")
+        } else if self.is_offsuit() {
+            write!(f, "{high}{low}o")
+        } else {
+            write!(f, "{high}{low}")
+        }
+    }
+}
+
+impl TryFrom<Two> for PreflopClass {
+    type Error = HandError;
+
+    fn try_from(two: Two) -> Result<Self, Self::Error> {
+        if !two.is_valid() {
+            return Err(HandError::InvalidCard);
+        }
+        PreflopClass::from_ranks(
+            two.first().get_card_rank(),
+            two.second().get_card_rank(),
+            two.is_suited(),
+        )
+        .ok_or(HandError::InvalidCard)
+    }
+}
+
+/// Parses the usual chart notation, such as `AKs`, `T9o` or `77`.
+impl TryFrom<&str> for PreflopClass {
+    type Error = HandError;
+
+    fn try_from(index: &str) -> Result<Self, Self::Error> {
+        let mut chars = index.trim().chars();
+        let first = CardRank::from_char(chars.next().ok_or(HandError::InvalidIndex)?);
+        let second = CardRank::from_char(chars.next().ok_or(HandError::InvalidIndex)?);
+        let suited = match (chars.next(), chars.next()) {
+            (None, None) if first == second => false,
+            (Some('s' | 'S'), None) if first != second => true,
+            (Some('o' | 'O'), None) if first != second => false,
+            _ => return Err(HandError::InvalidIndex),
+        };
+        PreflopClass::from_ranks(first, second, suited).ok_or(HandError::InvalidIndex)
+    }
+}
+
+/// A value for every one of the 169 `PreflopClasses`, such as a range chart, an equity table
+/// or push/fold ranges. Index it with a `PreflopClass`: `matrix[PreflopClass::AKs]`.
+///
+/// Serializes as a flat sequence of the 169 values in chart order.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PreflopMatrix<T> {
+    cells: Vec<T>,
+}
+
+impl<T: Clone> PreflopMatrix<T> {
+    /// Returns a `PreflopMatrix` with every cell set to the value.
+    #[must_use]
+    pub fn new(value: T) -> Self {
+        PreflopMatrix {
+            cells: alloc::vec![value; PreflopClass::COUNT],
+        }
+    }
+}
+
+impl<T> PreflopMatrix<T> {
+    /// Returns a `PreflopMatrix` with each cell set to the result of calling the function
+    /// with its `PreflopClass`.
+    pub fn from_fn<F: FnMut(PreflopClass) -> T>(f: F) -> Self {
+        PreflopMatrix {
+            cells: PreflopClass::ALL.into_iter().map(f).collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, class: PreflopClass) -> &T {
+        &self.cells[class.index()]
+    }
+
+    pub fn set(&mut self, class: PreflopClass, value: T) {
+        self.cells[class.index()] = value;
+    }
+
+    /// Iterates over every `PreflopClass` and its value in chart order.
+    pub fn iter(&self) -> impl Iterator<Item = (PreflopClass, &T)> {
+        PreflopClass::ALL.into_iter().zip(self.cells.iter())
+    }
+
+    /// Iterates over the 13 rows of the chart, from the aces row down to the deuces row.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(PreflopClass::GRID_SIZE)
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> PreflopMatrix<U> {
+        PreflopMatrix {
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone + Default> Default for PreflopMatrix<T> {
+    fn default() -> Self {
+        PreflopMatrix::new(T::default())
+    }
+}
+
+impl<T> Index<PreflopClass> for PreflopMatrix<T> {
+    type Output = T;
+
+    fn index(&self, class: PreflopClass) -> &Self::Output {
+        self.get(class)
+    }
+}
+
+impl<T> IndexMut<PreflopClass> for PreflopMatrix<T> {
+    fn index_mut(&mut self, class: PreflopClass) -> &mut Self::Output {
+        &mut self.cells[class.index()]
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Serialize> Serialize for PreflopMatrix<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.cells.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for PreflopMatrix<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let cells = Vec::<T>::deserialize(deserializer)?;
+        if cells.len() == PreflopClass::COUNT {
+            Ok(PreflopMatrix { cells })
+        } else {
+            Err(D::Error::invalid_length(cells.len(), &"169 preflop classes"))
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__preflop_tests {
+    use super::*;
+    use crate::cards::binary_card::{BinaryCard, BC64};
+    use alloc::string::ToString;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(1, 5)]
+    #[case(2, 5)]
+    #[case(3, 6)]
+    #[case(4, 8)]
+    #[case(5, 18)]
+    #[case(6, 10)]
+    #[case(7, 18)]
+    #[case(8, 15)]
+    #[case(9, 84)]
+    fn sklansky_group(#[case] group: u8, #[case] count: usize) {
+        let actual = PreflopClass::ALL
+            .iter()
+            .filter(|class| class.sklansky_group() == group)
+            .count();
+
+        assert_eq!(count, actual);
+    }
+
+    #[test]
+    fn sklansky_group__chart_order() {
+        assert_eq!(1, PreflopClass::AKs.sklansky_group());
+        assert_eq!(2, PreflopClass::AKo.sklansky_group());
+        assert_eq!(5, PreflopClass::_65s.sklansky_group());
+        assert_eq!(8, PreflopClass::_32s.sklansky_group());
+        assert_eq!(9, PreflopClass::_32o.sklansky_group());
+    }
+
+    #[test]
+    fn equity_vs_random() {
+        assert!((0.852 - PreflopClass::AA.equity_vs_random()).abs() < f32::EPSILON);
+        assert!((0.323 - PreflopClass::_32o.equity_vs_random()).abs() < f32::EPSILON);
+        for class in PreflopClass::ALL {
+            if class.is_suited() {
+                let offsuit = PreflopClass::from_grid(class.col(), class.row()).unwrap();
+                assert!(class.equity_vs_random() > offsuit.equity_vs_random());
+            }
+        }
+    }
+
+    #[test]
+    fn chen_formula() {
+        assert_eq!(20, PreflopClass::AA.chen_formula());
+        assert_eq!(12, PreflopClass::AKs.chen_formula());
+        assert_eq!(-1, PreflopClass::_72o.chen_formula());
+    }
+
+    #[test]
+    fn combos() {
+        let mut combos: Vec<BinaryCard> = Vec::new();
+        for class in PreflopClass::ALL {
+            for two in class.combos() {
+                assert!(two.is_valid());
+                assert_eq!(Ok(class), PreflopClass::try_from(*two));
+                combos.push(BinaryCard::from_two(*two));
+            }
+        }
+        combos.sort_unstable();
+        combos.dedup();
+
+        assert_eq!(Range::ALL_COMBOS, combos.len());
+    }
+
+    #[test]
+    fn combos__two_constants() {
+        assert_eq!(Two::AA, PreflopClass::AA.combos());
+        assert_eq!(Two::AKs, PreflopClass::AKs.combos());
+        assert_eq!(Two::AKo, PreflopClass::AKo.combos());
+        assert_eq!(Two::AQs, PreflopClass::AQs.combos());
+        assert_eq!(Two::AQo, PreflopClass::AQo.combos());
+    }
+
+    #[test]
+    fn suited_combos() {
+        assert_eq!(Two::AKs, PreflopClass::AKo.suited_combos());
+        assert_eq!(Two::AKs, PreflopClass::AKs.suited_combos());
+        assert_eq!(PreflopClass::_32s.combos(), PreflopClass::_32o.suited_combos());
+        assert!(PreflopClass::_22.suited_combos().is_empty());
+    }
+
+    #[test]
+    fn all() {
+        for (i, class) in PreflopClass::ALL.iter().enumerate() {
+            assert_eq!(i, class.index());
+            assert_eq!(Some(*class), PreflopClass::from_index(i));
+        }
+        assert_eq!(None, PreflopClass::from_index(PreflopClass::COUNT));
+        assert_eq!(13, PreflopClass::ALL.iter().filter(|class| class.is_pair()).count());
+        assert_eq!(78, PreflopClass::ALL.iter().filter(|class| class.is_suited()).count());
+        assert_eq!(78, PreflopClass::ALL.iter().filter(|class| class.is_offsuit()).count());
+    }
+
+    #[rstest]
+    #[case(PreflopClass::AA, 0, 0, "AA")]
+    #[case(PreflopClass::AKs, 0, 1, "AKs")]
+    #[case(PreflopClass::AKo, 1, 0, "AKo")]
+    #[case(PreflopClass::T9s, 4, 5, "T9s")]
+    #[case(PreflopClass::_72o, 12, 7, "72o")]
+    #[case(PreflopClass::_22, 12, 12, "22")]
+    fn grid(#[case] class: PreflopClass, #[case] row: usize, #[case] col: usize, #[case] name: &str) {
+        assert_eq!(row, class.row());
+        assert_eq!(col, class.col());
+        assert_eq!(Some(class), PreflopClass::from_grid(row, col));
+        assert_eq!(name, class.to_string());
+        assert_eq!(class, PreflopClass::try_from(name).unwrap());
+    }
+
+    #[test]
+    fn ranks() {
+        assert_eq!(CardRank::ACE, PreflopClass::AKo.high_rank());
+        assert_eq!(CardRank::KING, PreflopClass::AKo.low_rank());
+        assert_eq!(
+            Some(PreflopClass::AKs),
+            PreflopClass::from_ranks(CardRank::KING, CardRank::ACE, true)
+        );
+        assert_eq!(None, PreflopClass::from_ranks(CardRank::BLANK, CardRank::ACE, true));
+    }
+
+    #[rstest]
+    #[case("A♠ K♠", PreflopClass::AKs)]
+    #[case("K♥ A♠", PreflopClass::AKo)]
+    #[case("7♦ 7♣", PreflopClass::_77)]
+    fn try_from__two(#[case] index: &'static str, #[case] expected: PreflopClass) {
+        assert_eq!(expected, PreflopClass::try_from(Two::try_from(index).unwrap()).unwrap());
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("A")]
+    #[case("AAs")]
+    #[case("AK")]
+    #[case("AKx")]
+    #[case("AKso")]
+    #[case("XKs")]
+    fn try_from__str__invalid(#[case] index: &str) {
+        assert_eq!(HandError::InvalidIndex, PreflopClass::try_from(index).unwrap_err());
+    }
+
+    #[test]
+    fn try_from__two__invalid() {
+        assert_eq!(
+            HandError::InvalidCard,
+            PreflopClass::try_from(Two::default()).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn matrix__index() {
+        let mut matrix = PreflopMatrix::new(0u8);
+
+        matrix[PreflopClass::AKs] = 7;
+        matrix.set(PreflopClass::_22, 3);
+
+        assert_eq!(7, matrix[PreflopClass::AKs]);
+        assert_eq!(3, *matrix.get(PreflopClass::_22));
+        assert_eq!(0, matrix[PreflopClass::AKo]);
+    }
+
+    #[test]
+    fn matrix__from_fn() {
+        let matrix = PreflopMatrix::from_fn(PreflopClass::is_pair);
+
+        assert!(matrix[PreflopClass::AA]);
+        assert!(!matrix[PreflopClass::AKs]);
+        assert_eq!(13, matrix.iter().filter(|(_, pair)| **pair).count());
+        assert_eq!(
+            13,
+            matrix.map(|pair| u8::from(*pair)).iter().map(|(_, v)| *v).sum::<u8>()
+        );
+    }
+
+    #[test]
+    fn matrix__rows() {
+        let matrix = PreflopMatrix::from_fn(PreflopClass::index);
+        let rows: Vec<&[usize]> = matrix.rows().collect();
+
+        assert_eq!(13, rows.len());
+        assert_eq!(PreflopClass::AKo.index(), rows[1][0]);
+        assert_eq!(PreflopClass::KK.index(), rows[1][1]);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn matrix__serde() {
+        let matrix = PreflopMatrix::from_fn(|class| u16::try_from(class.index()).unwrap());
+
+        let json = serde_json::to_string(&matrix).unwrap();
+        let actual: PreflopMatrix<u16> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(matrix, actual);
+        assert!(serde_json::from_str::<PreflopMatrix<u16>>("[1, 2, 3]").is_err());
+    }
+}
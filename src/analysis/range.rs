@@ -0,0 +1,321 @@
+use crate::analysis::preflop::{PreflopClass, PreflopMatrix};
+use crate::cards::binary_card::{conflicts, BinaryCard};
+use crate::cards::two::Two;
+use crate::cards::HandValidator;
+use crate::deck::POKER_DECK;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::slice::Iter;
+
+/// How starting hands are ordered from best to worst when building a `Range` from a percentage.
+///
+/// * `EquityVsRandom` - all in equity against one random hand, from `PREFLOP_EQUITY`.
+/// * `Chen` - points from Bill Chen's formula, with ties broken by equity.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum StartingHandMetric {
+    #[default]
+    EquityVsRandom,
+    Chen,
+}
+
+impl StartingHandMetric {
+    /// Every `PreflopClass`, from best to worst.
+    fn ordered(self) -> [PreflopClass; PreflopClass::COUNT] {
+        let mut classes = PreflopClass::ALL;
+        match self {
+            StartingHandMetric::EquityVsRandom => {
+                classes.sort_by(|a, b| b.equity_vs_random().total_cmp(&a.equity_vs_random()));
+            },
+            StartingHandMetric::Chen => classes.sort_by(|a, b| {
+                b.chen_formula()
+                    .cmp(&a.chen_formula())
+                    .then(b.equity_vs_random().total_cmp(&a.equity_vs_random()))
+            }),
+        }
+        classes
+    }
+}
+
+/// A `Range` is the set of two card hands, or combos, that a player could be holding.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Range(Vec<Two>);
+
+impl Range {
+    /// The number of distinct two card combinations in a 52 card deck.
+    pub const ALL_COMBOS: usize = 1326;
+
+    #[must_use]
+    pub fn new() -> Self {
+        Range::default()
+    }
+
+    /// Returns a `Range` holding every possible two card hand.
+    #[must_use]
+    pub fn all() -> Self {
+        let deck = POKER_DECK;
+        let mut combos = Vec::with_capacity(Range::ALL_COMBOS);
+        for (i, first) in deck.iter().enumerate() {
+            for second in &deck[i + 1..] {
+                combos.push(Two::new(*first, *second));
+            }
+        }
+        Range(combos)
+    }
+
+    /// Returns the best `p` percent of all combos, ranked by the metric. Whole `PreflopClasses`
+    /// are added from best to worst, and each one is included if `percentile_of()` for it is no
+    /// more than `p`, so `Range::top_percent(0.5, StartingHandMetric::EquityVsRandom)` is just
+    /// the six combos of `AA`.
+    #[must_use]
+    pub fn top_percent(p: f32, metric: StartingHandMetric) -> Self {
+        let mut range = Range::new();
+        let mut combos = 0;
+        for class in metric.ordered() {
+            combos += class.combos().len();
+            if Range::percent_of_all(combos) > p {
+                break;
+            }
+            range.0.extend_from_slice(class.combos());
+        }
+        range
+    }
+
+    /// Returns the smallest percentage of all combos, ranked by the metric, that includes the
+    /// hand, or `None` if it isn't valid. `AA` is the top 0.45%, and `32o` is 100%.
+    #[must_use]
+    pub fn percentile_of(two: Two, metric: StartingHandMetric) -> Option<f32> {
+        let target = PreflopClass::try_from(two).ok()?;
+        let mut combos = 0;
+        for class in metric.ordered() {
+            combos += class.combos().len();
+            if class == target {
+                return Some(Range::percent_of_all(combos));
+            }
+        }
+        None
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn percent_of_all(combos: usize) -> f32 {
+        combos as f32 * 100.0 / Range::ALL_COMBOS as f32
+    }
+
+    /// Adds a combo to the `Range` unless it's invalid or already present.
+    pub fn push(&mut self, two: Two) {
+        let two = two.sort();
+        if two.is_valid() && !self.contains(&two) {
+            self.0.push(two);
+        }
+    }
+
+    #[must_use]
+    pub fn contains(&self, two: &Two) -> bool {
+        let two = two.sort();
+        self.0.iter().any(|combo| combo.sort() == two)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> Iter<'_, Two> {
+        self.0.iter()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the combos in the `Range` that don't contain any of the dead cards.
+    pub fn live(&self, dead: BinaryCard) -> impl Iterator<Item = &Two> {
+        self.0
+            .iter()
+            .filter(move |two| !conflicts(BinaryCard::from(**two), dead))
+    }
+
+    /// Returns a chart of the `PreflopClasses` that have at least one combo in the `Range`.
+    #[must_use]
+    pub fn to_matrix(&self) -> PreflopMatrix<bool> {
+        let mut matrix = PreflopMatrix::new(false);
+        for class in self.0.iter().filter_map(|two| PreflopClass::try_from(*two).ok()) {
+            matrix[class] = true;
+        }
+        matrix
+    }
+
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<Two> {
+        self.0.clone()
+    }
+}
+
+impl From<Vec<Two>> for Range {
+    fn from(combos: Vec<Two>) -> Self {
+        combos.into_iter().collect()
+    }
+}
+
+impl From<&[Two]> for Range {
+    fn from(combos: &[Two]) -> Self {
+        Range::from(combos.to_vec())
+    }
+}
+
+impl<'a> IntoIterator for &'a Range {
+    type Item = &'a Two;
+    type IntoIter = Iter<'a, Two>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Keeps the first of any combos with the same cards, the same as `Range::push()`, but tracks the
+/// combos already seen by `HandValidator::id()` rather than scanning the `Range` for each one.
+impl FromIterator<Two> for Range {
+    fn from_iter<I: IntoIterator<Item = Two>>(iter: I) -> Self {
+        let mut seen = BTreeSet::new();
+        Range(
+            iter.into_iter()
+                .map(|two| two.sort())
+                .filter(|two| two.is_valid() && seen.insert(two.id()))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__range_tests {
+    use super::*;
+    use crate::cards::binary_card::BC64;
+    use crate::CardNumber;
+
+    #[test]
+    fn all() {
+        let all = Range::all();
+
+        assert_eq!(Range::ALL_COMBOS, all.len());
+        assert!(all.contains(&Two::new(CardNumber::DEUCE_CLUBS, CardNumber::ACE_SPADES)));
+    }
+
+    #[test]
+    fn push() {
+        let mut range = Range::new();
+
+        range.push(Two::new(CardNumber::ACE_SPADES, CardNumber::KING_SPADES));
+        range.push(Two::new(CardNumber::KING_SPADES, CardNumber::ACE_SPADES));
+        range.push(Two::new(CardNumber::ACE_SPADES, CardNumber::ACE_SPADES));
+        range.push(Two::new(CardNumber::ACE_SPADES, CardNumber::BLANK));
+
+        assert_eq!(1, range.len());
+    }
+
+    #[test]
+    fn from__vec() {
+        let range = Range::from(Two::AA.to_vec());
+
+        assert_eq!(6, range.len());
+        assert!(!range.is_empty());
+        assert!(Range::new().is_empty());
+    }
+
+    #[test]
+    fn from__duplicates() {
+        let range = Range::from(alloc::vec![
+            Two::new(CardNumber::ACE_SPADES, CardNumber::KING_SPADES),
+            Two::new(CardNumber::KING_SPADES, CardNumber::ACE_SPADES),
+            Two::new(CardNumber::ACE_SPADES, CardNumber::ACE_SPADES),
+            Two::new(CardNumber::QUEEN_HEARTS, CardNumber::JACK_HEARTS),
+            Two::new(CardNumber::ACE_SPADES, CardNumber::KING_SPADES),
+        ]);
+
+        assert_eq!(
+            alloc::vec![
+                Two::new(CardNumber::ACE_SPADES, CardNumber::KING_SPADES),
+                Two::new(CardNumber::QUEEN_HEARTS, CardNumber::JACK_HEARTS),
+            ],
+            range.to_vec()
+        );
+        assert_eq!(
+            Range::ALL_COMBOS,
+            Range::all()
+                .iter()
+                .copied()
+                .chain(Range::all().to_vec())
+                .collect::<Range>()
+                .len()
+        );
+    }
+
+    #[test]
+    fn live() {
+        let range = Range::from(&Two::AA[..]);
+        let dead = BinaryCard::ACE_SPADES;
+
+        assert_eq!(3, range.live(dead).count());
+    }
+
+    #[test]
+    fn to_matrix() {
+        let range = Range::from(alloc::vec![
+            Two::new(CardNumber::ACE_SPADES, CardNumber::ACE_HEARTS),
+            Two::new(CardNumber::ACE_SPADES, CardNumber::KING_SPADES),
+        ]);
+
+        let matrix = range.to_matrix();
+
+        assert!(matrix[PreflopClass::AA]);
+        assert!(matrix[PreflopClass::AKs]);
+        assert!(!matrix[PreflopClass::AKo]);
+        assert_eq!(2, matrix.iter().filter(|(_, included)| **included).count());
+    }
+
+    #[test]
+    fn top_percent() {
+        let top = Range::top_percent(0.5, StartingHandMetric::EquityVsRandom);
+
+        assert_eq!(6, top.len());
+        assert!(top.to_vec().iter().all(Two::is_pocket_pair));
+        assert!(Range::top_percent(0.0, StartingHandMetric::Chen).is_empty());
+        assert_eq!(
+            Range::ALL_COMBOS,
+            Range::top_percent(100.0, StartingHandMetric::EquityVsRandom).len()
+        );
+        assert_eq!(
+            Range::ALL_COMBOS,
+            Range::top_percent(100.0, StartingHandMetric::Chen).len()
+        );
+    }
+
+    #[test]
+    fn top_percent__ten() {
+        let equity = Range::top_percent(10.0, StartingHandMetric::EquityVsRandom);
+        let chen = Range::top_percent(10.0, StartingHandMetric::Chen);
+
+        assert!(equity.len() <= 133 && equity.len() > 110);
+        assert!(chen.len() <= 133 && chen.len() > 110);
+        assert!(equity.to_matrix()[PreflopClass::AKo]);
+        assert!(chen.to_matrix()[PreflopClass::AKo]);
+        assert!(!equity.to_matrix()[PreflopClass::_32o]);
+    }
+
+    #[test]
+    fn percentile_of() {
+        let aces = Two::new(CardNumber::ACE_SPADES, CardNumber::ACE_CLUBS);
+        let seven_deuce = Two::new(CardNumber::SEVEN_SPADES, CardNumber::DEUCE_CLUBS);
+
+        let top = Range::percentile_of(aces, StartingHandMetric::EquityVsRandom).unwrap();
+
+        assert!((top - 600.0 / 1326.0).abs() < 1e-4);
+        assert!(Range::percentile_of(seven_deuce, StartingHandMetric::Chen).unwrap() > 90.0);
+        assert_eq!(None, Range::percentile_of(Two::default(), StartingHandMetric::Chen));
+        for p in [1.0, 5.0, 20.0, 50.0] {
+            for two in &Range::top_percent(p, StartingHandMetric::EquityVsRandom) {
+                assert!(Range::percentile_of(*two, StartingHandMetric::EquityVsRandom).unwrap() <= p);
+            }
+        }
+    }
+}
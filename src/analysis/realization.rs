@@ -0,0 +1,143 @@
+use crate::analysis::preflop::{PreflopClass, PreflopMatrix};
+use crate::table::Position;
+
+/// How much of its raw equity each `PreflopClass` realizes on the button, in chart order.
+/// Suited, connected and high cards play well after the flop and realize more than their
+/// equity, while offsuit gappers realize less.
+///
+/// These come from a simple playability model rather than a solver, as a reasonable default for
+/// range heuristics. Tables from a solver can be used instead with a `RealizationTable`.
+#[rustfmt::skip]
+pub const REALIZATION_ON_BUTTON: [f32; PreflopClass::COUNT] = [
+    1.10, 1.10, 1.10, 1.08, 1.05, 1.03, 1.02, 1.01, 1.04, 1.03, 1.02, 1.01, 1.00,
+    1.04, 1.08, 1.10, 1.08, 1.06, 1.03, 1.01, 1.00, 0.99, 0.99, 0.98, 0.97, 0.96,
+    1.01, 1.02, 1.06, 1.09, 1.06, 1.04, 1.01, 0.99, 0.98, 0.97, 0.97, 0.96, 0.95,
+    0.99, 0.99, 1.00, 1.04, 1.07, 1.04, 1.02, 0.99, 0.97, 0.96, 0.95, 0.95, 0.94,
+    0.96, 0.97, 0.97, 0.98, 1.02, 1.05, 1.02, 1.00, 0.97, 0.95, 0.94, 0.93, 0.93,
+    0.90, 0.94, 0.95, 0.95, 0.96, 1.00, 1.03, 1.00, 0.98, 0.95, 0.93, 0.92, 0.91,
+    0.89, 0.88, 0.92, 0.93, 0.93, 0.94, 0.98, 1.01, 0.98, 0.96, 0.93, 0.91, 0.90,
+    0.88, 0.87, 0.86, 0.90, 0.91, 0.91, 0.92, 0.96, 0.99, 0.96, 0.94, 0.91, 0.89,
+    0.88, 0.86, 0.85, 0.84, 0.88, 0.89, 0.89, 0.90, 0.94, 0.97, 0.94, 0.92, 0.89,
+    0.87, 0.86, 0.84, 0.83, 0.82, 0.86, 0.87, 0.87, 0.88, 0.92, 0.95, 0.92, 0.90,
+    0.86, 0.85, 0.84, 0.82, 0.81, 0.80, 0.84, 0.85, 0.85, 0.86, 0.90, 0.93, 0.90,
+    0.85, 0.84, 0.83, 0.82, 0.80, 0.79, 0.78, 0.82, 0.83, 0.83, 0.84, 0.88, 0.91,
+    0.84, 0.83, 0.82, 0.81, 0.80, 0.78, 0.77, 0.76, 0.80, 0.81, 0.81, 0.82, 0.86,
+];
+
+/// How much the realization on the button is scaled by in each `Position`, in the same order as
+/// `Position::ALL`. The blinds play most pots out of position, so they realize the least.
+pub const POSITION_REALIZATION: [f32; Position::COUNT] = [0.90, 0.91, 0.92, 0.93, 0.95, 0.97, 1.0, 0.80, 0.85];
+
+/// Returns the share of its raw equity that a hand realizes from the `Position`, from the
+/// default tables. Multiply it by the hand's equity for a value that accounts for how well the
+/// hand plays.
+///
+/// ```
+/// use ckc_rs::analysis::preflop::PreflopClass;
+/// use ckc_rs::analysis::realization::realization_factor;
+/// use ckc_rs::table::Position;
+///
+/// let suited = realization_factor(PreflopClass::T9s, Position::Button);
+/// let offsuit = realization_factor(PreflopClass::T9o, Position::Button);
+///
+/// assert!(suited > offsuit);
+/// assert!(realization_factor(PreflopClass::T9s, Position::BigBlind) < suited);
+/// ```
+#[must_use]
+pub fn realization_factor(class: PreflopClass, position: Position) -> f32 {
+    REALIZATION_ON_BUTTON[class.index()] * POSITION_REALIZATION[position.index()]
+}
+
+/// Realization factors for every `PreflopClass` in every `Position`, starting from the defaults,
+/// with any position's matrix replaceable by one from elsewhere, such as a solver.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RealizationTable {
+    matrices: [PreflopMatrix<f32>; Position::COUNT],
+}
+
+impl RealizationTable {
+    /// Replaces the factors for the `Position`.
+    #[must_use]
+    pub fn with_matrix(mut self, position: Position, matrix: PreflopMatrix<f32>) -> Self {
+        self.matrices[position.index()] = matrix;
+        self
+    }
+
+    #[must_use]
+    pub fn matrix(&self, position: Position) -> &PreflopMatrix<f32> {
+        &self.matrices[position.index()]
+    }
+
+    #[must_use]
+    pub fn factor(&self, class: PreflopClass, position: Position) -> f32 {
+        self.matrices[position.index()][class]
+    }
+
+    /// Scales the raw equity by the hand's realization factor.
+    #[must_use]
+    pub fn realized_equity(&self, class: PreflopClass, position: Position, equity: f32) -> f32 {
+        equity * self.factor(class, position)
+    }
+
+    /// The realized equity of every `PreflopClass` against a random hand from the `Position`,
+    /// using `PREFLOP_EQUITY` as the raw equity.
+    #[must_use]
+    pub fn realized_equities(&self, position: Position) -> PreflopMatrix<f32> {
+        PreflopMatrix::from_fn(|class| self.realized_equity(class, position, class.equity_vs_random()))
+    }
+}
+
+impl Default for RealizationTable {
+    fn default() -> Self {
+        RealizationTable {
+            matrices: Position::ALL.map(|position| PreflopMatrix::from_fn(|class| realization_factor(class, position))),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__realization_tests {
+    use super::*;
+
+    #[test]
+    fn realization_factor() {
+        for class in PreflopClass::ALL {
+            let button = super::realization_factor(class, Position::Button);
+
+            assert!(button > 0.5 && button <= 1.1, "{class}");
+            for position in Position::ALL {
+                assert!(super::realization_factor(class, position) <= button, "{class}");
+            }
+        }
+        assert!(
+            super::realization_factor(PreflopClass::_76s, Position::Button)
+                > super::realization_factor(PreflopClass::_72o, Position::Button)
+        );
+    }
+
+    #[test]
+    fn realization_table() {
+        let solver = PreflopMatrix::new(0.5);
+        let table = RealizationTable::default().with_matrix(Position::SmallBlind, solver.clone());
+
+        assert_eq!(&solver, table.matrix(Position::SmallBlind));
+        assert!((0.5 - table.factor(PreflopClass::AA, Position::SmallBlind)).abs() < f32::EPSILON);
+        assert!((0.25 - table.realized_equity(PreflopClass::AA, Position::SmallBlind, 0.5)).abs() < f32::EPSILON);
+        assert!(
+            (super::realization_factor(PreflopClass::AKs, Position::Cutoff)
+                - table.factor(PreflopClass::AKs, Position::Cutoff))
+            .abs()
+                < f32::EPSILON
+        );
+    }
+
+    #[test]
+    fn realized_equities() {
+        let equities = RealizationTable::default().realized_equities(Position::Button);
+
+        // Suited connectors play better than their raw equity, offsuit gappers worse.
+        assert!(equities[PreflopClass::_87s] > PreflopClass::_87s.equity_vs_random());
+        assert!(equities[PreflopClass::_93o] < PreflopClass::_93o.equity_vs_random());
+    }
+}
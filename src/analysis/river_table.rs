@@ -0,0 +1,385 @@
+use crate::analysis::canonical::{permute_suits, SUIT_PERMUTATIONS};
+use crate::analysis::equity_table::{range_bits, RangeBits, RANGE_WORDS};
+use crate::analysis::preflop::{PreflopClass, PreflopMatrix};
+use crate::analysis::range::Range;
+use crate::cards::binary_card::{conflicts, BinaryCard};
+use crate::cards::five::Five;
+use crate::cards::seven::Seven;
+use crate::cards::{HandRanker, HandValidator};
+use crate::deck::POKER_DECK;
+use crate::hand_rank::HandRankValue;
+use crate::math::Combinations;
+use crate::HandError;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// The bytes at the start of every saved `RiverTable`.
+const MAGIC: [u8; 4] = *b"CKCR";
+
+/// The version of the binary format written by `RiverTable::to_bytes()`.
+pub const RIVER_TABLE_VERSION: u8 = 1;
+
+/// The number of river boards that differ by more than a permutation of their suits.
+pub const CANONICAL_RIVERS: usize = 134_459;
+
+/// Stored in place of the equity of a class with no live combos on the board.
+const NO_EQUITY: u16 = u16::MAX;
+
+/// Equities are stored as a fraction of this, which keeps them within 0.00001 of the real thing.
+const EQUITY_SCALE: f64 = 65_534.0;
+
+/// The length of the magic bytes, version, villain range and number of entries.
+const HEADER_LEN: usize = 4 + 1 + RANGE_WORDS * 8 + 4;
+
+/// The length of one saved entry: the board and an equity for every `PreflopClass`.
+const ENTRY_LEN: usize = 8 + PreflopClass::COUNT * 2;
+
+/// The board relabeled to the suit permutation that gives the smallest `BinaryCard`.
+fn canonical(board: BinaryCard) -> BinaryCard {
+    SUIT_PERMUTATIONS
+        .iter()
+        .map(|permutation| permute_suits(board, *permutation))
+        .min()
+        .unwrap_or(board)
+}
+
+/// One river board for each of the `CANONICAL_RIVERS`, the one with the smallest `BinaryCard`
+/// of all its suit permutations.
+pub fn canonical_rivers() -> impl Iterator<Item = Five> {
+    Combinations::<5>::new(POKER_DECK.len())
+        .map(|indexes| Five::from(indexes.map(|i| POKER_DECK[i])))
+        .filter(|five| {
+            let bits = BinaryCard::from(*five);
+            bits == canonical(bits)
+        })
+}
+
+/// Returns the equity of every `PreflopClass` against the villain's range on a river board, or
+/// `None` for the classes with no combos left to play against it. Every hand that the hero's
+/// and the villain's live combos can be dealt counts the same, and a tie counts as half a win.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn river_equities(villain: &Range, board: Five) -> PreflopMatrix<Option<f64>> {
+    if !board.is_valid() {
+        return PreflopMatrix::new(None);
+    }
+    let dead = BinaryCard::from(board);
+    let value = |two| Seven::new(two, board).hand_rank_value();
+    let villains: Vec<(BinaryCard, HandRankValue)> = villain
+        .live(dead)
+        .map(|two| (BinaryCard::from(*two), value(*two)))
+        .collect();
+
+    PreflopMatrix::from_fn(|class| {
+        let mut half_wins = 0u64;
+        let mut hands = 0u64;
+        for hero in class
+            .combos()
+            .iter()
+            .filter(|two| !conflicts(BinaryCard::from(**two), dead))
+        {
+            let hero_bits = BinaryCard::from(*hero);
+            let hero_value = value(*hero);
+            for (villain_bits, villain_value) in &villains {
+                if hero_bits & villain_bits != 0 {
+                    continue;
+                }
+                hands += 1;
+                half_wins += match hero_value.cmp(villain_value) {
+                    Ordering::Less => 2,
+                    Ordering::Equal => 1,
+                    Ordering::Greater => 0,
+                };
+            }
+        }
+        (hands > 0).then(|| half_wins as f64 / (2 * hands) as f64)
+    })
+}
+
+/// The river equity of every `PreflopClass` against one villain range, worked out ahead of time
+/// for a set of boards, so that a trainer can look them up instead of calculating them while the
+/// user waits.
+///
+/// Boards that only differ by a permutation of their suits share an entry, which is only right
+/// if the villain's range is the same under every permutation, such as one made of whole
+/// `PreflopClasses` like `Range::top_percent()`, so `generate()` won't take any other range. Equities are stored as `u16`s, so they come
+/// back within 0.00001 of what `river_equities()` returned.
+///
+/// The `river_table` binary, built with the `std` feature, generates a table for every
+/// canonical river.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RiverTable {
+    villain: RangeBits,
+    entries: BTreeMap<BinaryCard, [u16; PreflopClass::COUNT]>,
+}
+
+impl RiverTable {
+    /// Works out the equities against the villain's range on each of the boards. Invalid boards
+    /// are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HandError::AsymmetricRange` if relabelling the suits of the villain's range
+    /// changes it, such as `A♠ K♠` without the other suited ace kings.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn generate<I: IntoIterator<Item = Five>>(villain: &Range, boards: I) -> Result<Self, HandError> {
+        let combos: Vec<BinaryCard> = villain.iter().map(|two| BinaryCard::from(*two)).collect();
+        let bits = range_bits(&combos, SUIT_PERMUTATIONS[0]);
+        if SUIT_PERMUTATIONS[1..]
+            .iter()
+            .any(|permutation| range_bits(&combos, *permutation) != bits)
+        {
+            return Err(HandError::AsymmetricRange);
+        }
+
+        let mut entries = BTreeMap::new();
+        for board in boards.into_iter().filter(HandValidator::is_valid) {
+            let equities = river_equities(villain, board);
+            let mut encoded = [NO_EQUITY; PreflopClass::COUNT];
+            for (class, equity) in equities.iter() {
+                if let Some(equity) = equity {
+                    encoded[class.index()] = (equity * EQUITY_SCALE + 0.5) as u16;
+                }
+            }
+            entries.insert(canonical(BinaryCard::from(board)), encoded);
+        }
+        Ok(RiverTable { villain: bits, entries })
+    }
+
+    fn bits(range: &Range) -> RangeBits {
+        let combos: Vec<BinaryCard> = range.iter().map(|two| BinaryCard::from(*two)).collect();
+        range_bits(&combos, SUIT_PERMUTATIONS[0])
+    }
+
+    /// Returns the hero's equity on the board, if it, or a suit permutation of it, is in the table
+    /// and the hero's class has any live combos on it.
+    #[must_use]
+    pub fn get(&self, hero: PreflopClass, board: Five) -> Option<f64> {
+        let equity = self.entries.get(&canonical(BinaryCard::from(board)))?[hero.index()];
+        (equity != NO_EQUITY).then(|| f64::from(equity) / EQUITY_SCALE)
+    }
+
+    /// Returns true if the table was generated against this range.
+    #[must_use]
+    pub fn is_against(&self, villain: &Range) -> bool {
+        self.villain == RiverTable::bits(villain)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Writes the table as `CKCR`, the `RIVER_TABLE_VERSION`, the villain's range as one bit per
+    /// combo, the number of boards as a `u32`, and then each board followed by the equity of
+    /// every `PreflopClass`, all little endian.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.entries.len() * ENTRY_LEN);
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(RIVER_TABLE_VERSION);
+        for word in &self.villain {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        bytes.extend_from_slice(&(self.entries.len() as u32).to_le_bytes());
+        for (board, equities) in &self.entries {
+            bytes.extend_from_slice(&board.to_le_bytes());
+            for equity in equities {
+                bytes.extend_from_slice(&equity.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Reads a table written by `RiverTable::to_bytes()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HandError::InvalidBinaryFormat` if the bytes don't start with `CKCR` and the
+    /// current `RIVER_TABLE_VERSION`, or aren't the right length for the number of boards.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, HandError> {
+        if bytes.len() < HEADER_LEN || bytes[..4] != MAGIC || bytes[4] != RIVER_TABLE_VERSION {
+            return Err(HandError::InvalidBinaryFormat);
+        }
+        let mut villain = [0; RANGE_WORDS];
+        for (word, chunk) in villain.iter_mut().zip(bytes[5..HEADER_LEN - 4].chunks_exact(8)) {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(chunk);
+            *word = u64::from_le_bytes(buf);
+        }
+        let len = &bytes[HEADER_LEN - 4..HEADER_LEN];
+        let len = u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize;
+        let body = &bytes[HEADER_LEN..];
+        if len.checked_mul(ENTRY_LEN) != Some(body.len()) {
+            return Err(HandError::InvalidBinaryFormat);
+        }
+
+        let mut entries = BTreeMap::new();
+        for entry in body.chunks_exact(ENTRY_LEN) {
+            let mut board = [0u8; 8];
+            board.copy_from_slice(&entry[..8]);
+            let mut equities = [NO_EQUITY; PreflopClass::COUNT];
+            for (equity, chunk) in equities.iter_mut().zip(entry[8..].chunks_exact(2)) {
+                *equity = u16::from_le_bytes([chunk[0], chunk[1]]);
+            }
+            entries.insert(u64::from_le_bytes(board), equities);
+        }
+        Ok(RiverTable { villain, entries })
+    }
+
+    /// Saves the table to a file, in the format of `RiverTable::to_bytes()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from writing the file.
+    #[cfg(feature = "std")]
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_bytes())
+    }
+
+    /// Loads a table saved by `RiverTable::save()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from reading the file, and an `InvalidData` error if it isn't a saved
+    /// `RiverTable`.
+    #[cfg(feature = "std")]
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        let bytes = std::fs::read(path)?;
+        RiverTable::from_bytes(&bytes)
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidData, "not a saved RiverTable"))
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__river_table_tests {
+    use super::*;
+    use crate::analysis::range::StartingHandMetric;
+    use crate::cards::two::Two;
+
+    fn board(index: &'static str) -> Five {
+        Five::try_from(index).unwrap()
+    }
+
+    #[test]
+    fn canonical_rivers() {
+        assert_eq!(CANONICAL_RIVERS, super::canonical_rivers().count());
+    }
+
+    #[test]
+    fn river_equities() {
+        let villain = Range::from(alloc::vec![Two::try_from("K♠ K♥").unwrap()]);
+
+        let equities = super::river_equities(&villain, board("K♦ 9♣ 8♠ 7♥ 2♦"));
+
+        assert_eq!(Some(0.0), equities[PreflopClass::AA]);
+        assert_eq!(Some(0.0), equities[PreflopClass::_99]);
+        assert_eq!(Some(1.0), equities[PreflopClass::T6s]);
+        // The only king left is K♣.
+        assert_eq!(None, equities[PreflopClass::KK]);
+        assert_eq!(
+            PreflopMatrix::new(None),
+            super::river_equities(&villain, Five::default())
+        );
+    }
+
+    #[test]
+    fn generate() {
+        let villain = Range::top_percent(20.0, StartingHandMetric::EquityVsRandom);
+        let boards = [board("A♠ K♥ 7♦ 4♣ 2♠"), board("Q♥ J♥ T♥ 3♥ 3♣")];
+
+        let table = RiverTable::generate(&villain, boards).unwrap();
+
+        assert_eq!(2, table.len());
+        assert!(table.is_against(&villain));
+        assert!(!table.is_against(&Range::all()));
+        for board in boards {
+            let equities = super::river_equities(&villain, board);
+            for class in PreflopClass::ALL {
+                let expected = equities[class].unwrap();
+                let got = table.get(class, board).unwrap();
+                assert!((expected - got).abs() < 0.00001, "{class}");
+            }
+        }
+        // The same board with its suits relabeled.
+        assert_eq!(
+            table.get(PreflopClass::AKs, boards[0]),
+            table.get(PreflopClass::AKs, board("A♦ K♣ 7♠ 4♥ 2♦"))
+        );
+        assert_eq!(None, table.get(PreflopClass::AA, board("A♠ K♥ 7♦ 4♣ 3♠")));
+    }
+
+    #[test]
+    fn generate__asymmetric() {
+        let villain = Range::from(alloc::vec![Two::try_from("A♠ K♠").unwrap()]);
+
+        assert_eq!(
+            HandError::AsymmetricRange,
+            RiverTable::generate(&villain, [board("Q♥ J♥ T♥ 3♥ 3♣")]).unwrap_err()
+        );
+        assert!(RiverTable::generate(&Range::all(), []).unwrap().is_empty());
+    }
+
+    #[test]
+    fn to_bytes() {
+        let villain = Range::top_percent(5.0, StartingHandMetric::EquityVsRandom);
+        let table = RiverTable::generate(&villain, [board("K♠ K♥ K♦ 4♣ 2♠")]).unwrap();
+
+        let bytes = table.to_bytes();
+        let loaded = RiverTable::from_bytes(&bytes).unwrap();
+
+        assert_eq!(HEADER_LEN + ENTRY_LEN, bytes.len());
+        assert_eq!(table, loaded);
+        assert!(loaded.is_against(&villain));
+        assert_eq!(None, loaded.get(PreflopClass::KK, board("K♠ K♥ K♦ 4♣ 2♠")));
+    }
+
+    #[test]
+    fn from_bytes__invalid() {
+        let bytes = RiverTable::default().to_bytes();
+        let mut wrong_magic = bytes.clone();
+        wrong_magic[0] = b'X';
+
+        assert!(RiverTable::from_bytes(&bytes).unwrap().is_empty());
+        assert_eq!(
+            HandError::InvalidBinaryFormat,
+            RiverTable::from_bytes(&wrong_magic).unwrap_err()
+        );
+        assert_eq!(
+            HandError::InvalidBinaryFormat,
+            RiverTable::from_bytes(&bytes[..HEADER_LEN - 1]).unwrap_err()
+        );
+        assert_eq!(
+            HandError::InvalidBinaryFormat,
+            RiverTable::from_bytes(&[bytes.clone(), alloc::vec![0]].concat()).unwrap_err()
+        );
+        let mut too_long = bytes;
+        too_long[HEADER_LEN - 4..].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            HandError::InvalidBinaryFormat,
+            RiverTable::from_bytes(&too_long).unwrap_err()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn save() {
+        let villain = Range::top_percent(5.0, StartingHandMetric::EquityVsRandom);
+        let table = RiverTable::generate(&villain, [board("A♠ K♥ 7♦ 4♣ 2♠")]).unwrap();
+        let path = std::env::temp_dir().join(alloc::format!("ckc-river-table-{}.bin", std::process::id()));
+
+        table.save(&path).unwrap();
+        let loaded = RiverTable::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(table, loaded);
+    }
+}
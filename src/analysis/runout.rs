@@ -0,0 +1,196 @@
+use crate::cards::binary_card::{BinaryCard, BC64};
+use crate::cards::seven::Seven;
+use crate::cards::six::Six;
+use crate::cards::{HandRanker, HandValidator};
+use crate::collections::{ArrayVec, CardList};
+use crate::deck::{DECK_SIZE, POKER_DECK};
+use crate::hand_rank::{HandRank, HandRankName};
+use crate::{CKCNumber, HandError, PokerCard};
+
+/// What a river card does for the hand.
+///
+/// * `Improves` - the hand becomes a stronger type, such as a pair becoming trips.
+/// * `PairsBoard` - the river pairs one of the board cards. That gives every hand an extra pair,
+///   so going up to a `Pair` or `TwoPair` this way doesn't count as improving, but filling up a
+///   set does.
+/// * `Brick` - the river changes nothing.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum RiverOutcome {
+    Improves(HandRankName),
+    PairsBoard,
+    #[default]
+    Brick,
+}
+
+/// A possible river card and the hand that it makes.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct RiverCard {
+    pub card: CKCNumber,
+    pub rank: HandRank,
+    pub outcome: RiverOutcome,
+}
+
+/// Every possible river card for a hand on the turn.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct OutcomeTable {
+    /// The hand as it stands on the turn.
+    pub current: HandRank,
+    /// Every live river card, in deck order.
+    pub rivers: ArrayVec<RiverCard, DECK_SIZE>,
+}
+
+impl OutcomeTable {
+    /// The number of river cards with the outcome.
+    #[must_use]
+    pub fn count(&self, outcome: RiverOutcome) -> usize {
+        self.rivers.iter().filter(|river| river.outcome == outcome).count()
+    }
+
+    /// The number of river cards that improve the hand to each `HandRankName`, strongest first.
+    #[must_use]
+    pub fn improvements(&self) -> ArrayVec<(HandRankName, usize), 9> {
+        let mut counts: ArrayVec<(HandRankName, usize), 9> = ArrayVec::new();
+        for river in &self.rivers {
+            if let RiverOutcome::Improves(name) = river.outcome {
+                match counts.iter_mut().find(|(n, _)| *n == name) {
+                    Some((_, count)) => *count += 1,
+                    // There are only nine names that a hand can improve to.
+                    None => {
+                        let _ = counts.push((name, 1));
+                    },
+                }
+            }
+        }
+        counts.sort();
+        counts
+    }
+
+    /// The cards that improve the hand.
+    #[must_use]
+    pub fn outs(&self) -> CardList {
+        let mut outs = CardList::new();
+        for river in self
+            .rivers
+            .iter()
+            .filter(|river| matches!(river.outcome, RiverOutcome::Improves(_)))
+        {
+            // There are never more rivers than there are cards in the deck.
+            let _ = outs.push(river.card);
+        }
+        outs
+    }
+}
+
+/// Deals every river card that isn't in the hand or dead, and sorts each one by what it does
+/// for the hand. The first two cards of the `Six` are the hole cards and the last four are the
+/// board.
+///
+/// # Errors
+///
+/// Returns `HandError::InvalidCard` if any card in the hand is invalid, and
+/// `HandError::DuplicateCard` if any card in it repeats.
+pub fn runout_outcomes(turn: Six, dead: BinaryCard) -> Result<OutcomeTable, HandError> {
+    if turn.is_corrupt() {
+        return Err(HandError::InvalidCard);
+    }
+    if !turn.are_unique() {
+        return Err(HandError::DuplicateCard);
+    }
+
+    let cards = turn.to_arr();
+    let current = turn.hand_rank();
+    let known = cards.iter().fold(dead, |bits, card| bits | BinaryCard::from_ckc(*card));
+
+    let mut rivers = ArrayVec::new();
+    for river in POKER_DECK
+        .into_iter()
+        .filter(|card| !known.has(BinaryCard::from_ckc(*card)))
+        .map(|card| {
+            let rank = Seven::from([cards[0], cards[1], cards[2], cards[3], cards[4], cards[5], card]).hand_rank();
+            let pairs_board = cards[2..]
+                .iter()
+                .any(|board| board.get_card_rank() == card.get_card_rank());
+            let improves = rank.name != current.name && rank.value < current.value;
+            let outcome = match (improves, pairs_board) {
+                (true, true) if matches!(rank.name, HandRankName::Pair | HandRankName::TwoPair) => {
+                    RiverOutcome::PairsBoard
+                },
+                (true, _) => RiverOutcome::Improves(rank.name),
+                (false, true) => RiverOutcome::PairsBoard,
+                (false, false) => RiverOutcome::Brick,
+            };
+            RiverCard { card, rank, outcome }
+        })
+    {
+        // There are never more rivers than there are cards in the deck.
+        let _ = rivers.push(river);
+    }
+
+    Ok(OutcomeTable { current, rivers })
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__runout_tests {
+    use super::*;
+    use crate::CardNumber;
+
+    #[test]
+    fn runout_outcomes__flush_draw() {
+        // Nut flush draw with two overs on a dry board.
+        let turn = Six::try_from("A♠ K♠ 9♠ 5♠ 2♦ 7♣").unwrap();
+
+        let table = runout_outcomes(turn, BinaryCard::BLANK).unwrap();
+
+        assert_eq!(HandRankName::HighCard, table.current.name);
+        assert_eq!(46, table.rivers.len());
+        assert_eq!(9, table.count(RiverOutcome::Improves(HandRankName::Flush)));
+        assert_eq!(6, table.count(RiverOutcome::Improves(HandRankName::Pair)));
+        // Three each of the nines and fives are left, and two each of the non spade deuces and
+        // sevens.
+        assert_eq!(10, table.count(RiverOutcome::PairsBoard));
+        assert_eq!(21, table.count(RiverOutcome::Brick));
+        assert_eq!(
+            &[(HandRankName::Flush, 9), (HandRankName::Pair, 6)],
+            table.improvements().as_slice()
+        );
+        assert_eq!(15, table.outs().len());
+    }
+
+    #[test]
+    fn runout_outcomes__dead() {
+        let turn = Six::try_from("A♠ K♠ 9♠ 5♠ 2♦ 7♣").unwrap();
+        let dead = BinaryCard::from_ckc(CardNumber::QUEEN_SPADES) | BinaryCard::from_ckc(CardNumber::ACE_HEARTS);
+
+        let table = runout_outcomes(turn, dead).unwrap();
+
+        assert_eq!(44, table.rivers.len());
+        assert_eq!(8, table.count(RiverOutcome::Improves(HandRankName::Flush)));
+        assert_eq!(5, table.count(RiverOutcome::Improves(HandRankName::Pair)));
+    }
+
+    #[test]
+    fn runout_outcomes__set() {
+        let turn = Six::try_from("7♠ 7♥ 9♠ 5♦ 2♦ 7♣").unwrap();
+
+        let table = runout_outcomes(turn, BinaryCard::BLANK).unwrap();
+
+        assert_eq!(HandRankName::ThreeOfAKind, table.current.name);
+        assert_eq!(1, table.count(RiverOutcome::Improves(HandRankName::FourOfAKind)));
+        // Pairing the nine, five or deuce fills up.
+        assert_eq!(9, table.count(RiverOutcome::Improves(HandRankName::FullHouse)));
+        assert_eq!(0, table.count(RiverOutcome::PairsBoard));
+    }
+
+    #[test]
+    fn runout_outcomes__errors() {
+        assert_eq!(
+            HandError::DuplicateCard,
+            runout_outcomes(Six::try_from("A♠ A♠ 9♠ 5♠ 2♦ 7♣").unwrap(), BinaryCard::BLANK).unwrap_err()
+        );
+        assert_eq!(
+            HandError::InvalidCard,
+            runout_outcomes(Six::default(), BinaryCard::BLANK).unwrap_err()
+        );
+    }
+}
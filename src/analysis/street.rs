@@ -0,0 +1,424 @@
+use crate::analysis::equity::Tally;
+use crate::analysis::range::Range;
+use crate::cancel::{Cancellation, Partial};
+use crate::cards::binary_card::{BinaryCard, BC64};
+use crate::cards::five::Five;
+use crate::cards::seven::{seven, Seven};
+use crate::cards::six::Six;
+use crate::cards::three::Three;
+use crate::cards::two::Two;
+use crate::cards::{HandRanker, HandValidator};
+use crate::deck::Deck;
+use crate::hand_rank::HandRank;
+use crate::{CKCNumber, HandError};
+use core::cmp::Ordering;
+
+/// Hero's share of the pot against a `Range` at each street of a flopped board.
+///
+/// * `flop` is the equity if the hands were shown down on the flop.
+/// * `turn` is the equity shown down on the turn, averaged over every live turn card.
+/// * `river` is the equity shown down on the river, averaged over every live turn and river,
+///   which is the hand's all in equity on the flop.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StreetEquity {
+    pub flop: f64,
+    pub turn: f64,
+    pub river: f64,
+}
+
+/// Enumerates every turn and river for hero's hand against each live combo in the villain's
+/// `Range`, returning hero's equity at the flop, the turn and the river.
+///
+/// # Errors
+///
+/// Returns `HandError::InvalidCard` if any of hero's cards or the flop are invalid,
+/// `HandError::DuplicateCard` if they share a card, and `HandError::NotEnoughCards` if the
+/// villain's `Range` has no combos left once hero's cards and the flop are removed.
+pub fn equity_by_street(hero: Two, villain: &Range, flop: Three) -> Result<StreetEquity, HandError> {
+    equity_by_street_cancellable(hero, villain, flop, Cancellation::NEVER).map(|partial| partial.result)
+}
+
+/// The same as `equity_by_street()`, except that it checks the `Cancellation` before each of the
+/// villain's combos, and stops if it's set. The equity of a cancelled run only counts the combos
+/// that were finished, and is zero if there weren't any.
+///
+/// # Errors
+///
+/// The same as `equity_by_street()`, except that `HandError::NotEnoughCards` is only returned if
+/// the run wasn't cancelled.
+pub fn equity_by_street_cancellable(
+    hero: Two,
+    villain: &Range,
+    flop: Three,
+    cancellation: Cancellation,
+) -> Result<Partial<StreetEquity>, HandError> {
+    if !hero.is_valid() || !flop.is_valid() {
+        return Err(HandError::InvalidCard);
+    }
+    let known = BinaryCard::from_two(hero) | BinaryCard::from_three(flop);
+    if known.count_ones() != 5 {
+        return Err(HandError::DuplicateCard);
+    }
+
+    let mut flop_tally = Tally::default();
+    let mut turn_tally = Tally::default();
+    let mut river_tally = Tally::default();
+
+    let mut complete = true;
+
+    for combo in villain.live(known) {
+        if cancellation.is_cancelled() {
+            complete = false;
+            break;
+        }
+        let dead = known | BinaryCard::from_two(*combo);
+        let live = Deck::live(dead);
+
+        flop_tally.record(five(hero, flop).hand_rank_value(), five(*combo, flop).hand_rank_value());
+
+        for (i, turn) in live.iter().enumerate() {
+            turn_tally.record(
+                six(hero, flop, *turn).hand_rank_value(),
+                six(*combo, flop, *turn).hand_rank_value(),
+            );
+
+            for river in &live[i + 1..] {
+                river_tally.record(
+                    seven(hero, flop, *turn, *river).hand_rank_value(),
+                    seven(*combo, flop, *turn, *river).hand_rank_value(),
+                );
+            }
+        }
+    }
+
+    if complete && flop_tally.total == 0 {
+        return Err(HandError::NotEnoughCards);
+    }
+
+    Ok(Partial {
+        result: StreetEquity {
+            flop: flop_tally.equity(),
+            turn: turn_tally.equity(),
+            river: river_tally.equity(),
+        },
+        complete,
+    })
+}
+
+/// How a hand changed from one street to the next.
+///
+/// * `Improved` - it made a better kind of hand, such as two pair from one pair.
+/// * `Stayed` - it's the same kind of hand as before, even if the kickers changed.
+/// * `Outdrawn` - it's a worse kind of hand than before.
+///
+/// Against a range, the same is true of hero's equity against it, so that a hand which stays a
+/// pair, while the river gives half of the range a flush, was outdrawn.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum StreetDelta {
+    Improved,
+    Stayed,
+    Outdrawn,
+}
+
+/// Classifies the change from a hand's best `HandRank` on one street to its best on the next.
+///
+/// ```
+/// use ckc_rs::analysis::street::{street_delta, StreetDelta};
+/// use ckc_rs::cards::seven::Seven;
+/// use ckc_rs::cards::six::Six;
+/// use ckc_rs::cards::HandRanker;
+///
+/// let turn = Six::try_from("A♠ K♥ A♦ 7♣ 4♠ 2♥").unwrap();
+/// let river = Seven::try_from("A♠ K♥ A♦ 7♣ 4♠ 2♥ K♦").unwrap();
+///
+/// assert_eq!(StreetDelta::Improved, street_delta(turn.hand_rank(), river.hand_rank()));
+/// ```
+#[must_use]
+pub fn street_delta(prev_best: HandRank, new_best: HandRank) -> StreetDelta {
+    // Stronger hands have names that sort first.
+    match new_best.name.cmp(&prev_best.name) {
+        Ordering::Less => StreetDelta::Improved,
+        Ordering::Equal => StreetDelta::Stayed,
+        Ordering::Greater => StreetDelta::Outdrawn,
+    }
+}
+
+/// How hero's hand changed on the river, from `river_delta()`.
+///
+/// * `hand` is the `street_delta()` of hero's own best hand.
+/// * `turn` and `river` are hero's equity against the villain's live combos if the hands were
+///   shown down on the turn and on the river.
+/// * `outdrawn_by` is the number of combos that hero beat on the turn and loses to on the river.
+/// * `outdrew` is the number of combos that beat hero on the turn and lose to hero on the river.
+/// * `combos` is the number of the villain's combos that are live.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RiverDelta {
+    pub hand: StreetDelta,
+    pub turn: f64,
+    pub river: f64,
+    pub outdrawn_by: usize,
+    pub outdrew: usize,
+    pub combos: usize,
+}
+
+impl RiverDelta {
+    /// Classifies the change in hero's equity against the range from the turn to the river.
+    #[must_use]
+    pub fn delta(&self) -> StreetDelta {
+        match self.river.partial_cmp(&self.turn) {
+            Some(Ordering::Greater) => StreetDelta::Improved,
+            Some(Ordering::Less) => StreetDelta::Outdrawn,
+            _ => StreetDelta::Stayed,
+        }
+    }
+}
+
+/// Compares hero's hand on the turn with the same hand on the `river`, on its own and against
+/// each combo in the villain's `Range` that's live. The `turn` holds hero's hole cards
+/// followed by the four cards of the board, as made by `Six::new()`.
+///
+/// ```
+/// use ckc_rs::analysis::range::Range;
+/// use ckc_rs::analysis::street::{river_delta, StreetDelta};
+/// use ckc_rs::cards::four::Four;
+/// use ckc_rs::cards::six::Six;
+/// use ckc_rs::cards::two::Two;
+/// use ckc_rs::CardNumber;
+///
+/// let turn = Six::new(Two::try_from("A♠ A♥").unwrap(), Four::try_from("K♦ 7♦ 4♦ 2♣").unwrap());
+/// let villain = Range::from(vec![Two::try_from("Q♦ J♠").unwrap(), Two::try_from("K♠ Q♠").unwrap()]);
+///
+/// let delta = river_delta(turn, CardNumber::NINE_DIAMONDS, &villain).unwrap();
+///
+/// // Still a pair of aces, but now behind the flush.
+/// assert_eq!(StreetDelta::Stayed, delta.hand);
+/// assert_eq!(StreetDelta::Outdrawn, delta.delta());
+/// assert_eq!(1, delta.outdrawn_by);
+/// ```
+///
+/// # Errors
+///
+/// Same as `HandValidator::validate()` for the `turn` and the `river` together, and
+/// `HandError::NotEnoughCards` if the villain's `Range` has no combos left once they're removed.
+pub fn river_delta(turn: Six, river: CKCNumber, villain: &Range) -> Result<RiverDelta, HandError> {
+    let [first, second, third, forth, fifth, sixth] = turn.to_arr();
+    let hero = Seven::from([first, second, third, forth, fifth, sixth, river]);
+    hero.validate()?;
+
+    let turn_rank = turn.hand_rank_value();
+    let river_rank = hero.hand_rank_value();
+    let mut turn_tally = Tally::default();
+    let mut river_tally = Tally::default();
+    let mut outdrawn_by = 0;
+    let mut outdrew = 0;
+
+    for combo in villain.live(hero.id()) {
+        let (a, b) = (combo.first(), combo.second());
+        let villain_turn = Six::from([a, b, third, forth, fifth, sixth]).hand_rank_value();
+        let villain_river = Seven::from([a, b, third, forth, fifth, sixth, river]).hand_rank_value();
+
+        turn_tally.record(turn_rank, villain_turn);
+        river_tally.record(river_rank, villain_river);
+        // Lower hand rank values are stronger hands.
+        if turn_rank < villain_turn && river_rank > villain_river {
+            outdrawn_by += 1;
+        } else if turn_rank > villain_turn && river_rank < villain_river {
+            outdrew += 1;
+        }
+    }
+
+    if turn_tally.total == 0 {
+        return Err(HandError::NotEnoughCards);
+    }
+    Ok(RiverDelta {
+        hand: street_delta(HandRank::from(turn_rank), HandRank::from(river_rank)),
+        turn: turn_tally.equity(),
+        river: river_tally.equity(),
+        outdrawn_by,
+        outdrew,
+        combos: usize::try_from(turn_tally.total).unwrap_or(usize::MAX),
+    })
+}
+
+fn five(two: Two, flop: Three) -> Five {
+    Five::from([two.first(), two.second(), flop.first(), flop.second(), flop.third()])
+}
+
+fn six(two: Two, flop: Three, turn: CKCNumber) -> Six {
+    Six::from([
+        two.first(),
+        two.second(),
+        flop.first(),
+        flop.second(),
+        flop.third(),
+        turn,
+    ])
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__street_tests {
+    use super::*;
+    use crate::cards::four::Four;
+    use crate::CardNumber;
+    use core::sync::atomic::{AtomicBool, Ordering};
+    use rstest::rstest;
+
+    #[test]
+    fn equity_by_street() {
+        let hero = Two::try_from("A♠ A♥").unwrap();
+        let villain = Range::from(vec![Two::try_from("K♠ K♥").unwrap()]);
+        let flop = Three::try_from("2♣ 7♦ 9♥").unwrap();
+
+        let equity = super::equity_by_street(hero, &villain, flop).unwrap();
+
+        assert!((equity.flop - 1.0).abs() < f64::EPSILON);
+        // The villain only wins on the turn by hitting one of the two remaining kings.
+        assert!((equity.turn - 43.0 / 45.0).abs() < f64::EPSILON);
+        assert!(equity.river < equity.turn);
+        assert!(equity.river > 0.9);
+    }
+
+    #[test]
+    fn equity_by_street__chop() {
+        let hero = Two::try_from("A♠ K♥").unwrap();
+        let villain = Range::from(vec![Two::try_from("A♦ K♣").unwrap()]);
+        let flop = Three::try_from("A♥ K♠ Q♦").unwrap();
+
+        let equity = super::equity_by_street(hero, &villain, flop).unwrap();
+
+        assert!((equity.flop - 0.5).abs() < f64::EPSILON);
+        assert!((equity.turn - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn equity_by_street_cancellable() {
+        let hero = Two::try_from("A♠ A♥").unwrap();
+        let villain = Range::from(vec![Two::try_from("K♠ K♥").unwrap(), Two::try_from("Q♠ Q♥").unwrap()]);
+        let flop = Three::try_from("2♣ 7♦ 9♥").unwrap();
+        let flag = AtomicBool::new(false);
+
+        let finished = super::equity_by_street_cancellable(hero, &villain, flop, Cancellation::from(&flag)).unwrap();
+        flag.store(true, Ordering::Relaxed);
+        let cancelled = super::equity_by_street_cancellable(hero, &villain, flop, Cancellation::from(&flag)).unwrap();
+
+        assert!(finished.complete);
+        assert_eq!(super::equity_by_street(hero, &villain, flop).unwrap(), finished.result);
+        assert!(!cancelled.complete);
+        assert_eq!(StreetEquity::default(), cancelled.result);
+    }
+
+    #[test]
+    fn equity_by_street__dead_combos() {
+        let hero = Two::try_from("A♠ A♥").unwrap();
+        let flop = Three::try_from("2♣ 7♦ 9♥").unwrap();
+        let villain = Range::from(vec![Two::try_from("A♠ K♥").unwrap(), Two::try_from("9♥ 9♠").unwrap()]);
+
+        assert_eq!(
+            HandError::NotEnoughCards,
+            super::equity_by_street(hero, &villain, flop).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn equity_by_street__duplicate() {
+        let hero = Two::try_from("A♠ A♥").unwrap();
+        let villain = Range::from(vec![Two::try_from("K♠ K♥").unwrap()]);
+        let flop = Three::try_from("A♠ 7♦ 9♥").unwrap();
+
+        assert_eq!(
+            HandError::DuplicateCard,
+            super::equity_by_street(hero, &villain, flop).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn equity_by_street__invalid() {
+        let villain = Range::from(vec![Two::try_from("K♠ K♥").unwrap()]);
+        let flop = Three::try_from("2♣ 7♦ 9♥").unwrap();
+
+        assert_eq!(
+            HandError::InvalidCard,
+            super::equity_by_street(Two::default(), &villain, flop).unwrap_err()
+        );
+    }
+
+    #[rstest]
+    #[case("A♠ A♦ 7♣ 4♠ 2♥", "A♠ A♦ K♥ K♦ 7♣", StreetDelta::Improved)]
+    #[case("A♠ A♦ 7♣ 4♠ 2♥", "A♠ A♦ K♥ 7♣ 4♠", StreetDelta::Stayed)]
+    #[case("A♠ K♠ 7♠ 4♠ 2♠", "A♠ A♦ K♥ 7♣ 4♠", StreetDelta::Outdrawn)]
+    fn street_delta(#[case] prev: &'static str, #[case] new: &'static str, #[case] expected: StreetDelta) {
+        let prev = Five::try_from(prev).unwrap().hand_rank();
+        let new = Five::try_from(new).unwrap().hand_rank();
+
+        assert_eq!(expected, super::street_delta(prev, new));
+    }
+
+    #[test]
+    fn street_delta__invalid() {
+        let pair = Five::try_from("A♠ A♦ 7♣ 4♠ 2♥").unwrap().hand_rank();
+
+        assert_eq!(StreetDelta::Improved, super::street_delta(HandRank::default(), pair));
+    }
+
+    #[test]
+    fn river_delta() {
+        let turn = Six::new(Two::try_from("A♠ A♥").unwrap(), Four::try_from("K♦ 7♦ 4♦ 2♣").unwrap());
+        let villain = Range::from(vec![Two::try_from("Q♦ J♠").unwrap(), Two::try_from("K♠ Q♠").unwrap()]);
+
+        let delta = super::river_delta(turn, CardNumber::NINE_DIAMONDS, &villain).unwrap();
+
+        assert_eq!(StreetDelta::Stayed, delta.hand);
+        assert_eq!(StreetDelta::Outdrawn, delta.delta());
+        assert!((delta.turn - 1.0).abs() < f64::EPSILON);
+        assert!((delta.river - 0.5).abs() < f64::EPSILON);
+        assert_eq!(1, delta.outdrawn_by);
+        assert_eq!(0, delta.outdrew);
+        assert_eq!(2, delta.combos);
+    }
+
+    #[test]
+    fn river_delta__outdrew() {
+        let turn = Six::new(Two::try_from("9♥ 8♥").unwrap(), Four::try_from("K♥ 7♥ 2♣ 3♠").unwrap());
+        let villain = Range::from(vec![Two::try_from("A♠ K♣").unwrap(), Two::try_from("K♥ Q♠").unwrap()]);
+
+        let delta = super::river_delta(turn, CardNumber::SIX_HEARTS, &villain).unwrap();
+
+        assert_eq!(StreetDelta::Improved, delta.hand);
+        assert_eq!(StreetDelta::Improved, delta.delta());
+        assert_eq!(1, delta.outdrew);
+        assert_eq!(0, delta.outdrawn_by);
+        // K♥ Q♠ is dead, since the K♥ is on the board.
+        assert_eq!(1, delta.combos);
+    }
+
+    #[test]
+    fn river_delta__stayed() {
+        let turn = Six::new(Two::try_from("A♠ A♥").unwrap(), Four::try_from("K♦ 7♣ 4♦ 2♣").unwrap());
+        let villain = Range::from(vec![Two::try_from("Q♠ J♠").unwrap()]);
+
+        let delta = super::river_delta(turn, CardNumber::NINE_HEARTS, &villain).unwrap();
+
+        assert_eq!(StreetDelta::Stayed, delta.hand);
+        assert_eq!(StreetDelta::Stayed, delta.delta());
+    }
+
+    #[test]
+    fn river_delta__invalid() {
+        let turn = Six::new(Two::try_from("A♠ A♥").unwrap(), Four::try_from("K♦ 7♦ 4♦ 2♣").unwrap());
+        let villain = Range::from(vec![Two::try_from("Q♠ J♠").unwrap()]);
+
+        assert_eq!(
+            HandError::DuplicateCard,
+            super::river_delta(turn, CardNumber::KING_DIAMONDS, &villain).unwrap_err()
+        );
+        assert_eq!(
+            HandError::Incomplete,
+            super::river_delta(turn, CardNumber::BLANK, &villain).unwrap_err()
+        );
+        assert_eq!(
+            HandError::NotEnoughCards,
+            super::river_delta(turn, CardNumber::QUEEN_SPADES, &villain).unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,504 @@
+use crate::analysis::equity::Tally;
+use crate::analysis::range::Range;
+use crate::cards::binary_card::{BinaryCard, BC64};
+use crate::cards::five::Five;
+use crate::cards::seven::Seven;
+use crate::cards::three::Three;
+use crate::cards::{HandRanker, HandValidator};
+use crate::deck::Deck;
+use crate::replay::Street;
+use crate::{CKCNumber, CardNumber, HandError};
+use alloc::vec::Vec;
+
+/// One board in a `RunoutTree`: the flop at the root, a turn below it, or a river below that.
+///
+/// The equity is the hero `Range`'s share of the pot against the villain's, counting every
+/// matchup of live combos on every runout of the board, so at the flop it's the all in equity
+/// and at the river it's the equity at showdown.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RunoutNode {
+    board: [CKCNumber; 5],
+    len: usize,
+    tally: Tally,
+    expanded: bool,
+    children: Vec<RunoutNode>,
+}
+
+impl RunoutNode {
+    /// The board at this node, from three to five cards.
+    #[must_use]
+    pub fn board(&self) -> &[CKCNumber] {
+        &self.board[..self.len]
+    }
+
+    /// The card dealt to reach this node, or `None` at the flop.
+    #[must_use]
+    pub fn card(&self) -> Option<CKCNumber> {
+        if self.len > 3 {
+            Some(self.board[self.len - 1])
+        } else {
+            None
+        }
+    }
+
+    #[must_use]
+    pub fn street(&self) -> Street {
+        match self.len {
+            3 => Street::Flop,
+            4 => Street::Turn,
+            _ => Street::River,
+        }
+    }
+
+    /// The hero's equity at this node, or zero if the ranges have no live matchups on it.
+    #[must_use]
+    pub fn equity(&self) -> f64 {
+        self.tally.equity()
+    }
+
+    /// The number of matchups of a hero combo against a villain combo, over every runout, that
+    /// the equity is made of.
+    #[must_use]
+    pub fn matchups(&self) -> u64 {
+        self.tally.total
+    }
+
+    /// True once the node's children have been dealt, which never happens for a river.
+    #[must_use]
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    /// The children that have been dealt and not pruned, in deck order. Empty until the node
+    /// is expanded.
+    #[must_use]
+    pub fn children(&self) -> &[RunoutNode] {
+        &self.children
+    }
+
+    #[must_use]
+    pub fn child(&self, card: CKCNumber) -> Option<&RunoutNode> {
+        self.children.iter().find(|child| child.card() == Some(card))
+    }
+
+    fn child_mut(&mut self, card: CKCNumber) -> Option<&mut RunoutNode> {
+        self.children.iter_mut().find(|child| child.card() == Some(card))
+    }
+
+    fn prune<F: FnMut(&RunoutNode) -> bool>(&mut self, keep: &mut F) {
+        self.children.retain(|child| keep(child));
+        for child in &mut self.children {
+            child.prune(keep);
+        }
+    }
+}
+
+/// The turns and rivers that can come on a flop, each with the equity of one `Range` against
+/// another, for exploring a board street by street.
+///
+/// Only the flop is worked out up front. The children of a node are dealt when it's expanded,
+/// so a tree that's only looked at along a few runouts never pays for the rest. Nodes are named
+/// by their path: the cards dealt after the flop, so `&[]` is the flop, `&[turn]` a turn and
+/// `&[turn, river]` a river.
+///
+/// ```
+/// use ckc_rs::analysis::range::Range;
+/// use ckc_rs::analysis::tree::RunoutTree;
+/// use ckc_rs::cards::binary_card::{BinaryCard, BC64};
+/// use ckc_rs::cards::three::Three;
+/// use ckc_rs::cards::two::Two;
+/// use ckc_rs::CardNumber;
+///
+/// let hero = Range::from(vec![Two::try_from("A♠ A♥").unwrap()]);
+/// let villain = Range::from(vec![Two::try_from("K♠ K♥").unwrap()]);
+/// let flop = Three::try_from("2♣ 7♦ 9♥").unwrap();
+///
+/// let mut tree = RunoutTree::new(&hero, &villain, flop, BinaryCard::BLANK).unwrap();
+/// assert!(tree.root().equity() > 0.9);
+///
+/// // Only the turns that give the villain a set.
+/// tree.expand_where(&[], |turn| turn.matchups() > 0 && turn.equity() < 0.5);
+/// assert_eq!(2, tree.root().children().len());
+///
+/// let rivers = tree.expand(&[CardNumber::KING_DIAMONDS]).unwrap();
+/// assert_eq!(2, rivers.iter().filter(|river| river.equity() == 1.0).count());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RunoutTree {
+    hero: Range,
+    villain: Range,
+    dead: BinaryCard,
+    root: RunoutNode,
+}
+
+impl RunoutTree {
+    /// Works out the equity of the hero's `Range` against the villain's on the flop. Combos
+    /// that use a dead card are left out, and dead cards never come on the turn or river.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HandError::InvalidCard` if the flop is invalid, `HandError::DuplicateCard` if a
+    /// card on it repeats or is dead, and `HandError::NotEnoughCards` if no live hero combo has
+    /// a live villain combo to play against.
+    pub fn new(hero: &Range, villain: &Range, flop: Three, dead: BinaryCard) -> Result<Self, HandError> {
+        if !flop.is_valid() {
+            return Err(HandError::InvalidCard);
+        }
+        let flop_bits = BinaryCard::from_three(flop);
+        if flop_bits.count_ones() != 3 || flop_bits & dead != 0 {
+            return Err(HandError::DuplicateCard);
+        }
+
+        let mut tree = RunoutTree {
+            hero: hero.clone(),
+            villain: villain.clone(),
+            dead,
+            root: RunoutNode {
+                board: [
+                    flop.first(),
+                    flop.second(),
+                    flop.third(),
+                    CardNumber::BLANK,
+                    CardNumber::BLANK,
+                ],
+                len: 3,
+                tally: Tally::default(),
+                expanded: false,
+                children: Vec::new(),
+            },
+        };
+        tree.root.tally = tree.tally(&tree.root.board, 3);
+        if tree.root.matchups() == 0 {
+            return Err(HandError::NotEnoughCards);
+        }
+        Ok(tree)
+    }
+
+    /// The node for the flop.
+    #[must_use]
+    pub fn root(&self) -> &RunoutNode {
+        &self.root
+    }
+
+    /// Returns the node at the end of the path, if every card on it has been dealt and not
+    /// pruned.
+    #[must_use]
+    pub fn node(&self, path: &[CKCNumber]) -> Option<&RunoutNode> {
+        path.iter().try_fold(&self.root, |node, card| node.child(*card))
+    }
+
+    /// Deals every card that isn't on the board or dead below the node at the end of the path,
+    /// and returns its children. Cards held in the ranges are dealt too, and a child where they
+    /// leave no matchups has an equity of zero. A node that's already expanded keeps the
+    /// children it has.
+    ///
+    /// Returns `None` if the path doesn't lead to a node, or leads to a river.
+    pub fn expand(&mut self, path: &[CKCNumber]) -> Option<&[RunoutNode]> {
+        self.expand_where(path, |_| true)
+    }
+
+    /// The same as `expand()`, except that only the children that `keep` returns true for are
+    /// kept, which includes any the node already had.
+    pub fn expand_where<F>(&mut self, path: &[CKCNumber], keep: F) -> Option<&[RunoutNode]>
+    where
+        F: FnMut(&RunoutNode) -> bool,
+    {
+        let mut node = &self.root;
+        for card in path {
+            node = node.child(*card)?;
+        }
+        if node.len == 5 {
+            return None;
+        }
+        let children = if node.expanded {
+            None
+        } else {
+            Some(self.deal(&node.board, node.len))
+        };
+
+        let mut node = &mut self.root;
+        for card in path {
+            node = node.child_mut(*card)?;
+        }
+        if let Some(children) = children {
+            node.children = children;
+            node.expanded = true;
+        }
+        node.children.retain(keep);
+        Some(&node.children)
+    }
+
+    /// Removes every node below the flop that `keep` returns false for, along with everything
+    /// below it. The nodes are visited from the top down, so `keep` isn't called for the
+    /// children of a node it has already removed.
+    pub fn prune<F: FnMut(&RunoutNode) -> bool>(&mut self, mut keep: F) {
+        self.root.prune(&mut keep);
+    }
+
+    /// Every node in the tree, depth first, starting with the flop, with the children of each
+    /// node in deck order.
+    #[must_use]
+    pub fn iter(&self) -> Nodes<'_> {
+        Nodes {
+            stack: alloc::vec![&self.root],
+        }
+    }
+
+    /// The live combos in the ranges don't change on the way down the tree, but working out
+    /// each child's equity still means ranking them on every runout below it.
+    fn deal(&self, board: &[CKCNumber; 5], len: usize) -> Vec<RunoutNode> {
+        let known = self.known(board, len);
+        Deck::live(known)
+            .iter()
+            .map(|card| {
+                let mut board = *board;
+                board[len] = *card;
+                RunoutNode {
+                    board,
+                    len: len + 1,
+                    tally: self.tally(&board, len + 1),
+                    expanded: false,
+                    children: Vec::new(),
+                }
+            })
+            .collect()
+    }
+
+    fn known(&self, board: &[CKCNumber; 5], len: usize) -> BinaryCard {
+        board[..len]
+            .iter()
+            .fold(self.dead, |bits, card| bits | BinaryCard::from_ckc(*card))
+    }
+
+    fn tally(&self, board: &[CKCNumber; 5], len: usize) -> Tally {
+        let mut tally = Tally::default();
+        let live = Deck::live(self.known(board, len));
+        let mut board = *board;
+        match len {
+            5 => self.showdown(&board, &mut tally),
+            4 => {
+                for river in &live {
+                    board[4] = *river;
+                    self.showdown(&board, &mut tally);
+                }
+            },
+            _ => {
+                for (i, turn) in live.iter().enumerate() {
+                    board[3] = *turn;
+                    for river in &live[i + 1..] {
+                        board[4] = *river;
+                        self.showdown(&board, &mut tally);
+                    }
+                }
+            },
+        }
+        tally
+    }
+
+    fn showdown(&self, board: &[CKCNumber; 5], tally: &mut Tally) {
+        let known = self.known(board, 5);
+        let five = Five::from(*board);
+        let ranked = |range: &Range| -> Vec<(BinaryCard, u16)> {
+            range
+                .live(known)
+                .map(|two| (BinaryCard::from_two(*two), Seven::new(*two, five).hand_rank_value()))
+                .collect()
+        };
+        let villain = ranked(&self.villain);
+        for (hero_bits, hero_value) in ranked(&self.hero) {
+            for (villain_bits, villain_value) in &villain {
+                if hero_bits & villain_bits == 0 {
+                    tally.record(hero_value, *villain_value);
+                }
+            }
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a RunoutTree {
+    type Item = &'a RunoutNode;
+    type IntoIter = Nodes<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// The iterator returned by `RunoutTree::iter()`.
+#[derive(Clone, Debug)]
+pub struct Nodes<'a> {
+    stack: Vec<&'a RunoutNode>,
+}
+
+impl<'a> Iterator for Nodes<'a> {
+    type Item = &'a RunoutNode;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.stack.extend(node.children.iter().rev());
+        Some(node)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__tree_tests {
+    use super::*;
+    use crate::analysis::street::equity_by_street;
+    use crate::cards::two::Two;
+
+    fn tree(hero: &'static str, villain: &'static str, flop: &'static str) -> RunoutTree {
+        let hero = Range::from(alloc::vec![Two::try_from(hero).unwrap()]);
+        let villain = Range::from(alloc::vec![Two::try_from(villain).unwrap()]);
+        RunoutTree::new(&hero, &villain, Three::try_from(flop).unwrap(), BinaryCard::BLANK).unwrap()
+    }
+
+    #[test]
+    fn new() {
+        let tree = tree("A♠ A♥", "K♠ K♥", "2♣ 7♦ 9♥");
+        let expected = equity_by_street(
+            Two::try_from("A♠ A♥").unwrap(),
+            &Range::from(alloc::vec![Two::try_from("K♠ K♥").unwrap()]),
+            Three::try_from("2♣ 7♦ 9♥").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(Street::Flop, tree.root().street());
+        assert_eq!(None, tree.root().card());
+        assert_eq!(990, tree.root().matchups());
+        assert!((expected.river - tree.root().equity()).abs() < f64::EPSILON);
+        assert!(!tree.root().is_expanded());
+        assert_eq!(1, tree.iter().count());
+    }
+
+    #[test]
+    fn new__ranges() {
+        let hero = Range::from(alloc::vec![
+            Two::try_from("A♠ A♥").unwrap(),
+            Two::try_from("K♦ Q♦").unwrap()
+        ]);
+        let villain = Range::from(alloc::vec![
+            Two::try_from("A♦ K♣").unwrap(),
+            Two::try_from("K♦ K♥").unwrap()
+        ]);
+        let flop = Three::try_from("J♦ T♦ 2♣").unwrap();
+        let dead = BinaryCard::from_ckc(CardNumber::QUEEN_CLUBS);
+
+        let tree = RunoutTree::new(&hero, &villain, flop, dead).unwrap();
+
+        // K♦ Q♦ and K♦ K♥ share the K♦, and the other three matchups see every runout of the 44
+        // cards left.
+        assert_eq!(3 * 946, tree.root().matchups());
+    }
+
+    #[test]
+    fn new__errors() {
+        let hero = Range::from(alloc::vec![Two::try_from("A♠ A♥").unwrap()]);
+        let flop = Three::try_from("2♣ 7♦ 9♥").unwrap();
+
+        assert_eq!(
+            HandError::InvalidCard,
+            RunoutTree::new(&hero, &hero, Three::default(), BinaryCard::BLANK).unwrap_err()
+        );
+        assert_eq!(
+            HandError::DuplicateCard,
+            RunoutTree::new(&hero, &hero, flop, BinaryCard::from_ckc(CardNumber::DEUCE_CLUBS)).unwrap_err()
+        );
+        assert_eq!(
+            HandError::NotEnoughCards,
+            RunoutTree::new(&hero, &hero, flop, BinaryCard::BLANK).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn expand() {
+        let mut tree = tree("A♠ A♥", "K♠ K♥", "2♣ 7♦ 9♥");
+
+        let turns = tree.expand(&[]).unwrap();
+
+        // The four cards in the hands come on the turn too, but leave nothing to play.
+        assert_eq!(49, turns.len());
+        assert!(turns.iter().all(|turn| turn.street() == Street::Turn));
+        let live: Vec<&RunoutNode> = turns.iter().filter(|turn| turn.matchups() > 0).collect();
+        assert_eq!(45, live.len());
+        assert!(live.iter().all(|turn| turn.matchups() == 44));
+        let total: f64 = live.iter().map(|turn| turn.equity()).sum();
+        assert!((total / 45.0 - tree.root().equity()).abs() < 1e-12);
+
+        let rivers = tree.expand(&[CardNumber::KING_DIAMONDS]).unwrap();
+        assert_eq!(48, rivers.len());
+        // Only the last two aces beat the villain's set.
+        assert_eq!(2, rivers.iter().filter(|river| river.equity() > 0.5).count());
+        assert_eq!(
+            Some(CardNumber::ACE_DIAMONDS),
+            tree.node(&[CardNumber::KING_DIAMONDS, CardNumber::ACE_DIAMONDS])
+                .and_then(RunoutNode::card)
+        );
+        assert_eq!(1 + 49 + 48, tree.iter().count());
+    }
+
+    #[test]
+    fn expand__missing() {
+        let mut tree = tree("A♠ A♥", "K♠ K♥", "2♣ 7♦ 9♥");
+
+        assert!(tree.expand(&[CardNumber::KING_DIAMONDS]).is_none());
+        assert!(tree.expand(&[CardNumber::ACE_SPADES]).is_none());
+        tree.expand(&[]);
+        tree.expand(&[CardNumber::KING_DIAMONDS]);
+        assert!(tree
+            .expand(&[CardNumber::KING_DIAMONDS, CardNumber::ACE_DIAMONDS])
+            .is_none());
+    }
+
+    #[test]
+    fn expand_where() {
+        let mut tree = tree("A♠ A♥", "K♠ K♥", "2♣ 7♦ 9♥");
+
+        tree.expand_where(&[], |turn| turn.matchups() > 0 && turn.equity() < 0.5);
+
+        let turns: Vec<Option<CKCNumber>> = tree.root().children().iter().map(RunoutNode::card).collect();
+        assert_eq!(
+            alloc::vec![Some(CardNumber::KING_DIAMONDS), Some(CardNumber::KING_CLUBS)],
+            turns
+        );
+        assert!(tree.root().is_expanded());
+        assert_eq!(
+            1,
+            tree.expand_where(&[], |turn| turn.board()[3] == CardNumber::KING_CLUBS)
+                .unwrap()
+                .len()
+        );
+    }
+
+    #[test]
+    fn prune() {
+        let mut tree = tree("A♠ A♥", "K♠ K♥", "2♣ 7♦ 9♥");
+        tree.expand(&[]);
+        tree.expand(&[CardNumber::KING_DIAMONDS]);
+        tree.expand(&[CardNumber::ACE_DIAMONDS]);
+        let mut visited = 0;
+
+        tree.prune(|node| {
+            visited += 1;
+            node.street() == Street::River || node.card() == Some(CardNumber::KING_DIAMONDS)
+        });
+
+        assert_eq!(49 + 48, visited);
+        assert_eq!(1 + 1 + 48, tree.iter().count());
+        assert!(tree.node(&[CardNumber::ACE_DIAMONDS]).is_none());
+    }
+
+    #[test]
+    fn iter() {
+        let mut tree = tree("A♠ A♥", "K♠ K♥", "2♣ 7♦ 9♥");
+        tree.expand_where(&[], |turn| turn.card() == Some(CardNumber::KING_DIAMONDS));
+        tree.expand_where(&[CardNumber::KING_DIAMONDS], |river| {
+            river.card() == Some(CardNumber::ACE_DIAMONDS)
+        });
+
+        let boards: Vec<usize> = tree.iter().map(|node| node.board().len()).collect();
+
+        assert_eq!(alloc::vec![3, 4, 5], boards);
+        assert_eq!(3, (&tree).into_iter().count());
+    }
+}
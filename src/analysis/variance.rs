@@ -0,0 +1,249 @@
+//! Turning equities into the numbers a bankroll cares about: how much a spot wins on average, how
+//! much the results swing around that, and how likely a bankroll is to run out before the edge
+//! shows.
+//!
+//! ```
+//! use ckc_rs::analysis::variance::WinRate;
+//!
+//! // Getting 100 big blinds in with 60% equity against a matching stack.
+//! let spot = WinRate::all_in(0.6, 100.0, 100.0);
+//! assert!((spot.ev - 20.0).abs() < 1e-9);
+//!
+//! // A typical small stakes win rate and standard deviation, in big blinds.
+//! let player = WinRate::from_per_100(5.0, 80.0);
+//! assert!(player.risk_of_ruin(1_000.0) < 0.25);
+//! assert!(player.risk_of_ruin(2_000.0) < 0.05);
+//! ```
+
+use crate::analysis::equity::{EquityEstimate, RunoutResult};
+use crate::evaluate::Outcome;
+use crate::math::{exp, sqrt};
+
+/// The result of a spot, or of a player, as the expected value per hand and the standard
+/// deviation of the result per hand, both in the same units as the amounts they came from.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WinRate {
+    pub ev: f64,
+    pub std_dev: f64,
+}
+
+impl WinRate {
+    #[must_use]
+    pub fn new(ev: f64, std_dev: f64) -> Self {
+        WinRate { ev, std_dev }
+    }
+
+    /// A `WinRate` from the figures that tracking software reports per 100 hands.
+    #[must_use]
+    pub fn from_per_100(ev: f64, std_dev: f64) -> Self {
+        WinRate {
+            ev: ev / 100.0,
+            std_dev: std_dev / 10.0,
+        }
+    }
+
+    /// The mean and standard deviation of a set of results, each given as its probability and
+    /// the amount won, which is negative for a loss. The probabilities don't need to add up to
+    /// one, as they're weighted by their total, so counts work just as well. No results, or no
+    /// weight, is a `WinRate` of zero.
+    #[must_use]
+    pub fn from_outcomes(outcomes: &[(f64, f64)]) -> Self {
+        let weight: f64 = outcomes.iter().map(|(probability, _)| probability).sum();
+        if weight <= 0.0 {
+            return WinRate::default();
+        }
+        let ev = outcomes
+            .iter()
+            .map(|(probability, amount)| probability * amount)
+            .sum::<f64>()
+            / weight;
+        let variance = outcomes
+            .iter()
+            .map(|(probability, amount)| probability * (amount - ev) * (amount - ev))
+            .sum::<f64>()
+            / weight;
+        WinRate::new(ev, sqrt(variance))
+    }
+
+    /// Getting all in for `stake` to win a `pot`, where `pot` is what hero wins on top of getting
+    /// the `stake` back, with `equity` taken as the chance of winning the whole pot. Ties make
+    /// the result swing less than this.
+    #[must_use]
+    pub fn all_in(equity: f64, pot: f64, stake: f64) -> Self {
+        WinRate::from_outcomes(&[(equity, pot), (1.0 - equity, -stake)])
+    }
+
+    /// The same as `all_in()`, but over every runout from `enumerate()`, each as likely as the
+    /// others, so that ties are counted as the split pots that they are.
+    ///
+    /// ```
+    /// use ckc_rs::analysis::equity::enumerate;
+    /// use ckc_rs::analysis::variance::WinRate;
+    /// use ckc_rs::cards::three::Three;
+    /// use ckc_rs::cards::two::Two;
+    /// use ckc_rs::cards::HandValidator;
+    ///
+    /// let hero = Two::try_from("A♠ K♥").unwrap();
+    /// let villain = Two::try_from("A♦ K♣").unwrap();
+    /// let flop = Three::try_from("7♦ 5♣ 2♠").unwrap().to_arr();
+    ///
+    /// let runouts = WinRate::from_runouts(enumerate(hero, villain, &flop).unwrap(), 100.0, 100.0);
+    ///
+    /// assert!(runouts.std_dev < WinRate::all_in(0.5, 100.0, 100.0).std_dev / 2.0);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_runouts<I>(runouts: I, pot: f64, stake: f64) -> Self
+    where
+        I: IntoIterator<Item = RunoutResult>,
+    {
+        // Every share of the pot that hero can end up with: none, half or all of it.
+        let mut counts = [0usize; 3];
+        for runout in runouts {
+            match runout.outcome {
+                Outcome::Loss => counts[0] += 1,
+                Outcome::Tie => counts[1] += 1,
+                Outcome::Win => counts[2] += 1,
+            }
+        }
+        let amount = |share: f64| share * (pot + stake) - stake;
+        WinRate::from_outcomes(&[
+            (counts[0] as f64, amount(0.0)),
+            (counts[1] as f64, amount(0.5)),
+            (counts[2] as f64, amount(1.0)),
+        ])
+    }
+
+    #[must_use]
+    pub fn ev_per_100(&self) -> f64 {
+        self.ev * 100.0
+    }
+
+    /// The standard deviation of the result over 100 hands, which is ten times that of one hand
+    /// as long as the hands are independent.
+    #[must_use]
+    pub fn std_dev_per_100(&self) -> f64 {
+        self.std_dev * 10.0
+    }
+
+    /// The 95% confidence interval for the true win rate per 100 hands, after a sample of
+    /// `hands` played at this `WinRate`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn confidence_interval(&self, hands: u64) -> (f64, f64) {
+        if hands == 0 {
+            return (f64::NEG_INFINITY, f64::INFINITY);
+        }
+        let margin = EquityEstimate::Z_95 * self.std_dev_per_100() / sqrt(hands as f64 / 100.0);
+        (self.ev_per_100() - margin, self.ev_per_100() + margin)
+    }
+
+    /// The chance of losing the whole `bankroll` at some point, playing forever at this
+    /// `WinRate`, which is `e^(-2 * ev * bankroll / std_dev^2)`. That treats the results as a
+    /// random walk that can't go broke halfway through a hand, which is close enough for a
+    /// bankroll of more than a few buy ins. A player who doesn't win goes broke in the end.
+    #[must_use]
+    pub fn risk_of_ruin(&self, bankroll: f64) -> f64 {
+        if bankroll <= 0.0 {
+            return 1.0;
+        }
+        if self.ev <= 0.0 {
+            return 1.0;
+        }
+        if self.std_dev <= 0.0 {
+            return 0.0;
+        }
+        exp(-2.0 * self.ev * bankroll / (self.std_dev * self.std_dev))
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod analysis__variance_tests {
+    use super::*;
+    use crate::analysis::equity::enumerate;
+    use crate::cards::three::Three;
+    use crate::cards::two::Two;
+    use rstest::rstest;
+
+    fn close(expected: f64, actual: f64) -> bool {
+        (expected - actual).abs() < 1e-9
+    }
+
+    #[test]
+    fn from_outcomes() {
+        let rate = WinRate::from_outcomes(&[(1.0, 10.0), (3.0, -2.0)]);
+
+        assert!(close(1.0, rate.ev));
+        // The squared distances from the mean are 81 and 9, weighted 1 to 3.
+        assert!(close(sqrt(27.0), rate.std_dev));
+        assert_eq!(WinRate::default(), WinRate::from_outcomes(&[]));
+        assert_eq!(WinRate::default(), WinRate::from_outcomes(&[(0.0, 10.0)]));
+    }
+
+    #[rstest]
+    #[case(0.5, 100.0, 100.0, 0.0, 100.0)]
+    #[case(0.8, 100.0, 100.0, 60.0, 80.0)]
+    #[case(0.25, 300.0, 100.0, 0.0, 400.0 * 0.433_012_701_892_219_3)]
+    #[case(1.0, 50.0, 100.0, 50.0, 0.0)]
+    fn all_in(#[case] equity: f64, #[case] pot: f64, #[case] stake: f64, #[case] ev: f64, #[case] std_dev: f64) {
+        let rate = WinRate::all_in(equity, pot, stake);
+
+        assert!(close(ev, rate.ev), "{rate:?}");
+        assert!(close(std_dev, rate.std_dev), "{rate:?}");
+    }
+
+    #[test]
+    fn from_runouts() {
+        let hero = Two::try_from("A♠ A♥").unwrap();
+        let villain = Two::try_from("K♠ K♥").unwrap();
+        let flop = Three::try_from("2♣ 7♦ 9♥").unwrap().to_arr();
+        let runouts: alloc::vec::Vec<RunoutResult> = enumerate(hero, villain, &flop).unwrap().collect();
+        let equity = runouts.iter().map(RunoutResult::equity).sum::<f64>() / 990.0;
+
+        assert_eq!(990, runouts.len());
+
+        let rate = WinRate::from_runouts(runouts, 100.0, 100.0);
+
+        // Without ties, the runouts are the same as going all in at the average equity.
+        assert!(close(WinRate::all_in(equity, 100.0, 100.0).ev, rate.ev));
+        assert!(close(WinRate::all_in(equity, 100.0, 100.0).std_dev, rate.std_dev));
+    }
+
+    #[test]
+    fn per_100() {
+        let rate = WinRate::from_per_100(5.0, 80.0);
+
+        assert!(close(0.05, rate.ev));
+        assert!(close(8.0, rate.std_dev));
+        assert!(close(5.0, rate.ev_per_100()));
+        assert!(close(80.0, rate.std_dev_per_100()));
+    }
+
+    #[test]
+    fn confidence_interval() {
+        let (low, high) = WinRate::from_per_100(5.0, 80.0).confidence_interval(10_000);
+
+        // 100 blocks of 100 hands shrink the standard deviation to 8.
+        assert!(close(5.0 - 8.0 * EquityEstimate::Z_95, low));
+        assert!(close(5.0 + 8.0 * EquityEstimate::Z_95, high));
+        assert_eq!(
+            (f64::NEG_INFINITY, f64::INFINITY),
+            WinRate::default().confidence_interval(0)
+        );
+    }
+
+    #[rstest]
+    #[case(5.0, 80.0, 1_000.0, 0.209_611_387_151_098_8)]
+    #[case(5.0, 80.0, 2_000.0, 0.043_936_933_623_407_4)]
+    #[case(10.0, 100.0, 1_500.0, 0.049_787_068_367_863_9)]
+    #[case(0.0, 80.0, 1_000.0, 1.0)]
+    #[case(-2.0, 80.0, 1_000.0, 1.0)]
+    #[case(5.0, 0.0, 1_000.0, 0.0)]
+    #[case(5.0, 80.0, 0.0, 1.0)]
+    fn risk_of_ruin(#[case] ev: f64, #[case] std_dev: f64, #[case] bankroll: f64, #[case] expected: f64) {
+        let risk = WinRate::from_per_100(ev, std_dev).risk_of_ruin(bankroll);
+
+        assert!(close(expected, risk), "{risk}");
+    }
+}
@@ -0,0 +1,221 @@
+//! Interchangeable hand evaluators.
+//!
+//! Code that is generic over an `EvalBackend` can switch between the Cactus Kev lookup tables of
+//! an `Evaluator` and any other way of ranking hands, such as a perfect hash or a two plus two
+//! table, without changing. `Reference` is a slow evaluator that works from first principles
+//! without any tables, for checking other backends against.
+
+use crate::cards::five::Five;
+use crate::cards::seven::Seven;
+use crate::cards::{HandValidator, Permutator};
+use crate::evaluate::Evaluator;
+use crate::hand_rank::{HandRankValue, NO_HAND_RANK_VALUE};
+use crate::math::choose;
+use crate::{CKCNumber, PokerCard};
+
+/// Ranks hands with the same `HandRankValues` as `HandRanker`, where lower is better.
+pub trait EvalBackend {
+    /// Returns the value of the five cards, or `NO_HAND_RANK_VALUE` if they aren't five valid,
+    /// distinct cards.
+    fn five_cards(&self, cards: [CKCNumber; 5]) -> HandRankValue;
+
+    /// Returns the value of the best five of the seven cards, or `NO_HAND_RANK_VALUE` if they
+    /// aren't seven valid, distinct cards.
+    fn seven_cards(&self, cards: [CKCNumber; 7]) -> HandRankValue {
+        let seven = Seven::from(cards);
+        if !seven.is_valid() {
+            return NO_HAND_RANK_VALUE;
+        }
+        Seven::PERMUTATIONS
+            .iter()
+            .map(|permutation| self.five_cards(seven.five_from_permutation(*permutation).to_arr()))
+            .min()
+            .unwrap_or(NO_HAND_RANK_VALUE)
+    }
+}
+
+impl EvalBackend for Evaluator<'_> {
+    fn five_cards(&self, cards: [CKCNumber; 5]) -> HandRankValue {
+        let five = Five::from(cards);
+        if !five.is_valid() {
+            return NO_HAND_RANK_VALUE;
+        }
+        self.five(&five)
+    }
+
+    fn seven_cards(&self, cards: [CKCNumber; 7]) -> HandRankValue {
+        let seven = Seven::from(cards);
+        if !seven.is_valid() {
+            return NO_HAND_RANK_VALUE;
+        }
+        self.best(&seven).0
+    }
+}
+
+/// Works out each value by counting how many hands of the same kind beat it, instead of looking
+/// it up. Much slower than an `Evaluator`, but it shares no tables or code with one.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Reference;
+
+impl Reference {
+    /// The ranks of the cards from deuce (0) to ace (12), most common first and then highest
+    /// first, along with whether the cards are all one suit.
+    fn ranks(cards: [CKCNumber; 5]) -> ([usize; 5], bool) {
+        let mut counts = [0usize; 13];
+        for card in cards {
+            counts[card.get_rank_bit().trailing_zeros() as usize] += 1;
+        }
+        let mut ranks = [0usize; 5];
+        let mut i = 0;
+        for count in (1..=4).rev() {
+            for rank in (0..13).rev().filter(|rank| counts[*rank] == count) {
+                for _ in 0..count {
+                    ranks[i] = rank;
+                    i += 1;
+                }
+            }
+        }
+        let flush = cards.iter().all(|card| card.get_suit_bit() == cards[0].get_suit_bit());
+        (ranks, flush)
+    }
+
+    /// The high card of the straight, from 3 for the wheel up to 12, if the ranks make one.
+    fn straight(ranks: [usize; 5]) -> Option<usize> {
+        let distinct = ranks.windows(2).all(|pair| pair[0] != pair[1]);
+        if !distinct {
+            None
+        } else if ranks[0] - ranks[4] == 4 {
+            Some(ranks[0])
+        } else if ranks == [12, 3, 2, 1, 0] {
+            Some(3)
+        } else {
+            None
+        }
+    }
+
+    /// How many sets of distinct ranks of the same size rank higher than this one, where `ranks`
+    /// are in descending order and each is below `limit`.
+    fn higher_sets(ranks: &[usize], limit: usize) -> usize {
+        let colex: u64 = ranks
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, rank)| choose(*rank, i + 1))
+            .sum();
+        usize::try_from(choose(limit, ranks.len()) - 1 - colex).unwrap_or_default()
+    }
+
+    /// How many sets of five distinct ranks that aren't straights rank higher than this one.
+    fn higher_non_straights(ranks: [usize; 5]) -> usize {
+        Reference::higher_sets(&ranks, 13) - Reference::higher_straights(ranks)
+    }
+
+    /// How many straights have rank bits higher than the five distinct ranks.
+    fn higher_straights(ranks: [usize; 5]) -> usize {
+        let bits: u32 = ranks.iter().map(|rank| 1 << rank).sum();
+        (4..=12)
+            .map(|high| 0b1_1111u32 << (high - 4))
+            .chain(core::iter::once(0b1_0000_0000_1111))
+            .filter(|straight| *straight > bits)
+            .count()
+    }
+
+    /// The rank with the ranks that are already used taken out, so that the ranks left run
+    /// from 0 without any gaps.
+    fn without(rank: usize, used: &[usize]) -> usize {
+        rank - used.iter().filter(|other| **other < rank).count()
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+impl EvalBackend for Reference {
+    fn five_cards(&self, cards: [CKCNumber; 5]) -> HandRankValue {
+        if !Five::from(cards).is_valid() {
+            return NO_HAND_RANK_VALUE;
+        }
+        let (ranks, flush) = Reference::ranks(cards);
+        let [first, second, third, fourth, fifth] = ranks;
+
+        // Each kind of hand starts at the first value that `HandRank::determine_name()` gives it.
+        let value = match (Reference::straight(ranks), flush) {
+            (Some(high), true) => 1 + (12 - high),
+            (None, true) => 323 + Reference::higher_non_straights(ranks),
+            _ if first == fourth => 11 + (12 - first) * 12 + (11 - Reference::without(fifth, &[first])),
+            _ if first == third && fourth == fifth => {
+                167 + (12 - first) * 12 + (11 - Reference::without(fourth, &[first]))
+            },
+            (Some(high), false) => 1600 + (12 - high),
+            _ if first == third => {
+                let kickers = [
+                    Reference::without(fourth, &[first]),
+                    Reference::without(fifth, &[first]),
+                ];
+                1610 + (12 - first) * 66 + Reference::higher_sets(&kickers, 12)
+            },
+            _ if first == second && third == fourth => {
+                2468 + Reference::higher_sets(&[first, third], 13) * 11
+                    + (10 - Reference::without(fifth, &[first, third]))
+            },
+            _ if first == second => {
+                let kickers = [third, fourth, fifth].map(|kicker| Reference::without(kicker, &[first]));
+                3326 + (12 - first) * 220 + Reference::higher_sets(&kickers, 12)
+            },
+            _ => 6186 + Reference::higher_non_straights(ranks),
+        };
+        value as HandRankValue
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod backend_tests {
+    use super::*;
+    use crate::cards::HandRanker;
+    use crate::deck::POKER_DECK;
+    use crate::hand_rank::HandRank;
+    use crate::CardNumber;
+    use rand::rngs::SmallRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    #[test]
+    fn reference__five_cards() {
+        for value in 1..=7462 {
+            let five = HandRank::from(value).example_hand();
+
+            assert_eq!(value, Reference.five_cards(five.to_arr()), "{five:?}");
+        }
+    }
+
+    #[test]
+    fn reference__five_cards__invalid() {
+        let cards = [
+            CardNumber::ACE_SPADES,
+            CardNumber::ACE_SPADES,
+            CardNumber::KING_SPADES,
+            CardNumber::QUEEN_SPADES,
+            CardNumber::JACK_SPADES,
+        ];
+
+        assert_eq!(NO_HAND_RANK_VALUE, Reference.five_cards(cards));
+        assert_eq!(NO_HAND_RANK_VALUE, Evaluator::default().five_cards(cards));
+    }
+
+    /// Differential test of random seven card hands against the lookup tables.
+    #[test]
+    fn seven_cards() {
+        let mut rng = SmallRng::seed_from_u64(7);
+        let evaluator = Evaluator::default();
+
+        for _ in 0..2_000 {
+            let mut cards = [0; 7];
+            for (card, dealt) in cards.iter_mut().zip(POKER_DECK.choose_multiple(&mut rng, 7)) {
+                *card = *dealt;
+            }
+
+            let expected = Seven::from(cards).hand_rank_value();
+            assert_eq!(expected, evaluator.seven_cards(cards));
+            assert_eq!(expected, Reference.seven_cards(cards));
+        }
+    }
+}
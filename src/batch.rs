@@ -0,0 +1,193 @@
+//! Ranking many five card hands at once.
+//!
+//! `hand_rank_values()` ranks a slice of hands eight at a time with `rank8()`, an experiment in
+//! SWAR (SIMD within a register) evaluation that only needs stable Rust: the rank bits of the
+//! eight hands sit in the 16 bit lanes of a `u128`, laid out the same as a `u16x8`, and their
+//! suits in the 4 bit lanes of a `u32`. Every value is the same as `HandRanker::hand_rank_value()`
+//! would return for the hand on its own.
+
+use crate::cards::five::Five;
+use crate::cards::HandRanker;
+use crate::hand_rank::{HandRankValue, NO_HAND_RANK_VALUE};
+use crate::CardNumber;
+use alloc::vec::Vec;
+
+/// The number of hands that `rank8()` ranks at once.
+pub const LANES: usize = 8;
+
+/// The masks for counting the bits in each 16 bit lane of a `u128`.
+const ALTERNATE_BITS: u128 = 0x5555_5555_5555_5555_5555_5555_5555_5555;
+const ALTERNATE_PAIRS: u128 = 0x3333_3333_3333_3333_3333_3333_3333_3333;
+const ALTERNATE_NIBBLES: u128 = 0x0F0F_0F0F_0F0F_0F0F_0F0F_0F0F_0F0F_0F0F;
+const LOW_BYTES: u128 = 0x00FF_00FF_00FF_00FF_00FF_00FF_00FF_00FF;
+
+/// Returns the `HandRankValue` of every hand, in the same order.
+///
+/// ```
+/// use ckc_rs::batch::hand_rank_values;
+/// use ckc_rs::cards::five::Five;
+///
+/// let hands = [
+///     Five::try_from("A♠ K♠ Q♠ J♠ T♠").unwrap(),
+///     Five::try_from("7♣ 5♦ 4♠ 3♥ 2♣").unwrap(),
+/// ];
+///
+/// assert_eq!(vec![1, 7462], hand_rank_values(&hands));
+/// ```
+#[must_use]
+pub fn hand_rank_values(hands: &[Five]) -> Vec<HandRankValue> {
+    let mut values = alloc::vec![NO_HAND_RANK_VALUE; hands.len()];
+    hand_rank_values_into(hands, &mut values);
+    values
+}
+
+/// Writes the `HandRankValue` of each hand into the same index of `values`, stopping at the end
+/// of whichever slice is shorter.
+pub fn hand_rank_values_into(hands: &[Five], values: &mut [HandRankValue]) {
+    let len = hands.len().min(values.len());
+    let mut hand_chunks = hands[..len].chunks_exact(LANES);
+    let mut value_chunks = values[..len].chunks_exact_mut(LANES);
+    let mut lanes = [Five::default(); LANES];
+
+    for (hands, values) in (&mut hand_chunks).zip(&mut value_chunks) {
+        lanes.copy_from_slice(hands);
+        values.copy_from_slice(&rank8(&lanes));
+    }
+    for (hand, value) in hand_chunks.remainder().iter().zip(value_chunks.into_remainder()) {
+        *value = hand.hand_rank_value();
+    }
+}
+
+/// Ranks eight hands at once, returning the same values as `HandRanker::hand_rank_value()`.
+///
+/// The rank bits of the hands are combined with `|` and counted in their lanes, and the suit
+/// bits combined with `&`, which sorts out the flushes and the hands with five different ranks
+/// for all eight at once without a branch. Only the rest, which have a rank more than once, search
+/// `PRODUCTS`.
+///
+/// So far it isn't much of a win. Measured on an `x86_64` release build over all 2,598,960 five
+/// card hands, it's 5 to 10% faster than `hand_rank_value()` one hand at a time when the hands
+/// come in a random order, where the scalar code mispredicts which hands have a pair, and about
+/// 15% slower when they come in deck order. Both spend most of their time on the same search.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn rank8(hands: &[Five; LANES]) -> [HandRankValue; LANES] {
+    let mut ranks = 0u128;
+    let mut suits = u32::MAX;
+    for card in 0..5 {
+        let mut rank_lanes = 0u128;
+        let mut suit_lanes = 0u32;
+        for (lane, hand) in hands.iter().enumerate() {
+            let ckc = hand[card];
+            rank_lanes |=
+                u128::from((ckc & CardNumber::MULTIPLES_FILTER) >> CardNumber::RANK_FLAG_SHIFT) << (16 * lane);
+            suit_lanes |= ((ckc & CardNumber::SUIT_FILTER) >> CardNumber::SUIT_SHIFT) << (4 * lane);
+        }
+        ranks |= rank_lanes;
+        suits &= suit_lanes;
+    }
+
+    let distinct = count_ones(ranks);
+
+    // The lanes with a rank more than once are packed to the front of `pending`, without a
+    // branch, so that only they search `PRODUCTS`.
+    let mut values = [NO_HAND_RANK_VALUE; LANES];
+    let mut pending = [0usize; LANES];
+    let mut len = 0;
+    for (lane, value) in values.iter_mut().enumerate() {
+        let index = (ranks >> (16 * lane)) as u16 as usize;
+        let flush = (suits >> (4 * lane)) & CardNumber::SUIT_SHORT_MASK != 0;
+        let unique = (distinct >> (16 * lane)) as u16 == 5;
+        let table = if flush {
+            &crate::lookups::FLUSHES
+        } else {
+            &crate::lookups::UNIQUE_5
+        };
+        *value = lookup(table, index);
+        pending[len] = lane;
+        len += usize::from(!(flush || unique && index < Five::POSSIBLE_COMBINATIONS));
+    }
+    for lane in &pending[..len] {
+        values[*lane] = multiples(hands[*lane].multiply_primes());
+    }
+    values
+}
+
+/// The value of the hand with a rank more than once whose primes multiply to the key.
+///
+/// Kept out of line so that the search in `Five::find_in_products()` is unrolled, which lets it
+/// compile without branches. Inlined into the loop over the lanes, it branches on every step.
+#[inline(never)]
+fn multiples(key: usize) -> HandRankValue {
+    // SAFETY: `find_in_products()` only returns indexes into `PRODUCTS`, which is the same
+    // length as `VALUES`.
+    unsafe { crate::lookups::entry(&crate::lookups::VALUES, Five::find_in_products(key)) }
+}
+
+/// The number of bits set in each 16 bit lane.
+fn count_ones(lanes: u128) -> u128 {
+    let pairs = lanes - ((lanes >> 1) & ALTERNATE_BITS);
+    let nibbles = (pairs & ALTERNATE_PAIRS) + ((pairs >> 2) & ALTERNATE_PAIRS);
+    let bytes = (nibbles + (nibbles >> 4)) & ALTERNATE_NIBBLES;
+    (bytes + (bytes >> 8)) & LOW_BYTES
+}
+
+/// Invalid cards can have rank bits past the end of the flush and unique tables.
+fn lookup(table: &[HandRankValue; Five::POSSIBLE_COMBINATIONS], index: usize) -> HandRankValue {
+    table.get(index).copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod batch_tests {
+    use super::*;
+    use crate::math::Combinations;
+    use crate::POKER_DECK;
+    use rstest::rstest;
+
+    #[test]
+    fn hand_rank_values__every_hand() {
+        let hands: Vec<Five> = Combinations::<5>::new(POKER_DECK.len())
+            .map(|indexes| Five::from(indexes.map(|i| POKER_DECK[i])))
+            .collect();
+
+        let values = hand_rank_values(&hands);
+
+        for (hand, value) in hands.iter().zip(values) {
+            assert_eq!(hand.hand_rank_value(), value, "{hand:?}");
+        }
+    }
+
+    #[test]
+    fn hand_rank_values_into() {
+        let hands = [
+            Five::try_from("A♠ K♠ Q♠ J♠ T♠").unwrap(),
+            Five::try_from("7♣ 5♦ 4♠ 3♥ 2♣").unwrap(),
+            Five::try_from("A♠ A♥ K♠ K♥ Q♦").unwrap(),
+        ];
+        let mut values = [0; 2];
+
+        super::hand_rank_values_into(&hands, &mut values);
+
+        assert_eq!([1, 7462], values);
+        assert!(hand_rank_values(&[]).is_empty());
+    }
+
+    #[rstest]
+    #[case([u32::MAX; 5])]
+    #[case([0xFFFF_1000; 5])]
+    #[case([0xFFFF_0000, 0x1000, 0x2000, 0x4000, 0x8000])]
+    #[case([CardNumber::BLANK; 5])]
+    #[case([CardNumber::ACE_SPADES | CardNumber::PAIR, CardNumber::KING_SPADES, CardNumber::QUEEN_SPADES, CardNumber::JACK_SPADES, CardNumber::TEN_SPADES])]
+    #[case([CardNumber::ACE_SPADES; 5])]
+    fn rank8__garbage(#[case] cards: [crate::CKCNumber; 5]) {
+        let mut hands = [Five::try_from("A♠ A♥ K♠ K♥ Q♦").unwrap(); LANES];
+        hands[3] = Five::from(cards);
+
+        let values = rank8(&hands);
+
+        for (hand, value) in hands.iter().zip(values) {
+            assert_eq!(hand.hand_rank_value(), value, "{hand:?}");
+        }
+    }
+}
@@ -0,0 +1,50 @@
+//! Generates a `RiverTable` of every `PreflopClass` against the top percent of starting hands,
+//! on every canonical river, and saves it to a file.
+//!
+//! ```txt
+//! cargo run --release --features std --bin river_table -- 20 top20.ckcr
+//! ```
+//!
+//! The full table is about 46MB, and takes a few minutes for a narrow range and around ten for
+//! every hand. An optional third argument stops after that many boards, for a quick, partial
+//! table.
+
+use ckc_rs::analysis::range::{Range, StartingHandMetric};
+use ckc_rs::analysis::river_table::{canonical_rivers, RiverTable, CANONICAL_RIVERS};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (Some(percent), Some(path)) = (args.first(), args.get(1)) else {
+        eprintln!("usage: river_table <percent> <path> [boards]");
+        return ExitCode::FAILURE;
+    };
+    let Ok(percent) = percent.parse::<f32>() else {
+        eprintln!("{percent} isn't a percentage");
+        return ExitCode::FAILURE;
+    };
+    let boards = match args.get(2).map(|boards| boards.parse::<usize>()) {
+        None => CANONICAL_RIVERS,
+        Some(Ok(boards)) => boards,
+        Some(Err(_)) => {
+            eprintln!("{} isn't a number of boards", args[2]);
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let villain = Range::top_percent(percent, StartingHandMetric::EquityVsRandom);
+    let Ok(table) = RiverTable::generate(&villain, canonical_rivers().take(boards)) else {
+        eprintln!("the top {percent}% isn't the same under every relabelling of the suits");
+        return ExitCode::FAILURE;
+    };
+    if let Err(e) = table.save(path) {
+        eprintln!("couldn't save {path}: {e}");
+        return ExitCode::FAILURE;
+    }
+    println!(
+        "saved {} boards against {} combos to {path}",
+        table.len(),
+        villain.len()
+    );
+    ExitCode::SUCCESS
+}
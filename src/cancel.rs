@@ -0,0 +1,54 @@
+//! Stopping long calculations early.
+//!
+//! The functions that enumerate a lot of hands, such as `equity_by_street_cancellable()`, take a
+//! `Cancellation` and check it as they go. Setting the `AtomicBool` behind it, from another
+//! thread or from a GUI's cancel button, makes them stop at the next check and return what they
+//! had worked out so far.
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// A flag that a calculation checks every so often, stopping once it's set.
+///
+/// ```
+/// use ckc_rs::cancel::Cancellation;
+/// use core::sync::atomic::{AtomicBool, Ordering};
+///
+/// let flag = AtomicBool::new(false);
+/// let cancellation = Cancellation::from(&flag);
+/// assert!(!cancellation.is_cancelled());
+///
+/// flag.store(true, Ordering::Relaxed);
+/// assert!(cancellation.is_cancelled());
+/// assert!(!Cancellation::NEVER.is_cancelled());
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Cancellation<'a>(Option<&'a AtomicBool>);
+
+impl<'a> Cancellation<'a> {
+    /// A `Cancellation` that's never cancelled, for running a calculation to the end.
+    pub const NEVER: Cancellation<'static> = Cancellation(None);
+
+    #[must_use]
+    pub fn new(flag: &'a AtomicBool) -> Self {
+        Cancellation(Some(flag))
+    }
+
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.is_some_and(|flag| flag.load(Ordering::Relaxed))
+    }
+}
+
+impl<'a> From<&'a AtomicBool> for Cancellation<'a> {
+    fn from(flag: &'a AtomicBool) -> Self {
+        Cancellation::new(flag)
+    }
+}
+
+/// The result of a calculation that may have been cancelled before it finished. If `complete`
+/// is false, `result` only covers the part that was done.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Partial<T> {
+    pub result: T,
+    pub complete: bool,
+}
@@ -0,0 +1,293 @@
+use crate::deck::POKER_DECK;
+use crate::{CKCNumber, CardNumber, CardRank, CardSuit, HandError, PokerCard};
+#[cfg(feature = "serde")]
+use alloc::string::String;
+use core::cmp::Ordering;
+use core::fmt;
+use core::str::FromStr;
+#[cfg(feature = "serde")]
+use serde::de::Error;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A `CKCNumber` that is known to be one of the 52 cards in the deck, or `BLANK`.
+///
+/// Displays with suit symbols, `A♠`, and serializes with suit letters, `As`. Converting to and
+/// from the underlying `CKCNumber` is free, so hands can still be evaluated on the raw numbers.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[repr(transparent)]
+pub struct Card(CKCNumber);
+
+impl Card {
+    pub const BLANK: Card = Card(CardNumber::BLANK);
+
+    #[must_use]
+    pub fn new(rank: CardRank, suit: CardSuit) -> Card {
+        Card(CKCNumber::create(rank, suit))
+    }
+
+    /// Every card in the deck, in the same order as the `POKER_DECK`.
+    pub fn all() -> impl Iterator<Item = Card> {
+        POKER_DECK.into_iter().map(Card)
+    }
+
+    #[must_use]
+    pub fn as_ckc(&self) -> CKCNumber {
+        self.0
+    }
+
+    /// Returns the card's character from the Unicode Playing Cards block, such as `🂡` for the
+    /// `A♠`, or the back of a card, `🂠`, for `BLANK`. The block's knights are never used.
+    #[must_use]
+    pub fn to_unicode_playing_card(&self) -> char {
+        let suit = match self.get_suit_bit() {
+            8 => 0x1F0A0,
+            4 => 0x1F0B0,
+            2 => 0x1F0C0,
+            1 => 0x1F0D0,
+            _ => return Card::UNICODE_BACK,
+        };
+        let rank = match self.get_card_rank() {
+            CardRank::BLANK => return Card::UNICODE_BACK,
+            CardRank::ACE => 0x1,
+            CardRank::JACK => 0xB,
+            CardRank::QUEEN => 0xD,
+            CardRank::KING => 0xE,
+            rank => rank as u32,
+        };
+        char::from_u32(suit + rank).unwrap_or(Card::UNICODE_BACK)
+    }
+
+    /// Writes the card the same as `Display`, with hearts and diamonds in red, using ANSI
+    /// escape codes.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from the writer.
+    #[cfg(feature = "ansi")]
+    pub fn write_pretty(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        match self.get_card_suit() {
+            CardSuit::HEARTS | CardSuit::DIAMONDS => write!(w, "\x1b[31m{self}\x1b[0m"),
+            _ => write!(w, "{self}"),
+        }
+    }
+
+    const UNICODE_BACK: char = '\u{1F0A0}';
+}
+
+impl PokerCard for Card {
+    fn as_u32(&self) -> u32 {
+        self.0
+    }
+
+    fn is_blank(&self) -> bool {
+        self.0 == CardNumber::BLANK
+    }
+}
+
+/// Anything that isn't a card, other than the multiples flags, which are stripped, becomes
+/// `Card::BLANK`.
+impl From<CKCNumber> for Card {
+    fn from(number: CKCNumber) -> Self {
+        Card(CKCNumber::filter(number.strip_multiples_flags()))
+    }
+}
+
+impl From<Card> for CKCNumber {
+    fn from(card: Card) -> Self {
+        card.0
+    }
+}
+
+impl FromStr for Card {
+    type Err = HandError;
+
+    /// # Errors
+    ///
+    /// Returns `HandError::InvalidIndex` if the index isn't a card.
+    fn from_str(index: &str) -> Result<Self, Self::Err> {
+        let index = index.trim();
+        let len = index.chars().count();
+        if len > 3 || (len == 3 && !index.starts_with("10")) {
+            return Err(HandError::InvalidIndex);
+        }
+        match CKCNumber::from_index(index) {
+            CardNumber::BLANK => Err(HandError::InvalidIndex),
+            number => Ok(Card(number)),
+        }
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.get_rank_char(), self.get_suit_char())
+    }
+}
+
+/// Cards are ordered by rank and then by suit, spades high.
+impl Ord for Card {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.get_card_rank() as u8, self.get_card_suit() as u8)
+            .cmp(&(other.get_card_rank() as u8, other.get_card_suit() as u8))
+    }
+}
+
+impl PartialOrd for Card {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Card {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!(
+            "{}{}",
+            self.get_rank_char(),
+            self.get_suit_letter().to_ascii_lowercase()
+        ))
+    }
+}
+
+/// Reads `__`, which is how `Card::BLANK` is written, back as `Card::BLANK`.
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match String::deserialize(deserializer)?.as_str() {
+            "__" => Ok(Card::BLANK),
+            index => Card::from_str(index).map_err(|_| D::Error::custom("invalid card index")),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod card_tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+    use rstest::rstest;
+
+    #[test]
+    fn new() {
+        assert_eq!(
+            CardNumber::ACE_SPADES,
+            Card::new(CardRank::ACE, CardSuit::SPADES).as_ckc()
+        );
+        assert_eq!(Card::BLANK, Card::new(CardRank::BLANK, CardSuit::SPADES));
+    }
+
+    #[test]
+    fn all() {
+        let cards: Vec<Card> = Card::all().collect();
+
+        assert_eq!(52, cards.len());
+        assert_eq!(Card::from(CardNumber::ACE_SPADES), cards[0]);
+        assert_eq!(Card::from(CardNumber::DEUCE_CLUBS), cards[51]);
+    }
+
+    #[test]
+    fn from__ckc_number() {
+        assert_eq!(
+            Card::from(CardNumber::KING_HEARTS),
+            Card::from(CardNumber::KING_HEARTS.flag_as_pair())
+        );
+        assert_eq!(Card::BLANK, Card::from(2));
+        assert_eq!(
+            CardNumber::KING_HEARTS,
+            CKCNumber::from(Card::from(CardNumber::KING_HEARTS))
+        );
+    }
+
+    #[rstest]
+    #[case("A♠", CardNumber::ACE_SPADES)]
+    #[case("As", CardNumber::ACE_SPADES)]
+    #[case(" td ", CardNumber::TEN_DIAMONDS)]
+    #[case("10c", CardNumber::TEN_CLUBS)]
+    fn from_str(#[case] index: &str, #[case] expected: CKCNumber) {
+        assert_eq!(Card::from(expected), index.parse::<Card>().unwrap());
+    }
+
+    #[test]
+    fn from_str__invalid() {
+        assert_eq!(HandError::InvalidIndex, "XX".parse::<Card>().unwrap_err());
+        assert_eq!(HandError::InvalidIndex, "".parse::<Card>().unwrap_err());
+        assert_eq!(HandError::InvalidIndex, "Asx".parse::<Card>().unwrap_err());
+        assert_eq!(HandError::InvalidIndex, "As Kd".parse::<Card>().unwrap_err());
+    }
+
+    #[rstest]
+    #[case(CardNumber::ACE_SPADES, '🂡')]
+    #[case(CardNumber::TEN_HEARTS, '🂺')]
+    #[case(CardNumber::JACK_DIAMONDS, '🃋')]
+    #[case(CardNumber::QUEEN_CLUBS, '🃝')]
+    #[case(CardNumber::KING_SPADES, '🂮')]
+    #[case(CardNumber::DEUCE_CLUBS, '🃒')]
+    #[case(CardNumber::BLANK, '🂠')]
+    fn to_unicode_playing_card(#[case] number: CKCNumber, #[case] expected: char) {
+        assert_eq!(expected, Card::from(number).to_unicode_playing_card());
+    }
+
+    #[test]
+    #[cfg(feature = "ansi")]
+    fn write_pretty() {
+        let mut pretty = alloc::string::String::new();
+
+        Card::from(CardNumber::ACE_SPADES).write_pretty(&mut pretty).unwrap();
+        Card::from(CardNumber::KING_HEARTS).write_pretty(&mut pretty).unwrap();
+
+        assert_eq!("A♠\x1b[31mK♥\x1b[0m", pretty);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("A♠", Card::from(CardNumber::ACE_SPADES).to_string());
+        assert_eq!("2♣", Card::from(CardNumber::DEUCE_CLUBS).to_string());
+        assert_eq!("__", Card::BLANK.to_string());
+    }
+
+    #[test]
+    fn ord() {
+        let mut cards = [
+            Card::from(CardNumber::KING_SPADES),
+            Card::from(CardNumber::ACE_CLUBS),
+            Card::from(CardNumber::ACE_SPADES),
+            Card::from(CardNumber::DEUCE_HEARTS),
+        ];
+
+        cards.sort();
+
+        assert_eq!(
+            [
+                Card::from(CardNumber::DEUCE_HEARTS),
+                Card::from(CardNumber::KING_SPADES),
+                Card::from(CardNumber::ACE_CLUBS),
+                Card::from(CardNumber::ACE_SPADES),
+            ],
+            cards
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let card = Card::from(CardNumber::ACE_SPADES);
+
+        let json = serde_json::to_string(&card).unwrap();
+
+        assert_eq!("\"As\"", json);
+        assert_eq!(card, serde_json::from_str::<Card>(&json).unwrap());
+        assert!(serde_json::from_str::<Card>("\"Xx\"").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde__round_trip() {
+        for card in Card::all().chain([Card::BLANK]) {
+            let json = serde_json::to_string(&card).unwrap();
+
+            assert_eq!(card, serde_json::from_str::<Card>(&json).unwrap());
+        }
+        assert_eq!("\"__\"", serde_json::to_string(&Card::BLANK).unwrap());
+    }
+}
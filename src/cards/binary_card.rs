@@ -1,11 +1,16 @@
+use crate::card::Card;
 use crate::cards::five::Five;
 use crate::cards::four::Four;
+use crate::cards::hand::Hand;
 use crate::cards::seven::Seven;
 use crate::cards::six::Six;
 use crate::cards::three::Three;
 use crate::cards::two::Two;
 use crate::cards::HandValidator;
-use crate::{CKCNumber, CardNumber, PokerCard};
+use crate::deck::POKER_DECK;
+use crate::{CKCNumber, CardNumber, CardRank, CardSuit, HandError, PokerCard, Shifty};
+use alloc::vec::Vec;
+use core::ops::{BitAnd, BitOr};
 
 pub type BinaryCard = u64;
 
@@ -68,8 +73,9 @@ pub trait BC64 {
     const BLANK:          u64 = 0;
 
     const ALL:            u64 = 0b1111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111_1111;
-    /// Used to check for values that wouldn't be a valid for a `BinaryCard`.
-    const OVERFLOW:       u64 = 0b1111_1111_1111_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000;
+    /// Every bit above the 52 cards, used to check for values that wouldn't be valid for a
+    /// `BinaryCard`.
+    const OVERFLOW:       u64 = !BinaryCard::ALL;
 
     //endregion
 
@@ -253,6 +259,36 @@ pub trait BC64 {
             | BinaryCard::from_ckc(seven.seventh())
     }
 
+    /// Returns the card of the rank and suit, the same card as
+    /// `BinaryCard::from_ckc(CKCNumber::create(rank, suit))`, or `BLANK` if either is blank.
+    #[must_use]
+    fn from_rank_and_suit(rank: CardRank, suit: CardSuit) -> BinaryCard {
+        match (rank, suit_shift(suit)) {
+            (CardRank::BLANK, _) | (_, None) => BinaryCard::BLANK,
+            (rank, Some(shift)) => 1 << (shift + rank as u32 - 2),
+        }
+    }
+
+    /// Returns the rank of a single card, the same as `PokerCard::get_card_rank()` for its
+    /// `CKCNumber`, or `CardRank::BLANK` if it isn't exactly one card.
+    #[must_use]
+    fn card_rank(&self) -> CardRank {
+        if !self.is_single_card() || !self.is_valid() {
+            return CardRank::BLANK;
+        }
+        CardRank::ALL[12 - (self.as_u64().trailing_zeros() % 13) as usize]
+    }
+
+    /// Returns the suit of a single card, the same as `PokerCard::get_card_suit()` for its
+    /// `CKCNumber`, or `CardSuit::BLANK` if it isn't exactly one card.
+    #[must_use]
+    fn card_suit(&self) -> CardSuit {
+        if !self.is_single_card() || !self.is_valid() {
+            return CardSuit::BLANK;
+        }
+        CardSuit::all()[3 - (self.as_u64().trailing_zeros() / 13) as usize]
+    }
+
     #[must_use]
     fn fold_in(&self, bc: BinaryCard) -> BinaryCard {
         self.as_u64() | bc
@@ -281,7 +317,24 @@ pub trait BC64 {
 
     #[must_use]
     fn is_valid(&self) -> bool {
-        (self.as_u64() != BinaryCard::BLANK) && ((self.as_u64() & BinaryCard::OVERFLOW).number_of_cards()) < 1
+        BinaryCard::try_from_bits(self.as_u64()).is_ok()
+    }
+
+    /// Returns the bits as a `BinaryCard` if they hold at least one card and nothing above the
+    /// 52 card bits.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HandError::BlankCard` if no bits are set, and `HandError::InvalidBinaryFormat`
+    /// if any bit above the 52nd is set.
+    fn try_from_bits(bits: u64) -> Result<BinaryCard, HandError> {
+        if bits == BinaryCard::BLANK {
+            Err(HandError::BlankCard)
+        } else if bits & BinaryCard::OVERFLOW != BinaryCard::BLANK {
+            Err(HandError::InvalidBinaryFormat)
+        } else {
+            Ok(bits)
+        }
     }
 
     #[must_use]
@@ -289,11 +342,69 @@ pub trait BC64 {
         self.as_u64().count_ones()
     }
 
+    /// Returns the 13 bit field of the cards in the suit, with deuces as bit 0 and aces as bit
+    /// 12, the same as the rank bits of a `CKCNumber`.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    fn suit_mask(&self, suit: CardSuit) -> u16 {
+        match suit_shift(suit) {
+            Some(shift) => ((self.as_u64() >> shift) & 0x1fff) as u16,
+            None => 0,
+        }
+    }
+
+    /// Returns the ranks of every card, in the same layout as `suit_mask()`.
+    #[must_use]
+    fn ranks_present(&self) -> u16 {
+        CardSuit::all().iter().fold(0, |mask, suit| mask | self.suit_mask(*suit))
+    }
+
+    #[must_use]
+    fn count_of_rank(&self, rank: CardRank) -> u32 {
+        if rank == CardRank::BLANK {
+            return 0;
+        }
+        // The rank's bit in each of the four suits.
+        let ranks = 0x0080_0400_2001 << (rank as u32 - 2);
+        (self.as_u64() & ranks).count_ones()
+    }
+
+    #[must_use]
+    fn count_of_suit(&self, suit: CardSuit) -> u32 {
+        self.suit_mask(suit).count_ones()
+    }
+
+    /// Returns each card as its own `BinaryCard`, in the same order as `peel()`, but without
+    /// changing the value. Bits outside of the deck are skipped.
+    #[must_use]
+    fn iter(&self) -> BinaryCardIter {
+        BinaryCardIter(self.as_u64() & BinaryCard::ALL)
+    }
+
+    /// Returns every card as a `CKCNumber`, in the same order as `iter()`.
+    #[must_use]
+    fn to_vec_ckc(&self) -> Vec<CKCNumber> {
+        self.iter()
+            .map(|bc| POKER_DECK[51 - bc.trailing_zeros() as usize])
+            .collect()
+    }
+
     fn peel(&mut self) -> BinaryCard;
 
     fn as_u64(&self) -> u64;
 }
 
+/// The position of the lowest bit of the suit's 13 bits, which is its deuce.
+fn suit_shift(suit: CardSuit) -> Option<u32> {
+    match suit {
+        CardSuit::SPADES => Some(39),
+        CardSuit::HEARTS => Some(26),
+        CardSuit::DIAMONDS => Some(13),
+        CardSuit::CLUBS => Some(0),
+        CardSuit::BLANK => None,
+    }
+}
+
 impl BC64 for BinaryCard {
     fn peel(&mut self) -> BinaryCard {
         for bc in BinaryCard::DECK {
@@ -310,6 +421,141 @@ impl BC64 for BinaryCard {
     }
 }
 
+/// Returns true if any card is in both `a` and `b`, such as a villain combo that uses one of the
+/// hero's cards or a card on the board. Every hand type and `Card` convert into a `BinaryCard`
+/// with `BinaryCard::from()`. A bare `CKCNumber` needs `BinaryCard::from_ckc()` instead, since
+/// `into()` would only widen the number rather than set its card's bit.
+///
+/// ```
+/// use ckc_rs::cards::binary_card::{conflicts, BinaryCard};
+/// use ckc_rs::cards::three::Three;
+/// use ckc_rs::cards::two::Two;
+///
+/// let hero = Two::try_from("A♠ K♠").unwrap();
+/// let flop = Three::try_from("K♥ 9♦ 2♣").unwrap();
+///
+/// assert!(!conflicts(BinaryCard::from(hero), BinaryCard::from(flop)));
+/// assert!(conflicts(BinaryCard::from(Two::try_from("K♥ Q♥").unwrap()), BinaryCard::from(flop)));
+/// ```
+#[must_use]
+pub fn conflicts(a: BinaryCard, b: BinaryCard) -> bool {
+    a & b != BinaryCard::BLANK
+}
+
+impl From<Card> for BinaryCard {
+    fn from(card: Card) -> Self {
+        BinaryCard::from_ckc(card.as_ckc())
+    }
+}
+
+impl From<Two> for BinaryCard {
+    fn from(two: Two) -> Self {
+        BinaryCard::from_two(two)
+    }
+}
+
+impl From<Three> for BinaryCard {
+    fn from(three: Three) -> Self {
+        BinaryCard::from_three(three)
+    }
+}
+
+impl From<Four> for BinaryCard {
+    fn from(four: Four) -> Self {
+        BinaryCard::from_four(four)
+    }
+}
+
+impl From<Five> for BinaryCard {
+    fn from(five: Five) -> Self {
+        BinaryCard::from_five(five)
+    }
+}
+
+impl From<Six> for BinaryCard {
+    fn from(six: Six) -> Self {
+        BinaryCard::from_six(six)
+    }
+}
+
+impl From<Seven> for BinaryCard {
+    fn from(seven: Seven) -> Self {
+        BinaryCard::from_seven(seven)
+    }
+}
+
+impl From<Hand> for BinaryCard {
+    fn from(hand: Hand) -> Self {
+        hand.id()
+    }
+}
+
+/// Implements `|` and `&` between a hand and each of the other types, giving the cards in either
+/// of them, or in both, as a `BinaryCard`.
+macro_rules! hand_bit_ops {
+    ($hand:ty => $($other:ty),+) => {
+        $(
+            impl BitOr<$other> for $hand {
+                type Output = BinaryCard;
+
+                fn bitor(self, other: $other) -> BinaryCard {
+                    BinaryCard::from(self) | BinaryCard::from(other)
+                }
+            }
+
+            impl BitAnd<$other> for $hand {
+                type Output = BinaryCard;
+
+                fn bitand(self, other: $other) -> BinaryCard {
+                    BinaryCard::from(self) & BinaryCard::from(other)
+                }
+            }
+        )+
+    };
+}
+
+hand_bit_ops!(Two => Two, Three, Four, Five, Six, Seven, BinaryCard);
+hand_bit_ops!(Three => Two, Three, Four, Five, Six, Seven, BinaryCard);
+hand_bit_ops!(Four => Two, Three, Four, Five, Six, Seven, BinaryCard);
+hand_bit_ops!(Five => Two, Three, Four, Five, Six, Seven, BinaryCard);
+hand_bit_ops!(Six => Two, Three, Four, Five, Six, Seven, BinaryCard);
+hand_bit_ops!(Seven => Two, Three, Four, Five, Six, Seven, BinaryCard);
+hand_bit_ops!(BinaryCard => Two, Three, Four, Five, Six, Seven);
+
+/// Iterates over the cards in a `BinaryCard`, from the ace of spades down to the deuce of clubs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BinaryCardIter(BinaryCard);
+
+impl Iterator for BinaryCardIter {
+    type Item = BinaryCard;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0 == BinaryCard::BLANK {
+            return None;
+        }
+        let card = 1 << self.0.ilog2();
+        self.0 ^= card;
+        Some(card)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for BinaryCardIter {}
+
+/// Moves every card to the next suit, the same as for a `CKCNumber`: spades to hearts, hearts to
+/// diamonds, diamonds to clubs and clubs to spades. Each suit is 13 bits, so this rotates the 52
+/// card bits by one suit. Bits outside of the deck are dropped.
+impl Shifty for BinaryCard {
+    fn shift_suit(&self) -> Self {
+        let cards = self & BinaryCard::ALL;
+        (cards >> 13) | ((cards & 0x1fff) << 39)
+    }
+}
+
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod alt__bit_card {
@@ -470,6 +716,88 @@ mod alt__bit_card {
         assert_eq!(2, aces.number_of_cards());
     }
 
+    #[test]
+    fn hand_bit_ops() {
+        let hero = Two::try_from("A♠ K♥").unwrap();
+        let flop = Three::try_from("K♥ 9♦ 2♣").unwrap();
+
+        assert_eq!(BinaryCard::from_index("A♠ K♥ 9♦ 2♣"), hero | flop);
+        assert_eq!(BinaryCard::KING_HEARTS, hero & flop);
+        assert_eq!(BinaryCard::KING_HEARTS, BinaryCard::KINGS & hero);
+        assert_eq!(BinaryCard::BLANK, hero & Two::try_from("Q♠ Q♥").unwrap());
+    }
+
+    /// Random hands that overlap by anything from none to all of the smaller one's cards give the
+    /// same results through the bit operators as through their `CKCNumbers`.
+    #[test]
+    fn hand_bit_ops__random() {
+        use crate::cards::HandRanker;
+        use rand::rngs::SmallRng;
+        use rand::seq::SliceRandom;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = SmallRng::seed_from_u64(2582);
+        for _ in 0..1_000 {
+            let mut deck = POKER_DECK;
+            deck.shuffle(&mut rng);
+            let offset = rng.gen_range(0..=5);
+            let five = Five::try_from(&deck[..5]).unwrap();
+            let seven = Seven::try_from(&deck[offset..offset + 7]).unwrap();
+
+            assert_eq!(BinaryCard::from(five) | BinaryCard::from(seven), five | seven);
+            assert_eq!(BinaryCard::from(five) & BinaryCard::from(seven), seven & five);
+            assert_eq!(5 - offset, (five & seven).number_of_cards() as usize);
+            assert_eq!(7 + offset, (five | seven).number_of_cards() as usize);
+            assert_eq!(
+                five.hand_rank_value(),
+                Five::try_from(five | BinaryCard::BLANK).unwrap().hand_rank_value()
+            );
+            assert_eq!(
+                seven.hand_rank_value(),
+                Seven::try_from(seven | BinaryCard::BLANK).unwrap().hand_rank_value()
+            );
+        }
+    }
+
+    #[test]
+    fn conflicts() {
+        let hero = Two::try_from("A♠ A♥").unwrap();
+        let board = Five::try_from("K♠ Q♠ J♠ T♠ 2♣").unwrap();
+
+        assert!(!super::conflicts(BinaryCard::from(hero), BinaryCard::from(board)));
+        assert!(super::conflicts(
+            BinaryCard::from(Two::try_from("2♣ 2♦").unwrap()),
+            BinaryCard::from(board)
+        ));
+        assert!(super::conflicts(
+            BinaryCard::from("A♥".parse::<Card>().unwrap()),
+            BinaryCard::from(hero)
+        ));
+        assert!(super::conflicts(BinaryCard::ACES, BinaryCard::from(hero)));
+        assert!(super::conflicts(
+            BinaryCard::from(Hand::Five(board)),
+            BinaryCard::from(Seven::new(hero, board))
+        ));
+        assert!(!super::conflicts(
+            BinaryCard::from(Two::default()),
+            BinaryCard::from(Two::default())
+        ));
+        assert!(!super::conflicts(
+            BinaryCard::from_ckc(CardNumber::ACE_SPADES),
+            BinaryCard::from_ckc(CardNumber::ACE_HEARTS)
+        ));
+    }
+
+    #[rstest]
+    #[case("A♠ K♠")]
+    #[case("A♠ K♠ Q♠")]
+    #[case("A♠ K♠ Q♠ J♠ T♠ 2♣ 2♦")]
+    fn from__hand(#[case] index: &str) {
+        let hand = Hand::try_from(BinaryCard::from_index(index)).unwrap();
+
+        assert_eq!(BinaryCard::from_index(index), BinaryCard::from(hand));
+    }
+
     #[test]
     fn has() {
         assert!(BinaryCard::ACES.has(BinaryCard::ACE_SPADES));
@@ -489,6 +817,17 @@ mod alt__bit_card {
         assert!(BinaryCard::ACES.is_valid());
         assert!(!BinaryCard::BLANK.is_valid());
         assert!(!BinaryCard::OVERFLOW.is_valid());
+        assert!(!(BinaryCard::ACE_SPADES | 1 << 52).is_valid());
+    }
+
+    #[rstest]
+    #[case(BinaryCard::ACE_SPADES, Ok(BinaryCard::ACE_SPADES))]
+    #[case(BinaryCard::ALL, Ok(BinaryCard::ALL))]
+    #[case(BinaryCard::BLANK, Err(HandError::BlankCard))]
+    #[case(1 << 52, Err(HandError::InvalidBinaryFormat))]
+    #[case(BinaryCard::ACES | 1 << 63, Err(HandError::InvalidBinaryFormat))]
+    fn try_from_bits(#[case] bits: u64, #[case] expected: Result<BinaryCard, HandError>) {
+        assert_eq!(expected, BinaryCard::try_from_bits(bits));
     }
 
     #[test]
@@ -496,6 +835,109 @@ mod alt__bit_card {
         assert_eq!(4, BinaryCard::ACES.number_of_cards());
     }
 
+    #[test]
+    fn from_rank_and_suit() {
+        for suit in CardSuit::all() {
+            for rank in CardRank::ALL {
+                let bc = BinaryCard::from_rank_and_suit(rank, suit);
+
+                assert_eq!(BinaryCard::from_ckc(CKCNumber::create(rank, suit)), bc);
+                assert_eq!(rank, bc.card_rank());
+                assert_eq!(suit, bc.card_suit());
+            }
+        }
+        assert_eq!(
+            BinaryCard::BLANK,
+            BinaryCard::from_rank_and_suit(CardRank::BLANK, CardSuit::SPADES)
+        );
+        assert_eq!(
+            BinaryCard::BLANK,
+            BinaryCard::from_rank_and_suit(CardRank::ACE, CardSuit::BLANK)
+        );
+    }
+
+    #[rstest]
+    #[case(BinaryCard::BLANK)]
+    #[case(BinaryCard::ACES)]
+    #[case(1 << 52)]
+    fn card_rank__not_one_card(#[case] bc: BinaryCard) {
+        assert_eq!(CardRank::BLANK, bc.card_rank());
+        assert_eq!(CardSuit::BLANK, bc.card_suit());
+    }
+
+    #[test]
+    fn suit_mask() {
+        let bc = BinaryCard::from_index("A♠ K♠ 2♠ 9♥ 2♣");
+
+        assert_eq!(0b1_1000_0000_0001, bc.suit_mask(CardSuit::SPADES));
+        assert_eq!(0b0_0000_1000_0000, bc.suit_mask(CardSuit::HEARTS));
+        assert_eq!(0, bc.suit_mask(CardSuit::DIAMONDS));
+        assert_eq!(0b0_0000_0000_0001, bc.suit_mask(CardSuit::CLUBS));
+        assert_eq!(0, bc.suit_mask(CardSuit::BLANK));
+    }
+
+    #[test]
+    fn ranks_present() {
+        let bc = BinaryCard::from_index("A♠ K♠ 2♠ 9♥ 2♣");
+
+        assert_eq!(0b1_1000_1000_0001, bc.ranks_present());
+        assert_eq!(0, BinaryCard::BLANK.ranks_present());
+        assert_eq!(0b1_1111_1111_1111, BinaryCard::ALL.ranks_present());
+    }
+
+    #[rstest]
+    #[case(CardRank::ACE, 1)]
+    #[case(CardRank::KING, 1)]
+    #[case(CardRank::NINE, 1)]
+    #[case(CardRank::TWO, 2)]
+    #[case(CardRank::THREE, 0)]
+    #[case(CardRank::BLANK, 0)]
+    fn count_of_rank(#[case] rank: CardRank, #[case] expected: u32) {
+        let bc = BinaryCard::from_index("A♠ K♠ 2♠ 9♥ 2♣");
+
+        assert_eq!(expected, bc.count_of_rank(rank));
+        assert_eq!(4, BinaryCard::ALL.count_of_rank(CardRank::TWO));
+    }
+
+    #[rstest]
+    #[case(CardSuit::SPADES, 3)]
+    #[case(CardSuit::HEARTS, 1)]
+    #[case(CardSuit::DIAMONDS, 0)]
+    #[case(CardSuit::CLUBS, 1)]
+    #[case(CardSuit::BLANK, 0)]
+    fn count_of_suit(#[case] suit: CardSuit, #[case] expected: u32) {
+        let bc = BinaryCard::from_index("A♠ K♠ 2♠ 9♥ 2♣");
+
+        assert_eq!(expected, bc.count_of_suit(suit));
+    }
+
+    #[test]
+    fn iter() {
+        let bc = BinaryCard::from_index("2♣ A♠ 9♥");
+        let mut iter = bc.iter();
+
+        assert_eq!(3, iter.len());
+        assert_eq!(Some(BinaryCard::ACE_SPADES), iter.next());
+        assert_eq!(Some(BinaryCard::NINE_HEARTS), iter.next());
+        assert_eq!(Some(BinaryCard::DEUCE_CLUBS), iter.next());
+        assert_eq!(None, iter.next());
+        assert_eq!(3, bc.number_of_cards());
+        assert_eq!(BinaryCard::DECK.to_vec(), BinaryCard::ALL.iter().collect::<Vec<_>>());
+        assert_eq!(0, (BinaryCard::OVERFLOW).iter().count());
+    }
+
+    #[test]
+    fn to_vec_ckc() {
+        let bc = BinaryCard::from_index("2♣ A♠ 9♥");
+
+        assert_eq!(
+            alloc::vec![CardNumber::ACE_SPADES, CardNumber::NINE_HEARTS, CardNumber::DEUCE_CLUBS],
+            bc.to_vec_ckc()
+        );
+        assert_eq!(POKER_DECK.to_vec(), BinaryCard::ALL.to_vec_ckc());
+        assert!(BinaryCard::BLANK.to_vec_ckc().is_empty());
+    }
+
     #[test]
     fn peel() {
         let mut cards = BinaryCard::ACES;
@@ -666,4 +1108,19 @@ mod alt__bit_card {
         assert_eq!(BinaryCard::BLANK, BinaryCard::ACE_DIAMONDS & BinaryCard::DEUCES);
     }
     //endregion Cards
+
+    #[test]
+    fn shifty__shift_suit() {
+        for card in crate::deck::POKER_DECK {
+            assert_eq!(
+                BinaryCard::from_ckc(card.shift_suit()),
+                BinaryCard::from_ckc(card).shift_suit()
+            );
+        }
+        let hand = BinaryCard::from_index("A♠ K♥ Q♦ J♣");
+
+        assert_eq!(BinaryCard::from_index("A♥ K♦ Q♣ J♠"), hand.shift_suit());
+        assert_eq!(hand, hand.shift_suit().shift_suit().shift_suit().shift_suit());
+        assert_eq!(BinaryCard::BLANK, BinaryCard::OVERFLOW.shift_suit());
+    }
 }
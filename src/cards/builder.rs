@@ -0,0 +1,185 @@
+use crate::cards::five::Five;
+use crate::cards::seven::Seven;
+use crate::cards::HandValidator;
+use crate::{CKCNumber, CardNumber, HandError, PokerCard};
+
+/// Builds a `Five`, one card at a time.
+pub type FiveBuilder = HandBuilder<5>;
+
+/// Builds a `Seven`, one card at a time.
+pub type SevenBuilder = HandBuilder<7>;
+
+/// Collects the cards of a hand as they arrive, such as from a parser, and only checks them once
+/// all of them are in, when `finish()` is called.
+///
+/// ```
+/// use ckc_rs::cards::builder::SevenBuilder;
+///
+/// let mut builder = SevenBuilder::new();
+/// builder.push_index("A♠ K♠").push_index("Q♠ J♠ T♠").push_index("2♣ 3♦");
+///
+/// assert_eq!(1, ckc_rs::cards::HandRanker::hand_rank_value(&builder.finish().unwrap()));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct HandBuilder<const N: usize> {
+    cards: [CKCNumber; N],
+    pushed: usize,
+    invalid_index: bool,
+}
+
+impl<const N: usize> HandBuilder<N> {
+    #[must_use]
+    pub fn new() -> Self {
+        HandBuilder {
+            cards: [CardNumber::BLANK; N],
+            pushed: 0,
+            invalid_index: false,
+        }
+    }
+
+    /// Returns how many cards have been pushed, including any past the size of the hand.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pushed
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pushed == 0
+    }
+
+    /// Adds a card. Cards past the size of the hand aren't kept, but are counted so that
+    /// `finish()` can return `HandError::TooManyCards`.
+    pub fn push(&mut self, card: CKCNumber) -> &mut Self {
+        if let Some(slot) = self.cards.get_mut(self.pushed) {
+            *slot = card;
+        }
+        self.pushed += 1;
+        self
+    }
+
+    /// Adds every card in a space separated index, such as `A♠ K♠`. A card that can't be parsed
+    /// makes `finish()` return `HandError::InvalidIndex`.
+    pub fn push_index(&mut self, index: &str) -> &mut Self {
+        for card in index.split_whitespace().map(CKCNumber::from_index) {
+            if card == CardNumber::BLANK {
+                self.invalid_index = true;
+            }
+            self.push(card);
+        }
+        self
+    }
+
+    fn validated<H: HandValidator + From<[CKCNumber; N]>>(&self) -> Result<H, HandError> {
+        if self.pushed < N {
+            return Err(HandError::NotEnoughCards);
+        }
+        if self.pushed > N {
+            return Err(HandError::TooManyCards);
+        }
+        if self.invalid_index {
+            return Err(HandError::InvalidIndex);
+        }
+        let hand = H::from(self.cards);
+        hand.validate().map(|()| hand)
+    }
+}
+
+impl<const N: usize> Default for HandBuilder<N> {
+    fn default() -> Self {
+        HandBuilder::new()
+    }
+}
+
+impl HandBuilder<5> {
+    /// Returns the `Five` once all five cards are in.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HandError::NotEnoughCards` or `HandError::TooManyCards` if the wrong number of
+    /// cards have been pushed, `HandError::InvalidIndex` if an index couldn't be parsed,
+    /// `HandError::Incomplete` if some of the cards are blank, `HandError::BlankCard` if all of
+    /// them are, `HandError::InvalidCard` if any card is invalid, and `HandError::DuplicateCard`
+    /// if any card appears more than once, checked in that order.
+    pub fn finish(&self) -> Result<Five, HandError> {
+        self.validated()
+    }
+}
+
+impl HandBuilder<7> {
+    /// Returns the `Seven` once all seven cards are in.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HandError::NotEnoughCards` or `HandError::TooManyCards` if the wrong number of
+    /// cards have been pushed, `HandError::InvalidIndex` if an index couldn't be parsed,
+    /// `HandError::Incomplete` if some of the cards are blank, `HandError::BlankCard` if all of
+    /// them are, `HandError::InvalidCard` if any card is invalid, and `HandError::DuplicateCard`
+    /// if any card appears more than once, checked in that order.
+    pub fn finish(&self) -> Result<Seven, HandError> {
+        self.validated()
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod cards_builder_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn finish() {
+        let mut builder = FiveBuilder::new();
+        builder
+            .push(CardNumber::ACE_SPADES)
+            .push(CardNumber::KING_SPADES)
+            .push_index("Q♠ J♠")
+            .push_index("T♠");
+
+        assert_eq!(Five::try_from("A♠ K♠ Q♠ J♠ T♠").unwrap(), builder.finish().unwrap());
+        assert_eq!(5, builder.len());
+    }
+
+    #[test]
+    fn finish__seven() {
+        let mut builder = SevenBuilder::default();
+        assert!(builder.is_empty());
+
+        for card in "KC 8C QD A♠ 9h 2C T♠".split(' ') {
+            builder.push_index(card);
+        }
+
+        assert_eq!(
+            Seven::try_from("KC 8C QD A♠ 9h 2C T♠").unwrap(),
+            builder.finish().unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case("A♠ K♠ Q♠ J♠", HandError::NotEnoughCards)]
+    #[case("A♠ K♠ Q♠ J♠ T♠ 9♠", HandError::TooManyCards)]
+    #[case("A♠ K♠ Q♠ J♠ XX", HandError::InvalidIndex)]
+    #[case("A♠ K♠ Q♠ J♠ A♠", HandError::DuplicateCard)]
+    fn finish__errors(#[case] index: &str, #[case] expected: HandError) {
+        let mut builder = FiveBuilder::new();
+        builder.push_index(index);
+
+        assert_eq!(expected, builder.finish().unwrap_err());
+    }
+
+    #[test]
+    fn finish__blank() {
+        let mut builder = FiveBuilder::new();
+        builder.push_index("A♠ K♠ Q♠ J♠").push(CardNumber::BLANK);
+
+        assert_eq!(HandError::Incomplete, builder.finish().unwrap_err());
+    }
+
+    #[test]
+    fn finish__invalid_card() {
+        let mut builder = FiveBuilder::new();
+        builder.push_index("A♠ K♠ Q♠ J♠").push(12345);
+
+        assert_eq!(HandError::InvalidCard, builder.finish().unwrap_err());
+    }
+}
@@ -1,10 +1,17 @@
-use crate::cards::{HandRanker, HandValidator};
+use crate::cards::{
+    hand_array, try_from_binary_card, try_from_slice, HandRanker, HandValidator, RankBits, StraightDraw,
+};
 use crate::hand_rank::HandRankValue;
-use crate::{CKCNumber, CardNumber, HandError, PokerCard, Shifty};
+use crate::{CKCNumber, CardNumber, CardRank, CardSuit, HandError, PokerCard, Shifty};
 use core::slice::Iter;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+// The unsafe lookups only rely on the tables' lengths, not on the cards being valid, so a
+// deserialized `Five` can't break them.
+#[allow(clippy::unsafe_derive_deserialize)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Five([CKCNumber; 5]);
 
 impl Five {
@@ -61,11 +68,40 @@ impl Five {
         self.0[4] = card_number;
     }
 
+    /// A `Five` with no cards, the same as `Five::default()`.
+    pub const EMPTY: Five = Five([CardNumber::BLANK; 5]);
+
+    /// Builds a `Five` from the rank and suit of each card, the same as `Two::from_ranks_suits()`.
+    ///
+    /// # Errors
+    ///
+    /// Same as `HandValidator::validate()`, where a `CardRank::BLANK` or `CardSuit::BLANK` is a
+    /// blank card.
+    pub fn from_ranks_suits(cards: [(CardRank, CardSuit); 5]) -> Result<Self, HandError> {
+        crate::cards::from_ranks_suits(cards)
+    }
+
     #[must_use]
     pub fn to_arr(&self) -> [CKCNumber; 5] {
         self.0
     }
 
+    /// Returns the cards separated by spaces, with hearts and diamonds in red, for terminals that
+    /// understand ANSI escape codes.
+    #[cfg(feature = "ansi")]
+    #[must_use]
+    pub fn to_pretty(&self) -> alloc::string::String {
+        let mut pretty = alloc::string::String::new();
+        for (i, card) in self.iter().enumerate() {
+            if i > 0 {
+                pretty.push(' ');
+            }
+            // Writing to a `String` can't fail.
+            let _ = crate::Card::from(*card).write_pretty(&mut pretty);
+        }
+        pretty
+    }
+
     //endregion
 
     fn from_index(index: &str) -> Option<[CKCNumber; 5]> {
@@ -76,42 +112,60 @@ impl Five {
         let third = CKCNumber::from_index(esses.next()?);
         let forth = CKCNumber::from_index(esses.next()?);
         let fifth = CKCNumber::from_index(esses.next()?);
+        if esses.next().is_some() {
+            return None;
+        }
         let hand: [CKCNumber; 5] = [first, second, third, forth, fifth];
         Some(hand)
     }
 
+    /// Returns the index of the product in `PRODUCTS`, or 0 if it isn't there.
     #[must_use]
-    #[allow(clippy::comparison_chain)]
     pub fn find_in_products(key: usize) -> usize {
+        let products = &crate::lookups::PRODUCTS;
+        // A binary search that keeps `low + len <= products.len()`, so that `mid`, which is
+        // always less than `low + len`, and `low` are both in bounds.
         let mut low = 0;
-        let mut high = 4887;
-        let mut mid;
-
-        while low <= high {
-            mid = (high + low) >> 1; // divide by two
-
-            let product = crate::lookups::PRODUCTS[mid] as usize;
-            if key < product {
-                high = mid - 1;
-            } else if key > product {
-                low = mid + 1;
-            } else {
-                return mid;
+        let mut len = products.len();
+        while len > 1 {
+            let half = len / 2;
+            let mid = low + half;
+            // SAFETY: `mid < low + len <= products.len()`.
+            if unsafe { crate::lookups::entry(products, mid) } as usize <= key {
+                low = mid;
             }
+            len -= half;
+        }
+        // SAFETY: `low < low + len <= products.len()`, since `len` is at least 1.
+        if unsafe { crate::lookups::entry(products, low) } as usize == key {
+            low
+        } else {
+            0
         }
-        0
     }
 
     fn not_unique(&self) -> HandRankValue {
-        crate::lookups::VALUES[Five::find_in_products(self.multiply_primes())]
+        // SAFETY: `find_in_products()` only returns indexes into `PRODUCTS`, which is the same
+        // length as `VALUES`.
+        unsafe { crate::lookups::entry(&crate::lookups::VALUES, Five::find_in_products(self.multiply_primes())) }
+    }
+
+    /// Only valid cards have their rank bits within `POSSIBLE_COMBINATIONS`.
+    fn flush(index: usize) -> HandRankValue {
+        if index >= Five::POSSIBLE_COMBINATIONS {
+            return crate::hand_rank::NO_HAND_RANK_VALUE;
+        }
+        // SAFETY: `FLUSHES` has `POSSIBLE_COMBINATIONS` entries.
+        unsafe { crate::lookups::entry(&crate::lookups::FLUSHES, index) }
     }
 
     #[allow(clippy::cast_possible_truncation)]
     fn unique(index: usize) -> HandRankValue {
-        if index > Five::POSSIBLE_COMBINATIONS {
+        if index >= Five::POSSIBLE_COMBINATIONS {
             return CardNumber::BLANK as HandRankValue;
         }
-        crate::lookups::UNIQUE_5[index]
+        // SAFETY: `UNIQUE_5` has `POSSIBLE_COMBINATIONS` entries.
+        unsafe { crate::lookups::entry(&crate::lookups::UNIQUE_5, index) }
     }
 
     //endregion
@@ -146,6 +200,31 @@ impl Five {
         self.or_rank_bits() == Five::WHEEL_OR_BITS
     }
 
+    /// Returns the `CardSuit` of the hand if it's a flush.
+    #[must_use]
+    pub fn flush_suit(&self) -> Option<CardSuit> {
+        if self.is_flush() {
+            Some(self.first().get_card_suit())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the `CardRank` of the top card of the hand if it's a straight. The ace in a
+    /// wheel plays low, so `5♥ 4♦ 3♣ 2♥ A♠` returns `CardRank::FIVE`.
+    #[must_use]
+    pub fn straight_high(&self) -> Option<CardRank> {
+        if self.is_wheel() {
+            return Some(CardRank::FIVE);
+        }
+        if !self.is_straight() {
+            return None;
+        }
+        let rank_bits = self.or_rank_bits();
+        let high_bit = 1 << (u32::BITS - 1 - rank_bits.leading_zeros());
+        Some((high_bit << CardNumber::RANK_FLAG_SHIFT).get_card_rank())
+    }
+
     #[must_use]
     pub fn multiply_primes(&self) -> usize {
         (self.first().get_rank_prime()
@@ -162,10 +241,107 @@ impl Five {
 
     #[must_use]
     pub fn or_rank_bits(&self) -> u32 {
-        self.or_bits() >> CardNumber::RANK_FLAG_SHIFT
+        (self.or_bits() & CardNumber::MULTIPLES_FILTER) >> CardNumber::RANK_FLAG_SHIFT
+    }
+
+    /// Returns the rank bits, in the same layout as `Five.or_rank_bits()`, of every rank that
+    /// would make a better straight than the hand already has if it were added to it.
+    ///
+    /// `9♠ 7♥ 6♦ 5♣ 3♣` returns the bits for the eight and the four.
+    #[must_use]
+    pub fn straight_outs(&self) -> u32 {
+        crate::cards::straight_outs(self.or_rank_bits())
+    }
+
+    /// Classifies the straight draw made by the five cards from their `straight_outs()`, such as
+    /// a player's hole cards plus a flop in hold'em.
+    ///
+    /// `9♠ 7♥ 6♦ 5♣ 3♣` returns `StraightDraw::DoubleGutshot`.
+    #[must_use]
+    pub fn straight_draw_type(&self) -> StraightDraw {
+        StraightDraw::from_rank_bits(self.or_rank_bits())
     }
 
     //endregion bitwise
+
+    //region multiples
+
+    /// Returns the hand with every card flagged by how many cards of its rank are in the hand:
+    /// `CardNumber::PAIR`, `CardNumber::TRIPS` or `CardNumber::QUADS`. Unpaired cards, and any
+    /// flags already on the cards, are cleared.
+    ///
+    /// The flags are the highest bits of a `CKCNumber`, above the rank bits. Validating and
+    /// ranking a hand ignores them, and `Five.strip_multiples()` clears them again.
+    #[must_use]
+    pub fn flag_multiples(&self) -> Five {
+        let stripped = self.strip_multiples();
+        let counts = stripped.rank_counts();
+        Five(stripped.0.map(|card| {
+            if CardNumber::filter(card) == CardNumber::BLANK {
+                return card;
+            }
+            match counts[card.get_rank_bit().trailing_zeros() as usize] {
+                2 => card.flag_as_pair(),
+                3 => card.flag_as_trips(),
+                4 => card.flag_as_quads(),
+                _ => card,
+            }
+        }))
+    }
+
+    #[must_use]
+    pub fn strip_multiples(&self) -> Five {
+        Five(self.0.map(|card| card.strip_multiples_flags()))
+    }
+
+    /// Sorts the hand for display, with the biggest group of cards of the same rank first, then
+    /// any smaller group, and then the unpaired cards, each from highest to lowest. A full house
+    /// sorts as `3♠ 3♥ 3♦ K♠ K♣`, and two pair as `9♠ 9♥ 5♦ 5♣ A♠`. The returned hand has no
+    /// multiples flags.
+    ///
+    /// It works by flagging the hand with `Five.flag_multiples()`, since the quads flag sorts
+    /// above the trips flag, which sorts above the pair flag and then the rank bits.
+    #[must_use]
+    pub fn sort_by_multiples(&self) -> Five {
+        self.flag_multiples().sort().strip_multiples()
+    }
+
+    //endregion multiples
+
+    //region board
+
+    /// Returns true if at least two cards on the board share a rank.
+    #[must_use]
+    pub fn is_paired_board(&self) -> bool {
+        self.rank_counts().iter().any(|count| *count >= 2)
+    }
+
+    /// Returns true if two different ranks each appear at least twice on the board, which
+    /// includes boards that are already a full house.
+    #[must_use]
+    pub fn is_double_paired(&self) -> bool {
+        self.rank_counts().iter().filter(|count| **count >= 2).count() >= 2
+    }
+
+    /// Returns true if exactly three cards on the board share a rank.
+    #[must_use]
+    pub fn trips_on_board(&self) -> bool {
+        self.rank_counts().contains(&3)
+    }
+
+    #[must_use]
+    pub fn quads_on_board(&self) -> bool {
+        self.rank_counts().contains(&4)
+    }
+
+    /// Returns true if some two hole cards make a full house with the board. That takes a paired
+    /// board, but with quads on board every hand is at least quads, so no hand is a full house.
+    #[must_use]
+    pub fn boat_possible(&self) -> bool {
+        self.is_paired_board() && !self.quads_on_board()
+    }
+
+    //endregion board
 }
 
 impl From<[CKCNumber; 5]> for Five {
@@ -174,12 +350,14 @@ impl From<[CKCNumber; 5]> for Five {
     }
 }
 
+hand_array!(Five, 5);
+
 impl HandRanker for Five {
     fn hand_rank_value_and_hand(&self) -> (HandRankValue, Five) {
         let i = self.or_rank_bits() as usize;
 
         let hrv: HandRankValue = if self.is_flush() {
-            crate::lookups::FLUSHES[i]
+            Five::flush(i)
         } else {
             // Continue to evaluate if it's not a flush and the cards aren't
             // unique (straight or high card).
@@ -202,9 +380,8 @@ impl HandRanker for Five {
 }
 
 impl HandValidator for Five {
-    // TODO: macro?
     fn are_unique(&self) -> bool {
-        !(1..5).any(|i| self.0[i..].contains(&self.0[i - 1]))
+        crate::cards::all_unique(&self.0)
     }
 
     fn first(&self) -> CKCNumber {
@@ -227,10 +404,15 @@ impl HandValidator for Five {
     }
 }
 
+impl RankBits for Five {}
+
 impl TryFrom<&'static str> for Five {
     type Error = HandError;
 
     fn try_from(index: &'static str) -> Result<Self, Self::Error> {
+        if crate::parse::card_count(index) > 5 {
+            return Err(HandError::TooManyCards);
+        }
         match Five::from_index(index) {
             None => Err(HandError::InvalidIndex),
             Some(five) => Ok(Five::from(five)),
@@ -238,6 +420,10 @@ impl TryFrom<&'static str> for Five {
     }
 }
 
+try_from_slice!(Five);
+
+try_from_binary_card!(Five);
+
 impl Shifty for Five {
     fn shift_suit(&self) -> Self {
         Five([
@@ -255,6 +441,7 @@ impl Shifty for Five {
 mod cards__five_tests {
     use super::*;
     use crate::hand_rank::{HandRankClass, HandRankName};
+    use crate::{BinaryCard, BC64};
     use alloc::format;
     #[rustfmt::skip]
     #[rstest]
@@ -1813,7 +2000,6 @@ mod cards__five_tests {
     #[case("8♣ 7♣ 5♣ 4♣ 2♣", 1588, HandRankName::Flush, HandRankClass::EightHighFlush)]
     #[case("8♣ 7♣ 5♣ 3♣ 2♣", 1589, HandRankName::Flush, HandRankClass::EightHighFlush)]
 
-
     #[case("8♣ 5♣ 4♣ 3♣ 2♣", 1595, HandRankName::Flush, HandRankClass::EightHighFlush)]
     #[case("7H 6H 5H 4H 2H", 1596, HandRankName::Flush, HandRankClass::SevenHighFlush)]
     #[case("7C 5C 4C 3C 2C", 1599, HandRankName::Flush, HandRankClass::SevenHighFlush)]
@@ -2092,6 +2278,46 @@ mod cards__five_tests {
         assert_eq!(five.hand_rank_value(), 1);
     }
 
+    /// The lookups skip their bounds checks with the `fast-unsafe` feature, so these run with and
+    /// without it to check that both give the same results as the plain tables.
+    #[test]
+    fn find_in_products() {
+        let products = &crate::lookups::PRODUCTS;
+
+        for (i, product) in products.iter().enumerate() {
+            assert_eq!(i, Five::find_in_products(*product as usize));
+            if !products.contains(&(product + 1)) {
+                assert_eq!(0, Five::find_in_products(*product as usize + 1));
+            }
+        }
+        assert_eq!(0, Five::find_in_products(0));
+        assert_eq!(0, Five::find_in_products(usize::MAX));
+    }
+
+    #[test]
+    fn hand_rank_value__every_value() {
+        use crate::backend::{EvalBackend, Reference};
+        use crate::hand_rank::HandRank;
+
+        for value in 1..=7462 {
+            let five = HandRank::from(value).example_hand();
+
+            assert_eq!(Reference.five_cards(five.to_arr()), five.hand_rank_value(), "{five:?}");
+        }
+    }
+
+    #[rstest]
+    #[case([u32::MAX; 5])]
+    #[case([0xFFFF_1000; 5])]
+    #[case([0xFFFF_0000, 0x1000, 0x2000, 0x4000, 0x8000])]
+    #[case([CardNumber::BLANK; 5])]
+    fn hand_rank_value__garbage(#[case] cards: [CKCNumber; 5]) {
+        let five = Five::from(cards);
+
+        assert!(!five.is_valid());
+        assert!(five.hand_rank_value() <= 7462);
+    }
+
     #[test]
     fn hand_rank_value__royal_flush() {
         assert_eq!(1, Five::try_from("A♠ K♠ Q♠ J♠ T♠").unwrap().hand_rank_value());
@@ -2113,7 +2339,7 @@ mod cards__five_tests {
         assert_eq!("00000100000000001000101000011111", format!("{:032b}", hand.third()));
         assert_eq!("00000010000000001000100100011101", format!("{:032b}", hand.forth()));
         assert_eq!("00000001000000001000100000010111", format!("{:032b}", hand.fifth()));
-        assert_eq!("00000000000000001000100000000001", format!("{:032b}", and_bits));
+        assert_eq!("00000000000000001000100000000001", format!("{and_bits:032b}"));
     }
 
     #[test]
@@ -2163,17 +2389,149 @@ mod cards__five_tests {
         assert!(!Five::try_from("7♥ 4D 3C 2H AS").unwrap().is_wheel());
     }
 
+    #[rstest]
+    #[case("A♠ K♠ Q♠ J♠ T♠", Some(CardSuit::SPADES))]
+    #[case("9♥ 7♥ 5♥ 3♥ 2♥", Some(CardSuit::HEARTS))]
+    #[case("K♦ 8♦ 6♦ 4♦ 3♦", Some(CardSuit::DIAMONDS))]
+    #[case("5♣ 4♣ 3♣ 2♣ A♣", Some(CardSuit::CLUBS))]
+    #[case("A♠ K♠ Q♠ J♠ T♥", None)]
+    fn flush_suit(#[case] index: &'static str, #[case] expected: Option<CardSuit>) {
+        assert_eq!(expected, Five::try_from(index).unwrap().flush_suit());
+    }
+
+    #[rstest]
+    #[case("A♠ K♠ Q♠ J♠ T♠", Some(CardRank::ACE))]
+    #[case("K♥ Q♦ J♣ T♠ 9♥", Some(CardRank::KING))]
+    #[case("6♠ 5♥ 4♦ 3♣ 2♠", Some(CardRank::SIX))]
+    #[case("5♥ 4♦ 3♣ 2♥ A♠", Some(CardRank::FIVE))]
+    #[case("A♠ K♠ Q♠ J♠ 9♠", None)]
+    #[case("A♠ A♥ K♦ Q♣ J♠", None)]
+    fn straight_high(#[case] index: &'static str, #[case] expected: Option<CardRank>) {
+        assert_eq!(expected, Five::try_from(index).unwrap().straight_high());
+    }
+
     #[test]
     fn or_rank_bits() {
         let or = Five::try_from("A♠ K♠ Q♠ J♠ T♠").unwrap().or_rank_bits();
 
-        assert_eq!("0001111100000000", format!("{:016b}", or));
-        assert_eq!("00000000000000000001111100000000", format!("{:032b}", or));
+        assert_eq!("0001111100000000", format!("{or:016b}"));
+        assert_eq!("00000000000000000001111100000000", format!("{or:032b}"));
         assert_eq!(8, or.trailing_zeros());
         assert_eq!(19, or.leading_zeros());
         assert_eq!(or, 7936);
     }
 
+    #[rstest]
+    #[case("9♠ 7♥ 6♦ 5♣ 3♣", 0b0_0000_0100_0100)]
+    #[case("9♠ 8♥ 7♦ 6♣ 2♣", 0b0_0001_0000_1000)]
+    #[case("A♠ K♥ Q♦ J♣ 2♣", 0b0_0001_0000_0000)]
+    #[case("A♠ 2♥ 3♦ 4♣ 9♣", 0b0_0000_0000_1000)]
+    #[case("A♠ 2♥ 3♦ 4♣ 6♣", 0b0_0000_0000_1000)]
+    #[case("A♠ K♥ Q♦ J♣ T♣", 0b0_0000_0000_0000)]
+    #[case("T♠ 9♥ 8♦ 7♣ 6♣", 0b0_0010_0000_0000)]
+    #[case("A♠ A♥ Q♦ Q♣ 2♣", 0b0_0000_0000_0000)]
+    fn straight_outs(#[case] index: &'static str, #[case] expected: u32) {
+        assert_eq!(expected, Five::try_from(index).unwrap().straight_outs());
+    }
+
+    #[rstest]
+    #[case("9♠ 7♥ 6♦ 5♣ 3♣", StraightDraw::DoubleGutshot)]
+    #[case("J♠ 9♥ 8♦ 7♣ 5♣", StraightDraw::DoubleGutshot)]
+    #[case("9♠ 8♥ 7♦ 6♣ 2♣", StraightDraw::OpenEnded)]
+    #[case("A♠ 2♥ 3♦ 4♣ 9♣", StraightDraw::Gutshot)]
+    #[case("A♠ A♥ Q♦ Q♣ 2♣", StraightDraw::None)]
+    fn straight_draw_type(#[case] index: &'static str, #[case] expected: StraightDraw) {
+        assert_eq!(expected, Five::try_from(index).unwrap().straight_draw_type());
+    }
+
+    #[test]
+    #[cfg(feature = "ansi")]
+    fn to_pretty() {
+        let five = Five::try_from("A♠ K♥ Q♦ J♣ T♠").unwrap();
+
+        assert_eq!("A♠ \x1b[31mK♥\x1b[0m \x1b[31mQ♦\x1b[0m J♣ T♠", five.to_pretty());
+    }
+
+    #[test]
+    fn index() {
+        let five = Five::try_from("A♠ K♣ Q♦ J♥ T♠").unwrap();
+
+        assert_eq!(CardNumber::ACE_SPADES, five[0]);
+        assert_eq!(CardNumber::QUEEN_DIAMONDS, five[2]);
+        assert_eq!(CardNumber::TEN_SPADES, five[Five::LEN - 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn index__out_of_bounds() {
+        let _ = Five::default()[Five::LEN];
+    }
+
+    #[test]
+    fn into_iter() {
+        let five = Five::try_from("A♠ K♣ Q♦ J♥ T♠").unwrap();
+        let mut by_ref = Vec::new();
+        let mut by_value = Vec::new();
+
+        for card in &five {
+            by_ref.push(*card);
+        }
+        for card in five {
+            by_value.push(card);
+        }
+
+        assert_eq!(five.to_arr().to_vec(), by_ref);
+        assert_eq!(by_ref, by_value);
+    }
+
+    #[test]
+    fn len() {
+        assert_eq!(5, Five::LEN);
+        assert_eq!(5, Five::default().len());
+    }
+
+    #[test]
+    fn is_undealt() {
+        let mut five = Five::EMPTY;
+        assert_eq!(Five::default(), five);
+        assert!(five.is_undealt() && !five.is_partial());
+
+        five.set_first(CardNumber::ACE_SPADES);
+        five.set_second(CardNumber::KING_SPADES);
+        assert!(!five.is_undealt() && five.is_partial());
+        assert_eq!(HandError::Incomplete, five.validate().unwrap_err());
+
+        five.set_third(CardNumber::QUEEN_SPADES);
+        five.set_forth(CardNumber::JACK_SPADES);
+        five.set_fifth(CardNumber::TEN_SPADES);
+        assert!(!five.is_undealt() && !five.is_partial());
+        assert_eq!(1, five.try_hand_rank().unwrap().value);
+    }
+
+    #[rstest]
+    #[case("A♠ K♥ Q♦ J♣ 9♠", false, false, false, false, false)]
+    #[case("A♠ A♥ Q♦ J♣ 9♠", true, false, false, false, true)]
+    #[case("A♠ A♥ Q♦ Q♣ 9♠", true, true, false, false, true)]
+    #[case("A♠ A♥ A♦ J♣ 9♠", true, false, true, false, true)]
+    #[case("A♠ A♥ A♦ 9♣ 9♠", true, true, true, false, true)]
+    #[case("A♠ A♥ A♦ A♣ 9♠", true, false, false, true, false)]
+    fn board(
+        #[case] index: &'static str,
+        #[case] paired: bool,
+        #[case] double_paired: bool,
+        #[case] trips: bool,
+        #[case] quads: bool,
+        #[case] boat_possible: bool,
+    ) {
+        let board = Five::try_from(index).unwrap();
+
+        assert_eq!(paired, board.is_paired_board());
+        assert_eq!(double_paired, board.is_double_paired());
+        assert_eq!(trips, board.trips_on_board());
+        assert_eq!(quads, board.quads_on_board());
+        assert_eq!(boat_possible, board.boat_possible());
+    }
+
     #[test]
     fn sort() {
         let five = Five::try_from("KC QD A♠ 9h T♠").unwrap().sort();
@@ -2183,6 +2541,46 @@ mod cards__five_tests {
         assert_eq!(five, expected);
     }
 
+    #[test]
+    fn flag_multiples() {
+        let five = Five::try_from("3♠ K♣ 3♥ K♠ 3♦").unwrap().flag_multiples();
+
+        assert_eq!(CardNumber::TREY_SPADES.flag_as_trips(), five.first());
+        assert_eq!(CardNumber::KING_CLUBS.flag_as_pair(), five.second());
+        assert_eq!(
+            Five::try_from("3♠ K♣ 3♥ K♠ 3♦").unwrap(),
+            five.flag_multiples().strip_multiples()
+        );
+    }
+
+    #[test]
+    fn flag_multiples__quads_and_blank() {
+        let five = Five::from([
+            CardNumber::NINE_SPADES,
+            CardNumber::NINE_HEARTS.flag_as_trips(),
+            CardNumber::NINE_DIAMONDS,
+            CardNumber::NINE_CLUBS,
+            CardNumber::BLANK,
+        ])
+        .flag_multiples();
+
+        assert_eq!(CardNumber::NINE_HEARTS.flag_as_quads(), five.second());
+        assert_eq!(CardNumber::BLANK, five.fifth());
+    }
+
+    #[rstest]
+    #[case("K♣ 3♠ 3♥ K♠ 3♦", "3♠ 3♥ 3♦ K♠ K♣")]
+    #[case("A♠ 5♦ 9♠ 5♣ 9♥", "9♠ 9♥ 5♦ 5♣ A♠")]
+    #[case("A♠ 2♦ K♠ 2♣ Q♥", "2♦ 2♣ A♠ K♠ Q♥")]
+    #[case("4♣ A♠ 4♠ 4♦ 4♥", "4♠ 4♥ 4♦ 4♣ A♠")]
+    #[case("KC QD A♠ 9h T♠", "A♠ KC QD T♠ 9h")]
+    fn sort_by_multiples(#[case] index: &'static str, #[case] expected: &'static str) {
+        let five = Five::try_from(index).unwrap().sort_by_multiples();
+
+        assert_eq!(Five::try_from(expected).unwrap(), five);
+        assert_eq!(Five::try_from(index).unwrap().hand_rank(), five.hand_rank());
+    }
+
     #[test]
     fn default() {
         let five = Five::default();
@@ -2264,6 +2662,142 @@ mod cards__five_tests {
         assert!(!third.are_unique());
     }
 
+    #[test]
+    fn rank_bits() {
+        for index in ["A♠ K♠ Q♠ J♠ T♠", "A♠ A♥ K♦ K♣ 2♠", "5♥ 4♦ 3♣ 2♥ A♠"] {
+            let five = Five::try_from(index).unwrap();
+
+            assert_eq!(five.multiply_primes() as u64, five.prime_product());
+            assert_eq!(five.or_rank_bits(), five.rank_mask());
+        }
+    }
+
+    #[test]
+    fn try_hand_rank() {
+        let hand = Five::try_from("A♠ A♥ K♦ Q♣ J♠").unwrap();
+
+        assert_eq!(hand.hand_rank(), hand.try_hand_rank().unwrap());
+        assert_eq!(HandError::BlankCard, Five::default().try_hand_rank().unwrap_err());
+        assert_eq!(
+            HandError::Incomplete,
+            Five::from([CardNumber::ACE_SPADES, 0, 0, 0, 0])
+                .try_hand_rank()
+                .unwrap_err()
+        );
+        assert_eq!(
+            HandError::InvalidCard,
+            Five::from([
+                CardNumber::ACE_SPADES | CardNumber::KING_HEARTS,
+                hand[1],
+                hand[2],
+                hand[3],
+                hand[4]
+            ])
+            .try_hand_rank()
+            .unwrap_err()
+        );
+        assert_eq!(
+            HandError::DuplicateCard,
+            Five::from([hand[0], hand[0], hand[2], hand[3], hand[4]])
+                .try_hand_rank()
+                .unwrap_err()
+        );
+        assert_eq!(
+            HandError::DuplicateCard,
+            Five::from([hand[0], hand[0].flag_as_pair(), hand[2], hand[3], hand[4]])
+                .try_hand_rank()
+                .unwrap_err()
+        );
+    }
+
+    #[rstest]
+    #[case("A♠ A♥ K♦ Q♣ J♠")]
+    #[case("3♠ K♣ 3♥ K♠ 3♦")]
+    #[case("9♠ 9♥ 9♦ 9♣ 2♠")]
+    #[case("A♠ K♠ Q♠ J♠ T♠")]
+    #[case("5♥ 4♦ 3♣ 2♥ A♠")]
+    fn hand_rank__flagged(#[case] index: &'static str) {
+        let five = Five::try_from(index).unwrap();
+        let flagged = five.flag_multiples();
+
+        assert!(flagged.is_valid());
+        assert_eq!(five.id(), flagged.id());
+        assert_eq!(five.hand_rank(), flagged.hand_rank());
+        assert_eq!(five.hand_rank(), flagged.try_hand_rank().unwrap());
+        assert_eq!(five.hand_rank_validated(), flagged.hand_rank_validated());
+    }
+
+    #[test]
+    fn cmp_by_rank() {
+        let aces = Five::try_from("A♠ A♥ K♦ Q♣ J♠").unwrap();
+        let kings = Five::try_from("K♠ K♥ A♦ Q♣ J♠").unwrap();
+
+        assert_eq!(core::cmp::Ordering::Greater, aces.cmp_by_rank(&kings));
+        assert_eq!(core::cmp::Ordering::Less, kings.cmp_by_rank(&aces));
+        assert_eq!(core::cmp::Ordering::Less, Five::default().cmp_by_rank(&kings));
+    }
+
+    #[test]
+    fn try_from__binary_card() {
+        let hand = Five::try_from("A♠ K♠ Q♠ J♠ T♠").unwrap();
+
+        let actual = Five::try_from(BinaryCard::from_five(hand));
+
+        assert_eq!(hand.sort(), actual.unwrap().sort());
+    }
+
+    #[test]
+    fn try_from__binary_card__not_enough() {
+        let bc = BinaryCard::from_index("A♠ K♠ Q♠ J♠ T♠") & !BinaryCard::ACE_SPADES;
+
+        assert_eq!(Five::try_from(bc).unwrap_err(), HandError::NotEnoughCards);
+        assert_eq!(
+            Five::try_from(BinaryCard::BLANK).unwrap_err(),
+            HandError::NotEnoughCards
+        );
+    }
+
+    #[test]
+    fn try_from__binary_card__too_many() {
+        let bc = BinaryCard::from_index("A♠ K♠ Q♠ J♠ T♠").fold_in(BinaryCard::DEUCE_CLUBS);
+
+        assert_eq!(Five::try_from(bc).unwrap_err(), HandError::TooManyCards);
+    }
+
+    #[test]
+    fn try_from__slice() {
+        let five = Five::try_from("A♠ K♠ Q♠ J♠ T♠").unwrap();
+
+        assert_eq!(five, Five::try_from(&five.to_arr()[..]).unwrap());
+    }
+
+    #[rstest]
+    #[case(&[CardNumber::ACE_SPADES, CardNumber::KING_SPADES], HandError::NotEnoughCards)]
+    #[case(&[CardNumber::ACE_SPADES; 6], HandError::TooManyCards)]
+    #[case(&[CardNumber::ACE_SPADES, CardNumber::KING_SPADES, CardNumber::QUEEN_SPADES, CardNumber::JACK_SPADES, CardNumber::BLANK], HandError::Incomplete)]
+    #[case(&[CardNumber::BLANK; 5], HandError::BlankCard)]
+    #[case(&[CardNumber::ACE_SPADES, CardNumber::KING_SPADES, CardNumber::QUEEN_SPADES, CardNumber::JACK_SPADES, 7], HandError::InvalidCard)]
+    #[case(&[CardNumber::ACE_SPADES, CardNumber::KING_SPADES, CardNumber::QUEEN_SPADES, CardNumber::JACK_SPADES, CardNumber::ACE_SPADES], HandError::DuplicateCard)]
+    fn try_from__slice__errors(#[case] cards: &[CKCNumber], #[case] expected: HandError) {
+        assert_eq!(expected, Five::try_from(cards).unwrap_err());
+    }
+
+    #[test]
+    fn try_from__binary_card__invalid_binary_format() {
+        let bc = (BinaryCard::from_index("A♠ K♠ Q♠ J♠ T♠") & !BinaryCard::ACE_SPADES)
+            .fold_in(0b1_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000);
+
+        assert_eq!(Five::try_from(bc).unwrap_err(), HandError::InvalidBinaryFormat);
+    }
+
+    #[test]
+    fn try_from__index__ten() {
+        assert_eq!(
+            Five::try_from("As Ks Qs Js 10s").unwrap(),
+            Five::try_from("A♠ K♠ Q♠ J♠ T♠").unwrap()
+        );
+    }
+
     #[test]
     fn try_from__index() {
         let five = Five::try_from("A♠ K♠ Q♠ J♠ T♠");
@@ -2307,6 +2841,15 @@ mod cards__five_tests {
         assert_eq!(
             Five::try_from("4♥ 4D 2S 2C A♥").unwrap().shift_suit(),
             Five::try_from("4D 4C 2H 2S AD").unwrap()
-        )
+        );
+    }
+
+    #[test]
+    fn try_from__index__too_many() {
+        assert_eq!(
+            Five::try_from("A♠ K♠ Q♠ J♠ T♠ 9♠").unwrap_err(),
+            HandError::TooManyCards
+        );
+        assert!(Five::from_index("A♠ K♠ Q♠ J♠ T♠ 9♠").is_none());
     }
 }
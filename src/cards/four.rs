@@ -1,9 +1,20 @@
-use crate::cards::HandValidator;
-use crate::{CKCNumber, HandError, PokerCard, Shifty};
+use crate::cards::five::Five;
+use crate::cards::seven::Seven;
+use crate::cards::three::Three;
+use crate::cards::{
+    best_straight, hand_array, try_from_binary_card, try_from_slice, HandRanker, HandValidator, RankBits, StraightDraw,
+    STRAIGHT_WINDOWS,
+};
+use crate::hand_rank::{HandRank, HandRankValue, NO_HAND_RANK_VALUE};
+use crate::math::Combinations;
+use crate::split::{Aces, BadugiRank};
+use crate::{CKCNumber, CardNumber, CardRank, CardSuit, HandError, PokerCard, Shifty};
 use core::slice::Iter;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Four([CKCNumber; 4]);
 
 impl Four {
@@ -42,6 +53,19 @@ impl Four {
         self.0[3] = card_number;
     }
 
+    /// A `Four` with no cards, the same as `Four::default()`.
+    pub const EMPTY: Four = Four([CardNumber::BLANK; 4]);
+
+    /// Builds a `Four` from the rank and suit of each card, the same as `Two::from_ranks_suits()`.
+    ///
+    /// # Errors
+    ///
+    /// Same as `HandValidator::validate()`, where a `CardRank::BLANK` or `CardSuit::BLANK` is a
+    /// blank card.
+    pub fn from_ranks_suits(cards: [(CardRank, CardSuit); 4]) -> Result<Self, HandError> {
+        crate::cards::from_ranks_suits(cards)
+    }
+
     #[must_use]
     pub fn to_arr(&self) -> [CKCNumber; 4] {
         self.0
@@ -49,6 +73,186 @@ impl Four {
 
     //endregion
 
+    #[must_use]
+    pub fn or_bits(&self) -> u32 {
+        self.first() | self.second() | self.third() | self.forth()
+    }
+
+    #[must_use]
+    pub fn or_rank_bits(&self) -> u32 {
+        (self.or_bits() & CardNumber::MULTIPLES_FILTER) >> CardNumber::RANK_FLAG_SHIFT
+    }
+
+    /// Classifies the straight draw made by the four cards, such as a player's hole cards plus
+    /// a flop in hold'em, or a hold'em flop and turn.
+    #[must_use]
+    pub fn straight_draw_type(&self) -> StraightDraw {
+        StraightDraw::from_rank_bits(self.or_rank_bits())
+    }
+
+    /// The rank of the best badugi in the four cards, with aces low.
+    #[must_use]
+    pub fn badugi_rank(&self) -> BadugiRank {
+        crate::split::badugi(*self, Aces::Low)
+    }
+
+    /// The best hand that the four cards make as Omaha hole cards on the `flop`, which has to
+    /// use exactly two of them with all three cards of the flop, along with the `Five` that
+    /// makes it. A pair of spades in a `Four` of spades doesn't make a flush on a rainbow flop.
+    #[must_use]
+    pub fn omaha_flop_value_and_hand(&self, flop: &Three) -> (HandRankValue, Five) {
+        let mut best_hrv = NO_HAND_RANK_VALUE;
+        let mut best_hand = Five::default();
+
+        for [a, b] in Four::OMAHA_PERMUTATIONS {
+            let hand = Five::new(
+                self.0[a as usize],
+                self.0[b as usize],
+                flop.first(),
+                flop.second(),
+                flop.third(),
+            );
+            let hrv = hand.hand_rank_value();
+            if (best_hrv == 0) || hrv != 0 && hrv < best_hrv {
+                best_hrv = hrv;
+                best_hand = hand;
+            }
+        }
+
+        (best_hrv, best_hand.sort())
+    }
+
+    /// The `HandRank` of `Four::omaha_flop_value_and_hand()`, for tracking the made hand of
+    /// Omaha hole cards on the flop.
+    ///
+    /// ```
+    /// use ckc_rs::cards::four::Four;
+    /// use ckc_rs::cards::three::Three;
+    /// use ckc_rs::hand_rank::HandRankName;
+    ///
+    /// let hole = Four::try_from("A♥ A♦ A♣ K♠").unwrap();
+    /// let flop = Three::try_from("K♥ K♦ 7♣").unwrap();
+    ///
+    /// // Only two of the aces play, so it's trip kings rather than aces full.
+    /// assert_eq!(HandRankName::ThreeOfAKind, hole.omaha_flop_rank(&flop).name);
+    /// ```
+    #[must_use]
+    pub fn omaha_flop_rank(&self, flop: &Three) -> HandRank {
+        let (hrv, _) = self.omaha_flop_value_and_hand(flop);
+        HandRank::from(hrv)
+    }
+
+    /// The same as `Four::omaha_flop_rank()`, after checking the hole cards and the flop.
+    ///
+    /// # Errors
+    ///
+    /// Same as `HandValidator::validate()` for the seven cards together, so a card in both the
+    /// hole cards and the flop is `HandError::DuplicateCard`.
+    pub fn try_omaha_flop_rank(&self, flop: &Three) -> Result<HandRank, HandError> {
+        Seven::from([
+            self.first(),
+            self.second(),
+            self.third(),
+            self.forth(),
+            flop.first(),
+            flop.second(),
+            flop.third(),
+        ])
+        .validate()?;
+        Ok(self.omaha_flop_rank(flop))
+    }
+
+    /// The straight and flush draws that the four cards have as Omaha hole cards on the `flop`,
+    /// under the same rule as `Four::omaha_flop_value_and_hand()`. Invalid cards have no draws.
+    ///
+    /// ```
+    /// use ckc_rs::cards::four::Four;
+    /// use ckc_rs::cards::three::Three;
+    ///
+    /// let hole = Four::try_from("K♥ Q♥ 9♣ 8♦").unwrap();
+    /// let draws = hole.omaha_draws(&Three::try_from("J♥ T♥ 2♠").unwrap());
+    ///
+    /// // Any ace, king, queen, nine, eight or seven makes a straight, and any heart a flush, but the
+    /// // A♥, 9♥, 8♥ and 7♥ make both.
+    /// assert_eq!(20, draws.straight_outs);
+    /// assert_eq!(9, draws.flush_outs);
+    /// assert!(draws.is_wrap());
+    /// assert_eq!(20 + 9 - 4, draws.outs);
+    /// ```
+    #[must_use]
+    pub fn omaha_draws(&self, flop: &Three) -> OmahaDraws {
+        let seen = Seven::from([
+            self.first(),
+            self.second(),
+            self.third(),
+            self.forth(),
+            flop.first(),
+            flop.second(),
+            flop.third(),
+        ]);
+        if !seen.is_valid() {
+            return OmahaDraws::default();
+        }
+
+        let hole = self.0.map(|card| card.strip_multiples_flags().get_rank_bit());
+        let board = flop.to_arr().map(|card| card.strip_multiples_flags().get_rank_bit());
+        let made = Four::omaha_best_straight(hole, &board);
+        let rank_counts = seen.rank_counts();
+        let straight_ranks = (0..13)
+            .map(|rank| 1 << rank)
+            .filter(|bit| {
+                let turn = [board[0], board[1], board[2], *bit];
+                Four::omaha_best_straight(hole, &turn) < made
+            })
+            .fold(0, |ranks, bit| ranks | bit);
+        let straight_outs = (0..13)
+            .filter(|rank| straight_ranks & (1 << rank) != 0)
+            .map(|rank| 4 - rank_counts[rank])
+            .sum();
+
+        let mut draws = OmahaDraws {
+            straight_ranks,
+            straight_outs,
+            outs: straight_outs,
+            ..OmahaDraws::default()
+        };
+        let draw = CardSuit::all()
+            .into_iter()
+            .zip(self.suit_counts())
+            .zip(flop.suit_counts())
+            .find(|((_, hole), on_flop)| *hole >= 2 && *on_flop == 2);
+        if let Some(((suit, in_hole), on_flop)) = draw {
+            let unseen = |card: &CKCNumber| !seen.iter().any(|seen| seen.strip_multiples_flags() == *card);
+            let cards = CardRank::ALL.map(|rank| CKCNumber::create(rank, suit));
+            let nut = cards
+                .iter()
+                .find(|card| !flop.iter().any(|on_flop| on_flop.strip_multiples_flags() == **card));
+            let both = cards
+                .iter()
+                .filter(|card| straight_ranks & card.get_rank_bit() != 0 && unseen(card))
+                .count();
+
+            draws.flush_suit = Some(suit);
+            draws.flush_outs = 13 - in_hole - on_flop;
+            draws.nut_flush_draw = nut.is_some_and(|nut| !unseen(nut));
+            draws.outs = straight_outs + draws.flush_outs - u8::try_from(both).unwrap_or_default();
+        }
+        draws
+    }
+
+    /// The index into `STRAIGHT_WINDOWS` of the best straight that two of the `hole` rank bits
+    /// make with three of the `board`, the same as `best_straight()`.
+    fn omaha_best_straight(hole: [u32; 4], board: &[u32]) -> usize {
+        let mut best = STRAIGHT_WINDOWS.len();
+        for [a, b] in Four::OMAHA_PERMUTATIONS {
+            for [c, d, e] in Combinations::<3>::new(board.len()) {
+                let bits = hole[a as usize] | hole[b as usize] | board[c] | board[d] | board[e];
+                best = best.min(best_straight(bits));
+            }
+        }
+        best
+    }
+
     fn from_index(index: &str) -> Option<[CKCNumber; 4]> {
         let mut esses = index.split_whitespace();
 
@@ -56,6 +260,9 @@ impl Four {
         let second = CKCNumber::from_index(esses.next()?);
         let third = CKCNumber::from_index(esses.next()?);
         let forth = CKCNumber::from_index(esses.next()?);
+        if esses.next().is_some() {
+            return None;
+        }
         let hand: [CKCNumber; 4] = [first, second, third, forth];
         Some(hand)
     }
@@ -67,14 +274,11 @@ impl From<[CKCNumber; 4]> for Four {
     }
 }
 
+hand_array!(Four, 4);
+
 impl HandValidator for Four {
     fn are_unique(&self) -> bool {
-        (self.first() != self.second())
-            && (self.first() != self.third())
-            && (self.first() != self.forth())
-            && (self.second() != self.third())
-            && (self.second() != self.forth())
-            && (self.third() != self.forth())
+        crate::cards::all_unique(&self.0)
     }
 
     fn first(&self) -> CKCNumber {
@@ -97,10 +301,15 @@ impl HandValidator for Four {
     }
 }
 
+impl RankBits for Four {}
+
 impl TryFrom<&'static str> for Four {
     type Error = HandError;
 
     fn try_from(index: &'static str) -> Result<Self, Self::Error> {
+        if crate::parse::card_count(index) > 4 {
+            return Err(HandError::TooManyCards);
+        }
         match Four::from_index(index) {
             None => Err(HandError::InvalidIndex),
             Some(four) => Ok(Four::from(four)),
@@ -108,6 +317,10 @@ impl TryFrom<&'static str> for Four {
     }
 }
 
+try_from_slice!(Four);
+
+try_from_binary_card!(Four);
+
 impl Shifty for Four {
     fn shift_suit(&self) -> Self {
         Four([
@@ -119,11 +332,50 @@ impl Shifty for Four {
     }
 }
 
+/// The draws that Omaha hole cards have on the flop, where a hand has to use exactly two of its
+/// hole cards. Outs are the cards that aren't in the hole cards or on the flop which would
+/// complete a draw on the turn, whether or not they also help someone else.
+///
+/// * `straight_ranks` - the rank bits, in the same layout as `Five::or_rank_bits()`, of every
+///   rank that would make a better straight than any already made.
+/// * `straight_outs` - the number of outs of those ranks. More than the eight of an open ended
+///   draw is a wrap, such as the 13, 17 and 20 out wraps.
+/// * `flush_suit` - the suit of a flush draw, which needs two hole cards of a suit that's on the
+///   flop twice.
+/// * `flush_outs` - the number of outs of that suit.
+/// * `nut_flush_draw` - true if the hole cards hold the highest card of `flush_suit` that isn't
+///   on the flop.
+/// * `outs` - the number of outs to either draw, counting a card that completes both only once.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct OmahaDraws {
+    pub straight_ranks: u32,
+    pub straight_outs: u8,
+    pub flush_suit: Option<CardSuit>,
+    pub flush_outs: u8,
+    pub nut_flush_draw: bool,
+    pub outs: u8,
+}
+
+impl OmahaDraws {
+    /// True for a straight draw with more outs than an open ended one.
+    #[must_use]
+    pub fn is_wrap(&self) -> bool {
+        self.straight_outs > 8
+    }
+
+    #[must_use]
+    pub fn is_flush_draw(&self) -> bool {
+        self.flush_suit.is_some()
+    }
+}
+
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod cards_four_tests {
     use super::*;
-    use crate::CardNumber;
+    use crate::hand_rank::HandRankName;
+    use crate::{BinaryCard, BC64};
+    use rstest::rstest;
 
     #[test]
     fn sort() {
@@ -134,6 +386,28 @@ mod cards_four_tests {
         assert_eq!(four, expected);
     }
 
+    #[test]
+    fn or_rank_bits() {
+        let four = Four::try_from("A♠ K♠ Q♠ J♠").unwrap();
+
+        assert_eq!(0b1_1110_0000_0000, four.or_rank_bits());
+    }
+
+    #[rstest]
+    #[case("8♠ 7♥ 6♦ 5♣", StraightDraw::OpenEnded)]
+    #[case("K♠ Q♥ J♦ T♣", StraightDraw::OpenEnded)]
+    #[case("9♠ 7♥ 6♦ 5♣", StraightDraw::Gutshot)]
+    #[case("A♠ K♥ Q♦ J♣", StraightDraw::Gutshot)]
+    #[case("A♠ 2♥ 3♦ 4♣", StraightDraw::Gutshot)]
+    #[case("A♠ 2♥ 3♦ 5♣", StraightDraw::Gutshot)]
+    #[case("J♠ 9♥ 8♦ 7♣", StraightDraw::Gutshot)]
+    #[case("J♠ 9♥ 8♦ 5♣", StraightDraw::None)]
+    #[case("8♠ 8♥ 6♦ 5♣", StraightDraw::None)]
+    #[case("A♠ A♥ K♦ K♣", StraightDraw::None)]
+    fn straight_draw_type(#[case] index: &'static str, #[case] expected: StraightDraw) {
+        assert_eq!(expected, Four::try_from(index).unwrap().straight_draw_type());
+    }
+
     #[test]
     fn default() {
         let four = Four::default();
@@ -147,6 +421,41 @@ mod cards_four_tests {
         assert!(!four.is_valid());
     }
 
+    #[test]
+    fn try_from__binary_card() {
+        let hand = Four::try_from("A♠ K♠ Q♠ J♠").unwrap();
+
+        let actual = Four::try_from(BinaryCard::from_four(hand));
+
+        assert_eq!(hand.sort(), actual.unwrap().sort());
+    }
+
+    #[test]
+    fn try_from__binary_card__not_enough() {
+        let bc = BinaryCard::from_index("A♠ K♠ Q♠ J♠") & !BinaryCard::ACE_SPADES;
+
+        assert_eq!(Four::try_from(bc).unwrap_err(), HandError::NotEnoughCards);
+        assert_eq!(
+            Four::try_from(BinaryCard::BLANK).unwrap_err(),
+            HandError::NotEnoughCards
+        );
+    }
+
+    #[test]
+    fn try_from__binary_card__too_many() {
+        let bc = BinaryCard::from_index("A♠ K♠ Q♠ J♠").fold_in(BinaryCard::DEUCE_CLUBS);
+
+        assert_eq!(Four::try_from(bc).unwrap_err(), HandError::TooManyCards);
+    }
+
+    #[test]
+    fn try_from__binary_card__invalid_binary_format() {
+        let bc = (BinaryCard::from_index("A♠ K♠ Q♠ J♠") & !BinaryCard::ACE_SPADES)
+            .fold_in(0b1_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000);
+
+        assert_eq!(Four::try_from(bc).unwrap_err(), HandError::InvalidBinaryFormat);
+    }
+
     #[test]
     fn try_from__index() {
         let four = Four::try_from("A♠ K♠ Q♠ J♠");
@@ -188,6 +497,134 @@ mod cards_four_tests {
         assert_eq!(
             Four::try_from("AH KH QH JH").unwrap().shift_suit(),
             Four::try_from("AD KD QD JD").unwrap()
-        )
+        );
+    }
+
+    #[test]
+    fn try_from__index__too_many() {
+        assert_eq!(Four::try_from("A♠ K♠ Q♠ J♠ T♠").unwrap_err(), HandError::TooManyCards);
+        assert!(Four::from_index("A♠ K♠ Q♠ J♠ T♠").is_none());
+    }
+
+    #[rstest]
+    #[case("A♥ A♦ A♣ K♠", "K♥ K♦ 7♣", HandRankName::ThreeOfAKind, "K♠ K♥ K♦ A♥ 7♣")]
+    #[case("A♠ K♠ 7♦ 2♣", "Q♠ J♠ T♠", HandRankName::StraightFlush, "A♠ K♠ Q♠ J♠ T♠")]
+    #[case("A♠ K♠ Q♠ J♠", "T♥ 9♦ 2♦", HandRankName::HighCard, "A♠ K♠ T♥ 9♦ 2♦")]
+    #[case("9♣ 8♣ 3♥ 3♦", "7♣ 6♦ 3♣", HandRankName::ThreeOfAKind, "3♥ 3♦ 3♣ 7♣ 6♦")]
+    fn omaha_flop_value_and_hand(
+        #[case] hole: &'static str,
+        #[case] flop: &'static str,
+        #[case] name: HandRankName,
+        #[case] best: &'static str,
+    ) {
+        let hole = Four::try_from(hole).unwrap();
+        let flop = Three::try_from(flop).unwrap();
+
+        let (hrv, hand) = hole.omaha_flop_value_and_hand(&flop);
+
+        assert_eq!(name, HandRank::from(hrv).name);
+        assert_eq!(Five::try_from(best).unwrap().sort(), hand);
+        assert_eq!(HandRank::from(hrv), hole.omaha_flop_rank(&flop));
+    }
+
+    #[test]
+    fn try_omaha_flop_rank() {
+        let hole = Four::try_from("A♥ A♦ A♣ K♠").unwrap();
+
+        assert_eq!(
+            HandRankName::ThreeOfAKind,
+            hole.try_omaha_flop_rank(&Three::try_from("K♥ K♦ 7♣").unwrap())
+                .unwrap()
+                .name
+        );
+        assert_eq!(
+            HandError::DuplicateCard,
+            hole.try_omaha_flop_rank(&Three::try_from("K♠ K♦ 7♣").unwrap())
+                .unwrap_err()
+        );
+        assert_eq!(
+            HandError::Incomplete,
+            hole.try_omaha_flop_rank(&Three::default()).unwrap_err()
+        );
+    }
+
+    #[rstest]
+    #[case("Q♣ J♦ 9♥ 8♠", "T♠ 7♦ 2♥", 13)]
+    #[case("J♣ 9♦ 8♥ 7♠", "T♠ 8♦ 2♥", 17)]
+    #[case("K♣ Q♦ 9♥ 8♠", "J♠ T♦ 2♥", 20)]
+    #[case("J♣ 9♦ 8♥ 7♠", "T♠ 6♦ 2♥", 9)]
+    #[case("9♣ 8♦ 7♥ 6♠", "T♠ 5♦ 2♥", 0)]
+    #[case("J♣ T♦ 9♥ 8♠", "K♠ 7♦ 2♥", 0)]
+    fn omaha_draws__wraps(#[case] hole: &'static str, #[case] flop: &'static str, #[case] outs: u8) {
+        let draws = Four::try_from(hole)
+            .unwrap()
+            .omaha_draws(&Three::try_from(flop).unwrap());
+
+        assert_eq!(outs, draws.straight_outs);
+        assert_eq!(outs, draws.outs);
+        assert_eq!(outs > 8, draws.is_wrap());
+        assert!(!draws.is_flush_draw());
+    }
+
+    #[test]
+    fn omaha_draws__straight_ranks() {
+        let draws = Four::try_from("Q♣ J♦ 9♥ 8♠")
+            .unwrap()
+            .omaha_draws(&Three::try_from("T♠ 7♦ 2♥").unwrap());
+
+        // Sixes, eights, nines and jacks.
+        assert_eq!(0b0_0010_1101_0000, draws.straight_ranks);
+    }
+
+    #[rstest]
+    #[case("A♥ 5♥ K♣ Q♦", "J♥ 7♥ 2♠", true)]
+    #[case("K♥ 5♥ Q♣ 9♦", "J♥ 7♥ 2♠", false)]
+    #[case("K♥ 5♥ Q♣ 9♦", "A♥ 7♥ 2♠", true)]
+    fn omaha_draws__flush(#[case] hole: &'static str, #[case] flop: &'static str, #[case] nuts: bool) {
+        let draws = Four::try_from(hole)
+            .unwrap()
+            .omaha_draws(&Three::try_from(flop).unwrap());
+
+        assert_eq!(Some(CardSuit::HEARTS), draws.flush_suit);
+        assert_eq!(9, draws.flush_outs);
+        assert_eq!(nuts, draws.nut_flush_draw);
+    }
+
+    #[rstest]
+    #[case("A♥ K♣ Q♦ 2♠", "J♥ 7♥ 2♥")]
+    #[case("A♥ K♣ Q♦ 2♠", "J♥ 7♥ 2♣")]
+    #[case("A♥ K♥ Q♥ 3♥", "J♣ 7♠ 2♦")]
+    fn omaha_draws__no_flush_draw(#[case] hole: &'static str, #[case] flop: &'static str) {
+        let draws = Four::try_from(hole)
+            .unwrap()
+            .omaha_draws(&Three::try_from(flop).unwrap());
+
+        assert!(!draws.is_flush_draw());
+        assert_eq!(0, draws.flush_outs);
+        assert!(!draws.nut_flush_draw);
+    }
+
+    #[test]
+    fn omaha_draws__combined() {
+        let draws = Four::try_from("K♥ Q♥ 9♣ 8♦")
+            .unwrap()
+            .omaha_draws(&Three::try_from("J♥ T♥ 2♠").unwrap());
+
+        // The A♥, 9♥, 8♥ and 7♥ make a straight and a flush, and the K♥ and Q♥ are in the hole.
+        assert_eq!(20, draws.straight_outs);
+        assert_eq!(9, draws.flush_outs);
+        assert_eq!(25, draws.outs);
+        assert!(!draws.nut_flush_draw);
+    }
+
+    #[test]
+    fn omaha_draws__invalid() {
+        let hole = Four::try_from("K♥ Q♥ 9♣ 8♦").unwrap();
+
+        assert_eq!(
+            OmahaDraws::default(),
+            hole.omaha_draws(&Three::try_from("K♥ T♥ 2♠").unwrap())
+        );
+        assert_eq!(OmahaDraws::default(), hole.omaha_draws(&Three::default()));
     }
 }
@@ -0,0 +1,199 @@
+use crate::cards::binary_card::BinaryCard;
+use crate::cards::five::Five;
+use crate::cards::four::Four;
+use crate::cards::seven::Seven;
+use crate::cards::six::Six;
+use crate::cards::three::Three;
+use crate::cards::two::Two;
+use crate::cards::{HandRanker, HandValidator};
+use crate::hand_rank::{HandRank, HandRankValue, NO_HAND_RANK_VALUE};
+use crate::{CKCNumber, HandError};
+use core::slice::Iter;
+
+/// A hand of two to seven cards, for code that works with hands whose size isn't known until
+/// runtime, such as ones stored as `BinaryCards`.
+///
+/// ```
+/// use ckc_rs::cards::binary_card::{BinaryCard, BC64};
+/// use ckc_rs::cards::hand::Hand;
+///
+/// let hand = Hand::try_from(BinaryCard::from_index("A♠ K♠ Q♠ J♠ T♠ 2♣")).unwrap();
+///
+/// assert_eq!(6, hand.len());
+/// assert_eq!(1, hand.hand_rank_value());
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Hand {
+    Two(Two),
+    Three(Three),
+    Four(Four),
+    Five(Five),
+    Six(Six),
+    Seven(Seven),
+}
+
+impl Hand {
+    #[must_use]
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        match self {
+            Hand::Two(_) => Two::LEN,
+            Hand::Three(_) => Three::LEN,
+            Hand::Four(_) => Four::LEN,
+            Hand::Five(_) => Five::LEN,
+            Hand::Six(_) => Six::LEN,
+            Hand::Seven(_) => Seven::LEN,
+        }
+    }
+
+    /// Returns the value of the best five cards in the hand, or `NO_HAND_RANK_VALUE` if it has
+    /// fewer than five cards.
+    #[must_use]
+    pub fn hand_rank_value(&self) -> HandRankValue {
+        match self {
+            Hand::Two(_) | Hand::Three(_) | Hand::Four(_) => NO_HAND_RANK_VALUE,
+            Hand::Five(five) => five.hand_rank_value(),
+            Hand::Six(six) => six.hand_rank_value(),
+            Hand::Seven(seven) => seven.hand_rank_value(),
+        }
+    }
+
+    #[must_use]
+    pub fn hand_rank(&self) -> HandRank {
+        HandRank::from(self.hand_rank_value())
+    }
+
+    /// Returns the `HandRank` of the best five cards in the hand.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HandError::NotEnoughCards` if the hand has fewer than five cards, and otherwise
+    /// the same errors as `HandRanker::try_hand_rank()`.
+    pub fn try_hand_rank(&self) -> Result<HandRank, HandError> {
+        match self {
+            Hand::Two(_) | Hand::Three(_) | Hand::Four(_) => Err(HandError::NotEnoughCards),
+            Hand::Five(five) => five.try_hand_rank(),
+            Hand::Six(six) => six.try_hand_rank(),
+            Hand::Seven(seven) => seven.try_hand_rank(),
+        }
+    }
+}
+
+impl HandValidator for Hand {
+    fn are_unique(&self) -> bool {
+        match self {
+            Hand::Two(two) => two.are_unique(),
+            Hand::Three(three) => three.are_unique(),
+            Hand::Four(four) => four.are_unique(),
+            Hand::Five(five) => five.are_unique(),
+            Hand::Six(six) => six.are_unique(),
+            Hand::Seven(seven) => seven.are_unique(),
+        }
+    }
+
+    fn first(&self) -> CKCNumber {
+        match self {
+            Hand::Two(two) => two.first(),
+            Hand::Three(three) => three.first(),
+            Hand::Four(four) => four.first(),
+            Hand::Five(five) => five.first(),
+            Hand::Six(six) => six.first(),
+            Hand::Seven(seven) => seven.first(),
+        }
+    }
+
+    fn sort(&self) -> Self {
+        let mut hand = *self;
+        hand.sort_in_place();
+        hand
+    }
+
+    fn sort_in_place(&mut self) {
+        match self {
+            Hand::Two(two) => two.sort_in_place(),
+            Hand::Three(three) => three.sort_in_place(),
+            Hand::Four(four) => four.sort_in_place(),
+            Hand::Five(five) => five.sort_in_place(),
+            Hand::Six(six) => six.sort_in_place(),
+            Hand::Seven(seven) => seven.sort_in_place(),
+        }
+    }
+
+    fn iter(&self) -> Iter<'_, CKCNumber> {
+        match self {
+            Hand::Two(two) => two.iter(),
+            Hand::Three(three) => three.iter(),
+            Hand::Four(four) => four.iter(),
+            Hand::Five(five) => five.iter(),
+            Hand::Six(six) => six.iter(),
+            Hand::Seven(seven) => seven.iter(),
+        }
+    }
+}
+
+/// Picks the variant by the number of cards.
+impl TryFrom<BinaryCard> for Hand {
+    type Error = HandError;
+
+    fn try_from(binary_card: BinaryCard) -> Result<Self, Self::Error> {
+        match crate::cards::number_of_cards(binary_card)? {
+            0..=1 => Err(HandError::NotEnoughCards),
+            2 => Ok(Hand::Two(Two::try_from(binary_card)?)),
+            3 => Ok(Hand::Three(Three::try_from(binary_card)?)),
+            4 => Ok(Hand::Four(Four::try_from(binary_card)?)),
+            5 => Ok(Hand::Five(Five::try_from(binary_card)?)),
+            6 => Ok(Hand::Six(Six::try_from(binary_card)?)),
+            7 => Ok(Hand::Seven(Seven::try_from(binary_card)?)),
+            _ => Err(HandError::TooManyCards),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod cards_hand_tests {
+    use super::*;
+    use crate::cards::binary_card::BC64;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("A♠ K♠", 2, NO_HAND_RANK_VALUE)]
+    #[case("A♠ K♠ Q♠", 3, NO_HAND_RANK_VALUE)]
+    #[case("A♠ K♠ Q♠ J♠", 4, NO_HAND_RANK_VALUE)]
+    #[case("A♠ K♠ Q♠ J♠ T♠", 5, 1)]
+    #[case("A♠ K♠ Q♠ J♠ 2♣ 2♦", 6, 5966)]
+    #[case("A♠ K♠ Q♠ J♠ T♠ 2♣ 2♦", 7, 1)]
+    fn try_from__binary_card(#[case] index: &str, #[case] len: usize, #[case] hand_rank_value: HandRankValue) {
+        let hand = Hand::try_from(BinaryCard::from_index(index)).unwrap();
+
+        assert_eq!(len, hand.len());
+        assert_eq!(hand_rank_value, hand.hand_rank_value());
+        assert_eq!(BinaryCard::from_index(index), hand.id());
+    }
+
+    #[rstest]
+    #[case(BinaryCard::BLANK, HandError::NotEnoughCards)]
+    #[case(BinaryCard::ACE_SPADES, HandError::NotEnoughCards)]
+    #[case(BinaryCard::ACES | BinaryCard::KINGS, HandError::TooManyCards)]
+    #[case(BinaryCard::ACES | 1 << 60, HandError::InvalidBinaryFormat)]
+    fn try_from__binary_card__errors(#[case] binary_card: BinaryCard, #[case] expected: HandError) {
+        assert_eq!(expected, Hand::try_from(binary_card).unwrap_err());
+    }
+
+    #[test]
+    fn try_hand_rank() {
+        let three = Hand::try_from(BinaryCard::from_index("A♠ K♠ Q♠")).unwrap();
+        let seven = Hand::try_from(BinaryCard::from_index("A♠ K♠ Q♠ J♠ T♠ 2♣ 2♦")).unwrap();
+
+        assert_eq!(HandError::NotEnoughCards, three.try_hand_rank().unwrap_err());
+        assert_eq!(seven.hand_rank(), seven.try_hand_rank().unwrap());
+    }
+
+    #[test]
+    fn sort() {
+        let hand = Hand::Three(Three::try_from("2♣ A♠ 9♥").unwrap());
+
+        assert_eq!(Hand::Three(Three::try_from("A♠ 9♥ 2♣").unwrap()), hand.sort());
+        assert!(hand.is_valid());
+    }
+}
@@ -1,15 +1,227 @@
+use crate::cards::binary_card::{BinaryCard, BC64};
 use crate::cards::five::Five;
-use crate::{CKCNumber, CardNumber};
+use crate::{CKCNumber, CardNumber, CardRank, CardSuit, HandError, PokerCard};
 use core::slice::Iter;
 
 pub mod binary_card;
+pub mod builder;
 pub mod five;
 pub mod four;
+pub mod hand;
+pub mod multi_deck;
+pub mod ranked;
 pub mod seven;
 pub mod six;
+pub mod sorted;
 pub mod three;
 pub mod two;
 
+/// A `BuildHasher` for maps keyed by `HandValidator::id()`, which skips hashing altogether.
+/// The ids cluster in a few bits, so keep it to lookups in small tables, and use the default
+/// hasher for large ones.
+#[cfg(feature = "nohash")]
+pub type BuildHandIdHasher = nohash_hasher::BuildNoHashHasher<BinaryCard>;
+
+/// The ten five card rank windows that make a straight, from ace high down to the wheel, in the
+/// same layout as `Five::or_rank_bits()`.
+pub const STRAIGHT_WINDOWS: [u32; 10] = [
+    0b1_1111_0000_0000,
+    0b0_1111_1000_0000,
+    0b0_0111_1100_0000,
+    0b0_0011_1110_0000,
+    0b0_0001_1111_0000,
+    0b0_0000_1111_1000,
+    0b0_0000_0111_1100,
+    0b0_0000_0011_1110,
+    0b0_0000_0001_1111,
+    Five::WHEEL_OR_BITS,
+];
+
+/// The shape of a straight draw, determined by how many ranks would complete it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum StraightDraw {
+    /// Two ranks complete the straight at either end of four consecutive ranks (8♠ 7♥ 6♦ 5♣).
+    OpenEnded,
+    /// Two ranks complete the straight, but the cards aren't consecutive (9♠ 7♥ 6♦ 5♣ 3♣).
+    DoubleGutshot,
+    /// A single rank completes the straight (9♠ 8♥ 6♦ 5♣ or A♠ K♥ Q♦ J♣).
+    Gutshot,
+    None,
+}
+
+impl StraightDraw {
+    /// Classifies the straight draw for a set of rank bits in the same layout as
+    /// `Five::or_rank_bits()`.
+    #[must_use]
+    pub fn from_rank_bits(rank_bits: u32) -> StraightDraw {
+        match straight_outs(rank_bits).count_ones() {
+            0 => StraightDraw::None,
+            1 => StraightDraw::Gutshot,
+            _ if (0..9).any(|i| rank_bits & (0b1111 << i) == 0b1111 << i) => StraightDraw::OpenEnded,
+            _ => StraightDraw::DoubleGutshot,
+        }
+    }
+}
+
+/// Returns the rank bits of every rank that would make a better straight than any already made if
+/// it were added to the passed in rank bits, in the same layout as `Five::or_rank_bits()`. Aces
+/// play both high and low.
+#[must_use]
+pub fn straight_outs(rank_bits: u32) -> u32 {
+    let best = best_straight(rank_bits);
+    (0..13)
+        .map(|rank| 1 << rank)
+        .filter(|bit| rank_bits & bit == 0 && best_straight(rank_bits | bit) < best)
+        .fold(0, |outs, bit| outs | bit)
+}
+
+/// Copies the cards out of a slice that should hold exactly `N` of them, for the
+/// `TryFrom<&[CKCNumber]>` impls of each hand.
+pub(crate) fn exactly<const N: usize>(cards: &[CKCNumber]) -> Result<[CKCNumber; N], HandError> {
+    match cards.len().cmp(&N) {
+        core::cmp::Ordering::Less => Err(HandError::NotEnoughCards),
+        core::cmp::Ordering::Greater => Err(HandError::TooManyCards),
+        core::cmp::Ordering::Equal => Ok(core::array::from_fn(|i| cards[i])),
+    }
+}
+
+/// Builds a hand from the rank and suit of each card, for the `from_ranks_suits()` of each hand.
+pub(crate) fn from_ranks_suits<H, const N: usize>(cards: [(CardRank, CardSuit); N]) -> Result<H, HandError>
+where
+    H: HandValidator + From<[CKCNumber; N]>,
+{
+    let hand = H::from(cards.map(|(rank, suit)| CKCNumber::create(rank, suit)));
+    hand.validate()?;
+    Ok(hand)
+}
+
+/// Counts the cards in a `BinaryCard`, returning `HandError::InvalidBinaryFormat` if any of the
+/// `BinaryCard::OVERFLOW` bits are set.
+pub(crate) fn number_of_cards(binary_card: BinaryCard) -> Result<u32, HandError> {
+    if binary_card & BinaryCard::OVERFLOW == BinaryCard::BLANK {
+        Ok(binary_card.number_of_cards())
+    } else {
+        Err(HandError::InvalidBinaryFormat)
+    }
+}
+
+/// Peels the cards out of a `BinaryCard` that should hold exactly `N` of them, for the
+/// `TryFrom<BinaryCard>` impls of each hand.
+pub(crate) fn peel_exactly<const N: usize>(binary_card: BinaryCard) -> Result<[CKCNumber; N], HandError> {
+    match (number_of_cards(binary_card)? as usize).cmp(&N) {
+        core::cmp::Ordering::Less => Err(HandError::NotEnoughCards),
+        core::cmp::Ordering::Greater => Err(HandError::TooManyCards),
+        core::cmp::Ordering::Equal => {
+            let mut bc = binary_card;
+            Ok(core::array::from_fn(|_| CKCNumber::from_binary_card(bc.peel())))
+        },
+    }
+}
+
+/// Adds `LEN`, `len()`, `Index<usize>` and both `IntoIterator`s to a hand type that wraps an
+/// array of `$len` cards.
+macro_rules! hand_array {
+    ($hand:ident, $len:literal) => {
+        impl $hand {
+            #[doc = concat!("The number of cards in a `", stringify!($hand), "`.")]
+            pub const LEN: usize = $len;
+
+            /// Always returns `LEN`, since a hand holds all of its cards even when some are blank.
+            #[must_use]
+            #[allow(clippy::len_without_is_empty, clippy::unused_self)]
+            pub const fn len(&self) -> usize {
+                Self::LEN
+            }
+        }
+
+        impl core::ops::Index<usize> for $hand {
+            type Output = $crate::CKCNumber;
+
+            fn index(&self, index: usize) -> &Self::Output {
+                &self.0[index]
+            }
+        }
+
+        impl IntoIterator for $hand {
+            type Item = $crate::CKCNumber;
+            type IntoIter = core::array::IntoIter<$crate::CKCNumber, $len>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.0.into_iter()
+            }
+        }
+
+        // `iter()` comes from `HandValidator`.
+        #[allow(clippy::into_iter_without_iter)]
+        impl<'a> IntoIterator for &'a $hand {
+            type Item = &'a $crate::CKCNumber;
+            type IntoIter = core::slice::Iter<'a, $crate::CKCNumber>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.0.iter()
+            }
+        }
+    };
+}
+pub(crate) use hand_array;
+
+/// Implements `TryFrom<BinaryCard>` for a hand type with a `LEN` and a `From` its array of
+/// cards, returning `HandError::InvalidBinaryFormat` if the cards don't make a valid hand.
+macro_rules! try_from_binary_card {
+    ($hand:ident) => {
+        impl TryFrom<$crate::cards::binary_card::BinaryCard> for $hand {
+            type Error = $crate::HandError;
+
+            fn try_from(binary_card: $crate::cards::binary_card::BinaryCard) -> Result<Self, Self::Error> {
+                let hand = $hand::from($crate::cards::peel_exactly::<{ $hand::LEN }>(binary_card)?);
+                if $crate::cards::HandValidator::is_valid(&hand) {
+                    Ok(hand)
+                } else {
+                    Err($crate::HandError::InvalidBinaryFormat)
+                }
+            }
+        }
+    };
+}
+pub(crate) use try_from_binary_card;
+
+/// Implements `TryFrom<&[CKCNumber]>` for a hand type with a `LEN` and a `From` its array of
+/// cards.
+macro_rules! try_from_slice {
+    ($hand:ident) => {
+        /// Unlike `From<[CKCNumber; LEN]>`, checks that there are exactly `LEN` cards and that the
+        /// hand is valid, so `try_from(&cards[..])` is the safe way to build one from outside data.
+        impl TryFrom<&[$crate::CKCNumber]> for $hand {
+            type Error = $crate::HandError;
+
+            fn try_from(cards: &[$crate::CKCNumber]) -> Result<Self, Self::Error> {
+                let hand = $hand::from($crate::cards::exactly::<{ $hand::LEN }>(cards)?);
+                $crate::cards::HandValidator::validate(&hand)?;
+                Ok(hand)
+            }
+        }
+    };
+}
+pub(crate) use try_from_slice;
+
+/// True if no card appears twice, for the `are_unique()` of each hand. A card flagged with
+/// `CardNumber::PAIR`, `TRIPS` or `QUADS` is the same card as it is without the flag.
+pub(crate) fn all_unique(cards: &[CKCNumber]) -> bool {
+    !(1..cards.len()).any(|i| {
+        let card = cards[i - 1].strip_multiples_flags();
+        cards[i..].iter().any(|other| other.strip_multiples_flags() == card)
+    })
+}
+
+/// Returns the index into `STRAIGHT_WINDOWS` of the best straight covered by the rank bits, or
+/// the number of windows if there isn't one.
+fn best_straight(rank_bits: u32) -> usize {
+    STRAIGHT_WINDOWS
+        .iter()
+        .position(|window| rank_bits & window == *window)
+        .unwrap_or(STRAIGHT_WINDOWS.len())
+}
+
 pub trait HandRanker {
     fn hand_rank(&self) -> crate::hand_rank::HandRank {
         crate::hand_rank::HandRank::from(self.hand_rank_value())
@@ -27,6 +239,26 @@ pub trait HandRanker {
     fn hand_rank_value_and_hand(&self) -> (crate::hand_rank::HandRankValue, Five);
 
     fn hand_rank_value_validated(&self) -> crate::hand_rank::HandRankValue;
+
+    /// Returns the `HandRank`, or why the hand can't be ranked, where `hand_rank_validated()`
+    /// would return the same invalid `HandRank` for every problem.
+    ///
+    /// # Errors
+    ///
+    /// Same as `HandValidator::validate()`, so a hand that's only partly dealt is
+    /// `HandError::Incomplete` rather than the zero `HandRank` of `hand_rank_validated()`.
+    fn try_hand_rank(&self) -> Result<crate::hand_rank::HandRank, crate::HandError>
+    where
+        Self: HandValidator,
+    {
+        self.validate()?;
+        Ok(self.hand_rank())
+    }
+
+    /// Orders hands by strength, so the stronger hand is `Greater`. Invalid hands sort lowest.
+    fn cmp_by_rank(&self, other: &Self) -> core::cmp::Ordering {
+        self.hand_rank_validated().cmp(&other.hand_rank_validated())
+    }
 }
 
 pub trait HandValidator {
@@ -43,19 +275,239 @@ pub trait HandValidator {
         self.iter().any(|c| c == &CardNumber::BLANK)
     }
 
+    /// True if every card is blank, as in a hand that hasn't been dealt yet, such as
+    /// `Five::EMPTY`.
+    fn is_undealt(&self) -> bool {
+        self.iter().all(|c| c == &CardNumber::BLANK)
+    }
+
+    /// True if some of the cards are blank but not all of them, as in a hand that's still being
+    /// dealt. A partial hand can't be ranked, and `validate()` returns `HandError::Incomplete`
+    /// for it.
+    fn is_partial(&self) -> bool {
+        self.contain_blank() && !self.is_undealt()
+    }
+
     /// A corrupt hand is one where any of the values in the array doesn't correspond to any
-    /// recognized `CardNumber` or is blank.
+    /// recognized `CardNumber` or is blank. The multiples flags that `Five::flag_multiples()`
+    /// sets for sorting are ignored, so a flagged hand can still be validated and ranked.
     fn is_corrupt(&self) -> bool {
-        self.iter().any(|c| CardNumber::filter(*c) == CardNumber::BLANK)
+        self.iter()
+            .any(|c| CardNumber::filter(c.strip_multiples_flags()) == CardNumber::BLANK)
     }
 
     fn is_valid(&self) -> bool {
         self.are_unique() && !self.is_corrupt()
     }
 
+    /// The same check as `is_valid()`, but says what's wrong with the hand.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HandError::Incomplete` if the hand `is_partial()`, `HandError::BlankCard` if it
+    /// `is_undealt()`, `HandError::InvalidCard` if any card isn't one of the 52 `CardNumbers`, and
+    /// `HandError::DuplicateCard` if any card appears more than once, checked in that order.
+    fn validate(&self) -> Result<(), HandError> {
+        if self.is_partial() {
+            Err(HandError::Incomplete)
+        } else if self.contain_blank() {
+            Err(HandError::BlankCard)
+        } else if self.is_corrupt() {
+            Err(HandError::InvalidCard)
+        } else if !self.are_unique() {
+            Err(HandError::DuplicateCard)
+        } else {
+            Ok(())
+        }
+    }
+
     fn iter(&self) -> Iter<'_, CKCNumber>;
+
+    /// Returns the cards in the hand as a single `BinaryCard`, for use as a cheap map key. The id
+    /// is the same whatever order the cards are in, and is part of the stable API, unlike the
+    /// derived `Hash`, which hashes the cards in the order they're stored and may change between
+    /// versions. Blank and invalid cards add nothing to the id, and a card with multiples flags
+    /// adds the same as it would without them.
+    ///
+    /// The id only has a bit set for each card, so it isn't well distributed as a hash. With the
+    /// `nohash` feature, `BuildHandIdHasher` uses the id as the hash as is, which is only worth it
+    /// for lookups in small tables.
+    fn id(&self) -> BinaryCard {
+        self.iter().fold(BinaryCard::BLANK, |id, card| {
+            id | BinaryCard::from_ckc(card.strip_multiples_flags())
+        })
+    }
+
+    /// True if the two hands hold the same cards, in whatever order, which is what deduplicating
+    /// generated hands usually wants where `==` compares the cards in order. Compares the
+    /// `id()` of each, so blank and invalid cards are ignored, a card held twice counts once,
+    /// and multiples flags don't matter.
+    fn set_eq(&self, other: &Self) -> bool
+    where
+        Self: Sized,
+    {
+        self.id() == other.id()
+    }
+
+    /// Writes the cards as a space separated index, such as `A♠ K♠`, without allocating.
+    /// Invalid cards are written as `__`.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from the writer.
+    fn write_index(&self, w: &mut impl core::fmt::Write) -> core::fmt::Result {
+        for (i, card) in self.iter().enumerate() {
+            if i > 0 {
+                w.write_char(' ')?;
+            }
+            w.write_char(card.get_rank_char())?;
+            w.write_char(card.get_suit_char())?;
+        }
+        Ok(())
+    }
+
+    /// Returns how many cards of each rank are in the hand, indexed from deuce (0) to ace (12)
+    /// the same as the rank bits of a `CKCNumber`. Invalid cards aren't counted.
+    fn rank_counts(&self) -> [u8; 13] {
+        let mut counts = [0u8; 13];
+        for card in self.iter().map(PokerCard::strip_multiples_flags) {
+            if CardNumber::filter(card) != CardNumber::BLANK {
+                counts[card.get_rank_bit().trailing_zeros() as usize] += 1;
+            }
+        }
+        counts
+    }
+
+    /// Returns how many cards of each suit are in the hand, indexed in the same order as
+    /// `CardSuit::all()`: spades, hearts, diamonds and clubs. Invalid cards aren't counted.
+    fn suit_counts(&self) -> [u8; 4] {
+        let mut counts = [0u8; 4];
+        for card in self.iter().map(PokerCard::strip_multiples_flags) {
+            if CardNumber::filter(card) != CardNumber::BLANK {
+                counts[3 - card.get_suit_bit().trailing_zeros() as usize] += 1;
+            }
+        }
+        counts
+    }
+
+    /// The most cards of any one suit in the hand.
+    fn flush_suit_count(&self) -> u8 {
+        self.suit_counts().into_iter().max().unwrap_or_default()
+    }
+
+    /// True if at least five of the cards are the same suit.
+    fn flush_possible(&self) -> bool {
+        self.flush_suit_count() >= 5
+    }
+
+    /// Returns the `CardSuit` with at least five cards in the hand, if there is one. `Five` has
+    /// its own `Five::flush_suit()` that reads the flush straight from the card bits.
+    fn flush_suit(&self) -> Option<CardSuit> {
+        CardSuit::all()
+            .into_iter()
+            .zip(self.suit_counts())
+            .find(|(_, count)| *count >= 5)
+            .map(|(suit, _)| suit)
+    }
+}
+
+/// The rank bit and prime machinery of the Cactus Kev evaluator for hands of any size, for
+/// building custom evaluators such as low ball tables.
+pub trait RankBits: HandValidator {
+    /// The product of the rank primes of every card in the hand. For a `Five` this is the same
+    /// as `Five::multiply_primes()`.
+    fn prime_product(&self) -> u64 {
+        self.iter()
+            .map(|card| u64::from(card.strip_multiples_flags().get_rank_prime()))
+            .product()
+    }
+
+    /// The OR of the rank bits of every card in the hand, with deuces as bit 0 and aces as bit
+    /// 12. For a `Five` this is the same as `Five::or_rank_bits()`.
+    fn rank_mask(&self) -> u32 {
+        self.iter()
+            .fold(0, |mask, card| mask | card.strip_multiples_flags().get_rank_bit())
+    }
 }
 
+/// A hand of more than five cards, ranked by the best five cards in it. Along with
+/// `HandValidator`, this is all that a hand needs to get `HandRanker`.
 pub trait Permutator {
+    /// The indexes of every five card combination of the hand's cards.
+    const PERMUTATIONS: &'static [[u8; 5]];
+
     fn five_from_permutation(&self, permutation: [u8; 5]) -> Five;
 }
+
+impl<T: Permutator + HandValidator> HandRanker for T {
+    fn hand_rank_value_and_hand(&self) -> (crate::hand_rank::HandRankValue, Five) {
+        let mut best_hrv = crate::hand_rank::NO_HAND_RANK_VALUE;
+        let mut best_hand = Five::default();
+
+        for perm in T::PERMUTATIONS {
+            let hand = self.five_from_permutation(*perm);
+            let hrv = hand.hand_rank_value();
+            if (best_hrv == 0) || hrv != 0 && hrv < best_hrv {
+                best_hrv = hrv;
+                best_hand = hand;
+            }
+        }
+
+        (best_hrv, best_hand.sort())
+    }
+
+    fn hand_rank_value_validated(&self) -> crate::hand_rank::HandRankValue {
+        if !self.is_valid() {
+            return crate::hand_rank::NO_HAND_RANK_VALUE;
+        }
+        self.hand_rank_value()
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod cards_tests {
+    use super::*;
+    use crate::cards::four::Four;
+    use crate::cards::seven::Seven;
+    use crate::cards::six::Six;
+    use crate::cards::three::Three;
+    use crate::cards::two::Two;
+    use alloc::vec::Vec;
+    use rstest::rstest;
+
+    fn ranks_suits<const N: usize>(index: &str) -> [(CardRank, CardSuit); N] {
+        let cards: Vec<CKCNumber> = index.split_whitespace().map(CKCNumber::from_index).collect();
+        core::array::from_fn(|i| (cards[i].get_card_rank(), cards[i].get_card_suit()))
+    }
+
+    #[rstest]
+    #[case("A♠ A♥", Ok(()))]
+    #[case("A♠ A♠", Err(HandError::DuplicateCard))]
+    #[case("A♠ __", Err(HandError::Incomplete))]
+    #[case("K♦ 7♣ 2♠", Ok(()))]
+    #[case("K♦ 7♣ K♦", Err(HandError::DuplicateCard))]
+    #[case("A♠ K♠ Q♠ J♠", Ok(()))]
+    #[case("A♠ K♠ Q♠ __", Err(HandError::Incomplete))]
+    #[case("A♠ K♠ Q♠ J♠ T♠", Ok(()))]
+    #[case("A♠ K♠ Q♠ J♠ A♠", Err(HandError::DuplicateCard))]
+    #[case("A♠ K♠ Q♠ J♠ T♠ 9♠", Ok(()))]
+    #[case("A♠ K♠ Q♠ J♠ T♠ __", Err(HandError::Incomplete))]
+    #[case("A♠ K♠ Q♠ J♠ T♠ 9♠ 8♠", Ok(()))]
+    #[case("A♠ K♠ Q♠ J♠ T♠ 9♠ 9♠", Err(HandError::DuplicateCard))]
+    fn from_ranks_suits(#[case] index: &str, #[case] expected: Result<(), HandError>) {
+        let cards: Result<Vec<CKCNumber>, HandError> = match index.split_whitespace().count() {
+            2 => Two::from_ranks_suits(ranks_suits(index)).map(|hand| hand.to_arr().to_vec()),
+            3 => Three::from_ranks_suits(ranks_suits(index)).map(|hand| hand.to_arr().to_vec()),
+            4 => Four::from_ranks_suits(ranks_suits(index)).map(|hand| hand.to_arr().to_vec()),
+            5 => Five::from_ranks_suits(ranks_suits(index)).map(|hand| hand.to_arr().to_vec()),
+            6 => Six::from_ranks_suits(ranks_suits(index)).map(|hand| hand.to_arr().to_vec()),
+            _ => Seven::from_ranks_suits(ranks_suits(index)).map(|hand| hand.to_arr().to_vec()),
+        };
+
+        assert_eq!(
+            expected.map(|()| index.split_whitespace().map(CKCNumber::from_index).collect()),
+            cards
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use crate::cards::binary_card::{BinaryCard, BC64};
+use crate::cards::{HandValidator, RankBits};
+use crate::{CKCNumber, CardNumber, HandError, PokerCard};
+use alloc::vec::Vec;
+use core::slice::Iter;
+
+/// A set of cards dealt from `N` decks shuffled together, such as a six deck shoe. Each deck
+/// has its own `BinaryCard`, so the same card can be in the set once per deck.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct MultiDeckSet<const N: usize>([BinaryCard; N]);
+
+impl<const N: usize> MultiDeckSet<N> {
+    #[must_use]
+    pub fn new() -> Self {
+        MultiDeckSet([BinaryCard::BLANK; N])
+    }
+
+    /// Adds the card from the deck with the passed in index.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HandError::InvalidCard` if the card isn't valid or there is no deck with that
+    /// index, and `HandError::DuplicateCard` if the card from that deck is already in the set.
+    pub fn insert(&mut self, card: CKCNumber, deck: usize) -> Result<(), HandError> {
+        let bc = BinaryCard::from_ckc(card.strip_multiples_flags());
+        let bits = self.0.get_mut(deck).ok_or(HandError::InvalidCard)?;
+        if bc == BinaryCard::BLANK {
+            return Err(HandError::InvalidCard);
+        }
+        if bits.has(bc) {
+            return Err(HandError::DuplicateCard);
+        }
+        *bits = bits.fold_in(bc);
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn contains(&self, card: CKCNumber, deck: usize) -> bool {
+        let bc = BinaryCard::from_ckc(card.strip_multiples_flags());
+        bc != BinaryCard::BLANK && self.0.get(deck).is_some_and(|bits| bits.has(bc))
+    }
+
+    /// The number of copies of the card in the set, across every deck.
+    #[must_use]
+    pub fn copies(&self, card: CKCNumber) -> usize {
+        (0..N).filter(|deck| self.contains(card, *deck)).count()
+    }
+
+    /// The cards from the deck with the passed in index.
+    #[must_use]
+    pub fn deck(&self, deck: usize) -> Option<BinaryCard> {
+        self.0.get(deck).copied()
+    }
+
+    /// Every distinct card in the set, ignoring which decks they came from.
+    #[must_use]
+    pub fn distinct(&self) -> BinaryCard {
+        self.0.iter().fold(BinaryCard::BLANK, |all, bits| all | bits)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|bits| *bits == BinaryCard::BLANK)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.iter().map(|bits| bits.number_of_cards() as usize).sum()
+    }
+}
+
+impl<const N: usize> Default for MultiDeckSet<N> {
+    fn default() -> Self {
+        MultiDeckSet::new()
+    }
+}
+
+/// A hand of cards dealt from `N` decks, where each card carries the index of the deck it came
+/// from. The same card can appear in the hand more than once as long as each copy comes from a
+/// different deck, so `are_unique()` only fails when a card repeats from the same deck.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct MultiDeckHand<const N: usize> {
+    cards: Vec<CKCNumber>,
+    decks: Vec<usize>,
+    set: MultiDeckSet<N>,
+}
+
+impl<const N: usize> MultiDeckHand<N> {
+    #[must_use]
+    pub fn new() -> Self {
+        MultiDeckHand::default()
+    }
+
+    /// Adds the card from the deck with the passed in index.
+    ///
+    /// # Errors
+    ///
+    /// See `MultiDeckSet::insert()`.
+    pub fn push(&mut self, card: CKCNumber, deck: usize) -> Result<(), HandError> {
+        self.set.insert(card, deck)?;
+        self.cards.push(card);
+        self.decks.push(deck);
+        Ok(())
+    }
+
+    /// The index of the deck that the card at the passed in position came from.
+    #[must_use]
+    pub fn deck_of(&self, index: usize) -> Option<usize> {
+        self.decks.get(index).copied()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// Returns the cards in the hand as a `MultiDeckSet`.
+    #[must_use]
+    pub fn to_set(&self) -> MultiDeckSet<N> {
+        self.set
+    }
+}
+
+impl<const N: usize> HandValidator for MultiDeckHand<N> {
+    fn are_unique(&self) -> bool {
+        let pairs: Vec<(CKCNumber, usize)> = self
+            .cards
+            .iter()
+            .map(PokerCard::strip_multiples_flags)
+            .zip(self.decks.iter().copied())
+            .collect();
+        !(1..pairs.len()).any(|i| pairs[i..].contains(&pairs[i - 1]))
+    }
+
+    fn first(&self) -> CKCNumber {
+        self.cards.first().copied().unwrap_or(CardNumber::BLANK)
+    }
+
+    fn sort(&self) -> Self {
+        let mut hand = self.clone();
+        hand.sort_in_place();
+        hand
+    }
+
+    /// Sorts the cards from highest to lowest, keeping each card with its deck, with copies of
+    /// the same card in deck order.
+    fn sort_in_place(&mut self) {
+        let mut pairs: Vec<(CKCNumber, usize)> = self.cards.iter().copied().zip(self.decks.iter().copied()).collect();
+        pairs.sort_unstable_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        (self.cards, self.decks) = pairs.into_iter().unzip();
+    }
+
+    fn iter(&self) -> Iter<'_, CKCNumber> {
+        self.cards.iter()
+    }
+}
+
+impl<const N: usize> RankBits for MultiDeckHand<N> {}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod cards__multi_deck_tests {
+    use super::*;
+
+    #[test]
+    fn multi_deck_set__insert() {
+        let mut set: MultiDeckSet<2> = MultiDeckSet::new();
+
+        assert!(set.insert(CardNumber::ACE_SPADES, 0).is_ok());
+        assert!(set.insert(CardNumber::ACE_SPADES, 1).is_ok());
+        assert_eq!(
+            HandError::DuplicateCard,
+            set.insert(CardNumber::ACE_SPADES, 1).unwrap_err()
+        );
+        assert_eq!(
+            HandError::InvalidCard,
+            set.insert(CardNumber::ACE_SPADES, 2).unwrap_err()
+        );
+        assert_eq!(HandError::InvalidCard, set.insert(CardNumber::BLANK, 0).unwrap_err());
+        assert_eq!(2, set.len());
+        assert_eq!(2, set.copies(CardNumber::ACE_SPADES));
+        assert_eq!(BinaryCard::ACE_SPADES, set.distinct());
+        assert_eq!(Some(BinaryCard::ACE_SPADES), set.deck(1));
+        assert_eq!(None, set.deck(2));
+    }
+
+    #[test]
+    fn multi_deck_set__default() {
+        let set: MultiDeckSet<6> = MultiDeckSet::default();
+
+        assert!(set.is_empty());
+        assert_eq!(0, set.len());
+        assert!(!set.contains(CardNumber::ACE_SPADES, 0));
+    }
+
+    #[test]
+    fn multi_deck_hand() {
+        let mut hand: MultiDeckHand<2> = MultiDeckHand::new();
+        hand.push(CardNumber::KING_HEARTS, 1).unwrap();
+        hand.push(CardNumber::ACE_SPADES, 1).unwrap();
+        hand.push(CardNumber::ACE_SPADES, 0).unwrap();
+
+        assert_eq!(
+            HandError::DuplicateCard,
+            hand.push(CardNumber::ACE_SPADES, 0).unwrap_err()
+        );
+        assert_eq!(3, hand.len());
+        assert_eq!(2, hand.to_set().copies(CardNumber::ACE_SPADES));
+        assert!(hand.is_valid());
+        assert_eq!([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2], hand.rank_counts());
+
+        let sorted = hand.sort();
+        assert_eq!(CardNumber::ACE_SPADES, sorted.first());
+        assert_eq!(Some(0), sorted.deck_of(0));
+        assert_eq!(Some(1), sorted.deck_of(1));
+        assert_eq!(Some(1), sorted.deck_of(2));
+        assert_eq!(CardNumber::KING_HEARTS, sorted.iter().copied().last().unwrap());
+    }
+
+    #[test]
+    fn multi_deck_hand__are_unique() {
+        let hand: MultiDeckHand<2> = MultiDeckHand {
+            cards: alloc::vec![CardNumber::ACE_SPADES, CardNumber::ACE_SPADES],
+            decks: alloc::vec![0, 0],
+            set: MultiDeckSet::new(),
+        };
+
+        assert!(!hand.are_unique());
+        assert!(!hand.is_valid());
+    }
+}
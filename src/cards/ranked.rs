@@ -0,0 +1,255 @@
+use crate::cards::five::Five;
+use crate::cards::HandRanker;
+use crate::deck::{Deck, POKER_DECK};
+use crate::hand_rank::{HandRank, HandRankValue};
+use crate::{CardNumber, HandError};
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+#[cfg(feature = "serde")]
+use serde::de::Error;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Wraps a hand so that it's ordered by its `HandRank` instead of by its cards, allowing
+/// collections of hands to be sorted by strength. The `HandRank` is calculated once, when the
+/// hand is wrapped.
+///
+/// Two `Ranked` hands are equal when they're of equal strength, even if their cards differ.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Ranked<T: HandRanker> {
+    hand: T,
+    rank: HandRank,
+}
+
+impl<T: HandRanker> Ranked<T> {
+    #[must_use]
+    pub fn new(hand: T) -> Self {
+        let rank = hand.hand_rank_validated();
+        Ranked { hand, rank }
+    }
+
+    #[must_use]
+    pub fn hand(&self) -> &T {
+        &self.hand
+    }
+
+    #[must_use]
+    pub fn rank(&self) -> HandRank {
+        self.rank
+    }
+
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.hand
+    }
+}
+
+impl<T: HandRanker> Deref for Ranked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.hand
+    }
+}
+
+impl<T: HandRanker> From<T> for Ranked<T> {
+    fn from(hand: T) -> Self {
+        Ranked::new(hand)
+    }
+}
+
+impl<T: HandRanker> PartialEq for Ranked<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.rank == other.rank
+    }
+}
+
+impl<T: HandRanker> Eq for Ranked<T> {}
+
+impl<T: HandRanker> Hash for Ranked<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rank.hash(state);
+    }
+}
+
+impl<T: HandRanker> PartialOrd for Ranked<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: HandRanker> Ord for Ranked<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank.cmp(&other.rank)
+    }
+}
+
+impl Ranked<Five> {
+    /// The version of the layout written by `to_record()`.
+    pub const RECORD_VERSION: u8 = 1;
+    pub const RECORD_LEN: usize = 8;
+
+    /// A blank card in a record.
+    const NO_CARD: u8 = u8::MAX;
+
+    /// Packs the hand and its `HandRank` into eight bytes: the `RECORD_VERSION`, the position
+    /// of each card in the `POKER_DECK` (`0xFF` for a blank), and the `HandRankValue` as a
+    /// little endian `u16`.
+    ///
+    /// This is also how a `Ranked<Five>` serializes, so a logged hand costs eight bytes in a
+    /// binary format such as postcard.
+    #[must_use]
+    pub fn to_record(&self) -> [u8; 8] {
+        let mut record = [Self::RECORD_VERSION; Self::RECORD_LEN];
+        for (byte, card) in record[1..6].iter_mut().zip(self.hand.to_arr()) {
+            *byte = Deck::position(card)
+                .and_then(|position| u8::try_from(position).ok())
+                .unwrap_or(Self::NO_CARD);
+        }
+        record[6..].copy_from_slice(&self.rank.value.to_le_bytes());
+        record
+    }
+
+    /// Unpacks a record written by `to_record()`, recalculating the `HandRank` from the cards.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HandError::InvalidBinaryFormat` if the record is the wrong length or version,
+    /// refers to a card that isn't in the deck, or has a `HandRankValue` that doesn't match the
+    /// cards.
+    pub fn from_record(record: &[u8]) -> Result<Self, HandError> {
+        if record.len() != Self::RECORD_LEN || record[0] != Self::RECORD_VERSION {
+            return Err(HandError::InvalidBinaryFormat);
+        }
+        let mut cards = [CardNumber::BLANK; 5];
+        for (card, byte) in cards.iter_mut().zip(&record[1..6]) {
+            if *byte != Self::NO_CARD {
+                *card = *POKER_DECK
+                    .get(usize::from(*byte))
+                    .ok_or(HandError::InvalidBinaryFormat)?;
+            }
+        }
+        let ranked = Ranked::new(Five::from(cards));
+        if ranked.rank.value != HandRankValue::from_le_bytes([record[6], record[7]]) {
+            return Err(HandError::InvalidBinaryFormat);
+        }
+        Ok(ranked)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Ranked<Five> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_record().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Ranked<Five> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let record = <[u8; 8]>::deserialize(deserializer)?;
+        Ranked::from_record(&record).map_err(|_| D::Error::custom("invalid ranked hand record"))
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod cards__ranked_tests {
+    use super::*;
+    use crate::cards::seven::Seven;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn ord() {
+        let mut hands: Vec<Ranked<Five>> = ["2♠ 3♥ 4♦ 5♣ 7♠", "A♠ K♠ Q♠ J♠ T♠", "A♥ A♦ K♣ Q♠ J♥"]
+            .into_iter()
+            .map(|index| Ranked::new(Five::try_from(index).unwrap()))
+            .collect();
+
+        hands.sort();
+
+        assert_eq!(Five::try_from("2♠ 3♥ 4♦ 5♣ 7♠").unwrap(), *hands[0].hand());
+        assert_eq!(Five::try_from("A♠ K♠ Q♠ J♠ T♠").unwrap(), hands[2].into_inner());
+    }
+
+    #[test]
+    fn eq() {
+        let a = Ranked::from(Seven::try_from("A♠ K♠ Q♠ J♠ T♠ 2♥ 3♦").unwrap());
+        let b = Ranked::from(Seven::try_from("A♥ K♥ Q♥ J♥ T♥ 2♠ 3♦").unwrap());
+
+        assert_eq!(a, b);
+        assert_eq!(1, a.rank().value);
+    }
+
+    #[test]
+    fn ord__invalid() {
+        let invalid = Ranked::new(Five::default());
+        let high_card = Ranked::new(Five::try_from("2♠ 3♥ 4♦ 5♣ 7♠").unwrap());
+
+        assert!(invalid < high_card);
+    }
+
+    #[test]
+    fn deref() {
+        let ranked = Ranked::new(Five::try_from("A♠ K♠ Q♠ J♠ T♠").unwrap());
+
+        assert!(ranked.is_straight_flush());
+    }
+
+    #[test]
+    fn to_record() {
+        let ranked = Ranked::new(Five::try_from("A♠ K♠ Q♠ J♠ T♠").unwrap());
+
+        let record = ranked.to_record();
+
+        assert_eq!([1, 0, 1, 2, 3, 4, 1, 0], record);
+        assert_eq!(ranked.hand(), Ranked::from_record(&record).unwrap().hand());
+        assert_eq!(ranked.rank(), Ranked::from_record(&record).unwrap().rank());
+    }
+
+    #[test]
+    fn from_record__blank() {
+        let ranked = Ranked::new(Five::default());
+
+        let record = ranked.to_record();
+
+        assert_eq!([1, 255, 255, 255, 255, 255, 0, 0], record);
+        assert_eq!(Five::default(), Ranked::from_record(&record).unwrap().into_inner());
+    }
+
+    #[test]
+    fn from_record__invalid() {
+        assert_eq!(
+            HandError::InvalidBinaryFormat,
+            Ranked::from_record(&[2, 0, 1, 2, 3, 4, 1, 0]).unwrap_err()
+        );
+        assert_eq!(
+            HandError::InvalidBinaryFormat,
+            Ranked::from_record(&[1, 0, 1, 2, 3, 4, 2, 0]).unwrap_err()
+        );
+        assert_eq!(
+            HandError::InvalidBinaryFormat,
+            Ranked::from_record(&[1, 0, 1, 2, 3, 52, 1, 0]).unwrap_err()
+        );
+        assert_eq!(
+            HandError::InvalidBinaryFormat,
+            Ranked::from_record(&[1, 0, 1, 2, 3, 4, 1]).unwrap_err()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let ranked = Ranked::new(Five::try_from("A♠ K♠ Q♠ J♠ 9♠").unwrap());
+
+        let json = serde_json::to_string(&ranked).unwrap();
+
+        assert_eq!("[1,0,1,2,3,5,67,1]", json);
+        assert_eq!(
+            ranked.hand(),
+            serde_json::from_str::<Ranked<Five>>(&json).unwrap().hand()
+        );
+        assert!(serde_json::from_str::<Ranked<Five>>("[1,0,1,2,3,5,66,1]").is_err());
+    }
+}
@@ -1,12 +1,28 @@
 use crate::cards::five::Five;
+use crate::cards::four::Four;
+use crate::cards::three::Three;
 use crate::cards::two::Two;
-use crate::cards::{HandRanker, HandValidator, Permutator};
-use crate::hand_rank::HandRankValue;
-use crate::{CKCNumber, HandError, PokerCard, Shifty};
+use crate::cards::{hand_array, try_from_binary_card, try_from_slice, HandRanker, HandValidator, Permutator, RankBits};
+use crate::hand_rank::{HandRankValue, NO_HAND_RANK_VALUE};
+use crate::{BinaryCard, CKCNumber, CardNumber, CardRank, CardSuit, HandError, PokerCard, Shifty, BC64};
 use core::slice::Iter;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+/// How many hole cards the best hand in a `Seven` needs.
+///
+/// * `Both` - only hands using both hole cards are that strong.
+/// * `One` - one hole card with four cards from the board is enough.
+/// * `PlaysTheBoard` - the board is as strong as anything the hole cards make.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum HoleUsage {
+    Both,
+    One,
+    PlaysTheBoard,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Seven([CKCNumber; 7]);
 
 impl Seven {
@@ -48,6 +64,60 @@ impl Seven {
         ])
     }
 
+    /// Builds a `Seven` from the cards of a hold'em hand as they're dealt, making sure that
+    /// every card is valid and that none of them repeat.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HandError::Incomplete` if some of the cards are blank, `HandError::BlankCard` if
+    /// all of them are, `HandError::InvalidCard` if any card is invalid, and
+    /// `HandError::DuplicateCard` if any card appears more than once.
+    pub fn new_from_holdem(two: Two, flop: Three, turn: CKCNumber, river: CKCNumber) -> Result<Self, HandError> {
+        Seven([
+            two.first(),
+            two.second(),
+            flop.first(),
+            flop.second(),
+            flop.third(),
+            turn,
+            river,
+        ])
+        .validated()
+    }
+
+    /// Builds a validated `Seven` from hole cards and the four cards of the board after the turn
+    /// plus the river.
+    ///
+    /// # Errors
+    ///
+    /// See `Seven::new_from_holdem()`.
+    pub fn from_two_and_four(two: Two, four: Four, river: CKCNumber) -> Result<Self, HandError> {
+        Seven([
+            two.first(),
+            two.second(),
+            four.first(),
+            four.second(),
+            four.third(),
+            four.forth(),
+            river,
+        ])
+        .validated()
+    }
+
+    /// Builds a validated `Seven` from hole cards and a complete board. Use `Seven::new()` when
+    /// the cards are already known to be good.
+    ///
+    /// # Errors
+    ///
+    /// See `Seven::new_from_holdem()`.
+    pub fn from_two_and_five(two: Two, five: Five) -> Result<Self, HandError> {
+        Seven::new(two, five).validated()
+    }
+
+    fn validated(self) -> Result<Self, HandError> {
+        self.validate().map(|()| self)
+    }
+
     //region accessors
 
     #[must_use]
@@ -108,6 +178,19 @@ impl Seven {
         self.0[6] = card_number;
     }
 
+    /// A `Seven` with no cards, the same as `Seven::default()`.
+    pub const EMPTY: Seven = Seven([CardNumber::BLANK; 7]);
+
+    /// Builds a `Seven` from the rank and suit of each card, the same as `Two::from_ranks_suits()`.
+    ///
+    /// # Errors
+    ///
+    /// Same as `HandValidator::validate()`, where a `CardRank::BLANK` or `CardSuit::BLANK` is a
+    /// blank card.
+    pub fn from_ranks_suits(cards: [(CardRank, CardSuit); 7]) -> Result<Self, HandError> {
+        crate::cards::from_ranks_suits(cards)
+    }
+
     #[must_use]
     pub fn to_arr(&self) -> [CKCNumber; 7] {
         self.0
@@ -115,6 +198,58 @@ impl Seven {
 
     //endregion
 
+    //region hole cards
+
+    /// Returns true if the `board` on its own is as strong as the best hand in the `Seven`, so
+    /// that the hole cards don't play. Returns false if any card on the `board` isn't in the hand.
+    #[must_use]
+    pub fn board_plays(&self, board: Five) -> bool {
+        let board_bits = BinaryCard::from_five(board);
+        board_bits.number_of_cards() == 5
+            && BinaryCard::from_seven(*self) & board_bits == board_bits
+            && board.hand_rank_value() == self.hand_rank_value()
+    }
+
+    /// Returns how many hole cards the best hand needs, taking the first two cards as the hole
+    /// cards, the way that `Seven::new()` orders them. When the best hand can be made more than
+    /// one way, the way with the fewest hole cards counts, so kickers that don't play don't count.
+    #[must_use]
+    pub fn hole_card_usage(&self) -> HoleUsage {
+        let (_, usage) = self.hand_rank_value_and_hole_usage();
+        usage
+    }
+
+    /// Returns the `HandRankValue` of the best hand along with its `HoleUsage`, from a single
+    /// pass over the hand's five card combinations.
+    #[must_use]
+    pub fn hand_rank_value_and_hole_usage(&self) -> (HandRankValue, HoleUsage) {
+        let mut best = NO_HAND_RANK_VALUE;
+        let mut fewest = 2;
+
+        for perm in Seven::FIVE_CARD_PERMUTATIONS {
+            let hrv = self.five_from_permutation(perm).hand_rank_value();
+            let hole = perm.iter().filter(|i| **i < 2).count();
+            if hrv == NO_HAND_RANK_VALUE {
+                continue;
+            }
+            if best == NO_HAND_RANK_VALUE || hrv < best {
+                best = hrv;
+                fewest = hole;
+            } else if hrv == best {
+                fewest = fewest.min(hole);
+            }
+        }
+
+        let usage = match fewest {
+            0 => HoleUsage::PlaysTheBoard,
+            1 => HoleUsage::One,
+            _ => HoleUsage::Both,
+        };
+        (best, usage)
+    }
+
+    //endregion hole cards
+
     fn from_index(index: &str) -> Option<[CKCNumber; 7]> {
         let mut esses = index.split_whitespace();
 
@@ -125,6 +260,9 @@ impl Seven {
         let fifth = CKCNumber::from_index(esses.next()?);
         let sixth = CKCNumber::from_index(esses.next()?);
         let seventh = CKCNumber::from_index(esses.next()?);
+        if esses.next().is_some() {
+            return None;
+        }
         let hand: [CKCNumber; 7] = [first, second, third, forth, fifth, sixth, seventh];
         Some(hand)
     }
@@ -136,42 +274,24 @@ impl From<[CKCNumber; 7]> for Seven {
     }
 }
 
-impl HandRanker for Seven {
-    fn hand_rank_value_and_hand(&self) -> (HandRankValue, Five) {
-        let mut best_hrv: HandRankValue = 0u16;
-        let mut best_hand = Five::default();
-
-        for perm in Seven::FIVE_CARD_PERMUTATIONS {
-            let hand = self.five_from_permutation(perm);
-            let hrv = hand.hand_rank_value();
-            if (best_hrv == 0) || hrv != 0 && hrv < best_hrv {
-                best_hrv = hrv;
-                best_hand = hand;
-            }
-        }
-
-        (best_hrv, best_hand.sort())
-    }
-
-    fn hand_rank_value_validated(&self) -> HandRankValue {
-        if !self.is_valid() {
-            return crate::hand_rank::NO_HAND_RANK_VALUE;
-        }
-        self.hand_rank_value()
-    }
+hand_array!(Seven, 7);
+
+/// The `Seven` made from hole cards and a complete board.
+pub(crate) fn seven(two: Two, flop: Three, turn: CKCNumber, river: CKCNumber) -> Seven {
+    Seven::from([
+        two.first(),
+        two.second(),
+        flop.first(),
+        flop.second(),
+        flop.third(),
+        turn,
+        river,
+    ])
 }
 
 impl HandValidator for Seven {
     fn are_unique(&self) -> bool {
-        let sorted = self.sort();
-        let mut last: CKCNumber = u32::MAX;
-        for c in sorted.iter() {
-            if *c >= last {
-                return false;
-            }
-            last = *c;
-        }
-        true
+        crate::cards::all_unique(&self.0)
     }
 
     fn first(&self) -> CKCNumber {
@@ -194,7 +314,11 @@ impl HandValidator for Seven {
     }
 }
 
+impl RankBits for Seven {}
+
 impl Permutator for Seven {
+    const PERMUTATIONS: &'static [[u8; 5]] = &Seven::FIVE_CARD_PERMUTATIONS;
+
     fn five_from_permutation(&self, permutation: [u8; 5]) -> Five {
         Five::new(
             self.0[permutation[0] as usize],
@@ -224,6 +348,9 @@ impl TryFrom<&'static str> for Seven {
     type Error = HandError;
 
     fn try_from(index: &'static str) -> Result<Self, Self::Error> {
+        if crate::parse::card_count(index) > 7 {
+            return Err(HandError::TooManyCards);
+        }
         match Seven::from_index(index) {
             None => Err(HandError::InvalidIndex),
             Some(seven) => Ok(Seven::from(seven)),
@@ -231,11 +358,54 @@ impl TryFrom<&'static str> for Seven {
     }
 }
 
+try_from_slice!(Seven);
+
+try_from_binary_card!(Seven);
+
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod cards_seven_tests {
     use super::*;
     use crate::CardNumber;
+    use rstest::rstest;
+
+    #[test]
+    fn rank_mask__multiples() {
+        let seven = Seven::try_from("A♠ K♥ Q♠ J♦ T♠ A♣ 2♦").unwrap();
+
+        assert_eq!(0b1_1110_0000_0001 | 0b1_0000_0000, seven.rank_mask());
+    }
+
+    #[test]
+    fn flush_suit() {
+        let flush = Seven::try_from("A♥ K♥ Q♠ J♥ T♥ 2♥ 3♥").unwrap();
+        let draw = Seven::try_from("A♠ K♥ Q♠ J♠ T♥ 2♠ 3♦").unwrap();
+
+        assert_eq!(6, flush.flush_suit_count());
+        assert!(flush.flush_possible());
+        assert_eq!(Some(CardSuit::HEARTS), flush.flush_suit());
+        assert_eq!(4, draw.flush_suit_count());
+        assert!(!draw.flush_possible());
+        assert_eq!(None, draw.flush_suit());
+        assert_eq!(0, Seven::default().flush_suit_count());
+    }
+
+    #[test]
+    fn index() {
+        let seven = Seven::try_from("KC 8C QD A♠ 9h 2C T♠").unwrap();
+
+        assert_eq!(CardNumber::KING_CLUBS, seven[0]);
+        assert_eq!(CardNumber::TEN_SPADES, seven[6]);
+    }
+
+    #[test]
+    fn into_iter() {
+        let seven = Seven::try_from("KC 8C QD A♠ 9h 2C T♠").unwrap();
+
+        assert_eq!(Seven::LEN, seven.len());
+        assert_eq!(seven.to_arr().to_vec(), seven.into_iter().collect::<Vec<CKCNumber>>());
+        assert_eq!(Some(&CardNumber::TEN_SPADES), (&seven).into_iter().last());
+    }
 
     #[test]
     fn sort() {
@@ -327,7 +497,233 @@ mod cards_seven_tests {
         assert_eq!(
             Seven::try_from("A♠ K♠ Q♠ J♠ T♠ 9♠ 8♠").unwrap().shift_suit(),
             Seven::try_from("A♥ K♥ Q♥ J♥ T♥ 9♥ 8♥").unwrap()
-        )
+        );
+    }
+
+    #[test]
+    fn rank_bits() {
+        let seven = Seven::try_from("A♠ A♥ A♦ K♣ K♠ 7♥ 2♦").unwrap();
+
+        assert_eq!(41 * 41 * 41 * 37 * 37 * 13 * 2, seven.prime_product());
+        assert_eq!(0b1_1000_0010_0001, seven.rank_mask());
+    }
+
+    #[test]
+    fn rank_counts() {
+        let seven = Seven::try_from("A♠ A♥ A♦ K♣ K♠ 7♥ 2♦").unwrap();
+
+        assert_eq!([1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 2, 3], seven.rank_counts());
+    }
+
+    #[test]
+    fn suit_counts() {
+        let seven = Seven::try_from("A♠ A♥ A♦ K♣ K♠ 7♥ 2♦").unwrap();
+
+        assert_eq!([2, 2, 2, 1], seven.suit_counts());
+    }
+
+    #[test]
+    fn new_from_holdem() {
+        let two = Two::try_from("A♠ K♠").unwrap();
+        let flop = Three::try_from("Q♠ J♠ T♠").unwrap();
+
+        let seven = Seven::new_from_holdem(two, flop, CardNumber::DEUCE_HEARTS, CardNumber::TREY_DIAMONDS).unwrap();
+
+        assert_eq!(Seven::try_from("A♠ K♠ Q♠ J♠ T♠ 2♥ 3♦").unwrap(), seven);
+        assert_eq!(
+            HandError::DuplicateCard,
+            Seven::new_from_holdem(two, flop, CardNumber::ACE_SPADES, CardNumber::TREY_DIAMONDS).unwrap_err()
+        );
+        assert_eq!(
+            HandError::Incomplete,
+            Seven::new_from_holdem(two, flop, CardNumber::BLANK, CardNumber::TREY_DIAMONDS).unwrap_err()
+        );
+        assert_eq!(
+            HandError::InvalidCard,
+            Seven::new_from_holdem(two, flop, 12, CardNumber::TREY_DIAMONDS).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn from_two_and_four() {
+        let two = Two::try_from("A♠ K♠").unwrap();
+        let four = Four::try_from("Q♠ J♠ T♠ 2♥").unwrap();
+
+        let seven = Seven::from_two_and_four(two, four, CardNumber::TREY_DIAMONDS).unwrap();
+
+        assert_eq!(Seven::try_from("A♠ K♠ Q♠ J♠ T♠ 2♥ 3♦").unwrap(), seven);
+        assert_eq!(
+            HandError::DuplicateCard,
+            Seven::from_two_and_four(two, four, CardNumber::DEUCE_HEARTS).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn from_two_and_five() {
+        let two = Two::try_from("A♠ K♠").unwrap();
+
+        assert_eq!(
+            Seven::try_from("A♠ K♠ Q♠ J♠ T♠ 2♥ 3♦").unwrap(),
+            Seven::from_two_and_five(two, Five::try_from("Q♠ J♠ T♠ 2♥ 3♦").unwrap()).unwrap()
+        );
+        assert_eq!(
+            HandError::DuplicateCard,
+            Seven::from_two_and_five(two, Five::try_from("A♠ J♠ T♠ 2♥ 3♦").unwrap()).unwrap_err()
+        );
+    }
+
+    #[rstest]
+    #[case("A♠ A♥", "A♦ K♣ 7♠ 4♥ 2♦", HoleUsage::Both)]
+    #[case("A♠ K♦", "Q♠ Q♥ Q♦ Q♣ 2♥", HoleUsage::One)]
+    #[case("A♥ 2♣", "K♥ Q♥ 9♥ 5♥ 3♠", HoleUsage::One)]
+    #[case("3♠ 4♦", "A♠ K♠ Q♠ J♠ T♠", HoleUsage::PlaysTheBoard)]
+    #[case("2♠ 3♦", "A♠ A♥ K♠ K♥ Q♦", HoleUsage::PlaysTheBoard)]
+    fn hole_card_usage(#[case] hole: &'static str, #[case] board: &'static str, #[case] expected: HoleUsage) {
+        let board = Five::try_from(board).unwrap();
+        let seven = Seven::new(Two::try_from(hole).unwrap(), board);
+
+        assert_eq!(expected, seven.hole_card_usage());
+        assert_eq!(expected == HoleUsage::PlaysTheBoard, seven.board_plays(board));
+        assert_eq!(seven.hand_rank_value(), seven.hand_rank_value_and_hole_usage().0);
+    }
+
+    #[test]
+    fn board_plays__not_in_hand() {
+        let seven = Seven::try_from("3♠ 4♦ A♠ K♠ Q♠ J♠ T♠").unwrap();
+
+        assert!(!seven.board_plays(Five::try_from("A♥ K♠ Q♠ J♠ T♠").unwrap()));
+    }
+
+    #[test]
+    fn try_hand_rank() {
+        let hand = Seven::try_from("A♠ A♥ K♦ Q♣ J♠ 2♥ 3♦").unwrap();
+
+        assert_eq!(hand.hand_rank(), hand.try_hand_rank().unwrap());
+        assert_eq!(
+            HandError::DuplicateCard,
+            Seven::try_from("A♠ A♥ K♦ Q♣ J♠ 2♥ A♠")
+                .unwrap()
+                .try_hand_rank()
+                .unwrap_err()
+        );
+        assert_eq!(
+            HandError::Incomplete,
+            Seven::try_from("A♠ A♥ K♦ Q♣ J♠ 2♥ __")
+                .unwrap()
+                .try_hand_rank()
+                .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn try_hand_rank__flagged() {
+        let hand = Seven::try_from("A♠ A♥ K♦ K♣ K♠ 2♥ 3♦").unwrap();
+        let flagged = Seven::from([
+            CardNumber::ACE_SPADES.flag_as_pair(),
+            CardNumber::ACE_HEARTS.flag_as_pair(),
+            CardNumber::KING_DIAMONDS.flag_as_trips(),
+            CardNumber::KING_CLUBS.flag_as_trips(),
+            CardNumber::KING_SPADES.flag_as_trips(),
+            CardNumber::DEUCE_HEARTS,
+            CardNumber::TREY_DIAMONDS,
+        ]);
+        let duplicate = Seven::from([
+            CardNumber::ACE_SPADES.flag_as_pair(),
+            CardNumber::ACE_SPADES,
+            CardNumber::KING_DIAMONDS,
+            CardNumber::KING_CLUBS,
+            CardNumber::KING_SPADES,
+            CardNumber::DEUCE_HEARTS,
+            CardNumber::TREY_DIAMONDS,
+        ]);
+
+        assert_eq!(hand.hand_rank(), flagged.try_hand_rank().unwrap());
+        assert_eq!(HandError::DuplicateCard, duplicate.try_hand_rank().unwrap_err());
+    }
+
+    #[test]
+    fn cmp_by_rank() {
+        let aces = Seven::try_from("A♠ A♥ K♦ Q♣ J♠ 2♥ 3♦").unwrap();
+        let kings = Seven::try_from("K♠ K♥ A♦ Q♣ J♠ 2♥ 3♦").unwrap();
+
+        assert_eq!(core::cmp::Ordering::Greater, aces.cmp_by_rank(&kings));
+        assert_eq!(core::cmp::Ordering::Less, kings.cmp_by_rank(&aces));
+        assert_eq!(core::cmp::Ordering::Less, Seven::default().cmp_by_rank(&kings));
+    }
+
+    #[test]
+    fn try_from__binary_card() {
+        let hand = Seven::try_from("A♠ K♠ Q♠ J♠ T♠ 9♠ 8♠").unwrap();
+
+        let actual = Seven::try_from(BinaryCard::from_seven(hand));
+
+        assert_eq!(hand.sort(), actual.unwrap().sort());
+    }
+
+    #[test]
+    fn try_from__binary_card__hand_rank() {
+        for window in crate::POKER_DECK.windows(7) {
+            let seven = Seven::from([
+                window[0], window[1], window[2], window[3], window[4], window[5], window[6],
+            ]);
+
+            let round_trip = Seven::try_from(BinaryCard::from_seven(seven)).unwrap();
+
+            assert_eq!(seven.hand_rank_value(), round_trip.hand_rank_value());
+        }
+    }
+
+    #[test]
+    fn try_from__binary_card__not_enough() {
+        let bc = BinaryCard::from_index("A♠ K♠ Q♠ J♠ T♠ 9♠ 8♠") & !BinaryCard::ACE_SPADES;
+
+        assert_eq!(Seven::try_from(bc).unwrap_err(), HandError::NotEnoughCards);
+        assert_eq!(
+            Seven::try_from(BinaryCard::BLANK).unwrap_err(),
+            HandError::NotEnoughCards
+        );
+    }
+
+    #[test]
+    fn try_from__binary_card__too_many() {
+        let bc = BinaryCard::from_index("A♠ K♠ Q♠ J♠ T♠ 9♠ 8♠").fold_in(BinaryCard::DEUCE_CLUBS);
+
+        assert_eq!(Seven::try_from(bc).unwrap_err(), HandError::TooManyCards);
+    }
+
+    #[test]
+    fn try_from__binary_card__invalid_binary_format() {
+        let bc = (BinaryCard::from_index("A♠ K♠ Q♠ J♠ T♠ 9♠ 8♠") & !BinaryCard::ACE_SPADES)
+            .fold_in(0b1_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000);
+
+        assert_eq!(Seven::try_from(bc).unwrap_err(), HandError::InvalidBinaryFormat);
+    }
+
+    #[test]
+    fn write_index() {
+        let mut index = String::new();
+
+        Seven::try_from("A♠ K♥ Q♦ J♣ T♠ 9♥ XX")
+            .unwrap()
+            .write_index(&mut index)
+            .unwrap();
+
+        assert_eq!("A♠ K♥ Q♦ J♣ T♠ 9♥ __", index);
+    }
+
+    #[test]
+    fn try_from__slice() {
+        let seven = Seven::try_from("A♠ K♠ Q♠ J♠ T♠ 2♣ 2♦").unwrap();
+
+        assert_eq!(seven, Seven::try_from(&seven.to_arr()[..]).unwrap());
+        assert_eq!(
+            HandError::NotEnoughCards,
+            Seven::try_from(&seven.to_arr()[1..]).unwrap_err()
+        );
+        assert_eq!(
+            HandError::BlankCard,
+            Seven::try_from(&[CardNumber::BLANK; 7][..]).unwrap_err()
+        );
     }
 
     #[test]
@@ -371,4 +767,45 @@ mod cards_seven_tests {
 
         assert!(seven.is_err());
     }
+
+    #[test]
+    fn try_from__index__too_many() {
+        assert_eq!(
+            Seven::try_from("A♠ K♠ Q♠ J♠ T♠ 9♠ 8♠ 7♠").unwrap_err(),
+            HandError::TooManyCards
+        );
+        assert!(Seven::from_index("A♠ K♠ Q♠ J♠ T♠ 9♠ 8♠ 7♠").is_none());
+    }
+
+    #[test]
+    fn id() {
+        let seven = Seven::try_from("A♠ K♥ Q♠ J♦ T♠ A♣ 2♦").unwrap();
+
+        assert_eq!(BinaryCard::from_seven(seven), seven.id());
+        assert_eq!(seven.id(), seven.sort().id());
+    }
+
+    #[test]
+    fn set_eq() {
+        let seven = Seven::try_from("A♠ K♥ Q♠ J♦ T♠ A♣ 2♦").unwrap();
+
+        assert!(seven.set_eq(&Seven::try_from("2♦ A♣ T♠ J♦ Q♠ K♥ A♠").unwrap()));
+        assert!(seven.set_eq(&seven.sort()));
+        assert!(!seven.set_eq(&Seven::try_from("A♠ K♥ Q♠ J♦ T♠ A♣ 2♣").unwrap()));
+    }
+
+    #[test]
+    #[cfg(feature = "nohash")]
+    fn id__nohash() {
+        let mut values: std::collections::HashMap<
+            BinaryCard,
+            crate::hand_rank::HandRankValue,
+            crate::cards::BuildHandIdHasher,
+        > = std::collections::HashMap::default();
+        let seven = Seven::try_from("A♠ K♥ Q♠ J♦ T♠ A♣ 2♦").unwrap();
+
+        values.insert(seven.id(), seven.hand_rank_value());
+
+        assert_eq!(Some(&1600), values.get(&seven.sort().id()));
+    }
 }
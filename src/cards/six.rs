@@ -1,13 +1,15 @@
 use crate::cards::five::Five;
+use crate::cards::four::Four;
 use crate::cards::three::Three;
 use crate::cards::two::Two;
-use crate::cards::{HandRanker, HandValidator, Permutator};
-use crate::hand_rank::HandRankValue;
-use crate::{CKCNumber, HandError, PokerCard, Shifty};
+use crate::cards::{hand_array, try_from_binary_card, try_from_slice, HandValidator, Permutator, RankBits};
+use crate::{CKCNumber, CardNumber, CardRank, CardSuit, HandError, PokerCard, Shifty};
 use core::slice::Iter;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Six([CKCNumber; 6]);
 
 impl Six {
@@ -21,6 +23,19 @@ impl Six {
         [1, 2, 3, 4, 5],
     ];
 
+    /// Hold'em hole cards and the board on the turn.
+    #[must_use]
+    pub fn new(two: Two, four: Four) -> Self {
+        Self([
+            two.first(),
+            two.second(),
+            four.first(),
+            four.second(),
+            four.third(),
+            four.forth(),
+        ])
+    }
+
     #[must_use]
     pub fn from_1_and_2_and_3(one: CKCNumber, two: Two, three: Three) -> Self {
         Self::from([
@@ -84,6 +99,19 @@ impl Six {
         self.0[5] = card_number;
     }
 
+    /// A `Six` with no cards, the same as `Six::default()`.
+    pub const EMPTY: Six = Six([CardNumber::BLANK; 6]);
+
+    /// Builds a `Six` from the rank and suit of each card, the same as `Two::from_ranks_suits()`.
+    ///
+    /// # Errors
+    ///
+    /// Same as `HandValidator::validate()`, where a `CardRank::BLANK` or `CardSuit::BLANK` is a
+    /// blank card.
+    pub fn from_ranks_suits(cards: [(CardRank, CardSuit); 6]) -> Result<Self, HandError> {
+        crate::cards::from_ranks_suits(cards)
+    }
+
     #[must_use]
     pub fn to_arr(&self) -> [CKCNumber; 6] {
         self.0
@@ -100,6 +128,9 @@ impl Six {
         let forth = CKCNumber::from_index(esses.next()?);
         let fifth = CKCNumber::from_index(esses.next()?);
         let sixth = CKCNumber::from_index(esses.next()?);
+        if esses.next().is_some() {
+            return None;
+        }
         let hand: [CKCNumber; 6] = [first, second, third, forth, fifth, sixth];
         Some(hand)
     }
@@ -111,42 +142,11 @@ impl From<[CKCNumber; 6]> for Six {
     }
 }
 
-impl HandRanker for Six {
-    fn hand_rank_value_and_hand(&self) -> (HandRankValue, Five) {
-        let mut best_hrv: HandRankValue = 0u16;
-        let mut best_hand = Five::default();
-
-        for perm in Six::FIVE_CARD_PERMUTATIONS {
-            let hand = self.five_from_permutation(perm);
-            let hrv = hand.hand_rank_value();
-            if (best_hrv == 0) || hrv != 0 && hrv < best_hrv {
-                best_hrv = hrv;
-                best_hand = hand;
-            }
-        }
-
-        (best_hrv, best_hand.sort())
-    }
-
-    fn hand_rank_value_validated(&self) -> HandRankValue {
-        if !self.is_valid() {
-            return crate::hand_rank::NO_HAND_RANK_VALUE;
-        }
-        self.hand_rank_value()
-    }
-}
+hand_array!(Six, 6);
 
 impl HandValidator for Six {
     fn are_unique(&self) -> bool {
-        let sorted = self.sort();
-        let mut last: CKCNumber = u32::MAX;
-        for c in sorted.iter() {
-            if *c >= last {
-                return false;
-            }
-            last = *c;
-        }
-        true
+        crate::cards::all_unique(&self.0)
     }
 
     fn first(&self) -> CKCNumber {
@@ -169,7 +169,11 @@ impl HandValidator for Six {
     }
 }
 
+impl RankBits for Six {}
+
 impl Permutator for Six {
+    const PERMUTATIONS: &'static [[u8; 5]] = &Six::FIVE_CARD_PERMUTATIONS;
+
     fn five_from_permutation(&self, permutation: [u8; 5]) -> Five {
         Five::new(
             self.0[permutation[0] as usize],
@@ -198,6 +202,9 @@ impl TryFrom<&'static str> for Six {
     type Error = HandError;
 
     fn try_from(index: &'static str) -> Result<Self, Self::Error> {
+        if crate::parse::card_count(index) > 6 {
+            return Err(HandError::TooManyCards);
+        }
         match Six::from_index(index) {
             None => Err(HandError::InvalidIndex),
             Some(six) => Ok(Six::from(six)),
@@ -205,11 +212,17 @@ impl TryFrom<&'static str> for Six {
     }
 }
 
+try_from_slice!(Six);
+
+try_from_binary_card!(Six);
+
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod cards_six_tests {
     use super::*;
+    use crate::cards::HandRanker;
     use crate::CardNumber;
+    use crate::{BinaryCard, BC64};
 
     #[test]
     fn five_from_permutation() {
@@ -257,6 +270,26 @@ mod cards_six_tests {
         );
     }
 
+    #[test]
+    fn rank_mask__multiples() {
+        let six = Six::try_from("A♠ K♥ Q♠ J♦ T♠ A♣").unwrap();
+
+        assert_eq!(0b1_1110_0000_0000 | 0b1_0000_0000, six.rank_mask());
+    }
+
+    #[test]
+    fn flush_suit() {
+        let flush = Six::try_from("A♠ K♥ Q♠ J♠ T♠ 2♠").unwrap();
+        let draw = Six::try_from("A♠ K♥ Q♠ J♠ T♥ 2♠").unwrap();
+
+        assert_eq!(5, flush.flush_suit_count());
+        assert!(flush.flush_possible());
+        assert_eq!(Some(CardSuit::SPADES), flush.flush_suit());
+        assert_eq!(4, draw.flush_suit_count());
+        assert!(!draw.flush_possible());
+        assert_eq!(None, draw.flush_suit());
+    }
+
     #[test]
     fn sort() {
         let six = Six::try_from("KC 8C QD A♠ 9h T♠").unwrap().sort();
@@ -286,7 +319,49 @@ mod cards_six_tests {
         assert_eq!(
             Six::try_from("T♠ A♠ K♠ J♠ Q♠ 9♠").unwrap().shift_suit(),
             Six::try_from("T♥ A♥ K♥ J♥ Q♥ 9♥").unwrap()
-        )
+        );
+    }
+
+    #[test]
+    fn cmp_by_rank() {
+        let aces = Six::try_from("A♠ A♥ K♦ Q♣ J♠ 2♥").unwrap();
+        let kings = Six::try_from("K♠ K♥ A♦ Q♣ J♠ 2♥").unwrap();
+
+        assert_eq!(core::cmp::Ordering::Greater, aces.cmp_by_rank(&kings));
+        assert_eq!(core::cmp::Ordering::Less, kings.cmp_by_rank(&aces));
+        assert_eq!(core::cmp::Ordering::Less, Six::default().cmp_by_rank(&kings));
+    }
+
+    #[test]
+    fn try_from__binary_card() {
+        let hand = Six::try_from("A♠ K♠ Q♠ J♠ T♠ 9♠").unwrap();
+
+        let actual = Six::try_from(BinaryCard::from_six(hand));
+
+        assert_eq!(hand.sort(), actual.unwrap().sort());
+    }
+
+    #[test]
+    fn try_from__binary_card__not_enough() {
+        let bc = BinaryCard::from_index("A♠ K♠ Q♠ J♠ T♠ 9♠") & !BinaryCard::ACE_SPADES;
+
+        assert_eq!(Six::try_from(bc).unwrap_err(), HandError::NotEnoughCards);
+        assert_eq!(Six::try_from(BinaryCard::BLANK).unwrap_err(), HandError::NotEnoughCards);
+    }
+
+    #[test]
+    fn try_from__binary_card__too_many() {
+        let bc = BinaryCard::from_index("A♠ K♠ Q♠ J♠ T♠ 9♠").fold_in(BinaryCard::DEUCE_CLUBS);
+
+        assert_eq!(Six::try_from(bc).unwrap_err(), HandError::TooManyCards);
+    }
+
+    #[test]
+    fn try_from__binary_card__invalid_binary_format() {
+        let bc = (BinaryCard::from_index("A♠ K♠ Q♠ J♠ T♠ 9♠") & !BinaryCard::ACE_SPADES)
+            .fold_in(0b1_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000);
+
+        assert_eq!(Six::try_from(bc).unwrap_err(), HandError::InvalidBinaryFormat);
     }
 
     #[test]
@@ -328,4 +403,21 @@ mod cards_six_tests {
 
         assert!(six.is_err());
     }
+
+    #[test]
+    fn try_from__index__too_many() {
+        assert_eq!(
+            Six::try_from("A♠ K♠ Q♠ J♠ T♠ 9♠ 8♠").unwrap_err(),
+            HandError::TooManyCards
+        );
+        assert!(Six::from_index("A♠ K♠ Q♠ J♠ T♠ 9♠ 8♠").is_none());
+    }
+
+    #[test]
+    fn new() {
+        let six = Six::new(Two::try_from("A♠ K♠").unwrap(), Four::try_from("Q♠ J♠ T♠ 2♣").unwrap());
+
+        assert_eq!(Six::try_from("A♠ K♠ Q♠ J♠ T♠ 2♣").unwrap(), six);
+        assert_eq!(1, six.hand_rank_value());
+    }
 }
@@ -0,0 +1,133 @@
+use crate::cards::HandValidator;
+use core::ops::Deref;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Wraps a hand so that its cards are always in the descending order of
+/// `HandValidator::sort()`, whatever order they were dealt in. Two `Sorted` hands holding the
+/// same cards are equal, hash the same and serialize the same, where `Five::from()` of two
+/// orderings of the same array are not.
+///
+/// A card flagged with `CardNumber::PAIR`, `TRIPS` or `QUADS` still sorts ahead of the same card
+/// without the flag, so use `HandValidator::set_eq()` to ignore the flags as well.
+///
+/// ```
+/// use ckc_rs::cards::five::Five;
+/// use ckc_rs::cards::sorted::Sorted;
+///
+/// let dealt = Five::try_from("9♥ A♠ T♠ K♣ Q♦").unwrap();
+/// let shown = Five::try_from("A♠ K♣ Q♦ T♠ 9♥").unwrap();
+///
+/// assert_ne!(dealt, shown);
+/// assert_eq!(Sorted::new(dealt), Sorted::new(shown));
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize), serde(transparent))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Sorted<T: HandValidator>(T);
+
+impl<T: HandValidator> Sorted<T> {
+    #[must_use]
+    pub fn new(mut hand: T) -> Self {
+        hand.sort_in_place();
+        Sorted(hand)
+    }
+
+    #[must_use]
+    pub fn hand(&self) -> &T {
+        &self.0
+    }
+
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: HandValidator> Deref for Sorted<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: HandValidator> From<T> for Sorted<T> {
+    fn from(hand: T) -> Self {
+        Sorted::new(hand)
+    }
+}
+
+/// Sorts the cards again, since they could have been written in any order.
+#[cfg(feature = "serde")]
+impl<'de, T: HandValidator + Deserialize<'de>> Deserialize<'de> for Sorted<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Sorted::new)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod cards__sorted_tests {
+    use super::*;
+    use crate::cards::five::Five;
+    use crate::cards::seven::Seven;
+    use crate::cards::two::Two;
+    use crate::cards::HandRanker;
+    use crate::CardNumber;
+    use std::collections::HashSet;
+
+    #[test]
+    fn new() {
+        let sorted = Sorted::new(Five::from([
+            CardNumber::NINE_HEARTS,
+            CardNumber::ACE_SPADES,
+            CardNumber::TEN_SPADES,
+            CardNumber::KING_CLUBS,
+            CardNumber::QUEEN_DIAMONDS,
+        ]));
+
+        assert_eq!(Five::try_from("A♠ K♣ Q♦ T♠ 9♥").unwrap(), sorted.into_inner());
+    }
+
+    #[test]
+    fn eq() {
+        let a = Sorted::from(Seven::try_from("2♥ A♠ K♠ 3♦ Q♠ J♠ T♠").unwrap());
+        let b = Sorted::from(Seven::try_from("A♠ K♠ Q♠ J♠ T♠ 3♦ 2♥").unwrap());
+
+        assert_eq!(a, b);
+        assert_ne!(Sorted::from(Seven::try_from("A♠ K♠ Q♠ J♠ T♠ 3♦ 2♠").unwrap()), b);
+    }
+
+    #[test]
+    fn hash() {
+        let hands: HashSet<Sorted<Two>> = ["A♠ K♥", "K♥ A♠", "K♠ A♥"]
+            .into_iter()
+            .map(|index| Sorted::new(Two::try_from(index).unwrap()))
+            .collect();
+
+        assert_eq!(2, hands.len());
+    }
+
+    #[test]
+    fn deref() {
+        let sorted = Sorted::new(Five::try_from("T♠ J♠ Q♠ K♠ A♠").unwrap());
+
+        assert_eq!(1, sorted.hand_rank_value());
+        assert_eq!(CardNumber::ACE_SPADES, sorted.hand().first());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde() {
+        let sorted = Sorted::new(Two::try_from("K♥ A♠").unwrap());
+
+        let json = serde_json::to_string(&sorted).unwrap();
+
+        assert_eq!(serde_json::to_string(&Two::try_from("A♠ K♥").unwrap()).unwrap(), json);
+        assert_eq!(
+            sorted,
+            serde_json::from_str::<Sorted<Two>>(&serde_json::to_string(&Two::try_from("K♥ A♠").unwrap()).unwrap())
+                .unwrap()
+        );
+    }
+}
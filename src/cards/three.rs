@@ -1,5 +1,5 @@
-use crate::cards::HandValidator;
-use crate::{CKCNumber, HandError, PokerCard, Shifty};
+use crate::cards::{hand_array, try_from_binary_card, try_from_slice, HandValidator, RankBits};
+use crate::{CKCNumber, CardNumber, CardRank, CardSuit, HandError, PokerCard, Shifty};
 use core::slice::Iter;
 
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -32,6 +32,19 @@ impl Three {
 
     //endregion
 
+    /// A `Three` with no cards, the same as `Three::default()`.
+    pub const EMPTY: Three = Three([CardNumber::BLANK; 3]);
+
+    /// Builds a `Three` from the rank and suit of each card, the same as `Two::from_ranks_suits()`.
+    ///
+    /// # Errors
+    ///
+    /// Same as `HandValidator::validate()`, where a `CardRank::BLANK` or `CardSuit::BLANK` is a
+    /// blank card.
+    pub fn from_ranks_suits(cards: [(CardRank, CardSuit); 3]) -> Result<Self, HandError> {
+        crate::cards::from_ranks_suits(cards)
+    }
+
     #[must_use]
     pub fn to_arr(&self) -> [CKCNumber; 3] {
         self.0
@@ -43,6 +56,9 @@ impl Three {
         let first = CKCNumber::from_index(esses.next()?);
         let second = CKCNumber::from_index(esses.next()?);
         let third = CKCNumber::from_index(esses.next()?);
+        if esses.next().is_some() {
+            return None;
+        }
         let hand: [CKCNumber; 3] = [first, second, third];
         Some(hand)
     }
@@ -54,10 +70,15 @@ impl From<[CKCNumber; 3]> for Three {
     }
 }
 
+hand_array!(Three, 3);
+
 impl TryFrom<&'static str> for Three {
     type Error = HandError;
 
     fn try_from(index: &'static str) -> Result<Self, Self::Error> {
+        if crate::parse::card_count(index) > 3 {
+            return Err(HandError::TooManyCards);
+        }
         match Three::from_index(index) {
             None => Err(HandError::InvalidIndex),
             Some(three) => Ok(Three::from(three)),
@@ -65,9 +86,13 @@ impl TryFrom<&'static str> for Three {
     }
 }
 
+try_from_slice!(Three);
+
+try_from_binary_card!(Three);
+
 impl HandValidator for Three {
     fn are_unique(&self) -> bool {
-        (self.first() != self.second()) && (self.first() != self.third()) && (self.second() != self.third())
+        crate::cards::all_unique(&self.0)
     }
 
     fn first(&self) -> CKCNumber {
@@ -90,6 +115,8 @@ impl HandValidator for Three {
     }
 }
 
+impl RankBits for Three {}
+
 impl Shifty for Three {
     fn shift_suit(&self) -> Self {
         Three([
@@ -105,6 +132,7 @@ impl Shifty for Three {
 mod cards_three_tests {
     use super::*;
     use crate::CardNumber;
+    use crate::{BinaryCard, BC64};
 
     #[test]
     fn sort() {
@@ -127,6 +155,41 @@ mod cards_three_tests {
         assert!(!three.is_valid());
     }
 
+    #[test]
+    fn try_from__binary_card() {
+        let hand = Three::try_from("A♠ K♠ Q♠").unwrap();
+
+        let actual = Three::try_from(BinaryCard::from_three(hand));
+
+        assert_eq!(hand.sort(), actual.unwrap().sort());
+    }
+
+    #[test]
+    fn try_from__binary_card__not_enough() {
+        let bc = BinaryCard::from_index("A♠ K♠ Q♠") & !BinaryCard::ACE_SPADES;
+
+        assert_eq!(Three::try_from(bc).unwrap_err(), HandError::NotEnoughCards);
+        assert_eq!(
+            Three::try_from(BinaryCard::BLANK).unwrap_err(),
+            HandError::NotEnoughCards
+        );
+    }
+
+    #[test]
+    fn try_from__binary_card__too_many() {
+        let bc = BinaryCard::from_index("A♠ K♠ Q♠").fold_in(BinaryCard::DEUCE_CLUBS);
+
+        assert_eq!(Three::try_from(bc).unwrap_err(), HandError::TooManyCards);
+    }
+
+    #[test]
+    fn try_from__binary_card__invalid_binary_format() {
+        let bc = (BinaryCard::from_index("A♠ K♠ Q♠") & !BinaryCard::ACE_SPADES)
+            .fold_in(0b1_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000_0000);
+
+        assert_eq!(Three::try_from(bc).unwrap_err(), HandError::InvalidBinaryFormat);
+    }
+
     #[test]
     fn try_from__index() {
         let three = Three::try_from("A♠ K♠ Q♠");
@@ -166,6 +229,12 @@ mod cards_three_tests {
         assert_eq!(
             Three::try_from("A♠ K♠ Q♠").unwrap().shift_suit(),
             Three::try_from("AH KH QH").unwrap()
-        )
+        );
+    }
+
+    #[test]
+    fn try_from__index__too_many() {
+        assert_eq!(Three::try_from("A♠ K♠ Q♠ J♠").unwrap_err(), HandError::TooManyCards);
+        assert!(Three::from_index("A♠ K♠ Q♠ J♠").is_none());
     }
 }
@@ -1,15 +1,60 @@
-use crate::cards::HandValidator;
-use crate::{BinaryCard, CKCNumber, CardNumber, HandError, PokerCard, Shifty, BC64};
+use crate::analysis::preflop::{PreflopClass, StartingHandTier};
+use crate::cards::{hand_array, try_from_binary_card, try_from_slice, HandValidator, RankBits};
+use crate::{CKCNumber, CardNumber, CardRank, CardSuit, HandError, PokerCard, Shifty};
 use core::cmp;
 use core::slice::Iter;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+/// The shape of a starting hand, for building ranges out of families of similar hands.
+///
+/// * `Broadway` - both cards are ten or higher, whether or not they're connected.
+/// * `WheelAce` - an ace with a five or lower, which connects through the wheel.
+/// * `Connector`, `OneGapper` and `TwoGapper` - zero, one or two ranks between the cards.
+/// * `Unconnected` - anything else that isn't a pair.
+///
+/// Each of the unpaired shapes has a `Suited` version.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ConnectorType {
+    Pair,
+    SuitedBroadway,
+    Broadway,
+    SuitedWheelAce,
+    WheelAce,
+    SuitedConnector,
+    Connector,
+    SuitedOneGapper,
+    OneGapper,
+    SuitedTwoGapper,
+    TwoGapper,
+    SuitedUnconnected,
+    Unconnected,
+    Invalid,
+}
+
+impl ConnectorType {
+    #[must_use]
+    pub fn is_suited(self) -> bool {
+        matches!(
+            self,
+            ConnectorType::SuitedBroadway
+                | ConnectorType::SuitedWheelAce
+                | ConnectorType::SuitedConnector
+                | ConnectorType::SuitedOneGapper
+                | ConnectorType::SuitedTwoGapper
+                | ConnectorType::SuitedUnconnected
+        )
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Two([CKCNumber; 2]);
 
 #[allow(non_upper_case_globals)]
 impl Two {
     //region hands
+    // Kept for compatibility. `PreflopClass::combos()` has the same tables for all 169 classes.
     pub const AA: [Two; 6] = [
         Two([CardNumber::ACE_SPADES, CardNumber::ACE_HEARTS]),
         Two([CardNumber::ACE_SPADES, CardNumber::ACE_DIAMONDS]),
@@ -78,7 +123,7 @@ impl Two {
 
     //endregion
     #[must_use]
-    pub fn new(first: CKCNumber, second: CKCNumber) -> Self {
+    pub const fn new(first: CKCNumber, second: CKCNumber) -> Self {
         Self([first, second])
     }
 
@@ -87,6 +132,9 @@ impl Two {
 
         let first = CKCNumber::from_index(esses.next()?);
         let second = CKCNumber::from_index(esses.next()?);
+        if esses.next().is_some() {
+            return None;
+        }
         let hand: [CKCNumber; 2] = [first, second];
         Some(hand)
     }
@@ -106,6 +154,29 @@ impl Two {
         self.0[1] = card_number;
     }
 
+    /// A `Two` with no cards, the same as `Two::default()`.
+    pub const EMPTY: Two = Two([CardNumber::BLANK; 2]);
+
+    /// Builds a `Two` from the rank and suit of each card, for making hands in code without
+    /// going through an index string or the `CardNumber` constants.
+    ///
+    /// ```
+    /// use ckc_rs::cards::two::Two;
+    /// use ckc_rs::{CardRank, CardSuit};
+    ///
+    /// let two = Two::from_ranks_suits([(CardRank::ACE, CardSuit::SPADES), (CardRank::KING, CardSuit::SPADES)]);
+    ///
+    /// assert_eq!(Two::try_from("A♠ K♠").unwrap(), two.unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as `HandValidator::validate()`, where a `CardRank::BLANK` or `CardSuit::BLANK` is a
+    /// blank card.
+    pub fn from_ranks_suits(cards: [(CardRank, CardSuit); 2]) -> Result<Self, HandError> {
+        crate::cards::from_ranks_suits(cards)
+    }
+
     #[must_use]
     pub fn to_arr(&self) -> [CKCNumber; 2] {
         self.0
@@ -141,18 +212,77 @@ impl Two {
             points += 2.0;
         }
 
-        points.ceil() as i8
+        // f32::ceil() needs std, so round up by hand.
+        let truncated = points as i8;
+        if points > f32::from(truncated) {
+            truncated + 1
+        } else {
+            truncated
+        }
+    }
+
+    /// Returns the hand's Sklansky-Malmuth group, from 1 for the strongest hands down to 9, or 0
+    /// if the hand isn't valid.
+    #[must_use]
+    pub fn sklansky_group(&self) -> u8 {
+        PreflopClass::try_from(*self).map_or(0, PreflopClass::sklansky_group)
+    }
+
+    /// Returns where the hand stands in each of the supported starting hand tier systems.
+    #[must_use]
+    pub fn starting_hand_tiers(&self) -> [StartingHandTier; 2] {
+        [
+            StartingHandTier::Chen(self.chen_formula()),
+            StartingHandTier::Sklansky(self.sklansky_group()),
+        ]
+    }
+
+    /// Returns the hand's share of the pot against a single random hand, given zero to five
+    /// board cards. Use an `analysis::EquityCache` to memoize repeated calls.
+    ///
+    /// # Errors
+    ///
+    /// See `analysis::equity_vs_random()`.
+    pub fn equity_vs_random(&self, board: &[CKCNumber]) -> Result<f64, HandError> {
+        crate::analysis::equity_vs_random(*self, board)
+    }
+
+    /// Classifies the shape of the hand. See `ConnectorType`.
+    #[must_use]
+    pub fn connector_type(&self) -> ConnectorType {
+        if !self.is_valid() {
+            return ConnectorType::Invalid;
+        }
+        if self.is_pocket_pair() {
+            return ConnectorType::Pair;
+        }
+        let sorted = self.sort();
+        let high = sorted.first().get_card_rank();
+        let low = sorted.second().get_card_rank();
+        let (suited, offsuit) = if low as u8 >= CardRank::TEN as u8 {
+            (ConnectorType::SuitedBroadway, ConnectorType::Broadway)
+        } else if high == CardRank::ACE && low as u8 <= CardRank::FIVE as u8 {
+            (ConnectorType::SuitedWheelAce, ConnectorType::WheelAce)
+        } else {
+            match self.get_gap() {
+                0 => (ConnectorType::SuitedConnector, ConnectorType::Connector),
+                1 => (ConnectorType::SuitedOneGapper, ConnectorType::OneGapper),
+                2 => (ConnectorType::SuitedTwoGapper, ConnectorType::TwoGapper),
+                _ => (ConnectorType::SuitedUnconnected, ConnectorType::Unconnected),
+            }
+        };
+        if self.is_suited() {
+            suited
+        } else {
+            offsuit
+        }
     }
 
     #[must_use]
     pub fn get_gap(&self) -> u8 {
         let s = self.sort();
         let distance_between = s.first().get_card_rank() as u8 - s.second().get_card_rank() as u8;
-        if distance_between < 1 {
-            0
-        } else {
-            distance_between - 1
-        }
+        distance_between.saturating_sub(1)
     }
 
     #[must_use]
@@ -208,13 +338,19 @@ impl From<[CKCNumber; 2]> for Two {
     }
 }
 
+hand_array!(Two, 2);
+
 impl TryFrom<&'static str> for Two {
     type Error = HandError;
 
     /// # Errors
     ///
-    /// Will return `CardError::InvalidIndex` for an invalid index.
+    /// Will return `CardError::InvalidIndex` for an invalid index, and `HandError::TooManyCards`
+    /// if it has more than two cards.
     fn try_from(index: &'static str) -> Result<Self, Self::Error> {
+        if crate::parse::card_count(index) > 2 {
+            return Err(HandError::TooManyCards);
+        }
         match Two::from_index(index) {
             None => Err(HandError::InvalidIndex),
             Some(five) => Ok(Two::from(five)),
@@ -222,32 +358,13 @@ impl TryFrom<&'static str> for Two {
     }
 }
 
-impl TryFrom<BinaryCard> for Two {
-    type Error = HandError;
+try_from_slice!(Two);
 
-    fn try_from(binary_card: BinaryCard) -> Result<Self, Self::Error> {
-        match binary_card.number_of_cards() {
-            0..=1 => Err(HandError::NotEnoughCards),
-            2 => {
-                let mut bc = binary_card;
-                let two = Two::new(
-                    CKCNumber::from_binary_card(bc.peel()),
-                    CKCNumber::from_binary_card(bc.peel()),
-                );
-                if two.is_valid() {
-                    Ok(two)
-                } else {
-                    Err(HandError::InvalidBinaryFormat)
-                }
-            },
-            _ => Err(HandError::TooManyCards),
-        }
-    }
-}
+try_from_binary_card!(Two);
 
 impl HandValidator for Two {
     fn are_unique(&self) -> bool {
-        self.first() != self.second()
+        crate::cards::all_unique(&self.0)
     }
 
     fn first(&self) -> CKCNumber {
@@ -270,6 +387,8 @@ impl HandValidator for Two {
     }
 }
 
+impl RankBits for Two {}
+
 impl Shifty for Two {
     fn shift_suit(&self) -> Self {
         Two::new(self.first().shift_suit(), self.second().shift_suit())
@@ -280,7 +399,7 @@ impl Shifty for Two {
 #[allow(non_snake_case)]
 mod cards_two_tests {
     use super::*;
-    use crate::CardNumber;
+    use crate::{BinaryCard, CardNumber, BC64};
     use rstest::rstest;
 
     #[test]
@@ -315,6 +434,29 @@ mod cards_two_tests {
         assert!(Two::new(CardNumber::ACE_SPADES, CardNumber::ACE_CLUBS).is_valid());
     }
 
+    #[test]
+    fn id() {
+        let ak = Two::new(CardNumber::ACE_SPADES, CardNumber::KING_CLUBS);
+
+        assert_eq!(BinaryCard::ACE_SPADES | BinaryCard::KING_CLUBS, ak.id());
+        assert_eq!(ak.id(), Two::new(CardNumber::KING_CLUBS, CardNumber::ACE_SPADES).id());
+        assert_eq!(
+            BinaryCard::ACE_SPADES,
+            Two::new(CardNumber::ACE_SPADES, CardNumber::BLANK).id()
+        );
+    }
+
+    #[test]
+    fn set_eq() {
+        let ak = Two::new(CardNumber::ACE_SPADES, CardNumber::KING_CLUBS);
+
+        assert_ne!(ak, Two::new(CardNumber::KING_CLUBS, CardNumber::ACE_SPADES));
+        assert!(ak.set_eq(&Two::new(CardNumber::KING_CLUBS, CardNumber::ACE_SPADES)));
+        assert!(ak.set_eq(&Two::new(CardNumber::KING_CLUBS, CardNumber::ACE_SPADES.flag_as_pair())));
+        assert!(!ak.set_eq(&Two::new(CardNumber::KING_CLUBS, CardNumber::ACE_HEARTS)));
+        assert!(!ak.set_eq(&Two::new(CardNumber::ACE_SPADES, CardNumber::BLANK)));
+    }
+
     #[rstest]
     #[case(20, Two::new(CardNumber::ACE_SPADES, CardNumber::ACE_CLUBS))]
     #[case(12, Two::new(CardNumber::ACE_SPADES, CardNumber::KING_SPADES))]
@@ -334,6 +476,55 @@ mod cards_two_tests {
         assert_eq!(chen_number, hand.chen_formula());
     }
 
+    #[rstest]
+    #[case(1, "A♠ A♣")]
+    #[case(1, "A♠ K♠")]
+    #[case(2, "A♠ K♣")]
+    #[case(4, "9♥ 8♥")]
+    #[case(5, "A♦ 2♦")]
+    #[case(8, "4♣ 2♣")]
+    #[case(9, "7♠ 2♦")]
+    #[case(0, "A♠ A♠")]
+    fn sklansky_group(#[case] group: u8, #[case] index: &'static str) {
+        assert_eq!(group, Two::try_from(index).unwrap().sklansky_group());
+    }
+
+    #[test]
+    fn starting_hand_tiers() {
+        let tiers = Two::try_from("J♠ T♠").unwrap().starting_hand_tiers();
+
+        assert_eq!([StartingHandTier::Chen(9), StartingHandTier::Sklansky(3)], tiers);
+    }
+
+    #[test]
+    fn equity_vs_random() {
+        let hero = Two::try_from("A♠ A♥").unwrap();
+        let board = [
+            CardNumber::ACE_DIAMONDS,
+            CardNumber::ACE_CLUBS,
+            CardNumber::KING_SPADES,
+            CardNumber::DEUCE_HEARTS,
+        ];
+
+        assert!((hero.equity_vs_random(&board).unwrap() - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn rank_counts() {
+        let mut expected = [0u8; 13];
+        expected[12] = 2;
+
+        assert_eq!(expected, Two::try_from("A♠ A♥").unwrap().rank_counts());
+        assert_eq!([0u8; 13], Two::default().rank_counts());
+    }
+
+    #[test]
+    fn suit_counts() {
+        assert_eq!([1, 1, 0, 0], Two::try_from("A♠ A♥").unwrap().suit_counts());
+        assert_eq!([0, 0, 0, 2], Two::try_from("7♣ 2♣").unwrap().suit_counts());
+        assert_eq!([0u8; 4], Two::default().suit_counts());
+    }
+
     #[test]
     fn get_gap() {
         assert_eq!(11, Two::new(CardNumber::DEUCE_CLUBS, CardNumber::ACE_CLUBS).get_gap());
@@ -348,6 +539,32 @@ mod cards_two_tests {
         assert_eq!(hand.high_card(), CardNumber::ACE_CLUBS);
     }
 
+    #[rstest]
+    #[case("A♠ A♥", ConnectorType::Pair)]
+    #[case("A♠ K♠", ConnectorType::SuitedBroadway)]
+    #[case("Q♠ T♥", ConnectorType::Broadway)]
+    #[case("A♠ 5♠", ConnectorType::SuitedWheelAce)]
+    #[case("2♦ A♠", ConnectorType::WheelAce)]
+    #[case("9♣ T♣", ConnectorType::SuitedConnector)]
+    #[case("5♦ 4♥", ConnectorType::Connector)]
+    #[case("J♠ 9♠", ConnectorType::SuitedOneGapper)]
+    #[case("8♠ 6♥", ConnectorType::OneGapper)]
+    #[case("7♥ 4♥", ConnectorType::SuitedTwoGapper)]
+    #[case("K♠ T♥", ConnectorType::Broadway)]
+    #[case("T♥ 7♠", ConnectorType::TwoGapper)]
+    #[case("A♠ 9♠", ConnectorType::SuitedUnconnected)]
+    #[case("7♠ 2♥", ConnectorType::Unconnected)]
+    fn connector_type(#[case] index: &'static str, #[case] expected: ConnectorType) {
+        assert_eq!(expected, Two::try_from(index).unwrap().connector_type());
+    }
+
+    #[test]
+    fn connector_type__invalid() {
+        assert_eq!(ConnectorType::Invalid, Two::default().connector_type());
+        assert!(ConnectorType::SuitedWheelAce.is_suited());
+        assert!(!ConnectorType::Pair.is_suited());
+    }
+
     #[test]
     fn is_connector() {
         assert!(Two::new(CardNumber::ACE_CLUBS, CardNumber::KING_SPADES).is_connector());
@@ -380,14 +597,13 @@ mod cards_two_tests {
         assert_eq!(
             Two::try_from("AS AD").unwrap().shift_suit(),
             Two::try_from("AH AC").unwrap()
-        )
+        );
     }
 
     #[test]
     fn try_from__binary_card() {
         let t = Two::try_from(BinaryCard::ACE_SPADES.fold_in(BinaryCard::ACE_DIAMONDS));
         assert!(t.is_ok());
-        assert!(!t.is_err());
         assert_eq!(Two::new(CardNumber::ACE_SPADES, CardNumber::ACE_DIAMONDS), t.unwrap());
     }
 
@@ -399,6 +615,14 @@ mod cards_two_tests {
         assert_eq!(Two::try_from(BinaryCard::BLANK).unwrap_err(), HandError::NotEnoughCards);
     }
 
+    #[test]
+    fn try_from__binary_card__overflow() {
+        assert_eq!(
+            Two::try_from(BinaryCard::ACE_SPADES | 1 << 52).unwrap_err(),
+            HandError::InvalidBinaryFormat
+        );
+    }
+
     #[test]
     fn try_from__binary_card__too_many() {
         let t = Two::try_from(
@@ -419,6 +643,30 @@ mod cards_two_tests {
         assert_eq!(t.unwrap_err(), HandError::InvalidBinaryFormat);
     }
 
+    #[test]
+    fn write_index() {
+        let mut index = String::new();
+
+        Two::try_from("A♠ K♥").unwrap().write_index(&mut index).unwrap();
+
+        assert_eq!("A♠ K♥", index);
+    }
+
+    #[test]
+    fn try_from__slice() {
+        let cards = [CardNumber::ACE_SPADES, CardNumber::KING_HEARTS];
+
+        assert_eq!(Two::from(cards), Two::try_from(&cards[..]).unwrap());
+        assert_eq!(
+            HandError::TooManyCards,
+            Two::try_from(&crate::deck::POKER_DECK[..3]).unwrap_err()
+        );
+        assert_eq!(
+            HandError::DuplicateCard,
+            Two::try_from(&[CardNumber::ACE_SPADES; 2][..]).unwrap_err()
+        );
+    }
+
     #[test]
     fn try_from__index() {
         let two = Two::try_from("J♠ T♠");
@@ -445,4 +693,10 @@ mod cards_two_tests {
 
         assert!(two.is_err());
     }
+
+    #[test]
+    fn try_from__index__too_many() {
+        assert_eq!(Two::try_from("A♠ K♠ Q♠").unwrap_err(), HandError::TooManyCards);
+        assert!(Two::from_index("A♠ K♠ Q♠").is_none());
+    }
 }
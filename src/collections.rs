@@ -0,0 +1,252 @@
+//! Fixed capacity collections, so that results of varying length don't need an allocator.
+//!
+//! Analysis functions that return a variable number of cards or combos, such as lists of live
+//! cards or outs, should return one of these rather than an `alloc::vec::Vec`, so that they can
+//! be used on targets without a heap.
+//!
+//! Some results still need the heap, since they have no useful bound or hold lists of lists:
+//!
+//! * `analysis::range::Range` holds up to 1,326 combos, too many to keep inline. So do
+//!   `NutAnalysis::hands` and the result of `analysis::bucket_hands()`, which have an entry for
+//!   each combo.
+//! * `analysis::canonical` takes hands of any length, and `Canonical::expand()` returns up to 24
+//!   hands of them.
+//! * `batch::hand_rank_values()` and `analysis::bucket_values()` return one value for each one
+//!   they're given. Use `batch::hand_rank_values_into()` to write hand rank values into a slice
+//!   instead.
+//! * `showdown::Showdown::pots` and `replay::replay()` hold a list of seats for each pot or
+//!   street.
+
+use crate::deck::DECK_SIZE;
+use crate::{CKCNumber, HandError};
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, DerefMut};
+use core::slice::Iter;
+
+/// Up to 52 cards, such as the cards left in a deck.
+pub type CardList = ArrayVec<CKCNumber, DECK_SIZE>;
+
+/// The most two card hands that can be dealt from one deck.
+pub const MAX_HANDS: usize = DECK_SIZE / 2;
+
+/// Up to 26 seat indexes, one for each two card hand that can be dealt from one deck.
+pub type SeatList = ArrayVec<usize, MAX_HANDS>;
+
+/// A `Vec` like list of up to `N` items, stored inline. It derefs to a slice of the items that
+/// have been pushed.
+#[derive(Clone, Copy)]
+pub struct ArrayVec<T, const N: usize> {
+    items: [T; N],
+    len: usize,
+}
+
+impl<T: Copy + Default, const N: usize> ArrayVec<T, N> {
+    #[must_use]
+    pub fn new() -> Self {
+        ArrayVec {
+            items: [T::default(); N],
+            len: 0,
+        }
+    }
+
+    /// Returns a list of the items in the slice.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HandError::TooManyCards` if there are more than `N` items.
+    pub fn from_slice(items: &[T]) -> Result<Self, HandError> {
+        let mut list = ArrayVec::new();
+        list.extend_from_slice(items)?;
+        Ok(list)
+    }
+
+    /// Appends every item in the slice. Nothing is appended if they won't all fit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HandError::TooManyCards` if there isn't room for all of the items.
+    pub fn extend_from_slice(&mut self, items: &[T]) -> Result<(), HandError> {
+        if self.len + items.len() > N {
+            return Err(HandError::TooManyCards);
+        }
+        self.items[self.len..self.len + items.len()].copy_from_slice(items);
+        self.len += items.len();
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> ArrayVec<T, N> {
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        &self.items[..self.len]
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.items[..self.len]
+    }
+
+    /// Appends an item to the end of the list.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HandError::TooManyCards` if the list is full.
+    pub fn push(&mut self, item: T) -> Result<(), HandError> {
+        match self.items.get_mut(self.len) {
+            Some(slot) => {
+                *slot = item;
+                self.len += 1;
+                Ok(())
+            },
+            None => Err(HandError::TooManyCards),
+        }
+    }
+}
+
+impl<T: Copy, const N: usize> ArrayVec<T, N> {
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        Some(self.items[self.len])
+    }
+}
+
+impl<T: Copy + Default, const N: usize> Default for ArrayVec<T, N> {
+    fn default() -> Self {
+        ArrayVec::new()
+    }
+}
+
+impl<T, const N: usize> Deref for ArrayVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> DerefMut for ArrayVec<T, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<T: core::fmt::Debug, const N: usize> core::fmt::Debug for ArrayVec<T, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+/// Only the pushed items are compared, not whatever is left in the rest of the storage.
+impl<T: PartialEq, const N: usize> PartialEq for ArrayVec<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for ArrayVec<T, N> {}
+
+impl<T: Hash, const N: usize> Hash for ArrayVec<T, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a ArrayVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod collections_tests {
+    use super::*;
+    use crate::CardNumber;
+
+    #[test]
+    fn push() {
+        let mut list: ArrayVec<CKCNumber, 2> = ArrayVec::new();
+
+        assert!(list.is_empty());
+        assert!(list.push(CardNumber::ACE_SPADES).is_ok());
+        assert!(list.push(CardNumber::KING_SPADES).is_ok());
+        assert!(list.is_full());
+        assert_eq!(
+            HandError::TooManyCards,
+            list.push(CardNumber::QUEEN_SPADES).unwrap_err()
+        );
+        assert_eq!(&[CardNumber::ACE_SPADES, CardNumber::KING_SPADES], list.as_slice());
+    }
+
+    #[test]
+    fn pop() {
+        let mut list = CardList::from_slice(&[CardNumber::ACE_SPADES, CardNumber::KING_SPADES]).unwrap();
+
+        assert_eq!(Some(CardNumber::KING_SPADES), list.pop());
+        assert_eq!(Some(CardNumber::ACE_SPADES), list.pop());
+        assert_eq!(None, list.pop());
+    }
+
+    #[test]
+    fn extend_from_slice() {
+        let mut list: ArrayVec<CKCNumber, 3> = ArrayVec::new();
+        list.push(CardNumber::ACE_SPADES).unwrap();
+
+        assert_eq!(
+            HandError::TooManyCards,
+            list.extend_from_slice(&[CardNumber::KING_SPADES; 3]).unwrap_err()
+        );
+        assert_eq!(1, list.len());
+        assert!(list.extend_from_slice(&[CardNumber::KING_SPADES; 2]).is_ok());
+        assert_eq!(3, list.len());
+    }
+
+    #[test]
+    fn eq() {
+        let mut a = CardList::from_slice(&[CardNumber::ACE_SPADES, CardNumber::KING_SPADES]).unwrap();
+        let b = CardList::from_slice(&[CardNumber::ACE_SPADES]).unwrap();
+        a.pop();
+
+        assert_eq!(a, b);
+        assert_eq!(DECK_SIZE, a.capacity());
+    }
+
+    #[test]
+    fn deref() {
+        let mut list = CardList::from_slice(&[CardNumber::DEUCE_SPADES, CardNumber::ACE_SPADES]).unwrap();
+        list.sort_unstable();
+
+        assert_eq!(CardNumber::DEUCE_SPADES, list[0]);
+        assert_eq!(2, (&list).into_iter().count());
+        assert!(list.contains(&CardNumber::ACE_SPADES));
+    }
+}
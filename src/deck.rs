@@ -1,4 +1,15 @@
+use crate::cards::binary_card::{BinaryCard, BC64};
+#[cfg(feature = "rand")]
+use crate::cards::five::Five;
+use crate::collections::CardList;
+#[cfg(feature = "rand")]
+use crate::HandError;
 use crate::{CKCNumber, CardNumber};
+use core::slice::Iter;
+#[cfg(feature = "rand")]
+use rand::seq::SliceRandom;
+#[cfg(feature = "rand")]
+use rand::Rng;
 
 pub const DECK_SIZE: usize = 52;
 
@@ -7,7 +18,8 @@ pub const DECK_SIZE: usize = 52;
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Deck([CKCNumber; DECK_SIZE]);
 
-pub const POKER_DECK: Deck = Deck([
+/// Every card in a Standard52 deck, sorted from the `A♠` down to the `2♣`.
+pub const POKER_DECK: [CKCNumber; DECK_SIZE] = [
     CardNumber::ACE_SPADES,
     CardNumber::KING_SPADES,
     CardNumber::QUEEN_SPADES,
@@ -60,9 +72,14 @@ pub const POKER_DECK: Deck = Deck([
     CardNumber::FOUR_CLUBS,
     CardNumber::TREY_CLUBS,
     CardNumber::DEUCE_CLUBS,
-]);
+];
 
 impl Deck {
+    #[must_use]
+    pub fn new() -> Self {
+        Deck(POKER_DECK)
+    }
+
     #[must_use]
     pub fn arr(&self) -> [CKCNumber; DECK_SIZE] {
         self.0
@@ -71,29 +88,157 @@ impl Deck {
     #[must_use]
     pub fn get(index: usize) -> CKCNumber {
         if index < Deck::len() {
-            POKER_DECK.0[index]
+            POKER_DECK[index]
         } else {
             CardNumber::BLANK
         }
     }
 
+    /// Iterates over every card in the `POKER_DECK`.
+    pub fn iter() -> Iter<'static, CKCNumber> {
+        POKER_DECK.iter()
+    }
+
     #[must_use]
     pub fn len() -> usize {
         DECK_SIZE
     }
+
+    /// Returns the index of the card in the `POKER_DECK`, or `None` if it isn't a valid card.
+    /// Multiples flags are ignored.
+    #[must_use]
+    pub fn position(card: CKCNumber) -> Option<usize> {
+        let card = card & CardNumber::MULTIPLES_FILTER;
+        POKER_DECK.iter().position(|c| *c == card)
+    }
+
+    /// Returns every card that isn't dead, in deck order.
+    #[must_use]
+    pub fn live(dead: BinaryCard) -> CardList {
+        let mut live = CardList::new();
+        for card in without(dead) {
+            // There are never more live cards than there are cards in the deck.
+            let _ = live.push(card);
+        }
+        live
+    }
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Deck::new()
+    }
+}
+
+impl IntoIterator for Deck {
+    type Item = CKCNumber;
+    type IntoIter = core::array::IntoIter<CKCNumber, DECK_SIZE>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Deck {
+    type Item = &'a CKCNumber;
+    type IntoIter = Iter<'a, CKCNumber>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// Iterates over the cards in the deck that aren't in `cards`, in deck order, without
+/// allocating. `cards` can be a hand, a board, or a `BinaryCard` of everything that's been
+/// dealt so far.
+///
+/// ```
+/// use ckc_rs::cards::binary_card::BinaryCard;
+/// use ckc_rs::cards::three::Three;
+/// use ckc_rs::cards::two::Two;
+/// use ckc_rs::deck::without;
+/// use ckc_rs::{CardSuit, PokerCard};
+///
+/// let hero = Two::try_from("A♠ K♠").unwrap();
+/// let flop = Three::try_from("Q♠ J♠ 2♥").unwrap();
+///
+/// let spades = without(BinaryCard::from(hero) | BinaryCard::from(flop))
+///     .filter(|card| card.get_card_suit() == CardSuit::SPADES)
+///     .count();
+///
+/// assert_eq!(50, without(hero).count());
+/// assert_eq!(9, spades);
+/// ```
+pub fn without(cards: impl Into<BinaryCard>) -> impl Iterator<Item = CKCNumber> {
+    let dead = cards.into();
+    POKER_DECK
+        .into_iter()
+        .filter(move |card| !dead.has(BinaryCard::from_ckc(*card)))
+}
+
+/// Deals out the rest of a board, keeping the cards already on it in order and filling the
+/// remaining streets with cards drawn uniformly from those that aren't on the board or dead.
+///
+/// # Errors
+///
+/// Returns `HandError::TooManyCards` if the partial board has more than five cards,
+/// `HandError::InvalidCard` if any of its cards are invalid, `HandError::DuplicateCard` if any of
+/// them repeat or are dead, and `HandError::NotEnoughCards` if there aren't enough live cards
+/// left to finish the board.
+#[cfg(feature = "rand")]
+pub fn complete_board<R: Rng + ?Sized>(
+    partial: &[CKCNumber],
+    dead: BinaryCard,
+    rng: &mut R,
+) -> Result<Five, HandError> {
+    if partial.len() > 5 {
+        return Err(HandError::TooManyCards);
+    }
+
+    let mut used = dead;
+    for card in partial {
+        let bc = BinaryCard::from_ckc(*card);
+        if bc == BinaryCard::BLANK {
+            return Err(HandError::InvalidCard);
+        }
+        if used.has(bc) {
+            return Err(HandError::DuplicateCard);
+        }
+        used = used.fold_in(bc);
+    }
+
+    let needed = 5 - partial.len();
+    let mut live = Deck::live(used);
+    if live.len() < needed {
+        return Err(HandError::NotEnoughCards);
+    }
+    let (drawn, _) = live.partial_shuffle(rng, needed);
+
+    let mut cards = [CardNumber::BLANK; 5];
+    for (i, card) in partial.iter().chain(drawn.iter()).enumerate() {
+        cards[i] = *card;
+    }
+    Ok(Five::from(cards))
 }
 
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod deck_tests {
     use super::*;
+    use crate::cards::seven::Seven;
+    use crate::cards::HandValidator;
+    use alloc::vec::Vec;
+    #[cfg(feature = "rand")]
+    use rand::rngs::SmallRng;
+    #[cfg(feature = "rand")]
+    use rand::SeedableRng;
 
     #[test]
     fn arr() {
         for i in 0..Deck::len() {
             let card = Deck::get(i);
 
-            assert_eq!(card, POKER_DECK.arr()[i]);
+            assert_eq!(card, Deck::new().arr()[i]);
         }
         assert_eq!(DECK_SIZE, Deck::len());
         assert_eq!(Deck::get(Deck::len()), CardNumber::BLANK);
@@ -101,12 +246,120 @@ mod deck_tests {
 
     #[test]
     fn get() {
-        for i in 0..Deck::len() {
+        for (i, expected) in POKER_DECK.iter().enumerate() {
             let card = Deck::get(i);
 
-            assert_eq!(card, POKER_DECK.0[i]);
+            assert_eq!(card, *expected);
         }
         assert_eq!(DECK_SIZE, Deck::len());
         assert_eq!(Deck::get(Deck::len()), CardNumber::BLANK);
     }
+
+    #[test]
+    fn iter() {
+        assert_eq!(DECK_SIZE, Deck::iter().count());
+        assert_eq!(Some(&CardNumber::ACE_SPADES), Deck::iter().next());
+        assert_eq!(
+            Deck::iter().copied().collect::<Vec<_>>(),
+            Deck::new().into_iter().collect::<Vec<_>>()
+        );
+        assert_eq!(DECK_SIZE, (&Deck::default()).into_iter().count());
+    }
+
+    #[test]
+    fn position() {
+        for (i, card) in Deck::iter().enumerate() {
+            assert_eq!(Some(i), Deck::position(*card));
+        }
+        assert_eq!(Some(0), Deck::position(CardNumber::ACE_SPADES | CardNumber::PAIR));
+        assert_eq!(None, Deck::position(CardNumber::BLANK));
+    }
+
+    #[test]
+    fn live() {
+        let live = Deck::live(BinaryCard::ACE_SPADES | BinaryCard::DEUCE_CLUBS);
+
+        assert_eq!(50, live.len());
+        assert_eq!(CardNumber::KING_SPADES, live[0]);
+        assert_eq!(CardNumber::TREY_CLUBS, live[49]);
+        assert!(Deck::live(BinaryCard::ALL).is_empty());
+        assert_eq!(DECK_SIZE, Deck::live(BinaryCard::BLANK).len());
+    }
+
+    #[test]
+    fn without() {
+        let seven = Seven::try_from("A♠ K♠ Q♠ J♠ T♠ 2♣ 2♦").unwrap();
+
+        let live: Vec<CKCNumber> = super::without(seven).collect();
+
+        assert_eq!(45, live.len());
+        assert_eq!(CardNumber::NINE_SPADES, live[0]);
+        assert_eq!(CardNumber::TREY_CLUBS, live[44]);
+        assert!(live.iter().all(|card| !seven.iter().any(|dead| dead == card)));
+        assert_eq!(0, super::without(BinaryCard::ALL).count());
+        assert_eq!(
+            Deck::iter().copied().collect::<Vec<_>>(),
+            super::without(BinaryCard::BLANK).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn complete_board() {
+        let mut rng = SmallRng::seed_from_u64(42);
+        let flop = [
+            CardNumber::ACE_SPADES,
+            CardNumber::KING_SPADES,
+            CardNumber::QUEEN_SPADES,
+        ];
+        let dead = BinaryCard::JACK_SPADES | BinaryCard::TEN_SPADES;
+
+        for _ in 0..100 {
+            let board = super::complete_board(&flop, dead, &mut rng).unwrap();
+
+            assert!(board.is_valid());
+            assert_eq!(flop, board.to_arr()[..3]);
+            assert_eq!(0, BinaryCard::from_five(board) & dead);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn complete_board__complete() {
+        let mut rng = SmallRng::seed_from_u64(42);
+        let board = Five::try_from("A♠ K♠ Q♠ J♠ T♠").unwrap();
+
+        assert_eq!(
+            board,
+            super::complete_board(&board.to_arr(), BinaryCard::BLANK, &mut rng).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn complete_board__errors() {
+        let mut rng = SmallRng::seed_from_u64(42);
+        let all_but_three = BinaryCard::DECK[3..].iter().fold(0, |bits, bc| bits | bc);
+
+        assert_eq!(
+            HandError::DuplicateCard,
+            super::complete_board(&[CardNumber::ACE_SPADES], BinaryCard::ACE_SPADES, &mut rng).unwrap_err()
+        );
+        assert_eq!(
+            HandError::DuplicateCard,
+            super::complete_board(&[CardNumber::ACE_SPADES, CardNumber::ACE_SPADES], 0, &mut rng).unwrap_err()
+        );
+        assert_eq!(
+            HandError::InvalidCard,
+            super::complete_board(&[CardNumber::BLANK], 0, &mut rng).unwrap_err()
+        );
+        assert_eq!(
+            HandError::TooManyCards,
+            super::complete_board(&POKER_DECK[..6], 0, &mut rng).unwrap_err()
+        );
+        assert_eq!(
+            HandError::NotEnoughCards,
+            super::complete_board(&[], all_but_three, &mut rng).unwrap_err()
+        );
+    }
 }
@@ -0,0 +1,290 @@
+//! Evaluators for hands of five faces with ranks but no suits, such as poker dice, where five
+//! dice with the faces nine through ace are ranked like a poker hand.
+//!
+//! `evaluate()` works for any type that implements `Face`, so a game only has to say how many
+//! faces there are and whether the highest one can also play low in a straight. `CardRank`
+//! implements it as well, which ranks a hand of cards the same as the Cactus Kev evaluator would
+//! if it ignored flushes.
+//!
+//! Every value in this module follows the same rule as `HandRankValue`: lower is better, and zero
+//! means the hand couldn't be ranked.
+
+use crate::CardRank;
+
+/// The most faces that `evaluate()` can handle, since each face is encoded in four bits.
+pub const MAX_FACES: u8 = 16;
+
+/// One of the faces that make up a hand, numbered from 0 for the lowest face up.
+pub trait Face: Copy {
+    /// The number of distinct faces, up to `MAX_FACES`.
+    const FACES: u8;
+
+    /// True if the highest face also plays below the lowest in a straight, the same as an ace
+    /// in a wheel.
+    const HIGHEST_PLAYS_LOW: bool = false;
+
+    /// Returns the face from 0 for the lowest to `FACES - 1` for the highest. Anything else makes
+    /// the hand invalid.
+    fn face(&self) -> u8;
+}
+
+/// A die for poker dice, which runs from nine up to ace.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Die {
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl Die {
+    /// The six faces, from `Nine` up to `Ace`.
+    pub const ALL: [Die; 6] = [Die::Nine, Die::Ten, Die::Jack, Die::Queen, Die::King, Die::Ace];
+
+    #[must_use]
+    pub fn from_char(symbol: char) -> Option<Die> {
+        match symbol {
+            '9' => Some(Die::Nine),
+            'T' | 't' | '0' => Some(Die::Ten),
+            'J' | 'j' => Some(Die::Jack),
+            'Q' | 'q' => Some(Die::Queen),
+            'K' | 'k' => Some(Die::King),
+            'A' | 'a' => Some(Die::Ace),
+            _ => None,
+        }
+    }
+}
+
+impl Face for Die {
+    const FACES: u8 = 6;
+
+    fn face(&self) -> u8 {
+        *self as u8
+    }
+}
+
+/// Deuces are 0 and aces are 12, and aces also play low in a wheel. `BLANK` is invalid.
+impl Face for CardRank {
+    const FACES: u8 = 13;
+    const HIGHEST_PLAYS_LOW: bool = true;
+
+    fn face(&self) -> u8 {
+        (*self as u8).wrapping_sub(2)
+    }
+}
+
+/// The name of a hand of faces, from best to worst.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum FaceHandName {
+    FiveOfAKind,
+    FourOfAKind,
+    FullHouse,
+    Straight,
+    ThreeOfAKind,
+    TwoPair,
+    Pair,
+    HighCard,
+    Invalid,
+}
+
+/// The rank of a hand of faces. Lower is better, and zero is an invalid hand.
+///
+/// The name is stored above the faces that play, so any full house beats any straight whatever
+/// the number of faces.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct FaceRank(u32);
+
+impl FaceRank {
+    /// The bit where the name starts, above five four bit faces.
+    const NAME_SHIFT: u32 = 20;
+
+    #[must_use]
+    pub fn value(self) -> u32 {
+        self.0
+    }
+
+    #[must_use]
+    pub fn name(self) -> FaceHandName {
+        match self.0.checked_sub(1).map(|value| value >> FaceRank::NAME_SHIFT) {
+            Some(0) => FaceHandName::FiveOfAKind,
+            Some(1) => FaceHandName::FourOfAKind,
+            Some(2) => FaceHandName::FullHouse,
+            Some(3) => FaceHandName::Straight,
+            Some(4) => FaceHandName::ThreeOfAKind,
+            Some(5) => FaceHandName::TwoPair,
+            Some(6) => FaceHandName::Pair,
+            Some(7) => FaceHandName::HighCard,
+            _ => FaceHandName::Invalid,
+        }
+    }
+
+    #[must_use]
+    pub fn is_valid(self) -> bool {
+        self.0 != 0
+    }
+}
+
+/// Ranks five faces, by how many of each face there are and then by the faces themselves, the
+/// same as a poker hand without flushes.
+///
+/// ```
+/// use ckc_rs::dice::{evaluate, Die, FaceHandName};
+///
+/// let full_house = evaluate([Die::Ace, Die::Ace, Die::Nine, Die::Ace, Die::Nine]);
+/// let straight = evaluate([Die::Ten, Die::Jack, Die::Queen, Die::King, Die::Ace]);
+///
+/// assert_eq!(FaceHandName::FullHouse, full_house.name());
+/// assert!(full_house < straight);
+/// ```
+#[must_use]
+pub fn evaluate<F: Face>(hand: [F; 5]) -> FaceRank {
+    if F::FACES > MAX_FACES {
+        return FaceRank::default();
+    }
+    let mut counts = [0u8; MAX_FACES as usize];
+    for face in hand.iter().map(Face::face) {
+        if face >= F::FACES {
+            return FaceRank::default();
+        }
+        counts[usize::from(face)] += 1;
+    }
+
+    // Each face that's in the hand, with how many of it there are, most common first and then
+    // highest first.
+    let mut groups = [(0u8, 0u8); 5];
+    let mut len = 0;
+    for face in 0..F::FACES {
+        let count = counts[usize::from(face)];
+        if count > 0 {
+            groups[len] = (count, face);
+            len += 1;
+        }
+    }
+    groups[..len].sort_unstable_by(|a, b| b.cmp(a));
+    let groups = &groups[..len];
+
+    let straight_high = match groups {
+        [(_, high), .., (_, low)] if len == 5 && high - low == 4 => Some(*high),
+        [(_, high), (_, second), ..] if len == 5 && F::HIGHEST_PLAYS_LOW && *high == F::FACES - 1 && *second == 3 => {
+            Some(*second)
+        },
+        _ => None,
+    };
+
+    let name = match (groups[0].0, groups.get(1).map(|group| group.0)) {
+        (5, _) => FaceHandName::FiveOfAKind,
+        (4, _) => FaceHandName::FourOfAKind,
+        (3, Some(2)) => FaceHandName::FullHouse,
+        (3, _) => FaceHandName::ThreeOfAKind,
+        (2, Some(2)) => FaceHandName::TwoPair,
+        (2, _) => FaceHandName::Pair,
+        _ if straight_high.is_some() => FaceHandName::Straight,
+        _ => FaceHandName::HighCard,
+    };
+
+    // The faces that play, flipped so that higher faces encode lower.
+    let faces = match straight_high {
+        Some(high) => u32::from(F::FACES - 1 - high),
+        None => groups
+            .iter()
+            .fold(0, |faces, (_, face)| (faces << 4) | u32::from(F::FACES - 1 - face)),
+    };
+
+    FaceRank(1 + ((name as u32) << FaceRank::NAME_SHIFT) + faces)
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod dice_tests {
+    use super::*;
+    use crate::cards::five::Five;
+    use crate::cards::HandRanker;
+    use crate::cards::HandValidator;
+    use crate::PokerCard;
+    use rstest::rstest;
+
+    fn dice(index: &str) -> [Die; 5] {
+        let mut dice = [Die::Nine; 5];
+        for (die, symbol) in dice.iter_mut().zip(index.chars()) {
+            *die = Die::from_char(symbol).unwrap();
+        }
+        dice
+    }
+
+    #[rstest]
+    #[case("AAAAA", FaceHandName::FiveOfAKind)]
+    #[case("9KKKK", FaceHandName::FourOfAKind)]
+    #[case("QQJJJ", FaceHandName::FullHouse)]
+    #[case("TJQKA", FaceHandName::Straight)]
+    #[case("9TJQK", FaceHandName::Straight)]
+    #[case("9TTTA", FaceHandName::ThreeOfAKind)]
+    #[case("99AKK", FaceHandName::TwoPair)]
+    #[case("9TJQQ", FaceHandName::Pair)]
+    #[case("9TJQA", FaceHandName::HighCard)]
+    fn evaluate__name(#[case] index: &str, #[case] expected: FaceHandName) {
+        assert_eq!(expected, evaluate(dice(index)).name());
+    }
+
+    #[test]
+    fn evaluate__order() {
+        let hands = [
+            "AAAAA", "99999", "AAAAK", "KKKKA", "AAAKK", "99QQQ", "TJQKA", "9TJQK", "AAAKQ", "AKKQQ", "9TJQQ", "AK99Q",
+            "AKQJ9", "AKQT9",
+        ];
+
+        for pair in hands.windows(2) {
+            assert!(evaluate(dice(pair[0])) < evaluate(dice(pair[1])), "{pair:?}");
+        }
+        assert_eq!(evaluate(dice("QKJAT")), evaluate(dice("AKQJT")));
+    }
+
+    #[test]
+    fn evaluate__invalid() {
+        let rank = evaluate([
+            CardRank::ACE,
+            CardRank::KING,
+            CardRank::QUEEN,
+            CardRank::JACK,
+            CardRank::BLANK,
+        ]);
+
+        assert!(!rank.is_valid());
+        assert_eq!(FaceHandName::Invalid, rank.name());
+        assert_eq!(FaceHandName::Invalid, FaceRank::default().name());
+    }
+
+    /// Without flushes, ranking the `CardRanks` orders hands the same as the Cactus Kev evaluator.
+    #[test]
+    fn evaluate__card_ranks() {
+        let hands = [
+            "A♠ A♥ A♦ A♣ K♠",
+            "K♠ K♥ K♦ 2♣ 2♠",
+            "A♠ K♥ Q♦ J♣ T♠",
+            "5♠ 4♥ 3♦ 2♣ A♠",
+            "7♠ 7♥ 7♦ A♣ K♠",
+            "A♠ A♥ 2♦ 2♣ K♠",
+            "K♠ K♥ Q♦ Q♣ A♠",
+            "2♠ 2♥ A♦ K♣ Q♠",
+            "A♠ K♥ Q♦ J♣ 9♠",
+            "7♠ 5♥ 4♦ 3♣ 2♠",
+        ];
+        let ranks = |index: &'static str| {
+            let five = Five::try_from(index).unwrap();
+            let mut ranks = [CardRank::BLANK; 5];
+            for (rank, card) in ranks.iter_mut().zip(five.iter()) {
+                *rank = card.get_card_rank();
+            }
+            (five.hand_rank_value(), evaluate(ranks))
+        };
+
+        for pair in hands.windows(2) {
+            let (a_value, a_rank) = ranks(pair[0]);
+            let (b_value, b_rank) = ranks(pair[1]);
+            assert!(a_value < b_value);
+            assert!(a_rank < b_rank, "{pair:?}");
+        }
+        assert_eq!(FaceHandName::Straight, ranks("5♠ 4♥ 3♦ 2♣ A♠").1.name());
+    }
+}
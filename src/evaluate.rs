@@ -0,0 +1,838 @@
+use crate::cards::binary_card::{BinaryCard, BC64};
+use crate::cards::five::Five;
+use crate::cards::seven::Seven;
+use crate::cards::six::Six;
+use crate::cards::HandValidator;
+use crate::cards::{HandRanker, Permutator};
+use crate::hand_rank::{HandRank, HandRankValue, NO_HAND_RANK_VALUE};
+use crate::math::next_combination;
+use crate::{CKCNumber, CardNumber, HandError};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+pub const POSSIBLE_COMBINATIONS: usize = 7937;
+
+/// The number of distinct five card hands with at least two cards of the same rank, which is the
+/// size of the products and values tables in an `Evaluator`.
+pub const MULTIPLES_TABLE_SIZE: usize = 4888;
+
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn five_cards(five_cards: [CKCNumber; 5]) -> HandRankValue {
+    Five::from(five_cards).hand_rank_value_validated()
+}
+
+#[must_use]
+#[deprecated(since = "0.1.9", note = "use Five.is_flush()")]
+pub fn is_flush(five_cards: [CKCNumber; 5]) -> bool {
+    (five_cards[0] & five_cards[1] & five_cards[2] & five_cards[3] & five_cards[4] & CardNumber::SUIT_FILTER) != 0
+}
+
+/// Returns a value that is made up of performing an or operation on all of the
+/// rank bit flags of the `PokerCard`.
+#[must_use]
+#[deprecated(since = "0.1.9", note = "use Five.or_rank_bits()")]
+pub fn or_rank_bits(five_cards: [CKCNumber; 5]) -> usize {
+    Five::from(five_cards).or_rank_bits() as usize
+}
+
+/// The result of a hand from the perspective of the first player in a `Comparison`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Tie,
+}
+
+impl From<Ordering> for Outcome {
+    fn from(ordering: Ordering) -> Self {
+        match ordering {
+            Ordering::Greater => Outcome::Win,
+            Ordering::Less => Outcome::Loss,
+            Ordering::Equal => Outcome::Tie,
+        }
+    }
+}
+
+/// What separated the two hands in a `Comparison`.
+///
+/// * `Name` - the hands are different types, such as a flush against a straight.
+/// * `Class` - the hands are the same type but different classes, such as aces full against kings full.
+/// * `Kicker` - the hands are the same class, and the side cards decided it.
+/// * `Nothing` - the hands tied.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Decider {
+    Name,
+    Class,
+    Kicker,
+    Nothing,
+}
+
+/// The outcome of a showdown between two hands, along with both of their `HandRanks`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Comparison {
+    pub outcome: Outcome,
+    pub decider: Decider,
+    pub a: HandRank,
+    pub b: HandRank,
+}
+
+impl Comparison {
+    #[must_use]
+    pub fn new(a: HandRank, b: HandRank) -> Self {
+        let outcome = Outcome::from(a.cmp(&b));
+        let decider = if outcome == Outcome::Tie {
+            Decider::Nothing
+        } else if a.name != b.name {
+            Decider::Name
+        } else if a.class != b.class {
+            Decider::Class
+        } else {
+            Decider::Kicker
+        };
+
+        Comparison { outcome, decider, a, b }
+    }
+
+    /// Returns the `HandRank` of the winning hand, or `None` if it's a tie.
+    #[must_use]
+    pub fn winner(&self) -> Option<HandRank> {
+        match self.outcome {
+            Outcome::Win => Some(self.a),
+            Outcome::Loss => Some(self.b),
+            Outcome::Tie => None,
+        }
+    }
+}
+
+/// Compares the first `Five` card hand against the second. Invalid hands lose to valid ones.
+#[must_use]
+pub fn compare(a: Five, b: Five) -> Comparison {
+    Comparison::new(a.hand_rank_validated(), b.hand_rank_validated())
+}
+
+/// Compares the best five cards of the first `Seven` card hand against the second.
+#[must_use]
+pub fn compare_seven(a: Seven, b: Seven) -> Comparison {
+    Comparison::new(a.hand_rank_validated(), b.hand_rank_validated())
+}
+
+/// Returns the `HandRank` and the best `Five` cards out of five to nine cards, walking every
+/// five card combination of however many cards are passed in.
+///
+/// # Errors
+///
+/// Returns `HandError::NotEnoughCards` for fewer than five cards, `HandError::TooManyCards` for
+/// more than nine, `HandError::InvalidCard` if any card is invalid, and
+/// `HandError::DuplicateCard` if any card appears more than once.
+pub fn best_five_of(cards: &[CKCNumber]) -> Result<(HandRank, Five), HandError> {
+    match cards.len() {
+        0..=4 => return Err(HandError::NotEnoughCards),
+        5..=9 => {},
+        _ => return Err(HandError::TooManyCards),
+    }
+
+    let mut bits = BinaryCard::BLANK;
+    for card in cards {
+        let bc = BinaryCard::from_ckc(*card);
+        if bc == BinaryCard::BLANK {
+            return Err(HandError::InvalidCard);
+        }
+        if bits.has(bc) {
+            return Err(HandError::DuplicateCard);
+        }
+        bits = bits.fold_in(bc);
+    }
+
+    let mut best_hrv: HandRankValue = 0;
+    let mut best_hand = Five::default();
+    let mut indexes = [0usize, 1, 2, 3, 4];
+    loop {
+        let hand = Five::from(indexes.map(|i| cards[i]));
+        let hrv = hand.hand_rank_value();
+        if best_hrv == 0 || (hrv != 0 && hrv < best_hrv) {
+            best_hrv = hrv;
+            best_hand = hand;
+        }
+        if !next_combination(&mut indexes, cards.len()) {
+            break;
+        }
+    }
+
+    Ok((HandRank::from(best_hrv), best_hand.sort()))
+}
+
+/// Returns the `HandRank` of the best five out of five to nine cards read straight from an
+/// iterator. The cards are collected into a stack buffer, so nothing is allocated.
+///
+/// # Errors
+///
+/// Same as `best_five_of()`. The iterator is only read one card past the ninth, so an endless
+/// iterator returns `HandError::TooManyCards`.
+pub fn rank_iter<I: IntoIterator<Item = CKCNumber>>(iter: I) -> Result<HandRank, HandError> {
+    let mut buffer = [CKCNumber::default(); 9];
+    let mut len = 0;
+    for card in iter {
+        if len == buffer.len() {
+            return Err(HandError::TooManyCards);
+        }
+        buffer[len] = card;
+        len += 1;
+    }
+    best_five_of(&buffer[..len]).map(|(rank, _)| rank)
+}
+
+/// The four lookup tables of the Cactus Kev evaluator, so that a game with different hand values,
+/// such as short deck, can rank hands with its own tables while sharing the rest of the code.
+/// `Evaluator::default()` uses the same tables as `HandRanker`.
+///
+/// * `flushes` and `unique_5` have an entry for every `Five::or_rank_bits()`, for hands that
+///   are flushes and hands with five distinct ranks that aren't. `NO_HAND_RANK_VALUE` means no
+///   such hand, and the lookup moves on to the next table, so a flush missing from `flushes` is
+///   ranked by `unique_5`.
+/// * `products` holds `Five::multiply_primes()` for every hand with a repeated rank, in
+///   ascending order, and `values` holds the value of the hand at the same index.
+#[derive(Clone, Copy, Debug)]
+pub struct Evaluator<'a> {
+    flushes: &'a [HandRankValue; POSSIBLE_COMBINATIONS],
+    unique_5: &'a [HandRankValue; POSSIBLE_COMBINATIONS],
+    products: &'a [u32; MULTIPLES_TABLE_SIZE],
+    values: &'a [HandRankValue; MULTIPLES_TABLE_SIZE],
+}
+
+impl<'a> Evaluator<'a> {
+    #[must_use]
+    pub fn new(
+        flushes: &'a [HandRankValue; POSSIBLE_COMBINATIONS],
+        unique_5: &'a [HandRankValue; POSSIBLE_COMBINATIONS],
+        products: &'a [u32; MULTIPLES_TABLE_SIZE],
+        values: &'a [HandRankValue; MULTIPLES_TABLE_SIZE],
+    ) -> Self {
+        Evaluator {
+            flushes,
+            unique_5,
+            products,
+            values,
+        }
+    }
+
+    /// Returns the value of the five cards, without checking that they're valid.
+    #[must_use]
+    pub fn five(&self, five: &Five) -> HandRankValue {
+        let index = five.or_rank_bits() as usize;
+        if five.is_flush() {
+            if let Some(&value) = self.flushes.get(index).filter(|value| **value != NO_HAND_RANK_VALUE) {
+                return value;
+            }
+        }
+        match self.unique_5.get(index) {
+            Some(&value) if value != NO_HAND_RANK_VALUE => value,
+            _ => u32::try_from(five.multiply_primes())
+                .ok()
+                .and_then(|product| self.products.binary_search(&product).ok())
+                .map_or(NO_HAND_RANK_VALUE, |i| self.values[i]),
+        }
+    }
+
+    /// Returns the value of the best five cards in the hand, along with those cards, the same as
+    /// `HandRanker::hand_rank_value_and_hand()` but with this evaluator's tables.
+    #[must_use]
+    pub fn best<T: Permutator>(&self, hand: &T) -> (HandRankValue, Five) {
+        let mut best_value = NO_HAND_RANK_VALUE;
+        let mut best_hand = Five::default();
+
+        for permutation in T::PERMUTATIONS {
+            let five = hand.five_from_permutation(*permutation);
+            let value = self.five(&five);
+            if best_value == NO_HAND_RANK_VALUE || value != NO_HAND_RANK_VALUE && value < best_value {
+                best_value = value;
+                best_hand = five;
+            }
+        }
+
+        (best_value, best_hand.sort())
+    }
+}
+
+impl Default for Evaluator<'static> {
+    fn default() -> Self {
+        Evaluator::new(
+            &crate::lookups::FLUSHES,
+            &crate::lookups::UNIQUE_5,
+            &crate::lookups::PRODUCTS,
+            &crate::lookups::VALUES,
+        )
+    }
+}
+
+/// Six known cards, such as hole cards and a turn board, prepared for ranking with each
+/// possible seventh card.
+///
+/// Of the 21 five card hands in seven cards, six don't use the seventh card at all. Those are
+/// evaluated once up front, so ranking each seventh card only takes the 15 hands that do
+/// include it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PartialEval {
+    six: [CKCNumber; 6],
+    best: HandRankValue,
+}
+
+impl PartialEval {
+    /// The four card subsets of the six cards, which make up the rest of every five card hand
+    /// that includes the seventh card.
+    const QUADS: [[usize; 4]; 15] = [
+        [0, 1, 2, 3],
+        [0, 1, 2, 4],
+        [0, 1, 2, 5],
+        [0, 1, 3, 4],
+        [0, 1, 3, 5],
+        [0, 1, 4, 5],
+        [0, 2, 3, 4],
+        [0, 2, 3, 5],
+        [0, 2, 4, 5],
+        [0, 3, 4, 5],
+        [1, 2, 3, 4],
+        [1, 2, 3, 5],
+        [1, 2, 4, 5],
+        [1, 3, 4, 5],
+        [2, 3, 4, 5],
+    ];
+
+    #[must_use]
+    pub fn new(six: Six) -> Self {
+        PartialEval {
+            six: six.to_arr(),
+            best: six.hand_rank_value(),
+        }
+    }
+
+    /// The `HandRankValue` of the best five of the six known cards.
+    #[must_use]
+    pub fn six_rank_value(&self) -> HandRankValue {
+        self.best
+    }
+
+    /// Returns the same `HandRankValue` as `Seven::hand_rank_value()` for the six cards plus
+    /// the passed in card.
+    #[must_use]
+    pub fn rank_with(&self, card: CKCNumber) -> HandRankValue {
+        let mut best = self.best;
+        for quad in PartialEval::QUADS {
+            let hrv = Five::new(
+                self.six[quad[0]],
+                self.six[quad[1]],
+                self.six[quad[2]],
+                self.six[quad[3]],
+                card,
+            )
+            .hand_rank_value();
+            if best == 0 || (hrv != 0 && hrv < best) {
+                best = hrv;
+            }
+        }
+        best
+    }
+}
+
+/// A fixed size, open addressing cache of `Seven` card `HandRankValues`, keyed by their
+/// `BinaryCard`. Equity loops that deal the same seven cards over and over, such as every
+/// villain hand on a shared board, can use it to skip the 21 five card evaluations.
+///
+/// The table never grows. When every slot a key can probe is taken, the entry in the key's
+/// first slot is replaced. With the `cache-stats` feature, the cache also counts its hits and
+/// misses.
+#[derive(Clone, Debug)]
+pub struct EvalCache {
+    keys: Vec<BinaryCard>,
+    values: Vec<HandRankValue>,
+    len: usize,
+    #[cfg(feature = "cache-stats")]
+    hits: u64,
+    #[cfg(feature = "cache-stats")]
+    misses: u64,
+}
+
+impl EvalCache {
+    /// The number of slots in an `EvalCache::default()`.
+    pub const DEFAULT_SLOTS: usize = 1 << 16;
+
+    /// How many slots past the first a key is looked for in.
+    const PROBES: usize = 8;
+
+    /// Returns an empty cache with room for at least the passed in number of entries, rounded
+    /// up to a power of two.
+    #[must_use]
+    pub fn new(slots: usize) -> Self {
+        let slots = slots.max(1).next_power_of_two();
+        EvalCache {
+            keys: alloc::vec![BinaryCard::BLANK; slots],
+            values: alloc::vec![0; slots],
+            len: 0,
+            #[cfg(feature = "cache-stats")]
+            hits: 0,
+            #[cfg(feature = "cache-stats")]
+            misses: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.keys.fill(BinaryCard::BLANK);
+        self.len = 0;
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the cached value for the seven cards, if there is one.
+    #[must_use]
+    pub fn get(&self, key: BinaryCard) -> Option<HandRankValue> {
+        self.slots(key).find(|i| self.keys[*i] == key).map(|i| self.values[i])
+    }
+
+    /// Caches the value for the seven cards.
+    pub fn insert(&mut self, key: BinaryCard, value: HandRankValue) {
+        if key == BinaryCard::BLANK {
+            return;
+        }
+        let slot = self
+            .slots(key)
+            .find(|i| self.keys[*i] == key || self.keys[*i] == BinaryCard::BLANK)
+            .unwrap_or_else(|| self.home(key));
+        if self.keys[slot] == BinaryCard::BLANK {
+            self.len += 1;
+        }
+        self.keys[slot] = key;
+        self.values[slot] = value;
+    }
+
+    /// Returns the `HandRankValue` of the `Seven`, only evaluating it if it isn't already
+    /// cached. Hands that don't have seven distinct cards are evaluated but never cached.
+    pub fn hand_rank_value(&mut self, seven: Seven) -> HandRankValue {
+        let key = BinaryCard::from_seven(seven);
+        if key.number_of_cards() != 7 {
+            return seven.hand_rank_value();
+        }
+        if let Some(value) = self.get(key) {
+            #[cfg(feature = "cache-stats")]
+            {
+                self.hits += 1;
+            }
+            return value;
+        }
+        #[cfg(feature = "cache-stats")]
+        {
+            self.misses += 1;
+        }
+        let value = seven.hand_rank_value();
+        self.insert(key, value);
+        value
+    }
+
+    #[cfg(feature = "cache-stats")]
+    #[must_use]
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    #[cfg(feature = "cache-stats")]
+    #[must_use]
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// The share of `hand_rank_value()` calls that were answered from the cache.
+    #[cfg(feature = "cache-stats")]
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / total as f64
+    }
+
+    /// Fibonacci hashing of the key into the table.
+    #[allow(clippy::cast_possible_truncation)]
+    fn home(&self, key: BinaryCard) -> usize {
+        let bits = self.capacity().trailing_zeros();
+        if bits == 0 {
+            return 0;
+        }
+        (key.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> (64 - bits)) as usize
+    }
+
+    fn slots(&self, key: BinaryCard) -> impl Iterator<Item = usize> {
+        let home = self.home(key);
+        let mask = self.capacity() - 1;
+        (0..=EvalCache::PROBES.min(mask)).map(move |i| (home + i) & mask)
+    }
+}
+
+impl Default for EvalCache {
+    fn default() -> Self {
+        EvalCache::new(EvalCache::DEFAULT_SLOTS)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod evaluate_tests {
+    use super::*;
+    use crate::hand_rank::{HandRankClass, HandRankName};
+    use rstest::rstest;
+
+    #[test]
+    fn evaluator__five() {
+        let evaluator = Evaluator::default();
+
+        for value in 1..=7462 {
+            assert_eq!(value, evaluator.five(&HandRank::from(value).example_hand()));
+        }
+        assert_eq!(NO_HAND_RANK_VALUE, evaluator.five(&Five::default()));
+    }
+
+    #[test]
+    fn evaluator__best() {
+        let seven = Seven::try_from("A♠ K♦ 9♠ 2♣ 7♠ 5♠ 3♠").unwrap();
+
+        assert_eq!(seven.hand_rank_value_and_hand(), Evaluator::default().best(&seven));
+    }
+
+    #[test]
+    fn evaluator__new() {
+        // Tables for a game where flushes don't count.
+        let no_flushes = [NO_HAND_RANK_VALUE; POSSIBLE_COMBINATIONS];
+        let evaluator = Evaluator::new(
+            &no_flushes,
+            &crate::lookups::UNIQUE_5,
+            &crate::lookups::PRODUCTS,
+            &crate::lookups::VALUES,
+        );
+        let seven = Seven::try_from("A♠ K♦ 9♠ 2♣ 7♠ 5♠ 3♠").unwrap();
+
+        assert_eq!(
+            Five::try_from("A♠ 9♥ 7♠ 5♠ 3♠").unwrap().hand_rank_value(),
+            evaluator.five(&Five::try_from("A♠ 9♠ 7♠ 5♠ 3♠").unwrap())
+        );
+        assert_eq!(HandRankName::HighCard, HandRank::from(evaluator.best(&seven).0).name);
+    }
+
+    #[test]
+    fn five_cards_royal_flush() {
+        let cards = [
+            CardNumber::ACE_SPADES,
+            CardNumber::KING_SPADES,
+            CardNumber::QUEEN_SPADES,
+            CardNumber::JACK_SPADES,
+            CardNumber::TEN_SPADES,
+        ];
+        assert_eq!(five_cards(cards), 1);
+    }
+
+    #[test]
+    fn five_cards_straight() {
+        let first = [
+            CardNumber::NINE_CLUBS,
+            CardNumber::KING_SPADES,
+            CardNumber::QUEEN_SPADES,
+            CardNumber::JACK_SPADES,
+            CardNumber::TEN_SPADES,
+        ];
+        let second = [
+            CardNumber::NINE_CLUBS,
+            CardNumber::QUEEN_SPADES,
+            CardNumber::JACK_SPADES,
+            CardNumber::TEN_SPADES,
+            CardNumber::EIGHT_CLUBS,
+        ];
+        assert_eq!(five_cards(first), 1601);
+        assert_eq!(five_cards(second), 1602);
+    }
+
+    #[test]
+    fn five_cards_two_pair() {
+        let cards = [
+            CardNumber::JACK_CLUBS,
+            CardNumber::DEUCE_CLUBS,
+            CardNumber::DEUCE_DIAMONDS,
+            CardNumber::JACK_SPADES,
+            CardNumber::TEN_SPADES,
+        ];
+        assert_eq!(five_cards(cards), 2922);
+    }
+
+    #[test]
+    fn five_cards_king_high() {
+        let first = [
+            CardNumber::JACK_CLUBS,
+            CardNumber::DEUCE_CLUBS,
+            CardNumber::TREY_CLUBS,
+            CardNumber::KING_SPADES,
+            CardNumber::TEN_SPADES,
+        ];
+        let second = [
+            CardNumber::JACK_CLUBS,
+            CardNumber::QUEEN_DIAMONDS,
+            CardNumber::TREY_CLUBS,
+            CardNumber::KING_SPADES,
+            CardNumber::TEN_SPADES,
+        ];
+        assert_eq!(five_cards(first), 6825);
+        assert_eq!(five_cards(second), 6684);
+    }
+
+    #[test]
+    fn check_dupes() {
+        let hand = [
+            CardNumber::JACK_CLUBS,
+            CardNumber::DEUCE_CLUBS,
+            CardNumber::TREY_CLUBS,
+            CardNumber::KING_SPADES,
+            CardNumber::JACK_CLUBS,
+        ];
+        assert_eq!(five_cards(hand), 0);
+    }
+
+    #[test]
+    fn check_corrupt() {
+        let first = [
+            CardNumber::JACK_CLUBS,
+            CardNumber::DEUCE_CLUBS,
+            23,
+            CardNumber::KING_SPADES,
+            CardNumber::TEN_SPADES,
+        ];
+        let second = [
+            CardNumber::JACK_CLUBS,
+            CardNumber::QUEEN_DIAMONDS,
+            CardNumber::TREY_CLUBS,
+            CardNumber::KING_SPADES,
+            CardNumber::BLANK,
+        ];
+        assert_eq!(five_cards(first), 0);
+        assert_eq!(five_cards(second), 0);
+    }
+
+    #[rstest]
+    #[case("A♠ K♠ Q♠ J♠ T♠", "A♥ A♦ A♣ A♠ K♥", Outcome::Win, Decider::Name)]
+    #[case("A♥ A♦ A♣ K♠ K♥", "K♦ K♣ K♠ A♥ A♦", Outcome::Win, Decider::Class)]
+    #[case("A♥ A♦ 9♣ 7♠ 5♥", "A♠ A♣ 9♦ 7♥ 6♣", Outcome::Loss, Decider::Kicker)]
+    #[case("A♥ K♦ Q♣ J♠ T♥", "A♠ K♣ Q♦ J♥ T♣", Outcome::Tie, Decider::Nothing)]
+    fn compare(#[case] a: &'static str, #[case] b: &'static str, #[case] outcome: Outcome, #[case] decider: Decider) {
+        let comparison = super::compare(Five::try_from(a).unwrap(), Five::try_from(b).unwrap());
+
+        assert_eq!(outcome, comparison.outcome);
+        assert_eq!(decider, comparison.decider);
+    }
+
+    #[test]
+    fn compare__invalid() {
+        let comparison = super::compare(Five::default(), Five::try_from("7♠ 5♥ 4♦ 3♣ 2♠").unwrap());
+
+        assert_eq!(Outcome::Loss, comparison.outcome);
+        assert_eq!(Decider::Name, comparison.decider);
+        assert!(comparison.a.is_invalid());
+    }
+
+    #[test]
+    fn compare_seven() {
+        let a = Seven::try_from("A♠ A♥ K♦ 8♣ 7♠ 3♥ 2♦").unwrap();
+        let b = Seven::try_from("K♠ K♥ K♣ 8♣ 7♠ 3♥ 2♦").unwrap();
+
+        let comparison = super::compare_seven(a, b);
+
+        assert_eq!(Outcome::Loss, comparison.outcome);
+        assert_eq!(Decider::Name, comparison.decider);
+        assert_eq!(HandRankName::Pair, comparison.a.name);
+        assert_eq!(HandRankClass::ThreeKings, comparison.b.class);
+        assert_eq!(Some(comparison.b), comparison.winner());
+    }
+
+    #[test]
+    fn comparison__winner() {
+        let a = HandRank::from(1);
+        let b = HandRank::from(2);
+
+        assert_eq!(Some(a), Comparison::new(a, b).winner());
+        assert_eq!(Some(a), Comparison::new(b, a).winner());
+        assert_eq!(None, Comparison::new(a, a).winner());
+    }
+
+    #[test]
+    fn best_five_of() {
+        let seven = Seven::try_from("A♠ A♥ K♦ 8♣ 7♠ 3♥ 2♦").unwrap();
+
+        let (rank, five) = super::best_five_of(&seven.to_arr()).unwrap();
+
+        assert_eq!(seven.hand_rank(), rank);
+        assert_eq!(Five::try_from("A♠ A♥ K♦ 8♣ 7♠").unwrap().sort(), five);
+    }
+
+    #[rstest]
+    #[case("A♠ K♠ Q♠ J♠ T♠", 1)]
+    #[case("9♠ 8♠ 7♠ 6♠ 5♠ 4♠", 6)]
+    #[case("A♥ A♦ 9♣ 7♠ 5♥ 4♦ 3♣ 2♠", 1609)]
+    #[case("A♥ A♦ A♣ A♠ 5♥ 5♦ 3♣ 2♠ K♦", 11)]
+    fn best_five_of__sizes(#[case] index: &'static str, #[case] expected: HandRankValue) {
+        use crate::PokerCard;
+
+        let cards: alloc::vec::Vec<CKCNumber> = index.split_whitespace().map(CKCNumber::from_index).collect();
+
+        assert_eq!(expected, super::best_five_of(&cards).unwrap().0.value);
+    }
+
+    #[test]
+    fn best_five_of__errors() {
+        let cards = [
+            CardNumber::ACE_SPADES,
+            CardNumber::KING_SPADES,
+            CardNumber::QUEEN_SPADES,
+            CardNumber::JACK_SPADES,
+            CardNumber::TEN_SPADES,
+            CardNumber::NINE_SPADES,
+            CardNumber::EIGHT_SPADES,
+            CardNumber::SEVEN_SPADES,
+            CardNumber::SIX_SPADES,
+            CardNumber::FIVE_SPADES,
+        ];
+
+        assert_eq!(HandError::NotEnoughCards, super::best_five_of(&cards[..4]).unwrap_err());
+        assert_eq!(HandError::TooManyCards, super::best_five_of(&cards).unwrap_err());
+        assert_eq!(
+            HandError::DuplicateCard,
+            super::best_five_of(&[cards[0], cards[1], cards[2], cards[3], cards[0]]).unwrap_err()
+        );
+        assert_eq!(
+            HandError::InvalidCard,
+            super::best_five_of(&[cards[0], cards[1], cards[2], cards[3], CardNumber::BLANK]).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn next_combination() {
+        let mut indexes = [0, 1, 2, 3, 4];
+        let mut count = 1;
+        while crate::math::next_combination(&mut indexes, 9) {
+            count += 1;
+        }
+
+        assert_eq!(126, count);
+        assert_eq!([4, 5, 6, 7, 8], indexes);
+    }
+
+    #[test]
+    fn rank_iter() {
+        use crate::PokerCard;
+
+        let cards = "A♠ K♠ 9♦ Q♠ 2♣ J♠ T♠".split_whitespace().map(CKCNumber::from_index);
+
+        let rank = super::rank_iter(cards).unwrap();
+
+        assert_eq!(1, rank.value);
+        assert_eq!(HandRankClass::RoyalFlush, rank.class);
+    }
+
+    #[test]
+    fn rank_iter__errors() {
+        assert_eq!(
+            HandError::NotEnoughCards,
+            super::rank_iter([CardNumber::ACE_SPADES, CardNumber::KING_SPADES]).unwrap_err()
+        );
+        assert_eq!(
+            HandError::TooManyCards,
+            super::rank_iter(core::iter::repeat(CardNumber::ACE_SPADES)).unwrap_err()
+        );
+        assert_eq!(
+            HandError::DuplicateCard,
+            super::rank_iter(core::iter::repeat(CardNumber::ACE_SPADES).take(5)).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn eval_cache() {
+        let seven = Seven::try_from("A♠ K♠ 9♦ Q♠ 2♣ J♠ T♠").unwrap();
+        let mut cache = EvalCache::default();
+
+        assert_eq!(seven.hand_rank_value(), cache.hand_rank_value(seven));
+        assert_eq!(1, cache.len());
+        assert_eq!(Some(1), cache.get(BinaryCard::from_seven(seven)));
+        assert_eq!(1, cache.hand_rank_value(seven));
+        assert_eq!(1, cache.len());
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(None, cache.get(BinaryCard::from_seven(seven)));
+    }
+
+    #[test]
+    fn eval_cache__duplicates_not_cached() {
+        let seven = Seven::try_from("A♠ A♠ 9♦ Q♠ 2♣ J♠ T♠").unwrap();
+        let mut cache = EvalCache::new(16);
+
+        cache.hand_rank_value(seven);
+
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn eval_cache__full() {
+        let mut cache = EvalCache::new(3);
+        assert_eq!(4, cache.capacity());
+
+        let board = Five::try_from("A♠ K♠ Q♠ J♠ 2♦").unwrap();
+        for card in [
+            CardNumber::TEN_SPADES,
+            CardNumber::NINE_SPADES,
+            CardNumber::EIGHT_SPADES,
+            CardNumber::SEVEN_SPADES,
+            CardNumber::SIX_SPADES,
+            CardNumber::FIVE_SPADES,
+        ] {
+            let seven = Seven::new(crate::cards::two::Two::new(card, CardNumber::DEUCE_CLUBS), board);
+            assert_eq!(seven.hand_rank_value(), cache.hand_rank_value(seven));
+        }
+
+        assert_eq!(4, cache.len());
+    }
+
+    #[test]
+    #[cfg(feature = "cache-stats")]
+    fn eval_cache__stats() {
+        let seven = Seven::try_from("A♠ K♠ 9♦ Q♠ 2♣ J♠ T♠").unwrap();
+        let mut cache = EvalCache::default();
+
+        cache.hand_rank_value(seven);
+        cache.hand_rank_value(seven);
+        cache.hand_rank_value(seven);
+
+        assert_eq!(2, cache.hits());
+        assert_eq!(1, cache.misses());
+        assert!((cache.hit_rate() - 2.0 / 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn partial_eval() {
+        let six = Six::try_from("A♠ K♠ 9♦ Q♠ 2♣ J♥").unwrap();
+        let partial = PartialEval::new(six);
+
+        assert_eq!(six.hand_rank_value(), partial.six_rank_value());
+        for card in crate::deck::POKER_DECK {
+            let arr = six.to_arr();
+            let seven = Seven::from([arr[0], arr[1], arr[2], arr[3], arr[4], arr[5], card]);
+            assert_eq!(seven.hand_rank_value(), partial.rank_with(card));
+        }
+    }
+}
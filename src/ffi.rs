@@ -0,0 +1,117 @@
+//! A C ABI for the evaluator, so that programs written in C, C++, C# and the like can rank hands
+//! without going through Rust. The matching declarations are in `include/ckc.h`, which can be
+//! regenerated with `cbindgen --config cbindgen.toml --output include/ckc.h`.
+//!
+//! The crate is built as an rlib by default. Build a library that C can link against with
+//! `cargo rustc --release --features ckc_ffi --crate-type cdylib`, or `staticlib` for a static
+//! one.
+//!
+//! Cards are passed as `CKCNumbers`, and every function returns zero for anything it can't rank
+//! or parse, the same as `NO_HAND_RANK_VALUE` and `CardNumber::BLANK`.
+
+use crate::card::Card;
+use crate::cards::five::Five;
+use crate::cards::seven::Seven;
+use crate::cards::HandRanker;
+use crate::hand_rank::NO_HAND_RANK_VALUE;
+use crate::{CKCNumber, CardNumber};
+use core::ffi::{c_char, CStr};
+
+/// Returns the `HandRankValue` of five cards, or zero if they aren't five valid, distinct cards.
+///
+/// # Safety
+///
+/// `cards` must be null or point to five readable `u32`s.
+#[no_mangle]
+pub unsafe extern "C" fn ckc_eval5(cards: *const u32) -> u16 {
+    if cards.is_null() {
+        return NO_HAND_RANK_VALUE;
+    }
+    let cards: [CKCNumber; 5] = core::ptr::read_unaligned(cards.cast::<[u32; 5]>());
+    Five::from(cards).hand_rank_value_validated()
+}
+
+/// Returns the `HandRankValue` of the best five of seven cards, or zero if they aren't seven
+/// valid, distinct cards.
+///
+/// # Safety
+///
+/// `cards` must be null or point to seven readable `u32`s.
+#[no_mangle]
+pub unsafe extern "C" fn ckc_eval7(cards: *const u32) -> u16 {
+    if cards.is_null() {
+        return NO_HAND_RANK_VALUE;
+    }
+    let cards: [CKCNumber; 7] = core::ptr::read_unaligned(cards.cast::<[u32; 7]>());
+    Seven::from(cards).hand_rank_value_validated()
+}
+
+/// Returns the card for an index such as `"AS"` or `"A♠"`, or zero if it isn't one.
+///
+/// # Safety
+///
+/// `index` must be null or point to a nul terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ckc_parse_card(index: *const c_char) -> u32 {
+    if index.is_null() {
+        return CardNumber::BLANK;
+    }
+    match CStr::from_ptr(index).to_str().map(str::parse::<Card>) {
+        Ok(Ok(card)) => CKCNumber::from(card),
+        _ => CardNumber::BLANK,
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod ffi_tests {
+    use super::*;
+
+    #[test]
+    fn ckc_eval5() {
+        let cards = [
+            CardNumber::ACE_SPADES,
+            CardNumber::KING_SPADES,
+            CardNumber::QUEEN_SPADES,
+            CardNumber::JACK_SPADES,
+            CardNumber::TEN_SPADES,
+        ];
+        let duplicate = [CardNumber::ACE_SPADES; 5];
+
+        unsafe {
+            assert_eq!(1, super::ckc_eval5(cards.as_ptr()));
+            assert_eq!(NO_HAND_RANK_VALUE, super::ckc_eval5(duplicate.as_ptr()));
+            assert_eq!(NO_HAND_RANK_VALUE, super::ckc_eval5(core::ptr::null()));
+        }
+    }
+
+    #[test]
+    fn ckc_eval7() {
+        let cards = [
+            CardNumber::DEUCE_CLUBS,
+            CardNumber::ACE_SPADES,
+            CardNumber::KING_SPADES,
+            CardNumber::SEVEN_DIAMONDS,
+            CardNumber::QUEEN_SPADES,
+            CardNumber::JACK_SPADES,
+            CardNumber::TEN_SPADES,
+        ];
+
+        unsafe {
+            assert_eq!(1, super::ckc_eval7(cards.as_ptr()));
+            assert_eq!(NO_HAND_RANK_VALUE, super::ckc_eval7(core::ptr::null()));
+        }
+    }
+
+    #[test]
+    fn ckc_parse_card() {
+        unsafe {
+            assert_eq!(CardNumber::ACE_SPADES, super::ckc_parse_card(b"AS\0".as_ptr().cast()));
+            assert_eq!(CardNumber::TEN_HEARTS, super::ckc_parse_card("T♥\0".as_ptr().cast()));
+            assert_eq!(CardNumber::BLANK, super::ckc_parse_card(b"XX\0".as_ptr().cast()));
+            assert_eq!(CardNumber::BLANK, super::ckc_parse_card(b"AsKd\0".as_ptr().cast()));
+            assert_eq!(CardNumber::BLANK, super::ckc_parse_card(b"Asx\0".as_ptr().cast()));
+            assert_eq!(CardNumber::BLANK, super::ckc_parse_card(core::ptr::null()));
+        }
+    }
+}
@@ -1,12 +1,20 @@
+use crate::cards::five::Five;
+use crate::cards::HandValidator;
+use crate::lookups::{SEVEN_CARD_CLASS_FREQUENCIES, SEVEN_CARD_VALUE_FREQUENCIES};
+use crate::math::choose;
+use crate::{CKCNumber, CardRank, CardSuit, PokerCard};
 use core::cmp::Ordering;
 use core::fmt;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "strum")]
 use strum::EnumIter;
 
 /// `HandRank` represents the value of a specific 5 card hand of poker. The lower the
 /// `HandRankValue` the better the hand. When a `HandRank` is instantiated it can only
 /// have a specific matching `HandRankName` and `HandRankValue`.
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct HandRank {
     pub value: HandRankValue,
     pub name: HandRankName,
@@ -43,323 +51,46 @@ impl HandRank {
         }
     }
 
+    /// Looks the value up in `HandRankClass::VALUE_RANGES`, so a value of 1 is `RoyalFlush`, and
+    /// anything outside of 1 to 7462 is `Invalid`.
     #[must_use]
-    #[allow(clippy::too_many_lines)]
     pub fn determine_class(hrv: &HandRankValue) -> HandRankClass {
         match *hrv {
-            1 => HandRankClass::RoyalFlush,
-            2 => HandRankClass::KingHighStraightFlush,
-            3 => HandRankClass::QueenHighStraightFlush,
-            4 => HandRankClass::JackHighStraightFlush,
-            5 => HandRankClass::TenHighStraightFlush,
-            6 => HandRankClass::NineHighStraightFlush,
-            7 => HandRankClass::EightHighStraightFlush,
-            8 => HandRankClass::SevenHighStraightFlush,
-            9 => HandRankClass::SixHighStraightFlush,
-            10 => HandRankClass::FiveHighStraightFlush,
-            11..=22 => HandRankClass::FourAces,
-            23..=34 => HandRankClass::FourKings,
-            35..=46 => HandRankClass::FourQueens,
-            47..=58 => HandRankClass::FourJacks,
-            59..=70 => HandRankClass::FourTens,
-            71..=82 => HandRankClass::FourNines,
-            83..=94 => HandRankClass::FourEights,
-            95..=106 => HandRankClass::FourSevens,
-            107..=118 => HandRankClass::FourSixes,
-            119..=130 => HandRankClass::FourFives,
-            131..=142 => HandRankClass::FourFours,
-            143..=154 => HandRankClass::FourTreys,
-            155..=166 => HandRankClass::FourDeuces,
-            167 => HandRankClass::AcesOverKings,
-            168 => HandRankClass::AcesOverQueens,
-            169 => HandRankClass::AcesOverJacks,
-            170 => HandRankClass::AcesOverTens,
-            171 => HandRankClass::AcesOverNines,
-            172 => HandRankClass::AcesOverEights,
-            173 => HandRankClass::AcesOverSevens,
-            174 => HandRankClass::AcesOverSixes,
-            175 => HandRankClass::AcesOverFives,
-            176 => HandRankClass::AcesOverFours,
-            177 => HandRankClass::AcesOverTreys,
-            178 => HandRankClass::AcesOverDeuces,
-            179 => HandRankClass::KingsOverAces,
-            180 => HandRankClass::KingsOverQueens,
-            181 => HandRankClass::KingsOverJacks,
-            182 => HandRankClass::KingsOverTens,
-            183 => HandRankClass::KingsOverNines,
-            184 => HandRankClass::KingsOverEights,
-            185 => HandRankClass::KingsOverSevens,
-            186 => HandRankClass::KingsOverSixes,
-            187 => HandRankClass::KingsOverFives,
-            188 => HandRankClass::KingsOverFours,
-            189 => HandRankClass::KingsOverTreys,
-            190 => HandRankClass::KingsOverDeuces,
-            191 => HandRankClass::QueensOverAces,
-            192 => HandRankClass::QueensOverKings,
-            193 => HandRankClass::QueensOverJacks,
-            194 => HandRankClass::QueensOverTens,
-            195 => HandRankClass::QueensOverNines,
-            196 => HandRankClass::QueensOverEights,
-            197 => HandRankClass::QueensOverSevens,
-            198 => HandRankClass::QueensOverSixes,
-            199 => HandRankClass::QueensOverFives,
-            200 => HandRankClass::QueensOverFours,
-            201 => HandRankClass::QueensOverTreys,
-            202 => HandRankClass::QueensOverDeuces,
-            203 => HandRankClass::JacksOverAces,
-            204 => HandRankClass::JacksOverKings,
-            205 => HandRankClass::JacksOverQueens,
-            206 => HandRankClass::JacksOverTens,
-            207 => HandRankClass::JacksOverNines,
-            208 => HandRankClass::JacksOverEights,
-            209 => HandRankClass::JacksOverSevens,
-            210 => HandRankClass::JacksOverSixes,
-            211 => HandRankClass::JacksOverFives,
-            212 => HandRankClass::JacksOverFours,
-            213 => HandRankClass::JacksOverTreys,
-            214 => HandRankClass::JacksOverDeuces,
-            215 => HandRankClass::TensOverAces,
-            216 => HandRankClass::TensOverKings,
-            217 => HandRankClass::TensOverQueens,
-            218 => HandRankClass::TensOverJacks,
-            219 => HandRankClass::TensOverNines,
-            220 => HandRankClass::TensOverEights,
-            221 => HandRankClass::TensOverSevens,
-            222 => HandRankClass::TensOverSixes,
-            223 => HandRankClass::TensOverFives,
-            224 => HandRankClass::TensOverFours,
-            225 => HandRankClass::TensOverTreys,
-            226 => HandRankClass::TensOverDeuces,
-            227 => HandRankClass::NinesOverAces,
-            228 => HandRankClass::NinesOverKings,
-            229 => HandRankClass::NinesOverQueens,
-            230 => HandRankClass::NinesOverJacks,
-            231 => HandRankClass::NinesOverTens,
-            232 => HandRankClass::NinesOverEights,
-            233 => HandRankClass::NinesOverSevens,
-            234 => HandRankClass::NinesOverSixes,
-            235 => HandRankClass::NinesOverFives,
-            236 => HandRankClass::NinesOverFours,
-            237 => HandRankClass::NinesOverTreys,
-            238 => HandRankClass::NinesOverDeuces,
-            239 => HandRankClass::EightsOverAces,
-            240 => HandRankClass::EightsOverKings,
-            241 => HandRankClass::EightsOverQueens,
-            242 => HandRankClass::EightsOverJacks,
-            243 => HandRankClass::EightsOverTens,
-            244 => HandRankClass::EightsOverNines,
-            245 => HandRankClass::EightsOverSevens,
-            246 => HandRankClass::EightsOverSixes,
-            247 => HandRankClass::EightsOverFives,
-            248 => HandRankClass::EightsOverFours,
-            249 => HandRankClass::EightsOverTreys,
-            250 => HandRankClass::EightsOverDeuces,
-            251 => HandRankClass::SevensOverAces,
-            252 => HandRankClass::SevensOverKings,
-            253 => HandRankClass::SevensOverQueens,
-            254 => HandRankClass::SevensOverJacks,
-            255 => HandRankClass::SevensOverTens,
-            256 => HandRankClass::SevensOverNines,
-            257 => HandRankClass::SevensOverEights,
-            258 => HandRankClass::SevensOverSixes,
-            259 => HandRankClass::SevensOverFives,
-            260 => HandRankClass::SevensOverFours,
-            261 => HandRankClass::SevensOverTreys,
-            262 => HandRankClass::SevensOverDeuces,
-            263 => HandRankClass::SixesOverAces,
-            264 => HandRankClass::SixesOverKings,
-            265 => HandRankClass::SixesOverQueens,
-            266 => HandRankClass::SixesOverJacks,
-            267 => HandRankClass::SixesOverTens,
-            268 => HandRankClass::SixesOverNines,
-            269 => HandRankClass::SixesOverEights,
-            270 => HandRankClass::SixesOverSevens,
-            271 => HandRankClass::SixesOverFives,
-            272 => HandRankClass::SixesOverFours,
-            273 => HandRankClass::SixesOverTreys,
-            274 => HandRankClass::SixesOverDeuces,
-            275 => HandRankClass::FivesOverAces,
-            276 => HandRankClass::FivesOverKings,
-            277 => HandRankClass::FivesOverQueens,
-            278 => HandRankClass::FivesOverJacks,
-            279 => HandRankClass::FivesOverTens,
-            280 => HandRankClass::FivesOverNines,
-            281 => HandRankClass::FivesOverEights,
-            282 => HandRankClass::FivesOverSevens,
-            283 => HandRankClass::FivesOverSixes,
-            284 => HandRankClass::FivesOverFours,
-            285 => HandRankClass::FivesOverTreys,
-            286 => HandRankClass::FivesOverDeuces,
-            287 => HandRankClass::FoursOverAces,
-            288 => HandRankClass::FoursOverKings,
-            289 => HandRankClass::FoursOverQueens,
-            290 => HandRankClass::FoursOverJacks,
-            291 => HandRankClass::FoursOverTens,
-            292 => HandRankClass::FoursOverNines,
-            293 => HandRankClass::FoursOverEights,
-            294 => HandRankClass::FoursOverSevens,
-            295 => HandRankClass::FoursOverSixes,
-            296 => HandRankClass::FoursOverFives,
-            297 => HandRankClass::FoursOverTreys,
-            298 => HandRankClass::FoursOverDeuces,
-            299 => HandRankClass::TreysOverAces,
-            300 => HandRankClass::TreysOverKings,
-            301 => HandRankClass::TreysOverQueens,
-            302 => HandRankClass::TreysOverJacks,
-            303 => HandRankClass::TreysOverTens,
-            304 => HandRankClass::TreysOverNines,
-            305 => HandRankClass::TreysOverEights,
-            306 => HandRankClass::TreysOverSevens,
-            307 => HandRankClass::TreysOverSixes,
-            308 => HandRankClass::TreysOverFives,
-            309 => HandRankClass::TreysOverFours,
-            310 => HandRankClass::TreysOverDeuces,
-            311 => HandRankClass::DeucesOverAces,
-            312 => HandRankClass::DeucesOverKings,
-            313 => HandRankClass::DeucesOverQueens,
-            314 => HandRankClass::DeucesOverJacks,
-            315 => HandRankClass::DeucesOverTens,
-            316 => HandRankClass::DeucesOverNines,
-            317 => HandRankClass::DeucesOverEights,
-            318 => HandRankClass::DeucesOverSevens,
-            319 => HandRankClass::DeucesOverSixes,
-            320 => HandRankClass::DeucesOverFives,
-            321 => HandRankClass::DeucesOverFours,
-            322 => HandRankClass::DeucesOverTreys,
-            323..=815 => HandRankClass::AceHighFlush,
-            816..=1144 => HandRankClass::KingHighFlush,
-            1145..=1353 => HandRankClass::QueenHighFlush,
-            1354..=1478 => HandRankClass::JackHighFlush,
-            1479..=1547 => HandRankClass::TenHighFlush,
-            1548..=1581 => HandRankClass::NineHighFlush,
-            1582..=1595 => HandRankClass::EightHighFlush,
-            1596..=1599 => HandRankClass::SevenHighFlush,
-            1600 => HandRankClass::AceHighStraight,
-            1601 => HandRankClass::KingHighStraight,
-            1602 => HandRankClass::QueenHighStraight,
-            1603 => HandRankClass::JackHighStraight,
-            1604 => HandRankClass::TenHighStraight,
-            1605 => HandRankClass::NineHighStraight,
-            1606 => HandRankClass::EightHighStraight,
-            1607 => HandRankClass::SevenHighStraight,
-            1608 => HandRankClass::SixHighStraight,
-            1609 => HandRankClass::FiveHighStraight,
-            1610..=1675 => HandRankClass::ThreeAces,
-            1676..=1741 => HandRankClass::ThreeKings,
-            1742..=1807 => HandRankClass::ThreeQueens,
-            1808..=1873 => HandRankClass::ThreeJacks,
-            1874..=1939 => HandRankClass::ThreeTens,
-            1940..=2005 => HandRankClass::ThreeNines,
-            2006..=2071 => HandRankClass::ThreeEights,
-            2072..=2137 => HandRankClass::ThreeSevens,
-            2138..=2203 => HandRankClass::ThreeSixes,
-            2204..=2269 => HandRankClass::ThreeFives,
-            2270..=2335 => HandRankClass::ThreeFours,
-            2336..=2401 => HandRankClass::ThreeTreys,
-            2402..=2467 => HandRankClass::ThreeDeuces,
-            2468..=2478 => HandRankClass::AcesAndKings,
-            2479..=2489 => HandRankClass::AcesAndQueens,
-            2490..=2500 => HandRankClass::AcesAndJacks,
-            2501..=2511 => HandRankClass::AcesAndTens,
-            2512..=2522 => HandRankClass::AcesAndNines,
-            2523..=2533 => HandRankClass::AcesAndEights,
-            2534..=2544 => HandRankClass::AcesAndSevens,
-            2545..=2555 => HandRankClass::AcesAndSixes,
-            2556..=2566 => HandRankClass::AcesAndFives,
-            2567..=2577 => HandRankClass::AcesAndFours,
-            2578..=2588 => HandRankClass::AcesAndTreys,
-            2589..=2599 => HandRankClass::AcesAndDeuces,
-            2600..=2610 => HandRankClass::KingsAndQueens,
-            2611..=2621 => HandRankClass::KingsAndJacks,
-            2622..=2632 => HandRankClass::KingsAndTens,
-            2633..=2643 => HandRankClass::KingsAndNines,
-            2644..=2654 => HandRankClass::KingsAndEights,
-            2655..=2665 => HandRankClass::KingsAndSevens,
-            2666..=2676 => HandRankClass::KingsAndSixes,
-            2677..=2687 => HandRankClass::KingsAndFives,
-            2688..=2698 => HandRankClass::KingsAndFours,
-            2699..=2709 => HandRankClass::KingsAndTreys,
-            2710..=2720 => HandRankClass::KingsAndDeuces,
-            2721..=2731 => HandRankClass::QueensAndJacks,
-            2732..=2742 => HandRankClass::QueensAndTens,
-            2743..=2753 => HandRankClass::QueensAndNines,
-            2754..=2764 => HandRankClass::QueensAndEights,
-            2765..=2775 => HandRankClass::QueensAndSevens,
-            2776..=2786 => HandRankClass::QueensAndSixes,
-            2787..=2797 => HandRankClass::QueensAndFives,
-            2798..=2808 => HandRankClass::QueensAndFours,
-            2809..=2819 => HandRankClass::QueensAndTreys,
-            2820..=2830 => HandRankClass::QueensAndDeuces,
-            2831..=2841 => HandRankClass::JacksAndTens,
-            2842..=2852 => HandRankClass::JacksAndNines,
-            2853..=2863 => HandRankClass::JacksAndEights,
-            2864..=2874 => HandRankClass::JacksAndSevens,
-            2875..=2885 => HandRankClass::JacksAndSixes,
-            2886..=2896 => HandRankClass::JacksAndFives,
-            2897..=2907 => HandRankClass::JacksAndFours,
-            2908..=2918 => HandRankClass::JacksAndTreys,
-            2919..=2929 => HandRankClass::JacksAndDeuces,
-            2930..=2940 => HandRankClass::TensAndNines,
-            2941..=2951 => HandRankClass::TensAndEights,
-            2952..=2962 => HandRankClass::TensAndSevens,
-            2963..=2973 => HandRankClass::TensAndSixes,
-            2974..=2984 => HandRankClass::TensAndFives,
-            2985..=2995 => HandRankClass::TensAndFours,
-            2996..=3006 => HandRankClass::TensAndTreys,
-            3007..=3017 => HandRankClass::TensAndDeuces,
-            3018..=3028 => HandRankClass::NinesAndEights,
-            3029..=3039 => HandRankClass::NinesAndSevens,
-            3040..=3050 => HandRankClass::NinesAndSixes,
-            3051..=3061 => HandRankClass::NinesAndFives,
-            3062..=3072 => HandRankClass::NinesAndFours,
-            3073..=3083 => HandRankClass::NinesAndTreys,
-            3084..=3094 => HandRankClass::NinesAndDeuces,
-            3095..=3105 => HandRankClass::EightsAndSevens,
-            3106..=3116 => HandRankClass::EightsAndSixes,
-            3117..=3127 => HandRankClass::EightsAndFives,
-            3128..=3138 => HandRankClass::EightsAndFours,
-            3139..=3149 => HandRankClass::EightsAndTreys,
-            3150..=3160 => HandRankClass::EightsAndDeuces,
-            3161..=3171 => HandRankClass::SevensAndSixes,
-            3172..=3182 => HandRankClass::SevensAndFives,
-            3183..=3193 => HandRankClass::SevensAndFours,
-            3194..=3204 => HandRankClass::SevensAndTreys,
-            3205..=3215 => HandRankClass::SevensAndDeuces,
-            3216..=3226 => HandRankClass::SixesAndFives,
-            3227..=3237 => HandRankClass::SixesAndFours,
-            3238..=3248 => HandRankClass::SixesAndTreys,
-            3249..=3259 => HandRankClass::SixesAndDeuces,
-            3260..=3270 => HandRankClass::FivesAndFours,
-            3271..=3281 => HandRankClass::FivesAndTreys,
-            3282..=3292 => HandRankClass::FivesAndDeuces,
-            3293..=3303 => HandRankClass::FoursAndTreys,
-            3304..=3314 => HandRankClass::FoursAndDeuces,
-            3315..=3325 => HandRankClass::TreysAndDeuces,
-            3326..=3545 => HandRankClass::PairOfAces,
-            3546..=3765 => HandRankClass::PairOfKings,
-            3766..=3985 => HandRankClass::PairOfQueens,
-            3986..=4205 => HandRankClass::PairOfJacks,
-            4206..=4425 => HandRankClass::PairOfTens,
-            4426..=4645 => HandRankClass::PairOfNines,
-            4646..=4865 => HandRankClass::PairOfEights,
-            4866..=5085 => HandRankClass::PairOfSevens,
-            5086..=5305 => HandRankClass::PairOfSixes,
-            5306..=5525 => HandRankClass::PairOfFives,
-            5526..=5745 => HandRankClass::PairOfFours,
-            5746..=5965 => HandRankClass::PairOfTreys,
-            5966..=6185 => HandRankClass::PairOfDeuces,
-            6186..=6678 => HandRankClass::AceHigh,
-            6679..=7007 => HandRankClass::KingHigh,
-            7008..=7216 => HandRankClass::QueenHigh,
-            7217..=7341 => HandRankClass::JackHigh,
-            7342..=7410 => HandRankClass::TenHigh,
-            7411..=7444 => HandRankClass::NineHigh,
-            7445..=7458 => HandRankClass::EightHigh,
-            7459..=7462 => HandRankClass::SevenHigh,
+            1..=WORST_HAND_RANK_VALUE => {
+                HandRankClass::ALL[HandRankClass::VALUE_RANGES.partition_point(|(_, highest)| highest < hrv)]
+            },
             _ => HandRankClass::Invalid,
         }
     }
 
+    /// Returns a representative `Five` card hand with the `HandRank's` value, so a value of 1
+    /// returns `A♠ K♠ Q♠ J♠ T♠`. Cards are spades wherever possible, with repeated ranks taking
+    /// the next suit along, and the last card of a hand that isn't a flush in hearts so that it
+    /// doesn't become one. Returns a blank `Five` for an invalid value.
+    #[must_use]
+    pub fn example_hand(&self) -> Five {
+        let Some((ranks, flush)) = crate::lookups::ranks_of(self.value) else {
+            return Five::default();
+        };
+
+        let all = CardSuit::all();
+        let mut suits = [CardSuit::SPADES; 5];
+        if !flush {
+            for (i, rank) in ranks.iter().enumerate() {
+                suits[i] = all[ranks[..i].iter().filter(|r| *r == rank).count()];
+            }
+            if suits.iter().all(|suit| *suit == CardSuit::SPADES) {
+                suits[4] = CardSuit::HEARTS;
+            }
+        }
+
+        let mut cards = [CKCNumber::default(); 5];
+        for (i, (rank, suit)) in ranks.iter().zip(suits).enumerate() {
+            cards[i] = CKCNumber::create(CardRank::ALL[12 - rank], suit);
+        }
+        Five::from(cards).sort()
+    }
+
     #[must_use]
     pub fn is_a_valid_hand_rank(&self) -> bool {
         self == &HandRank::from(self.value)
@@ -369,6 +100,54 @@ impl HandRank {
     pub fn is_invalid(&self) -> bool {
         self.name == HandRankName::Invalid
     }
+
+    /// Returns how many of the 7,462 distinct `HandRankValues` this one beats, so a royal flush
+    /// beats 7,461 and 7-5-4-3-2 beats none. An invalid `HandRank` beats nothing.
+    #[must_use]
+    pub fn beats_count(&self) -> usize {
+        if self.is_invalid() {
+            return 0;
+        }
+        usize::from(WORST_HAND_RANK_VALUE - self.value)
+    }
+
+    /// Returns the share of the 7,462 distinct `HandRankValues` that this one beats.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn percentile(&self) -> f64 {
+        self.beats_count() as f64 / f64::from(WORST_HAND_RANK_VALUE)
+    }
+
+    /// Returns how many of the 133,784,560 seven card hands this one beats, by their best five
+    /// cards. Unlike `beats_count()`, this allows for some values being far more likely than
+    /// others, since there are many more ways to make a pair than a straight flush.
+    #[must_use]
+    pub fn seven_card_beats_count(&self) -> u64 {
+        if self.is_invalid() {
+            return 0;
+        }
+        SEVEN_CARD_VALUE_FREQUENCIES[usize::from(self.value) + 1..]
+            .iter()
+            .map(|frequency| u64::from(*frequency))
+            .sum()
+    }
+
+    /// Returns the share of the 133,784,560 seven card hands that this one beats, by their best
+    /// five cards.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn seven_card_percentile(&self) -> f64 {
+        self.seven_card_beats_count() as f64 / choose(52, 7) as f64
+    }
+
+    /// Writes the same text as `Display`, straight into the writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from the writer.
+    pub fn write_index(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        write!(w, "{self}")
+    }
 }
 
 impl Default for HandRank {
@@ -419,6 +198,36 @@ impl Ord for HandRank {
     }
 }
 
+/// Serializes a `HandRank` as nothing but its `HandRankValue`, rebuilding the name and class
+/// when it's deserialized. Use it on a field with `#[serde(with = "ckc_rs::hand_rank::compact")]`.
+///
+/// The value is a single `u16`, so it takes one to three bytes in a binary format such as
+/// postcard, instead of the strings for the name and class.
+#[cfg(feature = "serde")]
+pub mod compact {
+    use super::{HandRank, HandRankValue, NO_HAND_RANK_VALUE};
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// # Errors
+    ///
+    /// Returns any error from the serializer.
+    pub fn serialize<S: Serializer>(rank: &HandRank, serializer: S) -> Result<S::Ok, S::Error> {
+        rank.value.serialize(serializer)
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the value isn't zero or one of the 7462 valid `HandRankValues`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<HandRank, D::Error> {
+        let rank = HandRank::from(HandRankValue::deserialize(deserializer)?);
+        if rank.is_invalid() && rank.value != NO_HAND_RANK_VALUE {
+            return Err(D::Error::custom("invalid hand rank value"));
+        }
+        Ok(rank)
+    }
+}
+
 /// `HandRankValue` is the integer representing the `HandRank` for a particular five card
 /// `PokerHand`. This value is used to compare one hand against the other, the lower the value,
 /// the stronger the hand in a traditional, highest to lowest, ranking. A `HandRankValue` can have
@@ -428,11 +237,16 @@ pub type HandRankValue = u16;
 
 pub const NO_HAND_RANK_VALUE: HandRankValue = 0;
 
+/// The `HandRankValue` of 7-5-4-3-2 offsuit, the weakest hand.
+const WORST_HAND_RANK_VALUE: HandRankValue = 7462;
+
 /// `HandRankName` represents the
 /// [traditional name](https://en.wikipedia.org/wiki/List_of_poker_hands) of a five card
 /// `PokerHand`.
 #[allow(clippy::module_name_repetitions)]
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, EnumIter, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "strum", derive(EnumIter))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum HandRankName {
     StraightFlush,
     FourOfAKind,
@@ -443,12 +257,53 @@ pub enum HandRankName {
     TwoPair,
     Pair,
     HighCard,
+    #[default]
     Invalid,
 }
 
+impl HandRankName {
+    /// The lowest and highest `HandRankValue` with the name, or `(0, 0)` for `Invalid`.
+    #[must_use]
+    pub fn value_range(self) -> (HandRankValue, HandRankValue) {
+        if self == HandRankName::Invalid {
+            return (NO_HAND_RANK_VALUE, NO_HAND_RANK_VALUE);
+        }
+        value_range(|hrv| HandRank::determine_name(&hrv).cmp(&self))
+    }
+
+    /// The number of distinct `HandRankValues` with the name, such as 10 for `StraightFlush`.
+    #[must_use]
+    pub fn count(self) -> usize {
+        match self.value_range() {
+            (NO_HAND_RANK_VALUE, _) => 0,
+            (lowest, highest) => usize::from(highest - lowest) + 1,
+        }
+    }
+
+    /// The number of the 133,784,560 seven card hands whose best five cards have the name, such
+    /// as 41,584 for `StraightFlush`, or zero for `Invalid`.
+    #[must_use]
+    pub fn seven_card_count(self) -> u32 {
+        SEVEN_CARD_CLASS_FREQUENCIES
+            .get(self as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The chance that seven random cards make the name as their best five, such as about 0.438
+    /// for `Pair`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn seven_card_probability(self) -> f64 {
+        f64::from(self.seven_card_count()) / choose(52, 7) as f64
+    }
+}
+
 /// `HandRankClass` represents the more specific type of the five card `PokerHand`.
 #[allow(clippy::module_name_repetitions)]
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, EnumIter, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "strum", derive(EnumIter))]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum HandRankClass {
     RoyalFlush,
     KingHighStraightFlush,
@@ -762,16 +617,773 @@ pub enum HandRankClass {
     Invalid,
 }
 
+impl HandRankClass {
+    /// Every `HandRankClass` but `Invalid`, from strongest to weakest, in the order they're
+    /// declared, so that `HandRankClass::ALL[class as usize]` is always `class`.
+    pub const ALL: [HandRankClass; 309] = [
+        HandRankClass::RoyalFlush,
+        HandRankClass::KingHighStraightFlush,
+        HandRankClass::QueenHighStraightFlush,
+        HandRankClass::JackHighStraightFlush,
+        HandRankClass::TenHighStraightFlush,
+        HandRankClass::NineHighStraightFlush,
+        HandRankClass::EightHighStraightFlush,
+        HandRankClass::SevenHighStraightFlush,
+        HandRankClass::SixHighStraightFlush,
+        HandRankClass::FiveHighStraightFlush,
+        HandRankClass::FourAces,
+        HandRankClass::FourKings,
+        HandRankClass::FourQueens,
+        HandRankClass::FourJacks,
+        HandRankClass::FourTens,
+        HandRankClass::FourNines,
+        HandRankClass::FourEights,
+        HandRankClass::FourSevens,
+        HandRankClass::FourSixes,
+        HandRankClass::FourFives,
+        HandRankClass::FourFours,
+        HandRankClass::FourTreys,
+        HandRankClass::FourDeuces,
+        HandRankClass::AcesOverKings,
+        HandRankClass::AcesOverQueens,
+        HandRankClass::AcesOverJacks,
+        HandRankClass::AcesOverTens,
+        HandRankClass::AcesOverNines,
+        HandRankClass::AcesOverEights,
+        HandRankClass::AcesOverSevens,
+        HandRankClass::AcesOverSixes,
+        HandRankClass::AcesOverFives,
+        HandRankClass::AcesOverFours,
+        HandRankClass::AcesOverTreys,
+        HandRankClass::AcesOverDeuces,
+        HandRankClass::KingsOverAces,
+        HandRankClass::KingsOverQueens,
+        HandRankClass::KingsOverJacks,
+        HandRankClass::KingsOverTens,
+        HandRankClass::KingsOverNines,
+        HandRankClass::KingsOverEights,
+        HandRankClass::KingsOverSevens,
+        HandRankClass::KingsOverSixes,
+        HandRankClass::KingsOverFives,
+        HandRankClass::KingsOverFours,
+        HandRankClass::KingsOverTreys,
+        HandRankClass::KingsOverDeuces,
+        HandRankClass::QueensOverAces,
+        HandRankClass::QueensOverKings,
+        HandRankClass::QueensOverJacks,
+        HandRankClass::QueensOverTens,
+        HandRankClass::QueensOverNines,
+        HandRankClass::QueensOverEights,
+        HandRankClass::QueensOverSevens,
+        HandRankClass::QueensOverSixes,
+        HandRankClass::QueensOverFives,
+        HandRankClass::QueensOverFours,
+        HandRankClass::QueensOverTreys,
+        HandRankClass::QueensOverDeuces,
+        HandRankClass::JacksOverAces,
+        HandRankClass::JacksOverKings,
+        HandRankClass::JacksOverQueens,
+        HandRankClass::JacksOverTens,
+        HandRankClass::JacksOverNines,
+        HandRankClass::JacksOverEights,
+        HandRankClass::JacksOverSevens,
+        HandRankClass::JacksOverSixes,
+        HandRankClass::JacksOverFives,
+        HandRankClass::JacksOverFours,
+        HandRankClass::JacksOverTreys,
+        HandRankClass::JacksOverDeuces,
+        HandRankClass::TensOverAces,
+        HandRankClass::TensOverKings,
+        HandRankClass::TensOverQueens,
+        HandRankClass::TensOverJacks,
+        HandRankClass::TensOverNines,
+        HandRankClass::TensOverEights,
+        HandRankClass::TensOverSevens,
+        HandRankClass::TensOverSixes,
+        HandRankClass::TensOverFives,
+        HandRankClass::TensOverFours,
+        HandRankClass::TensOverTreys,
+        HandRankClass::TensOverDeuces,
+        HandRankClass::NinesOverAces,
+        HandRankClass::NinesOverKings,
+        HandRankClass::NinesOverQueens,
+        HandRankClass::NinesOverJacks,
+        HandRankClass::NinesOverTens,
+        HandRankClass::NinesOverEights,
+        HandRankClass::NinesOverSevens,
+        HandRankClass::NinesOverSixes,
+        HandRankClass::NinesOverFives,
+        HandRankClass::NinesOverFours,
+        HandRankClass::NinesOverTreys,
+        HandRankClass::NinesOverDeuces,
+        HandRankClass::EightsOverAces,
+        HandRankClass::EightsOverKings,
+        HandRankClass::EightsOverQueens,
+        HandRankClass::EightsOverJacks,
+        HandRankClass::EightsOverTens,
+        HandRankClass::EightsOverNines,
+        HandRankClass::EightsOverSevens,
+        HandRankClass::EightsOverSixes,
+        HandRankClass::EightsOverFives,
+        HandRankClass::EightsOverFours,
+        HandRankClass::EightsOverTreys,
+        HandRankClass::EightsOverDeuces,
+        HandRankClass::SevensOverAces,
+        HandRankClass::SevensOverKings,
+        HandRankClass::SevensOverQueens,
+        HandRankClass::SevensOverJacks,
+        HandRankClass::SevensOverTens,
+        HandRankClass::SevensOverNines,
+        HandRankClass::SevensOverEights,
+        HandRankClass::SevensOverSixes,
+        HandRankClass::SevensOverFives,
+        HandRankClass::SevensOverFours,
+        HandRankClass::SevensOverTreys,
+        HandRankClass::SevensOverDeuces,
+        HandRankClass::SixesOverAces,
+        HandRankClass::SixesOverKings,
+        HandRankClass::SixesOverQueens,
+        HandRankClass::SixesOverJacks,
+        HandRankClass::SixesOverTens,
+        HandRankClass::SixesOverNines,
+        HandRankClass::SixesOverEights,
+        HandRankClass::SixesOverSevens,
+        HandRankClass::SixesOverFives,
+        HandRankClass::SixesOverFours,
+        HandRankClass::SixesOverTreys,
+        HandRankClass::SixesOverDeuces,
+        HandRankClass::FivesOverAces,
+        HandRankClass::FivesOverKings,
+        HandRankClass::FivesOverQueens,
+        HandRankClass::FivesOverJacks,
+        HandRankClass::FivesOverTens,
+        HandRankClass::FivesOverNines,
+        HandRankClass::FivesOverEights,
+        HandRankClass::FivesOverSevens,
+        HandRankClass::FivesOverSixes,
+        HandRankClass::FivesOverFours,
+        HandRankClass::FivesOverTreys,
+        HandRankClass::FivesOverDeuces,
+        HandRankClass::FoursOverAces,
+        HandRankClass::FoursOverKings,
+        HandRankClass::FoursOverQueens,
+        HandRankClass::FoursOverJacks,
+        HandRankClass::FoursOverTens,
+        HandRankClass::FoursOverNines,
+        HandRankClass::FoursOverEights,
+        HandRankClass::FoursOverSevens,
+        HandRankClass::FoursOverSixes,
+        HandRankClass::FoursOverFives,
+        HandRankClass::FoursOverTreys,
+        HandRankClass::FoursOverDeuces,
+        HandRankClass::TreysOverAces,
+        HandRankClass::TreysOverKings,
+        HandRankClass::TreysOverQueens,
+        HandRankClass::TreysOverJacks,
+        HandRankClass::TreysOverTens,
+        HandRankClass::TreysOverNines,
+        HandRankClass::TreysOverEights,
+        HandRankClass::TreysOverSevens,
+        HandRankClass::TreysOverSixes,
+        HandRankClass::TreysOverFives,
+        HandRankClass::TreysOverFours,
+        HandRankClass::TreysOverDeuces,
+        HandRankClass::DeucesOverAces,
+        HandRankClass::DeucesOverKings,
+        HandRankClass::DeucesOverQueens,
+        HandRankClass::DeucesOverJacks,
+        HandRankClass::DeucesOverTens,
+        HandRankClass::DeucesOverNines,
+        HandRankClass::DeucesOverEights,
+        HandRankClass::DeucesOverSevens,
+        HandRankClass::DeucesOverSixes,
+        HandRankClass::DeucesOverFives,
+        HandRankClass::DeucesOverFours,
+        HandRankClass::DeucesOverTreys,
+        HandRankClass::AceHighFlush,
+        HandRankClass::KingHighFlush,
+        HandRankClass::QueenHighFlush,
+        HandRankClass::JackHighFlush,
+        HandRankClass::TenHighFlush,
+        HandRankClass::NineHighFlush,
+        HandRankClass::EightHighFlush,
+        HandRankClass::SevenHighFlush,
+        HandRankClass::AceHighStraight,
+        HandRankClass::KingHighStraight,
+        HandRankClass::QueenHighStraight,
+        HandRankClass::JackHighStraight,
+        HandRankClass::TenHighStraight,
+        HandRankClass::NineHighStraight,
+        HandRankClass::EightHighStraight,
+        HandRankClass::SevenHighStraight,
+        HandRankClass::SixHighStraight,
+        HandRankClass::FiveHighStraight,
+        HandRankClass::ThreeAces,
+        HandRankClass::ThreeKings,
+        HandRankClass::ThreeQueens,
+        HandRankClass::ThreeJacks,
+        HandRankClass::ThreeTens,
+        HandRankClass::ThreeNines,
+        HandRankClass::ThreeEights,
+        HandRankClass::ThreeSevens,
+        HandRankClass::ThreeSixes,
+        HandRankClass::ThreeFives,
+        HandRankClass::ThreeFours,
+        HandRankClass::ThreeTreys,
+        HandRankClass::ThreeDeuces,
+        HandRankClass::AcesAndKings,
+        HandRankClass::AcesAndQueens,
+        HandRankClass::AcesAndJacks,
+        HandRankClass::AcesAndTens,
+        HandRankClass::AcesAndNines,
+        HandRankClass::AcesAndEights,
+        HandRankClass::AcesAndSevens,
+        HandRankClass::AcesAndSixes,
+        HandRankClass::AcesAndFives,
+        HandRankClass::AcesAndFours,
+        HandRankClass::AcesAndTreys,
+        HandRankClass::AcesAndDeuces,
+        HandRankClass::KingsAndQueens,
+        HandRankClass::KingsAndJacks,
+        HandRankClass::KingsAndTens,
+        HandRankClass::KingsAndNines,
+        HandRankClass::KingsAndEights,
+        HandRankClass::KingsAndSevens,
+        HandRankClass::KingsAndSixes,
+        HandRankClass::KingsAndFives,
+        HandRankClass::KingsAndFours,
+        HandRankClass::KingsAndTreys,
+        HandRankClass::KingsAndDeuces,
+        HandRankClass::QueensAndJacks,
+        HandRankClass::QueensAndTens,
+        HandRankClass::QueensAndNines,
+        HandRankClass::QueensAndEights,
+        HandRankClass::QueensAndSevens,
+        HandRankClass::QueensAndSixes,
+        HandRankClass::QueensAndFives,
+        HandRankClass::QueensAndFours,
+        HandRankClass::QueensAndTreys,
+        HandRankClass::QueensAndDeuces,
+        HandRankClass::JacksAndTens,
+        HandRankClass::JacksAndNines,
+        HandRankClass::JacksAndEights,
+        HandRankClass::JacksAndSevens,
+        HandRankClass::JacksAndSixes,
+        HandRankClass::JacksAndFives,
+        HandRankClass::JacksAndFours,
+        HandRankClass::JacksAndTreys,
+        HandRankClass::JacksAndDeuces,
+        HandRankClass::TensAndNines,
+        HandRankClass::TensAndEights,
+        HandRankClass::TensAndSevens,
+        HandRankClass::TensAndSixes,
+        HandRankClass::TensAndFives,
+        HandRankClass::TensAndFours,
+        HandRankClass::TensAndTreys,
+        HandRankClass::TensAndDeuces,
+        HandRankClass::NinesAndEights,
+        HandRankClass::NinesAndSevens,
+        HandRankClass::NinesAndSixes,
+        HandRankClass::NinesAndFives,
+        HandRankClass::NinesAndFours,
+        HandRankClass::NinesAndTreys,
+        HandRankClass::NinesAndDeuces,
+        HandRankClass::EightsAndSevens,
+        HandRankClass::EightsAndSixes,
+        HandRankClass::EightsAndFives,
+        HandRankClass::EightsAndFours,
+        HandRankClass::EightsAndTreys,
+        HandRankClass::EightsAndDeuces,
+        HandRankClass::SevensAndSixes,
+        HandRankClass::SevensAndFives,
+        HandRankClass::SevensAndFours,
+        HandRankClass::SevensAndTreys,
+        HandRankClass::SevensAndDeuces,
+        HandRankClass::SixesAndFives,
+        HandRankClass::SixesAndFours,
+        HandRankClass::SixesAndTreys,
+        HandRankClass::SixesAndDeuces,
+        HandRankClass::FivesAndFours,
+        HandRankClass::FivesAndTreys,
+        HandRankClass::FivesAndDeuces,
+        HandRankClass::FoursAndTreys,
+        HandRankClass::FoursAndDeuces,
+        HandRankClass::TreysAndDeuces,
+        HandRankClass::PairOfAces,
+        HandRankClass::PairOfKings,
+        HandRankClass::PairOfQueens,
+        HandRankClass::PairOfJacks,
+        HandRankClass::PairOfTens,
+        HandRankClass::PairOfNines,
+        HandRankClass::PairOfEights,
+        HandRankClass::PairOfSevens,
+        HandRankClass::PairOfSixes,
+        HandRankClass::PairOfFives,
+        HandRankClass::PairOfFours,
+        HandRankClass::PairOfTreys,
+        HandRankClass::PairOfDeuces,
+        HandRankClass::AceHigh,
+        HandRankClass::KingHigh,
+        HandRankClass::QueenHigh,
+        HandRankClass::JackHigh,
+        HandRankClass::TenHigh,
+        HandRankClass::NineHigh,
+        HandRankClass::EightHigh,
+        HandRankClass::SevenHigh,
+    ];
+
+    /// The lowest and highest `HandRankValue` of each class in `HandRankClass::ALL`, at the same
+    /// index. The ranges run on from each other, from 1 up to 7462, and
+    /// `HandRank::determine_class()` searches them.
+    #[rustfmt::skip]
+    pub const VALUE_RANGES: [(HandRankValue, HandRankValue); 309] = [
+        (1, 1),
+        (2, 2),
+        (3, 3),
+        (4, 4),
+        (5, 5),
+        (6, 6),
+        (7, 7),
+        (8, 8),
+        (9, 9),
+        (10, 10),
+        (11, 22),
+        (23, 34),
+        (35, 46),
+        (47, 58),
+        (59, 70),
+        (71, 82),
+        (83, 94),
+        (95, 106),
+        (107, 118),
+        (119, 130),
+        (131, 142),
+        (143, 154),
+        (155, 166),
+        (167, 167),
+        (168, 168),
+        (169, 169),
+        (170, 170),
+        (171, 171),
+        (172, 172),
+        (173, 173),
+        (174, 174),
+        (175, 175),
+        (176, 176),
+        (177, 177),
+        (178, 178),
+        (179, 179),
+        (180, 180),
+        (181, 181),
+        (182, 182),
+        (183, 183),
+        (184, 184),
+        (185, 185),
+        (186, 186),
+        (187, 187),
+        (188, 188),
+        (189, 189),
+        (190, 190),
+        (191, 191),
+        (192, 192),
+        (193, 193),
+        (194, 194),
+        (195, 195),
+        (196, 196),
+        (197, 197),
+        (198, 198),
+        (199, 199),
+        (200, 200),
+        (201, 201),
+        (202, 202),
+        (203, 203),
+        (204, 204),
+        (205, 205),
+        (206, 206),
+        (207, 207),
+        (208, 208),
+        (209, 209),
+        (210, 210),
+        (211, 211),
+        (212, 212),
+        (213, 213),
+        (214, 214),
+        (215, 215),
+        (216, 216),
+        (217, 217),
+        (218, 218),
+        (219, 219),
+        (220, 220),
+        (221, 221),
+        (222, 222),
+        (223, 223),
+        (224, 224),
+        (225, 225),
+        (226, 226),
+        (227, 227),
+        (228, 228),
+        (229, 229),
+        (230, 230),
+        (231, 231),
+        (232, 232),
+        (233, 233),
+        (234, 234),
+        (235, 235),
+        (236, 236),
+        (237, 237),
+        (238, 238),
+        (239, 239),
+        (240, 240),
+        (241, 241),
+        (242, 242),
+        (243, 243),
+        (244, 244),
+        (245, 245),
+        (246, 246),
+        (247, 247),
+        (248, 248),
+        (249, 249),
+        (250, 250),
+        (251, 251),
+        (252, 252),
+        (253, 253),
+        (254, 254),
+        (255, 255),
+        (256, 256),
+        (257, 257),
+        (258, 258),
+        (259, 259),
+        (260, 260),
+        (261, 261),
+        (262, 262),
+        (263, 263),
+        (264, 264),
+        (265, 265),
+        (266, 266),
+        (267, 267),
+        (268, 268),
+        (269, 269),
+        (270, 270),
+        (271, 271),
+        (272, 272),
+        (273, 273),
+        (274, 274),
+        (275, 275),
+        (276, 276),
+        (277, 277),
+        (278, 278),
+        (279, 279),
+        (280, 280),
+        (281, 281),
+        (282, 282),
+        (283, 283),
+        (284, 284),
+        (285, 285),
+        (286, 286),
+        (287, 287),
+        (288, 288),
+        (289, 289),
+        (290, 290),
+        (291, 291),
+        (292, 292),
+        (293, 293),
+        (294, 294),
+        (295, 295),
+        (296, 296),
+        (297, 297),
+        (298, 298),
+        (299, 299),
+        (300, 300),
+        (301, 301),
+        (302, 302),
+        (303, 303),
+        (304, 304),
+        (305, 305),
+        (306, 306),
+        (307, 307),
+        (308, 308),
+        (309, 309),
+        (310, 310),
+        (311, 311),
+        (312, 312),
+        (313, 313),
+        (314, 314),
+        (315, 315),
+        (316, 316),
+        (317, 317),
+        (318, 318),
+        (319, 319),
+        (320, 320),
+        (321, 321),
+        (322, 322),
+        (323, 815),
+        (816, 1144),
+        (1145, 1353),
+        (1354, 1478),
+        (1479, 1547),
+        (1548, 1581),
+        (1582, 1595),
+        (1596, 1599),
+        (1600, 1600),
+        (1601, 1601),
+        (1602, 1602),
+        (1603, 1603),
+        (1604, 1604),
+        (1605, 1605),
+        (1606, 1606),
+        (1607, 1607),
+        (1608, 1608),
+        (1609, 1609),
+        (1610, 1675),
+        (1676, 1741),
+        (1742, 1807),
+        (1808, 1873),
+        (1874, 1939),
+        (1940, 2005),
+        (2006, 2071),
+        (2072, 2137),
+        (2138, 2203),
+        (2204, 2269),
+        (2270, 2335),
+        (2336, 2401),
+        (2402, 2467),
+        (2468, 2478),
+        (2479, 2489),
+        (2490, 2500),
+        (2501, 2511),
+        (2512, 2522),
+        (2523, 2533),
+        (2534, 2544),
+        (2545, 2555),
+        (2556, 2566),
+        (2567, 2577),
+        (2578, 2588),
+        (2589, 2599),
+        (2600, 2610),
+        (2611, 2621),
+        (2622, 2632),
+        (2633, 2643),
+        (2644, 2654),
+        (2655, 2665),
+        (2666, 2676),
+        (2677, 2687),
+        (2688, 2698),
+        (2699, 2709),
+        (2710, 2720),
+        (2721, 2731),
+        (2732, 2742),
+        (2743, 2753),
+        (2754, 2764),
+        (2765, 2775),
+        (2776, 2786),
+        (2787, 2797),
+        (2798, 2808),
+        (2809, 2819),
+        (2820, 2830),
+        (2831, 2841),
+        (2842, 2852),
+        (2853, 2863),
+        (2864, 2874),
+        (2875, 2885),
+        (2886, 2896),
+        (2897, 2907),
+        (2908, 2918),
+        (2919, 2929),
+        (2930, 2940),
+        (2941, 2951),
+        (2952, 2962),
+        (2963, 2973),
+        (2974, 2984),
+        (2985, 2995),
+        (2996, 3006),
+        (3007, 3017),
+        (3018, 3028),
+        (3029, 3039),
+        (3040, 3050),
+        (3051, 3061),
+        (3062, 3072),
+        (3073, 3083),
+        (3084, 3094),
+        (3095, 3105),
+        (3106, 3116),
+        (3117, 3127),
+        (3128, 3138),
+        (3139, 3149),
+        (3150, 3160),
+        (3161, 3171),
+        (3172, 3182),
+        (3183, 3193),
+        (3194, 3204),
+        (3205, 3215),
+        (3216, 3226),
+        (3227, 3237),
+        (3238, 3248),
+        (3249, 3259),
+        (3260, 3270),
+        (3271, 3281),
+        (3282, 3292),
+        (3293, 3303),
+        (3304, 3314),
+        (3315, 3325),
+        (3326, 3545),
+        (3546, 3765),
+        (3766, 3985),
+        (3986, 4205),
+        (4206, 4425),
+        (4426, 4645),
+        (4646, 4865),
+        (4866, 5085),
+        (5086, 5305),
+        (5306, 5525),
+        (5526, 5745),
+        (5746, 5965),
+        (5966, 6185),
+        (6186, 6678),
+        (6679, 7007),
+        (7008, 7216),
+        (7217, 7341),
+        (7342, 7410),
+        (7411, 7444),
+        (7445, 7458),
+        (7459, 7462),
+    ];
+
+    /// The lowest `HandRankValue` in the class, or zero for `Invalid`.
+    #[must_use]
+    pub const fn min_value(self) -> HandRankValue {
+        match self {
+            HandRankClass::Invalid => NO_HAND_RANK_VALUE,
+            _ => HandRankClass::VALUE_RANGES[self as usize].0,
+        }
+    }
+
+    /// The highest `HandRankValue` in the class, or zero for `Invalid`.
+    #[must_use]
+    pub const fn max_value(self) -> HandRankValue {
+        match self {
+            HandRankClass::Invalid => NO_HAND_RANK_VALUE,
+            _ => HandRankClass::VALUE_RANGES[self as usize].1,
+        }
+    }
+
+    /// The lowest and highest `HandRankValue` in the class, or `(0, 0)` for `Invalid`.
+    #[must_use]
+    pub fn value_range(self) -> (HandRankValue, HandRankValue) {
+        (self.min_value(), self.max_value())
+    }
+
+    /// The number of distinct `HandRankValues` in the class, such as 12 for `FourAces`.
+    #[must_use]
+    pub fn count(self) -> usize {
+        match self.value_range() {
+            (NO_HAND_RANK_VALUE, _) => 0,
+            (lowest, highest) => usize::from(highest - lowest) + 1,
+        }
+    }
+
+    /// The `HandRankName` that the class belongs to.
+    #[must_use]
+    pub fn name(self) -> HandRankName {
+        HandRank::determine_name(&self.value_range().0)
+    }
+}
+
+/// `HandRankName` is declared from strongest to weakest, so each name covers a single run of
+/// values. Binary searches `determine_name()`, by way of `compare`, for where that run starts and
+/// ends.
+fn value_range(compare: impl Fn(HandRankValue) -> Ordering) -> (HandRankValue, HandRankValue) {
+    let partition = |before: &dyn Fn(Ordering) -> bool| {
+        let (mut low, mut high): (HandRankValue, HandRankValue) = (1, 7463);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if before(compare(mid)) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    };
+    let lowest = partition(&|ordering| ordering == Ordering::Less);
+    let highest = partition(&|ordering| ordering != Ordering::Greater) - 1;
+    (lowest, highest)
+}
+
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod hand_rank_tests {
     use super::*;
-    use crate::cards::five::Five;
     use crate::cards::HandRanker;
     use crate::parse::five_from_index;
     use alloc::format;
     use rstest::rstest;
 
+    #[test]
+    fn example_hand() {
+        for value in 1..=7462 {
+            let hand = HandRank::from(value).example_hand();
+
+            assert!(hand.is_valid());
+            assert_eq!(value, hand.hand_rank_value());
+        }
+        assert_eq!(Five::default(), HandRank::default().example_hand());
+        assert_eq!(Five::default(), HandRank::from(7463).example_hand());
+    }
+
+    #[rstest]
+    #[case(1, 7461)]
+    #[case(1609, 5853)]
+    #[case(7462, 0)]
+    #[case(0, 0)]
+    #[case(7463, 0)]
+    fn beats_count(#[case] value: HandRankValue, #[case] expected: usize) {
+        assert_eq!(expected, HandRank::from(value).beats_count());
+    }
+
+    #[test]
+    fn percentile() {
+        assert!((HandRank::from(7462).percentile()).abs() < f64::EPSILON);
+        assert!((HandRank::from(3731).percentile() - 0.5).abs() < f64::EPSILON);
+        assert!(HandRank::from(1).percentile() > 0.9998);
+        assert!(HandRank::default().percentile().abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn seven_card_beats_count() {
+        // The worst two pair beats all 58,627,800 seven card hands with one pair and all
+        // 23,294,460 with no pair.
+        assert_eq!(81_922_260, HandRank::from(3325).seven_card_beats_count());
+        // The 4,324 royal flushes are the only hands that a royal flush doesn't beat.
+        assert_eq!(133_784_560 - 4_324, HandRank::from(1).seven_card_beats_count());
+        assert_eq!(0, HandRank::from(7462).seven_card_beats_count());
+        assert_eq!(0, HandRank::default().seven_card_beats_count());
+    }
+
+    #[test]
+    fn seven_card_percentile() {
+        let worst_two_pair = HandRank::from(3325);
+
+        // The worst two pair beats 55% of the values, but 61% of seven card hands, since most
+        // of them have no more than a pair.
+        assert!((worst_two_pair.percentile() - 4137.0 / 7462.0).abs() < f64::EPSILON);
+        assert!((worst_two_pair.seven_card_percentile() - 81_922_260.0 / 133_784_560.0).abs() < f64::EPSILON);
+        assert!(HandRank::from(1).seven_card_percentile() > 0.9999);
+    }
+
+    #[rstest]
+    #[case(1, "A♠ K♠ Q♠ J♠ T♠")]
+    #[case(11, "A♠ A♥ A♦ A♣ K♠")]
+    #[case(322, "3♠ 3♥ 2♠ 2♥ 2♦")]
+    #[case(1609, "A♠ 5♠ 4♠ 3♠ 2♥")]
+    #[case(3325, "3♠ 3♥ 2♠ 2♥ 4♠")]
+    #[case(7462, "7♠ 5♠ 4♠ 3♠ 2♥")]
+    fn example_hand__cards(#[case] value: HandRankValue, #[case] expected: &'static str) {
+        assert_eq!(
+            Five::try_from(expected).unwrap().sort(),
+            HandRank::from(value).example_hand()
+        );
+    }
+
     #[test]
     fn is_aligned() {
         assert!(HandRank::from(0).is_a_valid_hand_rank());
@@ -802,6 +1414,15 @@ mod hand_rank_tests {
         );
     }
 
+    #[test]
+    fn write_index() {
+        let mut index = String::new();
+
+        HandRank::from(1).write_index(&mut index).unwrap();
+
+        assert_eq!(HandRank::from(1).to_string(), index);
+    }
+
     #[test]
     fn ord() {
         assert!(HandRank::from(1) > HandRank::from(2));
@@ -1291,4 +1912,140 @@ mod hand_rank_tests {
         // Restore the hook so other panics aren't suppressed.
         // panic::set_hook(hook);
     }
+
+    #[test]
+    fn hand_rank_class__value_range() {
+        let mut count = 0;
+        for value in 1..=7462 {
+            let class = HandRank::determine_class(&value);
+            let (lowest, highest) = class.value_range();
+
+            assert!(lowest <= value && value <= highest);
+            if value == lowest {
+                count += class.count();
+            }
+        }
+        assert_eq!(7462, count);
+        assert_eq!((1, 1), HandRankClass::RoyalFlush.value_range());
+        assert_eq!((11, 22), HandRankClass::FourAces.value_range());
+        assert_eq!((7459, 7462), HandRankClass::SevenHigh.value_range());
+        assert_eq!((0, 0), HandRankClass::Invalid.value_range());
+        assert_eq!(0, HandRankClass::Invalid.count());
+    }
+
+    #[test]
+    fn hand_rank_class__all() {
+        for (i, class) in HandRankClass::ALL.iter().enumerate() {
+            assert_eq!(i, *class as usize);
+        }
+        assert_eq!(HandRankClass::ALL.len(), HandRankClass::Invalid as usize);
+    }
+
+    #[test]
+    #[cfg(feature = "strum")]
+    fn hand_rank_class__all__enum_iter() {
+        use strum::IntoEnumIterator;
+
+        let classes: alloc::vec::Vec<HandRankClass> = HandRankClass::iter().collect();
+
+        assert_eq!(HandRankClass::ALL.as_slice(), &classes[..classes.len() - 1]);
+        assert_eq!(Some(&HandRankClass::Invalid), classes.last());
+    }
+
+    #[rstest]
+    #[case(0, HandRankClass::Invalid)]
+    #[case(1, HandRankClass::RoyalFlush)]
+    #[case(10, HandRankClass::FiveHighStraightFlush)]
+    #[case(11, HandRankClass::FourAces)]
+    #[case(166, HandRankClass::FourDeuces)]
+    #[case(167, HandRankClass::AcesOverKings)]
+    #[case(322, HandRankClass::DeucesOverTreys)]
+    #[case(323, HandRankClass::AceHighFlush)]
+    #[case(1599, HandRankClass::SevenHighFlush)]
+    #[case(1600, HandRankClass::AceHighStraight)]
+    #[case(1609, HandRankClass::FiveHighStraight)]
+    #[case(1610, HandRankClass::ThreeAces)]
+    #[case(2467, HandRankClass::ThreeDeuces)]
+    #[case(2468, HandRankClass::AcesAndKings)]
+    #[case(3325, HandRankClass::TreysAndDeuces)]
+    #[case(3326, HandRankClass::PairOfAces)]
+    #[case(6185, HandRankClass::PairOfDeuces)]
+    #[case(6186, HandRankClass::AceHigh)]
+    #[case(7462, HandRankClass::SevenHigh)]
+    #[case(7463, HandRankClass::Invalid)]
+    fn hand_rank_class__value_ranges(#[case] value: HandRankValue, #[case] expected: HandRankClass) {
+        assert_eq!(expected, HandRank::determine_class(&value));
+    }
+
+    #[rstest]
+    #[case(HandRankName::StraightFlush, (1, 10), 10)]
+    #[case(HandRankName::FourOfAKind, (11, 166), 156)]
+    #[case(HandRankName::FullHouse, (167, 322), 156)]
+    #[case(HandRankName::Flush, (323, 1599), 1277)]
+    #[case(HandRankName::Straight, (1600, 1609), 10)]
+    #[case(HandRankName::ThreeOfAKind, (1610, 2467), 858)]
+    #[case(HandRankName::TwoPair, (2468, 3325), 858)]
+    #[case(HandRankName::Pair, (3326, 6185), 2860)]
+    #[case(HandRankName::HighCard, (6186, 7462), 1277)]
+    #[case(HandRankName::Invalid, (0, 0), 0)]
+    fn hand_rank_name__value_range(
+        #[case] name: HandRankName,
+        #[case] expected: (HandRankValue, HandRankValue),
+        #[case] count: usize,
+    ) {
+        assert_eq!(expected, name.value_range());
+        assert_eq!(count, name.count());
+    }
+
+    #[rstest]
+    #[case(HandRankName::StraightFlush, 41_584)]
+    #[case(HandRankName::FullHouse, 3_473_184)]
+    #[case(HandRankName::Pair, 58_627_800)]
+    #[case(HandRankName::HighCard, 23_294_460)]
+    #[case(HandRankName::Invalid, 0)]
+    fn hand_rank_name__seven_card_count(#[case] name: HandRankName, #[case] expected: u32) {
+        assert_eq!(expected, name.seven_card_count());
+    }
+
+    #[test]
+    fn hand_rank_name__seven_card_probability() {
+        assert!((HandRankName::Pair.seven_card_probability() - 0.438_225_46).abs() < 1e-8);
+        assert!((HandRankName::StraightFlush.seven_card_probability() - 0.000_310_83).abs() < 1e-8);
+        assert!(HandRankName::Invalid.seven_card_probability().abs() < f64::EPSILON);
+    }
+
+    #[rstest]
+    #[case(HandRankClass::RoyalFlush, HandRankName::StraightFlush)]
+    #[case(HandRankClass::AcesOverKings, HandRankName::FullHouse)]
+    #[case(HandRankClass::TreysAndDeuces, HandRankName::TwoPair)]
+    #[case(HandRankClass::SevenHigh, HandRankName::HighCard)]
+    #[case(HandRankClass::Invalid, HandRankName::Invalid)]
+    fn hand_rank_class__name(#[case] class: HandRankClass, #[case] expected: HandRankName) {
+        assert_eq!(expected, class.name());
+    }
+
+    #[cfg(feature = "serde")]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Logged {
+        #[serde(with = "crate::hand_rank::compact")]
+        rank: HandRank,
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn compact() {
+        let logged = Logged {
+            rank: HandRank::from(1603),
+        };
+
+        let json = serde_json::to_string(&logged).unwrap();
+
+        assert_eq!("{\"rank\":1603}", json);
+        assert_eq!(logged, serde_json::from_str::<Logged>(&json).unwrap());
+        assert_eq!(
+            HandRank::default(),
+            serde_json::from_str::<Logged>("{\"rank\":0}").unwrap().rank
+        );
+        assert!(serde_json::from_str::<Logged>("{\"rank\":7463}").is_err());
+    }
 }
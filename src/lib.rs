@@ -1,4 +1,4 @@
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 #![warn(clippy::pedantic)]
 #![allow(clippy::unreadable_literal)]
 
@@ -6,13 +6,35 @@ extern crate alloc;
 
 use crate::cards::binary_card::{BinaryCard, BC64};
 use crate::parse::get_rank_and_suit;
+#[cfg(feature = "strum")]
 use strum::EnumIter;
 
+pub mod analysis;
+pub mod backend;
+pub mod batch;
+pub mod cancel;
+pub mod card;
 pub mod cards;
+pub mod collections;
 pub mod deck;
+pub mod dice;
+pub mod evaluate;
+#[cfg(feature = "ckc_ffi")]
+pub mod ffi;
 pub mod hand_rank;
 mod lookups;
+pub mod math;
 pub mod parse;
+pub mod prelude;
+pub mod replay;
+pub mod showdown;
+#[cfg(feature = "rand")]
+pub mod sim;
+pub mod split;
+pub mod table;
+
+pub use card::Card;
+pub use deck::{Deck, DECK_SIZE, POKER_DECK};
 
 /// A `PokerCard` is a u32 representation of a variant of Cactus Kev's binary
 /// representation of a poker card as designed for rapid hand evaluation as
@@ -131,7 +153,10 @@ mod card_number_tests {
     }
 }
 
-#[derive(Clone, Copy, Debug, EnumIter, Eq, Hash, PartialEq)]
+/// The rank of a card. With the `strum` feature, `CardRank::iter()` runs from `ACE` down to `TWO`,
+/// followed by `BLANK`.
+#[cfg_attr(feature = "strum", derive(EnumIter))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum CardRank {
     ACE = 14,
     KING = 13,
@@ -150,6 +175,73 @@ pub enum CardRank {
 }
 
 impl CardRank {
+    /// The thirteen ranks, from `ACE` down to `TWO`.
+    pub const ALL: [CardRank; 13] = [
+        CardRank::ACE,
+        CardRank::KING,
+        CardRank::QUEEN,
+        CardRank::JACK,
+        CardRank::TEN,
+        CardRank::NINE,
+        CardRank::EIGHT,
+        CardRank::SEVEN,
+        CardRank::SIX,
+        CardRank::FIVE,
+        CardRank::FOUR,
+        CardRank::THREE,
+        CardRank::TWO,
+    ];
+
+    /// Returns the `CardRank` with the matching discriminant, so `14` is an `ACE` and `2` is a
+    /// `TWO`. Anything else is `BLANK`.
+    #[must_use]
+    pub fn from_u8(value: u8) -> CardRank {
+        match value {
+            14 => CardRank::ACE,
+            13 => CardRank::KING,
+            12 => CardRank::QUEEN,
+            11 => CardRank::JACK,
+            10 => CardRank::TEN,
+            9 => CardRank::NINE,
+            8 => CardRank::EIGHT,
+            7 => CardRank::SEVEN,
+            6 => CardRank::SIX,
+            5 => CardRank::FIVE,
+            4 => CardRank::FOUR,
+            3 => CardRank::THREE,
+            2 => CardRank::TWO,
+            _ => CardRank::BLANK,
+        }
+    }
+
+    /// The number of ranks between the two, so `ACE` and `TEN` are a distance of 4 apart in
+    /// either direction. Aces only play high. Returns 0 if either rank is `BLANK`.
+    #[must_use]
+    pub fn distance(self, other: CardRank) -> u8 {
+        if self == CardRank::BLANK || other == CardRank::BLANK {
+            return 0;
+        }
+        (self as u8).abs_diff(other as u8)
+    }
+
+    /// Returns the next highest rank, or `None` for `ACE` and `BLANK`.
+    #[must_use]
+    pub fn next(self) -> Option<CardRank> {
+        match self {
+            CardRank::ACE | CardRank::BLANK => None,
+            _ => Some(CardRank::from_u8(self as u8 + 1)),
+        }
+    }
+
+    /// Returns the next lowest rank, or `None` for `TWO` and `BLANK`.
+    #[must_use]
+    pub fn prev(self) -> Option<CardRank> {
+        match self {
+            CardRank::TWO | CardRank::BLANK => None,
+            _ => Some(CardRank::from_u8(self as u8 - 1)),
+        }
+    }
+
     #[must_use]
     pub fn from_char(index: char) -> CardRank {
         match index {
@@ -246,9 +338,52 @@ mod card_rank_tests {
     fn from_char(#[case] input: char, #[case] expected: CardRank) {
         assert_eq!(expected, CardRank::from_char(input));
     }
+
+    #[test]
+    #[cfg(feature = "strum")]
+    fn all() {
+        use strum::IntoEnumIterator;
+
+        let iterated: Vec<CardRank> = CardRank::iter().collect();
+
+        assert_eq!(CardRank::ALL.to_vec(), iterated[..13]);
+        assert_eq!(CardRank::BLANK, iterated[13]);
+    }
+
+    #[rstest]
+    #[case(CardRank::ACE, CardRank::TEN, 4)]
+    #[case(CardRank::TEN, CardRank::ACE, 4)]
+    #[case(CardRank::ACE, CardRank::TWO, 12)]
+    #[case(CardRank::SEVEN, CardRank::SEVEN, 0)]
+    #[case(CardRank::SEVEN, CardRank::BLANK, 0)]
+    fn distance(#[case] rank: CardRank, #[case] other: CardRank, #[case] expected: u8) {
+        assert_eq!(expected, rank.distance(other));
+    }
+
+    #[test]
+    fn from_u8() {
+        for rank in CardRank::ALL {
+            assert_eq!(rank, CardRank::from_u8(rank as u8));
+        }
+        assert_eq!(CardRank::BLANK, CardRank::from_u8(1));
+        assert_eq!(CardRank::BLANK, CardRank::from_u8(15));
+    }
+
+    #[rstest]
+    #[case(CardRank::TWO, Some(CardRank::THREE), None)]
+    #[case(CardRank::TEN, Some(CardRank::JACK), Some(CardRank::NINE))]
+    #[case(CardRank::ACE, None, Some(CardRank::KING))]
+    #[case(CardRank::BLANK, None, None)]
+    fn next_and_prev(#[case] rank: CardRank, #[case] next: Option<CardRank>, #[case] prev: Option<CardRank>) {
+        assert_eq!(next, rank.next());
+        assert_eq!(prev, rank.prev());
+    }
 }
 
-#[derive(Clone, Copy, Debug, EnumIter, Eq, Hash, PartialEq)]
+/// The suit of a card. With the `strum` feature, `CardSuit::iter()` runs `SPADES`, `HEARTS`,
+/// `DIAMONDS`, `CLUBS`, followed by `BLANK`.
+#[cfg_attr(feature = "strum", derive(EnumIter))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum CardSuit {
     SPADES = 4,
     HEARTS = 3,
@@ -258,6 +393,12 @@ pub enum CardSuit {
 }
 
 impl CardSuit {
+    /// The four suits, in the same order as the `POKER_DECK`.
+    #[must_use]
+    pub fn all() -> [CardSuit; 4] {
+        [CardSuit::SPADES, CardSuit::HEARTS, CardSuit::DIAMONDS, CardSuit::CLUBS]
+    }
+
     #[must_use]
     pub fn binary_signature(&self) -> u32 {
         match self {
@@ -286,6 +427,19 @@ mod card_suit_tests {
     use super::*;
     use rstest::rstest;
 
+    #[test]
+    #[cfg(feature = "strum")]
+    fn all() {
+        use strum::IntoEnumIterator;
+
+        let iterated: Vec<CardSuit> = CardSuit::iter().take(4).collect();
+
+        assert_eq!(CardSuit::all().to_vec(), iterated);
+        for (i, suit) in CardSuit::all().iter().enumerate() {
+            assert_eq!(*suit, POKER_DECK[i * 13].get_card_suit());
+        }
+    }
+
     #[test]
     fn binary_signature() {
         assert_eq!(32768, CardSuit::SPADES.binary_signature());
@@ -319,138 +473,9 @@ mod card_suit_tests {
     }
 }
 
-pub mod evaluate {
-    use crate::cards::five::Five;
-    use crate::cards::HandRanker;
-    use crate::hand_rank::HandRankValue;
-    use crate::{CKCNumber, CardNumber};
-
-    pub const POSSIBLE_COMBINATIONS: usize = 7937;
-
-    #[must_use]
-    #[allow(clippy::cast_possible_truncation)]
-    pub fn five_cards(five_cards: [CKCNumber; 5]) -> HandRankValue {
-        Five::from(five_cards).hand_rank_value_validated()
-    }
-
-    #[must_use]
-    #[deprecated(since = "0.1.9", note = "use Five.is_flush()")]
-    pub fn is_flush(five_cards: [CKCNumber; 5]) -> bool {
-        (five_cards[0] & five_cards[1] & five_cards[2] & five_cards[3] & five_cards[4] & CardNumber::SUIT_FILTER) != 0
-    }
-
-    /// Returns a value that is made up of performing an or operation on all of the
-    /// rank bit flags of the `PokerCard`.
-    #[must_use]
-    #[deprecated(since = "0.1.9", note = "use Five.or_rank_bits()")]
-    pub fn or_rank_bits(five_cards: [CKCNumber; 5]) -> usize {
-        Five::from(five_cards).or_rank_bits() as usize
-    }
-}
-
-#[cfg(test)]
-mod evaluate_tests {
-    use super::*;
-
-    #[test]
-    fn five_cards_royal_flush() {
-        let cards = [
-            CardNumber::ACE_SPADES,
-            CardNumber::KING_SPADES,
-            CardNumber::QUEEN_SPADES,
-            CardNumber::JACK_SPADES,
-            CardNumber::TEN_SPADES,
-        ];
-        assert_eq!(evaluate::five_cards(cards), 1);
-    }
-
-    #[test]
-    fn five_cards_straight() {
-        let first = [
-            CardNumber::NINE_CLUBS,
-            CardNumber::KING_SPADES,
-            CardNumber::QUEEN_SPADES,
-            CardNumber::JACK_SPADES,
-            CardNumber::TEN_SPADES,
-        ];
-        let second = [
-            CardNumber::NINE_CLUBS,
-            CardNumber::QUEEN_SPADES,
-            CardNumber::JACK_SPADES,
-            CardNumber::TEN_SPADES,
-            CardNumber::EIGHT_CLUBS,
-        ];
-        assert_eq!(evaluate::five_cards(first), 1601);
-        assert_eq!(evaluate::five_cards(second), 1602);
-    }
-
-    #[test]
-    fn five_cards_two_pair() {
-        let cards = [
-            CardNumber::JACK_CLUBS,
-            CardNumber::DEUCE_CLUBS,
-            CardNumber::DEUCE_DIAMONDS,
-            CardNumber::JACK_SPADES,
-            CardNumber::TEN_SPADES,
-        ];
-        assert_eq!(evaluate::five_cards(cards), 2922);
-    }
-
-    #[test]
-    fn five_cards_king_high() {
-        let first = [
-            CardNumber::JACK_CLUBS,
-            CardNumber::DEUCE_CLUBS,
-            CardNumber::TREY_CLUBS,
-            CardNumber::KING_SPADES,
-            CardNumber::TEN_SPADES,
-        ];
-        let second = [
-            CardNumber::JACK_CLUBS,
-            CardNumber::QUEEN_DIAMONDS,
-            CardNumber::TREY_CLUBS,
-            CardNumber::KING_SPADES,
-            CardNumber::TEN_SPADES,
-        ];
-        assert_eq!(evaluate::five_cards(first), 6825);
-        assert_eq!(evaluate::five_cards(second), 6684);
-    }
-
-    #[test]
-    fn check_dupes() {
-        let hand = [
-            CardNumber::JACK_CLUBS,
-            CardNumber::DEUCE_CLUBS,
-            CardNumber::TREY_CLUBS,
-            CardNumber::KING_SPADES,
-            CardNumber::JACK_CLUBS,
-        ];
-        assert_eq!(evaluate::five_cards(hand), 0);
-    }
-
-    #[test]
-    fn check_corrupt() {
-        let first = [
-            CardNumber::JACK_CLUBS,
-            CardNumber::DEUCE_CLUBS,
-            23,
-            CardNumber::KING_SPADES,
-            CardNumber::TEN_SPADES,
-        ];
-        let second = [
-            CardNumber::JACK_CLUBS,
-            CardNumber::QUEEN_DIAMONDS,
-            CardNumber::TREY_CLUBS,
-            CardNumber::KING_SPADES,
-            CardNumber::BLANK,
-        ];
-        assert_eq!(evaluate::five_cards(first), 0);
-        assert_eq!(evaluate::five_cards(second), 0);
-    }
-}
-
 #[derive(Debug, PartialEq)]
 pub enum HandError {
+    AsymmetricRange,
     BlankCard,
     DuplicateCard,
     Incomplete,
@@ -458,6 +483,7 @@ pub enum HandError {
     InvalidCard,
     InvalidCardCount,
     InvalidIndex,
+    InvalidSeatCount,
     NotEnoughCards,
     TooManyCards,
 }
@@ -595,6 +621,22 @@ pub trait PokerCard {
         CKCNumber::create(rank, suit)
     }
 
+    /// Converts a card from Cactus Kev's original layout, where the suit bits run `cdhs` instead
+    /// of `SHDC`, so that tables and test vectors made for his evaluator can be read without
+    /// mixing up the suits. Anything that isn't a card in that layout is `CardNumber::BLANK`.
+    ///
+    /// ```
+    /// use ckc_rs::{CKCNumber, CardNumber, PokerCard};
+    ///
+    /// // The king of diamonds from <https://suffe.cool/poker/evaluator.html>.
+    /// assert_eq!(CardNumber::KING_DIAMONDS, CKCNumber::from_cactus_kev(0x0800_4B25));
+    /// assert_eq!(0x0800_4B25, CardNumber::KING_DIAMONDS.to_cactus_kev());
+    /// ```
+    #[must_use]
+    fn from_cactus_kev(number: u32) -> CKCNumber {
+        CKCNumber::filter(reverse_suit_bits(number))
+    }
+
     //endregion
 
     fn as_u32(&self) -> u32;
@@ -618,6 +660,16 @@ pub trait PokerCard {
         }
     }
 
+    /// Converts the card to Cactus Kev's original layout, the reverse of `from_cactus_kev()`.
+    /// The multiples flags are dropped, since his layout doesn't have them, and anything that
+    /// isn't a card is zero.
+    fn to_cactus_kev(&self) -> u32 {
+        match CKCNumber::filter(self.strip_multiples_flags()) {
+            CardNumber::BLANK => 0,
+            card => reverse_suit_bits(card),
+        }
+    }
+
     fn get_card_suit(&self) -> CardSuit {
         match self.get_suit_bit() {
             8 => CardSuit::SPADES,
@@ -731,6 +783,13 @@ pub trait PokerCard {
     //endregion
 }
 
+/// Swaps the suit bits between the `SHDC` order of a `CKCNumber` and Cactus Kev's `cdhs`.
+fn reverse_suit_bits(number: u32) -> u32 {
+    let suits = (number & CardNumber::SUIT_FILTER) >> CardNumber::SUIT_SHIFT;
+    let reversed = suits.reverse_bits() >> (32 - 4);
+    (number & !CardNumber::SUIT_FILTER) | (reversed << CardNumber::SUIT_SHIFT)
+}
+
 impl PokerCard for CKCNumber {
     fn as_u32(&self) -> u32 {
         *self
@@ -759,6 +818,7 @@ impl Shifty for CKCNumber {
 }
 
 #[cfg(test)]
+#[allow(non_snake_case)]
 mod poker_card_tests {
     use super::*;
     use rstest::rstest;
@@ -776,6 +836,33 @@ mod poker_card_tests {
         assert_eq!(<CKCNumber as PokerCard>::filter(2), CardNumber::BLANK);
     }
 
+    /// The examples from <https://suffe.cool/poker/evaluator.html>.
+    #[rstest]
+    #[case(0x0800_4B25, CardNumber::KING_DIAMONDS)]
+    #[case(0x0008_1307, CardNumber::FIVE_SPADES)]
+    #[case(0x0200_891D, CardNumber::JACK_CLUBS)]
+    #[case(0x1000_2C29, CardNumber::ACE_HEARTS)]
+    fn from_cactus_kev(#[case] original: u32, #[case] expected: CKCNumber) {
+        assert_eq!(expected, CKCNumber::from_cactus_kev(original));
+        assert_eq!(original, expected.to_cactus_kev());
+    }
+
+    #[test]
+    fn from_cactus_kev__round_trip() {
+        for card in crate::POKER_DECK {
+            assert_eq!(card, CKCNumber::from_cactus_kev(card.to_cactus_kev()));
+            assert_eq!(card.to_cactus_kev(), card.flag_as_pair().to_cactus_kev());
+        }
+    }
+
+    #[test]
+    fn from_cactus_kev__invalid() {
+        assert_eq!(CardNumber::BLANK, CKCNumber::from_cactus_kev(0));
+        assert_eq!(CardNumber::BLANK, CKCNumber::from_cactus_kev(0x0800_6B25));
+        assert_eq!(0, CardNumber::BLANK.to_cactus_kev());
+        assert_eq!(0, 2.to_cactus_kev());
+    }
+
     #[rstest]
     #[case("A♠", CardNumber::ACE_SPADES)]
     #[case("ks", CardNumber::KING_SPADES)]
@@ -848,7 +935,7 @@ mod poker_card_tests {
     #[case("3s", 1.5)]
     #[case("2S", 1.0)]
     fn get_chen_points(#[case] index: &str, #[case] expected: f32) {
-        assert_eq!(CKCNumber::from_index(index).get_chen_points(), expected);
+        assert!((CKCNumber::from_index(index).get_chen_points() - expected).abs() < f32::EPSILON);
     }
 
     #[test]
@@ -0,0 +1,141 @@
+//! Generates the Cactus Kev lookup tables from first principles.
+//!
+//! This file is shared between `build.rs`, which writes the tables out at compile time, and the
+//! library's golden tests, which verify that the generated tables match the ones originally
+//! borrowed from [pokereval-rs](https://github.com/vsupalov/pokereval-rs).
+//!
+//! Ranks are indexed from deuce (0) to ace (12), matching the `r` and `b` bits of a `CKCNumber`.
+//! Every five card hand falls into one of two buckets:
+//!
+//! * Hands with five distinct ranks (straights, flushes and high cards) are indexed by the OR
+//!   of their rank bits into `FLUSHES` or `UNIQUE_5`.
+//! * Hands with duplicate ranks are identified by the product of their rank primes, which is
+//!   binary searched in `PRODUCTS` to find the matching entry in `VALUES`.
+
+/// Size of the `FLUSHES` and `UNIQUE_5` tables; one larger than the OR of the top five rank bits.
+pub const RANK_BITS_TABLE_SIZE: usize = 7937;
+
+/// Number of distinct five card hands that contain at least two cards of the same rank.
+pub const MULTIPLES_TABLE_SIZE: usize = 4888;
+
+pub const PRIMES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+const ACE: usize = 12;
+const WHEEL: usize = 0b1_0000_0000_1111;
+
+const STRAIGHT_FLUSH_OFFSET: u16 = 1;
+const FOUR_OF_A_KIND_OFFSET: u16 = 11;
+const FLUSH_OFFSET: u16 = 323;
+const STRAIGHT_OFFSET: u16 = 1600;
+const THREE_OF_A_KIND_OFFSET: u16 = 1610;
+const HIGH_CARD_OFFSET: u16 = 6186;
+
+/// The rank bits of every straight, from ace high down to the wheel.
+#[must_use]
+pub fn straights() -> [usize; 10] {
+    let mut straights = [WHEEL; 10];
+    for (i, straight) in straights.iter_mut().take(9).enumerate() {
+        *straight = 0b1_1111_0000_0000 >> i;
+    }
+    straights
+}
+
+/// Returns the `FLUSHES` table.
+#[must_use]
+pub fn flushes() -> Vec<u16> {
+    distinct_ranks(STRAIGHT_FLUSH_OFFSET, FLUSH_OFFSET)
+}
+
+/// Returns the `UNIQUE_5` table.
+#[must_use]
+pub fn unique5() -> Vec<u16> {
+    distinct_ranks(STRAIGHT_OFFSET, HIGH_CARD_OFFSET)
+}
+
+/// Returns the `PRODUCTS` and `VALUES` tables, sorted by product so that they can be binary
+/// searched.
+///
+/// Hands are generated from strongest to weakest (four of a kind, full houses, three of a kind,
+/// two pair, and then pairs) so their values run consecutively from 11 to 6185, skipping the
+/// flush and straight values which live in the rank bit tables.
+#[must_use]
+pub fn products_and_values() -> (Vec<u32>, Vec<u16>) {
+    let mut entries: Vec<(u32, u16)> = Vec::with_capacity(MULTIPLES_TABLE_SIZE);
+    let mut value = FOUR_OF_A_KIND_OFFSET;
+    let mut push = |ranks: [usize; 5]| {
+        entries.push((ranks.iter().map(|r| PRIMES[*r]).product(), value));
+        value += 1;
+        if value == FLUSH_OFFSET {
+            value = THREE_OF_A_KIND_OFFSET;
+        }
+    };
+
+    for quads in descending(ACE) {
+        for kicker in descending(ACE).filter(|r| *r != quads) {
+            push([quads, quads, quads, quads, kicker]);
+        }
+    }
+
+    for trips in descending(ACE) {
+        for pair in descending(ACE).filter(|r| *r != trips) {
+            push([trips, trips, trips, pair, pair]);
+        }
+    }
+
+    for trips in descending(ACE) {
+        for first in descending(ACE).filter(|r| *r != trips) {
+            for second in descending(first).skip(1).filter(|r| *r != trips) {
+                push([trips, trips, trips, first, second]);
+            }
+        }
+    }
+
+    for high in descending(ACE) {
+        for low in descending(high).skip(1) {
+            for kicker in descending(ACE).filter(|r| *r != high && *r != low) {
+                push([high, high, low, low, kicker]);
+            }
+        }
+    }
+
+    for pair in descending(ACE) {
+        for first in descending(ACE).filter(|r| *r != pair) {
+            for second in descending(first).skip(1).filter(|r| *r != pair) {
+                for third in descending(second).skip(1).filter(|r| *r != pair) {
+                    push([pair, pair, first, second, third]);
+                }
+            }
+        }
+    }
+
+    assert_eq!(entries.len(), MULTIPLES_TABLE_SIZE);
+
+    entries.sort_unstable_by_key(|(product, _)| *product);
+    entries.into_iter().unzip()
+}
+
+/// Ranks from `top` down to the deuce.
+fn descending(top: usize) -> impl Iterator<Item = usize> {
+    (0..=top).rev()
+}
+
+/// Assigns values to every combination of five distinct ranks. Straights get the first ten
+/// values starting at `straight_offset`, and everything else is ranked by its rank bits in
+/// descending order starting at `other_offset`. Entries that aren't five distinct ranks are zero.
+fn distinct_ranks(straight_offset: u16, other_offset: u16) -> Vec<u16> {
+    let mut table = vec![0u16; RANK_BITS_TABLE_SIZE];
+    let straights = straights();
+
+    for (value, straight) in (straight_offset..).zip(straights) {
+        table[straight] = value;
+    }
+
+    let others = (0..RANK_BITS_TABLE_SIZE)
+        .rev()
+        .filter(|bits| bits.count_ones() == 5 && !straights.contains(bits));
+    for (value, bits) in (other_offset..).zip(others) {
+        table[bits] = value;
+    }
+
+    table
+}
@@ -0,0 +1,244 @@
+/// The number of ways to choose `k` items out of `n`, or zero if `k` is greater than `n`.
+///
+/// ```
+/// use ckc_rs::math::choose;
+///
+/// assert_eq!(1_326, choose(52, 2));
+/// assert_eq!(133_784_560, choose(52, 7));
+/// ```
+///
+/// # Panics
+///
+/// Panics if the result doesn't fit in a `u64`. Use `checked_choose()` for counts that might
+/// not.
+#[must_use]
+pub const fn choose(n: usize, k: usize) -> u64 {
+    match checked_choose(n, k) {
+        Some(result) => result,
+        None => panic!("choose overflowed a u64"),
+    }
+}
+
+/// The number of ways to choose `k` items out of `n`, the same as `choose()`, or `None` if the
+/// result doesn't fit in a `u64`.
+///
+/// ```
+/// use ckc_rs::math::checked_choose;
+///
+/// assert_eq!(Some(1_326), checked_choose(52, 2));
+/// assert_eq!(None, checked_choose(100, 50));
+/// ```
+#[must_use]
+pub const fn checked_choose(n: usize, k: usize) -> Option<u64> {
+    if k > n {
+        return Some(0);
+    }
+    let k = if k > n - k { n - k } else { k };
+    let mut result: u64 = 1;
+    let mut i = 0;
+    while i < k {
+        // The result so far times n - i is always divisible by i + 1, so dividing out what
+        // the result shares with i + 1 first leaves a divisor of n - i, and only the next result
+        // itself needs to fit.
+        let divisor = (i + 1) as u64;
+        let common = gcd(result, divisor);
+        let factor = (n - i) as u64 / (divisor / common);
+        result = match (result / common).checked_mul(factor) {
+            Some(next) => next,
+            None => return None,
+        };
+        i += 1;
+    }
+    Some(result)
+}
+
+const fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+    a
+}
+
+/// Advances the indexes to the next combination of `n` in lexicographic order, returning
+/// false once every combination has been visited. The indexes must be strictly increasing and
+/// less than `n`, such as `[0, 1, 2, 3, 4]` for the first five card combination.
+pub fn next_combination(indexes: &mut [usize], n: usize) -> bool {
+    let k = indexes.len();
+    if k > n {
+        return false;
+    }
+    let Some(i) = (0..k).rev().find(|i| indexes[*i] < n - k + i) else {
+        return false;
+    };
+    indexes[i] += 1;
+    for j in i + 1..k {
+        indexes[j] = indexes[j - 1] + 1;
+    }
+    true
+}
+
+/// Returns the combination of `K` indexes out of `n` at the passed in position in lexicographic
+/// order, or `None` if the rank is out of range or the number of combinations doesn't fit in a
+/// `u64`. The inverse of counting through
+/// `Combinations`, so a simulation can jump straight to any board.
+///
+/// ```
+/// use ckc_rs::math::unrank;
+///
+/// assert_eq!(Some([0, 1, 2]), unrank::<3>(0, 52));
+/// assert_eq!(Some([49, 50, 51]), unrank::<3>(22_099, 52));
+/// assert_eq!(None, unrank::<3>(22_100, 52));
+/// ```
+#[must_use]
+pub fn unrank<const K: usize>(mut rank: u64, n: usize) -> Option<[usize; K]> {
+    if rank >= checked_choose(n, K)? {
+        return None;
+    }
+    let mut indexes = [0usize; K];
+    let mut next = 0;
+    for (i, index) in indexes.iter_mut().enumerate() {
+        loop {
+            let skipped = checked_choose(n - next - 1, K - i - 1)?;
+            if rank < skipped {
+                break;
+            }
+            rank -= skipped;
+            next += 1;
+        }
+        *index = next;
+        next += 1;
+    }
+    Some(indexes)
+}
+
+/// Iterates through every combination of `K` indexes out of `n` in lexicographic order.
+///
+/// ```
+/// use ckc_rs::math::Combinations;
+///
+/// let mut flops = Combinations::<3>::new(52);
+///
+/// assert_eq!(Some([0, 1, 2]), flops.next());
+/// assert_eq!(Some([0, 1, 3]), flops.next());
+/// assert_eq!(22_098, flops.count());
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Combinations<const K: usize> {
+    n: usize,
+    next: Option<[usize; K]>,
+}
+
+impl<const K: usize> Combinations<K> {
+    #[must_use]
+    pub fn new(n: usize) -> Self {
+        Combinations {
+            n,
+            next: if K <= n {
+                Some(core::array::from_fn(|i| i))
+            } else {
+                None
+            },
+        }
+    }
+}
+
+impl<const K: usize> Iterator for Combinations<K> {
+    type Item = [usize; K];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        let mut indexes = current;
+        self.next = if next_combination(&mut indexes, self.n) {
+            Some(indexes)
+        } else {
+            None
+        };
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod math__combinatorics_tests {
+    use super::*;
+    use crate::cards::seven::Seven;
+    use crate::cards::six::Six;
+    use alloc::vec::Vec;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(52, 2, 1_326)]
+    #[case(52, 5, 2_598_960)]
+    #[case(7, 5, 21)]
+    #[case(5, 0, 1)]
+    #[case(5, 5, 1)]
+    #[case(5, 6, 0)]
+    #[case(0, 0, 1)]
+    fn choose(#[case] n: usize, #[case] k: usize, #[case] expected: u64) {
+        assert_eq!(expected, super::choose(n, k));
+        assert_eq!(Some(expected), checked_choose(n, k));
+    }
+
+    #[test]
+    fn checked_choose__large() {
+        // Multiplying before dividing would overflow partway through both of these.
+        assert_eq!(Some(14_226_520_737_620_288_370), checked_choose(67, 33));
+        assert_eq!(Some(10_666_658_666_668_000_000), checked_choose(4_000_000, 3));
+        assert_eq!(None, checked_choose(68, 34));
+    }
+
+    #[test]
+    fn next_combination() {
+        let mut indexes = [0, 1];
+        let mut count = 1;
+        while super::next_combination(&mut indexes, 4) {
+            count += 1;
+        }
+
+        assert_eq!(6, count);
+        assert_eq!([2, 3], indexes);
+        assert!(!super::next_combination(&mut [0, 1, 2], 2));
+    }
+
+    #[test]
+    fn combinations__permutation_tables() {
+        let sevens: Vec<[usize; 5]> = Combinations::<5>::new(7).collect();
+        let sixes: Vec<[usize; 5]> = Combinations::<5>::new(6).collect();
+
+        assert_eq!(
+            Seven::FIVE_CARD_PERMUTATIONS.map(|p| p.map(usize::from)).to_vec(),
+            sevens
+        );
+        assert_eq!(Six::FIVE_CARD_PERMUTATIONS.map(|p| p.map(usize::from)).to_vec(), sixes);
+    }
+
+    #[test]
+    fn combinations__empty() {
+        assert_eq!(0, Combinations::<3>::new(2).count());
+        assert_eq!(1, Combinations::<0>::new(2).count());
+    }
+
+    #[test]
+    fn unrank() {
+        for (rank, combination) in Combinations::<4>::new(9).enumerate() {
+            assert_eq!(Some(combination), super::unrank::<4>(rank as u64, 9));
+        }
+        assert_eq!(None, super::unrank::<4>(super::choose(9, 4), 9));
+        assert_eq!(None, super::unrank::<4>(0, 3));
+    }
+
+    #[test]
+    fn unrank__large() {
+        let last = super::choose(4_000_000, 3) - 1;
+
+        assert_eq!(Some([0, 1, 2]), super::unrank::<3>(0, 4_000_000));
+        assert_eq!(
+            Some([3_999_997, 3_999_998, 3_999_999]),
+            super::unrank::<3>(last, 4_000_000)
+        );
+        assert_eq!(None, super::unrank::<3>(last + 1, 4_000_000));
+        assert_eq!(None, super::unrank::<34>(0, 68));
+    }
+}
@@ -0,0 +1,124 @@
+//! Floating point functions that `core` leaves to the standard library, for `no_std` builds.
+
+/// The square root of `x`, or zero for zero, negative and `NaN` inputs.
+///
+/// ```
+/// use ckc_rs::math::sqrt;
+///
+/// assert!((sqrt(2.0) - core::f64::consts::SQRT_2).abs() < 1e-15);
+/// assert_eq!(0.0, sqrt(-1.0));
+/// ```
+#[must_use]
+pub fn sqrt(x: f64) -> f64 {
+    if x.is_nan() || x <= 0.0 {
+        return 0.0;
+    }
+    if x.is_infinite() {
+        return x;
+    }
+    // Halving the exponent gives a first guess within a factor of two, and Newton's method
+    // doubles the correct bits on every step.
+    let mut guess = f64::from_bits((x.to_bits() >> 1) + (1023 << 51));
+    for _ in 0..6 {
+        guess = (guess + x / guess) / 2.0;
+    }
+    guess
+}
+
+/// The natural logarithm of 2, split into a high part with its low bits clear, so that
+/// multiplying it by a whole number is exact, and the rest.
+const LN_2_HIGH: f64 = 6.931_471_803_691_238e-1;
+const LN_2_LOW: f64 = 1.908_214_929_270_587_7e-10;
+
+/// `e` raised to the power of `x`, with a relative error under `1e-15`. Large inputs overflow to
+/// infinity, and `NaN` stays `NaN`.
+///
+/// ```
+/// use ckc_rs::math::exp;
+///
+/// assert!((exp(1.0) - core::f64::consts::E).abs() < 1e-15);
+/// assert_eq!(0.0, exp(-1000.0));
+/// ```
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn exp(x: f64) -> f64 {
+    if x.is_nan() {
+        return x;
+    }
+    if x > 709.8 {
+        return f64::INFINITY;
+    }
+    if x < -745.2 {
+        return 0.0;
+    }
+    // e^x = 2^k * e^r, with k the nearest whole number to x / ln 2, which leaves r within
+    // ln 2 / 2 of zero, where the series converges quickly.
+    let half = if x < 0.0 { -0.5 } else { 0.5 };
+    let k = (x / core::f64::consts::LN_2 + half) as i32;
+    let r = (x - f64::from(k) * LN_2_HIGH) - f64::from(k) * LN_2_LOW;
+
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    for n in 1..=16 {
+        term *= r / f64::from(n);
+        sum += term;
+    }
+    scale(sum, k)
+}
+
+/// Multiplies `x` by 2 to the power of `k`, in two steps where `2^k` on its own isn't a normal
+/// `f64`.
+#[allow(clippy::cast_sign_loss)]
+fn scale(x: f64, k: i32) -> f64 {
+    let power = |k: i32| f64::from_bits(((k + 1023) as u64) << 52);
+    match k {
+        1024.. => x * power(1023) * power(k - 1023),
+        ..=-1023 => x * power(-1022) * power(k + 1022),
+        _ => x * power(k),
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod math__float_tests {
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(1.0, 1.0)]
+    #[case(4.0, 2.0)]
+    #[case(0.25, 0.5)]
+    #[case(1e-12, 1e-6)]
+    #[case(1e300, 1e150)]
+    fn sqrt(#[case] x: f64, #[case] expected: f64) {
+        assert!((super::sqrt(x) - expected).abs() <= expected * 1e-15, "{x}");
+    }
+
+    #[rstest]
+    #[case(0.0, 1.0)]
+    #[case(1.0, core::f64::consts::E)]
+    #[case(-1.0, 1.0 / core::f64::consts::E)]
+    #[case(core::f64::consts::LN_10, 10.0)]
+    #[case(0.5, 1.648_721_270_700_128_2)]
+    #[case(20.0, 485_165_195.409_790_3)]
+    #[case(-20.0, 2.061_153_622_438_558e-9)]
+    #[case(709.0, 8.218_407_461_554_972e307)]
+    #[case(-708.0, 3.307_553_003_638_408e-308)]
+    fn exp(#[case] x: f64, #[case] expected: f64) {
+        assert!((super::exp(x) - expected).abs() <= expected * 1e-15, "{x}");
+    }
+
+    #[test]
+    fn exp__edges() {
+        assert!(super::exp(710.0).is_infinite());
+        assert!(super::exp(-740.0) > 0.0);
+        assert!(super::exp(-746.0).abs() < f64::EPSILON);
+        assert!(super::exp(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn sqrt__edges() {
+        assert!(super::sqrt(0.0).abs() < f64::EPSILON);
+        assert!(super::sqrt(f64::NAN).abs() < f64::EPSILON);
+        assert!(super::sqrt(f64::INFINITY).is_infinite());
+    }
+}
@@ -0,0 +1,7 @@
+//! Small math helpers shared across the crate.
+
+pub mod combinatorics;
+pub mod float;
+
+pub use combinatorics::{checked_choose, choose, next_combination, unrank, Combinations};
+pub use float::{exp, sqrt};
@@ -1,4 +1,9 @@
 use crate::{CKCNumber, CardRank, CardSuit, PokerCard};
+use alloc::string::String;
+use core::fmt;
+
+/// The characters, other than whitespace, that are treated as separators between cards.
+pub const SEPARATORS: [char; 5] = [',', '/', '-', '|', ';'];
 
 #[must_use]
 pub fn five_from_index(index: &str) -> Option<[CKCNumber; 5]> {
@@ -9,15 +14,64 @@ pub fn five_from_index(index: &str) -> Option<[CKCNumber; 5]> {
     let third = CKCNumber::from_index(esses.next()?);
     let forth = CKCNumber::from_index(esses.next()?);
     let fifth = CKCNumber::from_index(esses.next()?);
+    if esses.next().is_some() {
+        return None;
+    }
     let hand: [CKCNumber; 5] = [first, second, third, forth, fifth];
     Some(hand)
 }
 
+/// The number of whitespace separated card tokens in an index.
+#[must_use]
+pub fn card_count(index: &str) -> usize {
+    index.split_whitespace().count()
+}
+
+/// Normalizes a line of cards into the space separated, upper case form used by the index
+/// parsers, so that input like `"As,Kd/Qh  Jc-Ts"` becomes `"AS KD QH JC TS"`. Any run of
+/// whitespace and `SEPARATORS` becomes a single space, and leading and trailing separators are
+/// dropped. Suit symbols are left as they are.
+///
+/// The result can be parsed with `BinaryCard::from_index()` or `five_from_index()`.
+#[must_use]
+pub fn sanitize(index: &str) -> String {
+    let mut sanitized = String::with_capacity(index.len());
+    // Writing to a `String` never fails.
+    let _ = write_sanitized(index, &mut sanitized);
+    sanitized
+}
+
+/// Writes the same normalized line of cards as `sanitize()`, without allocating.
+///
+/// # Errors
+///
+/// Returns any error from the writer.
+pub fn write_sanitized(index: &str, w: &mut impl fmt::Write) -> fmt::Result {
+    let tokens = index
+        .split(|c: char| c.is_whitespace() || SEPARATORS.contains(&c))
+        .filter(|token| !token.is_empty());
+    for (i, token) in tokens.enumerate() {
+        if i > 0 {
+            w.write_char(' ')?;
+        }
+        for c in token.chars() {
+            w.write_char(c.to_ascii_uppercase())?;
+        }
+    }
+    Ok(())
+}
+
+/// Splits a card index such as `A♠` or `ks` into its rank and suit. Tens can be written as `T`,
+/// `0` or `10`, as they are in many exported hand histories.
 #[must_use]
 pub fn get_rank_and_suit(index: &str) -> (CardRank, CardSuit) {
-    let mut chars = index.chars();
+    let mut chars = index.chars().peekable();
     let rank: CardRank = match chars.next() {
         None => return (CardRank::BLANK, CardSuit::BLANK),
+        Some('1') if chars.peek() == Some(&'0') => {
+            chars.next();
+            CardRank::TEN
+        },
         Some(r) => CardRank::from_char(r),
     };
     let suit: CardSuit = match chars.next() {
@@ -28,6 +82,7 @@ pub fn get_rank_and_suit(index: &str) -> (CardRank, CardSuit) {
 }
 
 #[cfg(test)]
+#[allow(non_snake_case)]
 mod parse_tests {
     use super::*;
     use rstest::rstest;
@@ -37,9 +92,59 @@ mod parse_tests {
     #[case("a♠", CardRank::ACE, CardSuit::SPADES)]
     #[case("AS", CardRank::ACE, CardSuit::SPADES)]
     #[case("As", CardRank::ACE, CardSuit::SPADES)]
+    #[case("T♥", CardRank::TEN, CardSuit::HEARTS)]
+    #[case("0♥", CardRank::TEN, CardSuit::HEARTS)]
+    #[case("10♥", CardRank::TEN, CardSuit::HEARTS)]
+    #[case("10c", CardRank::TEN, CardSuit::CLUBS)]
+    #[case("1c", CardRank::BLANK, CardSuit::CLUBS)]
+    #[case("10", CardRank::BLANK, CardSuit::BLANK)]
     fn get_rank_and_suits(#[case] index: &str, #[case] rank: CardRank, #[case] suit: CardSuit) {
         let (actual_rank, actual_suit) = get_rank_and_suit(index);
         assert_eq!(rank, actual_rank);
         assert_eq!(suit, actual_suit);
     }
+
+    #[rstest]
+    #[case("", 0)]
+    #[case("A♠", 1)]
+    #[case("  A♠   K♠ ", 2)]
+    #[case("A♠ K♠ Q♠ J♠ T♠ 9♠", 6)]
+    fn card_count(#[case] index: &str, #[case] expected: usize) {
+        assert_eq!(expected, super::card_count(index));
+    }
+
+    #[rstest]
+    #[case("As,Kd/Qh  Jc-Ts", "AS KD QH JC TS")]
+    #[case(" a♠ | k♠; ", "A♠ K♠")]
+    #[case("10h,9h", "10H 9H")]
+    #[case(",/-", "")]
+    #[case("", "")]
+    fn sanitize(#[case] index: &str, #[case] expected: &str) {
+        assert_eq!(expected, super::sanitize(index));
+    }
+
+    #[test]
+    fn write_sanitized() {
+        let mut sanitized = String::new();
+
+        super::write_sanitized(" a♠ | k♠; ", &mut sanitized).unwrap();
+
+        assert_eq!("A♠ K♠", sanitized);
+    }
+
+    #[test]
+    fn sanitize__parses() {
+        let five = five_from_index(&super::sanitize("As,Kd/Qh  Jc-Ts")).unwrap();
+
+        assert_eq!(
+            [
+                crate::CardNumber::ACE_SPADES,
+                crate::CardNumber::KING_DIAMONDS,
+                crate::CardNumber::QUEEN_HEARTS,
+                crate::CardNumber::JACK_CLUBS,
+                crate::CardNumber::TEN_SPADES,
+            ],
+            five
+        );
+    }
 }
@@ -0,0 +1,25 @@
+//! The traits that most code needs in scope for method resolution, along with the types they're
+//! used with, so that one `use` brings in the lot.
+//!
+//! ```
+//! use ckc_rs::prelude::*;
+//!
+//! let hand = Seven::try_from("A♠ K♠ Q♠ J♠ T♠ 2♥ 3♦").unwrap();
+//!
+//! assert_eq!(HandRankName::StraightFlush, hand.hand_rank().name);
+//! assert_eq!(CardRank::ACE, hand.first().get_card_rank());
+//! assert_eq!(7, hand.id().number_of_cards());
+//! ```
+
+pub use crate::card::Card;
+pub use crate::cards::binary_card::{BinaryCard, BC64};
+pub use crate::cards::five::Five;
+pub use crate::cards::four::Four;
+pub use crate::cards::seven::Seven;
+pub use crate::cards::six::Six;
+pub use crate::cards::three::Three;
+pub use crate::cards::two::Two;
+pub use crate::cards::{HandRanker, HandValidator};
+pub use crate::deck::{Deck, POKER_DECK};
+pub use crate::hand_rank::{HandRank, HandRankClass, HandRankName, HandRankValue};
+pub use crate::{CKCNumber, CardNumber, CardRank, CardSuit, HandError, PokerCard, Shifty};
@@ -0,0 +1,228 @@
+//! Replays the cards dealt in a hand, street by street, so that the best hand of each seat can
+//! be checked against a real hand history. Betting isn't tracked.
+use crate::cards::binary_card::{BinaryCard, BC64};
+use crate::cards::five::Five;
+use crate::cards::seven::Seven;
+use crate::cards::six::Six;
+use crate::cards::three::Three;
+use crate::cards::two::Two;
+use crate::cards::{HandRanker, HandValidator};
+use crate::collections::SeatList;
+use crate::hand_rank::{HandRank, HandRankValue};
+use crate::{CKCNumber, HandError};
+use alloc::vec::Vec;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Street {
+    Flop,
+    Turn,
+    River,
+}
+
+/// The cards dealt in a hand of hold'em. Hands that end before the river leave the later
+/// streets as `None`.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Deal {
+    pub seats: Vec<Two>,
+    pub flop: Three,
+    pub turn: Option<CKCNumber>,
+    pub river: Option<CKCNumber>,
+}
+
+impl Deal {
+    #[must_use]
+    pub fn new(seats: Vec<Two>, flop: Three) -> Self {
+        Deal {
+            seats,
+            flop,
+            turn: None,
+            river: None,
+        }
+    }
+
+    #[must_use]
+    pub fn turn(mut self, turn: CKCNumber) -> Self {
+        self.turn = Some(turn);
+        self
+    }
+
+    #[must_use]
+    pub fn river(mut self, river: CKCNumber) -> Self {
+        self.river = Some(river);
+        self
+    }
+}
+
+/// A seat's best five cards, and their `HandRank`, at a specific `Street`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SeatHand {
+    pub hand: Five,
+    pub rank: HandRank,
+}
+
+/// Every seat's `SeatHand` at a `Street`, in seat order.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct StreetHands {
+    pub street: Street,
+    pub seats: Vec<SeatHand>,
+}
+
+impl StreetHands {
+    /// The seats holding the best hand on the street.
+    #[must_use]
+    pub fn leaders(&self) -> SeatList {
+        let best = self.seats.iter().map(|seat| seat.rank).max().unwrap_or_default();
+        let mut leaders = SeatList::new();
+        for (i, _) in self.seats.iter().enumerate().filter(|(_, seat)| seat.rank == best) {
+            // Every seat's cards are different, so there are never more seats than hands in the deck.
+            let _ = leaders.push(i);
+        }
+        leaders
+    }
+}
+
+/// Returns every seat's best hand at each street that was dealt.
+///
+/// # Errors
+///
+/// Returns `HandError::InvalidCard` if any card is invalid, `HandError::DuplicateCard` if any
+/// card is dealt more than once, and `HandError::Incomplete` if there's a river without a turn.
+pub fn replay(deal: &Deal) -> Result<Vec<StreetHands>, HandError> {
+    if deal.river.is_some() && deal.turn.is_none() {
+        return Err(HandError::Incomplete);
+    }
+    validate(deal)?;
+
+    let mut streets = Vec::with_capacity(3);
+
+    streets.push(street(Street::Flop, deal, |hole| {
+        Five::from([
+            hole.first(),
+            hole.second(),
+            deal.flop.first(),
+            deal.flop.second(),
+            deal.flop.third(),
+        ])
+        .hand_rank_value_and_hand()
+    }));
+
+    if let Some(turn) = deal.turn {
+        streets.push(street(Street::Turn, deal, |hole| {
+            Six::from_1_and_2_and_3(turn, hole, deal.flop).hand_rank_value_and_hand()
+        }));
+
+        if let Some(river) = deal.river {
+            let board = Five::from([deal.flop.first(), deal.flop.second(), deal.flop.third(), turn, river]);
+            streets.push(street(Street::River, deal, |hole| {
+                Seven::new(hole, board).hand_rank_value_and_hand()
+            }));
+        }
+    }
+
+    Ok(streets)
+}
+
+fn street<F>(street: Street, deal: &Deal, best: F) -> StreetHands
+where
+    F: Fn(Two) -> (HandRankValue, Five),
+{
+    let seats = deal
+        .seats
+        .iter()
+        .map(|hole| {
+            let (value, hand) = best(*hole);
+            SeatHand {
+                hand,
+                rank: HandRank::from(value),
+            }
+        })
+        .collect();
+    StreetHands { street, seats }
+}
+
+fn validate(deal: &Deal) -> Result<(), HandError> {
+    let mut cards: Vec<CKCNumber> = deal.seats.iter().flat_map(Two::to_arr).collect();
+    cards.extend(deal.flop.to_arr());
+    cards.extend(deal.turn);
+    cards.extend(deal.river);
+
+    let mut dealt = BinaryCard::BLANK;
+    for card in cards {
+        let bc = BinaryCard::from_ckc(card);
+        if bc == BinaryCard::BLANK {
+            return Err(HandError::InvalidCard);
+        }
+        if dealt.has(bc) {
+            return Err(HandError::DuplicateCard);
+        }
+        dealt = dealt.fold_in(bc);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod replay_tests {
+    use super::*;
+    use crate::hand_rank::HandRankName;
+    use crate::CardNumber;
+
+    fn deal() -> Deal {
+        Deal::new(
+            alloc::vec![Two::try_from("A♠ A♥").unwrap(), Two::try_from("9♠ 8♠").unwrap()],
+            Three::try_from("A♦ 7♠ 6♠").unwrap(),
+        )
+        .turn(CardNumber::DEUCE_SPADES)
+        .river(CardNumber::FIVE_SPADES)
+    }
+
+    #[test]
+    fn replay() {
+        let streets = super::replay(&deal()).unwrap();
+
+        assert_eq!(3, streets.len());
+        assert_eq!(Street::Flop, streets[0].street);
+        assert_eq!(HandRankName::ThreeOfAKind, streets[0].seats[0].rank.name);
+        assert_eq!(HandRankName::HighCard, streets[0].seats[1].rank.name);
+        assert_eq!(&[0], streets[0].leaders().as_slice());
+        assert_eq!(HandRankName::ThreeOfAKind, streets[1].seats[0].rank.name);
+        assert_eq!(HandRankName::Flush, streets[1].seats[1].rank.name);
+        assert_eq!(&[1], streets[1].leaders().as_slice());
+        assert_eq!(HandRankName::StraightFlush, streets[2].seats[1].rank.name);
+        assert_eq!(Five::try_from("9♠ 8♠ 7♠ 6♠ 5♠").unwrap(), streets[2].seats[1].hand);
+    }
+
+    #[test]
+    fn replay__flop_only() {
+        let mut deal = deal();
+        deal.turn = None;
+        deal.river = None;
+
+        let streets = super::replay(&deal).unwrap();
+
+        assert_eq!(1, streets.len());
+        assert_eq!(Five::try_from("A♠ A♥ A♦ 7♠ 6♠").unwrap(), streets[0].seats[0].hand);
+    }
+
+    #[test]
+    fn replay__river_without_turn() {
+        let mut deal = deal();
+        deal.turn = None;
+
+        assert_eq!(HandError::Incomplete, super::replay(&deal).unwrap_err());
+    }
+
+    #[test]
+    fn replay__duplicate_card() {
+        let deal = deal().river(CardNumber::ACE_SPADES);
+
+        assert_eq!(HandError::DuplicateCard, super::replay(&deal).unwrap_err());
+    }
+
+    #[test]
+    fn replay__invalid_card() {
+        let deal = deal().turn(CardNumber::BLANK);
+
+        assert_eq!(HandError::InvalidCard, super::replay(&deal).unwrap_err());
+    }
+}
@@ -0,0 +1,341 @@
+//! Resolves a multiway showdown on a complete board, splitting the chips in play into a main
+//! pot and any side pots.
+use crate::cards::binary_card::{BinaryCard, BC64};
+use crate::cards::five::Five;
+use crate::cards::seven::Seven;
+use crate::cards::two::Two;
+use crate::cards::{HandRanker, HandValidator};
+use crate::collections::{ArrayVec, SeatList, MAX_HANDS};
+use crate::hand_rank::HandRank;
+use crate::HandError;
+use alloc::vec::Vec;
+
+pub type Chips = u64;
+
+/// A player at showdown, with the total number of chips they've put into the pot.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Player {
+    pub hole: Two,
+    pub bet: Chips,
+    pub folded: bool,
+}
+
+impl Player {
+    #[must_use]
+    pub fn new(hole: Two, bet: Chips) -> Self {
+        Player {
+            hole,
+            bet,
+            folded: false,
+        }
+    }
+
+    /// A `Player` whose chips are in the pot, but who can't win any of it.
+    #[must_use]
+    pub fn folded(hole: Two, bet: Chips) -> Self {
+        Player {
+            hole,
+            bet,
+            folded: true,
+        }
+    }
+}
+
+/// A main or side pot. `eligible` and `winners` are indexes into the players passed to
+/// `showdown()`.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Pot {
+    pub amount: Chips,
+    pub eligible: SeatList,
+    pub winners: SeatList,
+}
+
+/// The result of a showdown. `ranks` and `payouts` are in the same order as the players.
+/// Folded players have an invalid `HandRank`.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Showdown {
+    pub ranks: ArrayVec<HandRank, MAX_HANDS>,
+    pub pots: Vec<Pot>,
+    pub payouts: ArrayVec<Chips, MAX_HANDS>,
+}
+
+impl Showdown {
+    /// The indexes of the players who won at least part of the main pot.
+    #[must_use]
+    pub fn winners(&self) -> SeatList {
+        self.pots.first().map(|pot| pot.winners).unwrap_or_default()
+    }
+}
+
+/// Splits the chips in play into pots by bet level and awards each of them to the best hands
+/// that are eligible for it.
+///
+/// Chips that can't be split evenly between tied players go one at a time to the winners in
+/// the order the players were passed in. Chips from folded players that bet more than anyone
+/// left in the hand are added to the last pot contested, and chips from folded players below
+/// every bet that's still live go into the first pot, so the pots always add up to every chip
+/// that was bet.
+///
+/// # Errors
+///
+/// Returns `HandError::InvalidCard` if the board or any player's hole cards are invalid,
+/// `HandError::DuplicateCard` if any card is dealt more than once, and `HandError::Incomplete`
+/// if every player has folded.
+pub fn showdown(board: Five, players: &[Player]) -> Result<Showdown, HandError> {
+    if !board.is_valid() || players.iter().any(|player| !player.hole.is_valid()) {
+        return Err(HandError::InvalidCard);
+    }
+
+    let mut dealt = BinaryCard::from_five(board);
+    for player in players {
+        let hole = BinaryCard::from_two(player.hole);
+        if dealt & hole != 0 {
+            return Err(HandError::DuplicateCard);
+        }
+        dealt |= hole;
+    }
+
+    if players.iter().all(|player| player.folded) {
+        return Err(HandError::Incomplete);
+    }
+
+    // Every player's cards are different, so there are never more players than hands in the deck.
+    let mut ranks: ArrayVec<HandRank, MAX_HANDS> = ArrayVec::new();
+    let mut payouts: ArrayVec<Chips, MAX_HANDS> = ArrayVec::new();
+    for player in players {
+        let rank = if player.folded {
+            HandRank::default()
+        } else {
+            Seven::new(player.hole, board).hand_rank()
+        };
+        let _ = ranks.push(rank);
+        let _ = payouts.push(0);
+    }
+
+    let pots = pots(players, &ranks);
+
+    for pot in &pots {
+        let winners = pot.winners.len() as Chips;
+        let share = pot.amount / winners;
+        let odd_chips = pot.amount % winners;
+        for (i, winner) in pot.winners.iter().enumerate() {
+            payouts[*winner] += share + Chips::from((i as Chips) < odd_chips);
+        }
+    }
+
+    Ok(Showdown { ranks, pots, payouts })
+}
+
+fn pots(players: &[Player], ranks: &[HandRank]) -> Vec<Pot> {
+    let mut levels: Vec<Chips> = players.iter().map(|player| player.bet).filter(|bet| *bet > 0).collect();
+    levels.sort_unstable();
+    levels.dedup();
+
+    let mut pots: Vec<Pot> = Vec::new();
+    let mut previous: Chips = 0;
+    // Chips from a level that nobody left in the hand reached, waiting for a pot to go into.
+    let mut dead: Chips = 0;
+    for level in levels {
+        dead += players
+            .iter()
+            .map(|player| player.bet.min(level) - player.bet.min(previous))
+            .sum::<Chips>();
+        previous = level;
+
+        let eligible = seats(players, |player| !player.folded && player.bet >= level);
+
+        if eligible.is_empty() {
+            if let Some(last) = pots.last_mut() {
+                last.amount += dead;
+                dead = 0;
+            }
+            continue;
+        }
+
+        pots.push(pot(dead, eligible, ranks));
+        dead = 0;
+    }
+
+    // Only folded players put chips in, so everyone still in the hand contests them.
+    if dead > 0 {
+        let eligible = seats(players, |player| !player.folded);
+        pots.push(pot(dead, eligible, ranks));
+    }
+    pots
+}
+
+/// The indexes of the players that match the predicate.
+fn seats(players: &[Player], predicate: impl Fn(&Player) -> bool) -> SeatList {
+    let mut seats = SeatList::new();
+    for (i, _) in players.iter().enumerate().filter(|(_, player)| predicate(player)) {
+        // `showdown()` has already checked that there are no more players than hands in the deck.
+        let _ = seats.push(i);
+    }
+    seats
+}
+
+fn pot(amount: Chips, eligible: SeatList, ranks: &[HandRank]) -> Pot {
+    let best = eligible.iter().map(|i| ranks[*i]).max().unwrap_or_default();
+    let mut winners = SeatList::new();
+    for i in eligible.iter().copied().filter(|i| ranks[*i] == best) {
+        let _ = winners.push(i);
+    }
+    Pot {
+        amount,
+        eligible,
+        winners,
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod showdown_tests {
+    use super::*;
+    use crate::hand_rank::HandRankName;
+
+    fn two(index: &'static str) -> Two {
+        Two::try_from(index).unwrap()
+    }
+
+    fn board() -> Five {
+        Five::try_from("A♠ K♦ 8♣ 7♠ 2♥").unwrap()
+    }
+
+    #[test]
+    fn showdown__heads_up() {
+        let players = [Player::new(two("A♥ A♦"), 100), Player::new(two("K♠ K♥"), 100)];
+
+        let showdown = super::showdown(board(), &players).unwrap();
+
+        assert_eq!(1, showdown.pots.len());
+        assert_eq!(&[0], showdown.winners().as_slice());
+        assert_eq!(&[200, 0], showdown.payouts.as_slice());
+        assert_eq!(HandRankName::ThreeOfAKind, showdown.ranks[0].name);
+    }
+
+    #[test]
+    fn showdown__side_pot() {
+        // The short stack has the best hand but can only win the main pot.
+        let players = [
+            Player::new(two("A♥ A♦"), 50),
+            Player::new(two("K♠ K♥"), 200),
+            Player::new(two("Q♠ Q♥"), 200),
+        ];
+
+        let showdown = super::showdown(board(), &players).unwrap();
+
+        assert_eq!(2, showdown.pots.len());
+        assert_eq!(150, showdown.pots[0].amount);
+        assert_eq!(&[0, 1, 2], showdown.pots[0].eligible.as_slice());
+        assert_eq!(300, showdown.pots[1].amount);
+        assert_eq!(&[1, 2], showdown.pots[1].eligible.as_slice());
+        assert_eq!(&[150, 300, 0], showdown.payouts.as_slice());
+    }
+
+    #[test]
+    fn showdown__split_with_odd_chip() {
+        let players = [
+            Player::new(two("Q♠ J♥"), 50),
+            Player::new(two("Q♦ J♣"), 50),
+            Player::folded(two("9♠ 9♥"), 1),
+        ];
+
+        let showdown = super::showdown(board(), &players).unwrap();
+
+        assert_eq!(&[0, 1], showdown.winners().as_slice());
+        assert_eq!(&[51, 50, 0], showdown.payouts.as_slice());
+        assert!(showdown.ranks[2].is_invalid());
+    }
+
+    #[test]
+    fn showdown__folded_over_bet() {
+        let players = [
+            Player::new(two("Q♠ J♥"), 50),
+            Player::new(two("T♦ 9♣"), 50),
+            Player::folded(two("9♠ 9♥"), 80),
+        ];
+
+        let showdown = super::showdown(board(), &players).unwrap();
+
+        assert_eq!(1, showdown.pots.len());
+        assert_eq!(&[180, 0, 0], showdown.payouts.as_slice());
+    }
+
+    #[test]
+    fn showdown__uncalled_bet() {
+        let players = [Player::new(two("Q♠ J♥"), 50), Player::new(two("T♦ 9♣"), 20)];
+
+        let showdown = super::showdown(board(), &players).unwrap();
+
+        assert_eq!(&[70, 0], showdown.payouts.as_slice());
+        assert_eq!(&[0], showdown.pots[1].winners.as_slice());
+    }
+
+    #[test]
+    fn showdown__duplicate_card() {
+        let players = [Player::new(two("A♠ A♦"), 100), Player::new(two("K♠ K♥"), 100)];
+
+        assert_eq!(
+            HandError::DuplicateCard,
+            super::showdown(board(), &players).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn showdown__everyone_folded() {
+        let players = [Player::folded(two("A♥ A♦"), 100)];
+
+        assert_eq!(HandError::Incomplete, super::showdown(board(), &players).unwrap_err());
+    }
+
+    #[test]
+    fn showdown__invalid() {
+        let players = [Player::new(Two::default(), 100)];
+
+        assert_eq!(HandError::InvalidCard, super::showdown(board(), &players).unwrap_err());
+    }
+
+    #[test]
+    fn showdown__folded_under_every_bet() {
+        // Only the folded players put chips in, and nobody else has bet.
+        let players = [
+            Player::folded(two("9♠ 9♥"), 10),
+            Player::new(two("A♥ A♦"), 0),
+            Player::folded(two("Q♠ Q♥"), 25),
+            Player::new(two("K♠ K♥"), 0),
+        ];
+
+        let showdown = super::showdown(board(), &players).unwrap();
+
+        assert_eq!(1, showdown.pots.len());
+        assert_eq!(&[1, 3], showdown.pots[0].eligible.as_slice());
+        assert_eq!(&[0, 35, 0, 0], showdown.payouts.as_slice());
+    }
+
+    #[test]
+    fn showdown__pots_add_up() {
+        let tables = [
+            alloc::vec![Player::folded(two("9♠ 9♥"), 10), Player::new(two("A♥ A♦"), 0),],
+            alloc::vec![
+                Player::folded(two("9♠ 9♥"), 10),
+                Player::new(two("A♥ A♦"), 5),
+                Player::new(two("K♠ K♥"), 40),
+                Player::folded(two("Q♠ Q♥"), 60),
+            ],
+            alloc::vec![
+                Player::new(two("A♥ A♦"), 50),
+                Player::new(two("K♠ K♥"), 200),
+                Player::new(two("Q♠ Q♥"), 200),
+                Player::folded(two("J♠ J♥"), 300),
+            ],
+        ];
+
+        for players in tables {
+            let showdown = super::showdown(board(), &players).unwrap();
+            let bet: Chips = players.iter().map(|player| player.bet).sum();
+
+            assert_eq!(bet, showdown.pots.iter().map(|pot| pot.amount).sum::<Chips>());
+            assert_eq!(bet, showdown.payouts.iter().sum::<Chips>());
+        }
+    }
+}
@@ -0,0 +1,540 @@
+//! Deals complete hands of hold'em to showdown, over and over, and hands each one to a
+//! `Visitor` so that any statistic can be collected from the results.
+use crate::analysis::preflop::{PreflopClass, PreflopMatrix};
+use crate::cancel::Cancellation;
+use crate::cards::five::Five;
+use crate::cards::seven::Seven;
+use crate::cards::two::Two;
+use crate::cards::HandRanker;
+use crate::deck::POKER_DECK;
+use crate::hand_rank::{HandRank, HandRankName, HandRankValue};
+use crate::math::Combinations;
+use crate::replay::Street;
+use crate::{CKCNumber, HandError};
+use alloc::vec::Vec;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// The most seats that can be dealt in from one deck with a full board left over.
+pub const MAX_SEATS: usize = 23;
+
+/// One simulated hand, dealt all the way to showdown. `ranks` and `equities` are in seat order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SimHand {
+    pub seats: Vec<Two>,
+    pub board: Five,
+    pub ranks: Vec<HandRank>,
+    /// The seats that won the pot, more than one if it was split.
+    pub winners: Vec<usize>,
+    /// Each seat's share of the pot if everyone was all in on the street set with
+    /// `Simulator::equity_on()`, or empty if it wasn't set.
+    pub equities: Vec<f64>,
+}
+
+impl SimHand {
+    /// The same hand with every seat's hole cards, and their results, moved `by` seats to the
+    /// left, so that seat 0's cards end up in seat `by`. The board doesn't change.
+    #[must_use]
+    pub fn rotated(&self, by: usize) -> SimHand {
+        let seats = self.seats.len();
+        if seats == 0 {
+            return self.clone();
+        }
+        let by = by % seats;
+        let mut hand = self.clone();
+        hand.seats.rotate_right(by);
+        hand.ranks.rotate_right(by);
+        if !hand.equities.is_empty() {
+            hand.equities.rotate_right(by);
+        }
+        hand.winners = self.winners.iter().map(|winner| (winner + by) % seats).collect();
+        hand.winners.sort_unstable();
+        hand
+    }
+}
+
+/// Collects statistics from every `SimHand` that the `Simulator` deals. Any closure that takes a
+/// `&SimHand` is a `Visitor`.
+pub trait Visitor {
+    fn visit(&mut self, hand: &SimHand);
+}
+
+impl<F: FnMut(&SimHand)> Visitor for F {
+    fn visit(&mut self, hand: &SimHand) {
+        self(hand);
+    }
+}
+
+/// The basic statistics for each seat across a run of simulated hands.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SimStats {
+    pub hands: usize,
+    /// The number of pots each seat won, with split pots shared between the winners.
+    pub wins: Vec<f64>,
+    /// The total of each seat's all in equity, if the `Simulator` calculates it.
+    pub equity: Vec<f64>,
+    /// How many pots were won with each `HandRankName`, strongest first.
+    pub winning_names: [usize; 9],
+}
+
+impl SimStats {
+    /// The share of the pots won by the seat.
+    #[must_use]
+    pub fn win_rate(&self, seat: usize) -> f64 {
+        self.per_hand(self.wins.get(seat))
+    }
+
+    /// The seat's average all in equity.
+    #[must_use]
+    pub fn mean_equity(&self, seat: usize) -> f64 {
+        self.per_hand(self.equity.get(seat))
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn per_hand(&self, total: Option<&f64>) -> f64 {
+        match (total, self.hands) {
+            (Some(total), 1..) => total / self.hands as f64,
+            _ => 0.0,
+        }
+    }
+}
+
+impl Visitor for SimStats {
+    #[allow(clippy::cast_precision_loss)]
+    fn visit(&mut self, hand: &SimHand) {
+        self.hands += 1;
+        self.wins.resize(hand.seats.len(), 0.0);
+        for winner in &hand.winners {
+            self.wins[*winner] += 1.0 / hand.winners.len() as f64;
+        }
+        if let Some(name) = hand.winners.first().map(|winner| hand.ranks[*winner].name) {
+            if name != HandRankName::Invalid {
+                self.winning_names[name as usize] += 1;
+            }
+        }
+        if !hand.equities.is_empty() {
+            self.equity.resize(hand.seats.len(), 0.0);
+            for (total, equity) in self.equity.iter_mut().zip(&hand.equities) {
+                *total += equity;
+            }
+        }
+    }
+}
+
+/// Deals hands of hold'em to a fixed number of seats.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Simulator {
+    seats: usize,
+    equity: Option<Street>,
+}
+
+impl Simulator {
+    /// # Errors
+    ///
+    /// Returns `HandError::Incomplete` if there are no seats, and `HandError::NotEnoughCards` if
+    /// there are more than `MAX_SEATS`.
+    pub fn new(seats: usize) -> Result<Self, HandError> {
+        match seats {
+            0 => Err(HandError::Incomplete),
+            1..=MAX_SEATS => Ok(Simulator { seats, equity: None }),
+            _ => Err(HandError::NotEnoughCards),
+        }
+    }
+
+    /// Calculates every seat's exact all in equity on the street for each hand. On the flop that
+    /// means running out every turn and river, so it slows each hand down by a few hundred
+    /// evaluations per seat.
+    #[must_use]
+    pub fn equity_on(mut self, street: Street) -> Self {
+        self.equity = Some(street);
+        self
+    }
+
+    #[must_use]
+    pub fn seats(&self) -> usize {
+        self.seats
+    }
+
+    /// Deals one hand to showdown.
+    pub fn deal<R: Rng + ?Sized>(&self, rng: &mut R) -> SimHand {
+        let mut deck = POKER_DECK;
+        let needed = self.seats * 2 + 5;
+        let (dealt, live) = deck.partial_shuffle(rng, needed);
+
+        let seats: Vec<Two> = dealt
+            .chunks(2)
+            .take(self.seats)
+            .map(|hole| Two::new(hole[0], hole[1]))
+            .collect();
+        let board = Five::from([
+            dealt[needed - 5],
+            dealt[needed - 4],
+            dealt[needed - 3],
+            dealt[needed - 2],
+            dealt[needed - 1],
+        ]);
+
+        let values: Vec<HandRankValue> = seats
+            .iter()
+            .map(|hole| Seven::new(*hole, board).hand_rank_value())
+            .collect();
+        let equities = match self.equity {
+            None => Vec::new(),
+            Some(street) => equities(&seats, board, street, live),
+        };
+
+        SimHand {
+            ranks: values.iter().map(|value| HandRank::from(*value)).collect(),
+            winners: winners(&values),
+            seats,
+            board,
+            equities,
+        }
+    }
+
+    /// Deals the number of hands, passing each one to the visitor.
+    pub fn run<R: Rng + ?Sized, V: Visitor + ?Sized>(&self, hands: usize, rng: &mut R, visitor: &mut V) {
+        for _ in 0..hands {
+            visitor.visit(&self.deal(rng));
+        }
+    }
+
+    /// The same as `run()`, except that it checks the `Cancellation` before each hand, and stops
+    /// if it's set. Returns the number of hands that were dealt.
+    pub fn run_cancellable<R: Rng + ?Sized, V: Visitor + ?Sized>(
+        &self,
+        hands: usize,
+        rng: &mut R,
+        visitor: &mut V,
+        cancellation: Cancellation,
+    ) -> usize {
+        for dealt in 0..hands {
+            if cancellation.is_cancelled() {
+                return dealt;
+            }
+            visitor.visit(&self.deal(rng));
+        }
+        hands
+    }
+}
+
+/// Deals for duplicate poker, where every set of cards is played once from each seat so that
+/// the luck of the deal cancels out when comparing strategies. See `duplicate_deals()`.
+#[derive(Clone, Debug)]
+pub struct DuplicateDeals<R> {
+    simulator: Simulator,
+    rng: R,
+}
+
+impl<R: Rng> Iterator for DuplicateDeals<R> {
+    /// One `SimHand` for each seat, where the hand at index `i` is the first one `rotated()` by
+    /// `i` seats.
+    type Item = Vec<SimHand>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let hand = self.simulator.deal(&mut self.rng);
+        Some((0..self.simulator.seats).map(|by| hand.rotated(by)).collect())
+    }
+}
+
+/// Deals an endless, repeatable run of hands from the seed, with each one rotated through every
+/// seat. Two runs with the same seed deal the same cards, so strategies can be compared on the
+/// same cards, in the same seats.
+///
+/// ```
+/// use ckc_rs::sim::{duplicate_deals, Simulator};
+/// use rand::rngs::SmallRng;
+///
+/// let simulator = Simulator::new(3).unwrap();
+/// let deals: Vec<_> = duplicate_deals::<SmallRng>(simulator, 42).take(10).collect();
+///
+/// for rotations in &deals {
+///     assert_eq!(3, rotations.len());
+///     assert_eq!(rotations[0].seats[0], rotations[1].seats[1]);
+///     assert_eq!(rotations[0].board, rotations[2].board);
+/// }
+/// assert_eq!(deals, duplicate_deals::<SmallRng>(simulator, 42).take(10).collect::<Vec<_>>());
+/// ```
+#[must_use]
+pub fn duplicate_deals<R: Rng + SeedableRng>(simulator: Simulator, seed: u64) -> DuplicateDeals<R> {
+    DuplicateDeals {
+        simulator,
+        rng: R::seed_from_u64(seed),
+    }
+}
+
+/// How often a `PreflopClass` was dealt, and how many of those hands it won at showdown.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StartingHandFrequency {
+    pub dealt: usize,
+    /// The pots won, with split pots shared between the winners.
+    pub wins: f64,
+}
+
+impl StartingHandFrequency {
+    /// The share of the pots won when the hand was dealt.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn win_rate(&self) -> f64 {
+        match self.dealt {
+            0 => 0.0,
+            dealt => self.wins / dealt as f64,
+        }
+    }
+}
+
+/// Deals hands to the number of players, with everyone going to showdown, and tallies how
+/// often each `PreflopClass` is dealt and wins.
+///
+/// # Errors
+///
+/// Same as `Simulator::new()`.
+#[allow(clippy::cast_precision_loss)]
+pub fn starting_hand_frequencies<R: Rng + ?Sized>(
+    num_players: usize,
+    num_deals: usize,
+    rng: &mut R,
+) -> Result<PreflopMatrix<StartingHandFrequency>, HandError> {
+    let mut frequencies: PreflopMatrix<StartingHandFrequency> = PreflopMatrix::default();
+    Simulator::new(num_players)?.run(num_deals, rng, &mut |hand: &SimHand| {
+        for (seat, hole) in hand.seats.iter().enumerate() {
+            if let Ok(class) = PreflopClass::try_from(*hole) {
+                frequencies[class].dealt += 1;
+                if hand.winners.contains(&seat) {
+                    frequencies[class].wins += 1.0 / hand.winners.len() as f64;
+                }
+            }
+        }
+    });
+    Ok(frequencies)
+}
+
+fn winners(values: &[HandRankValue]) -> Vec<usize> {
+    let best = values.iter().copied().min().unwrap_or_default();
+    (0..values.len()).filter(|i| values[*i] == best).collect()
+}
+
+/// Every seat's share of the pot across every runout from the street, with the cards that were
+/// actually dealt later in the hand counting as live.
+#[allow(clippy::cast_precision_loss)]
+fn equities(seats: &[Two], board: Five, street: Street, undealt: &[CKCNumber]) -> Vec<f64> {
+    let known = match street {
+        Street::Flop => 3,
+        Street::Turn => 4,
+        Street::River => 5,
+    };
+    let board = board.to_arr();
+    let mut live = undealt.to_vec();
+    live.extend_from_slice(&board[known..]);
+
+    let mut shares = alloc::vec![0.0; seats.len()];
+    let mut runouts = 0;
+    let mut cards = board;
+    let mut tally = |cards: [CKCNumber; 5]| {
+        let board = Five::from(cards);
+        let values: Vec<HandRankValue> = seats
+            .iter()
+            .map(|hole| Seven::new(*hole, board).hand_rank_value())
+            .collect();
+        let winners = winners(&values);
+        for winner in &winners {
+            shares[*winner] += 1.0 / winners.len() as f64;
+        }
+        runouts += 1;
+    };
+    match street {
+        Street::Flop => {
+            for [turn, river] in Combinations::<2>::new(live.len()) {
+                cards[3] = live[turn];
+                cards[4] = live[river];
+                tally(cards);
+            }
+        },
+        Street::Turn => {
+            for river in &live {
+                cards[4] = *river;
+                tally(cards);
+            }
+        },
+        Street::River => tally(cards),
+    }
+    shares.iter().map(|share| share / f64::from(runouts)).collect()
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod sim_tests {
+    use super::*;
+    use crate::cards::binary_card::{BinaryCard, BC64};
+    use crate::cards::HandValidator;
+    use core::sync::atomic::{AtomicBool, Ordering};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn new() {
+        assert_eq!(6, Simulator::new(6).unwrap().seats());
+        assert_eq!(HandError::Incomplete, Simulator::new(0).unwrap_err());
+        assert_eq!(HandError::NotEnoughCards, Simulator::new(24).unwrap_err());
+    }
+
+    #[test]
+    fn deal() {
+        let mut rng = SmallRng::seed_from_u64(42);
+        let simulator = Simulator::new(MAX_SEATS).unwrap();
+
+        let hand = simulator.deal(&mut rng);
+
+        let dealt = hand.seats.iter().fold(BinaryCard::from_five(hand.board), |bits, hole| {
+            bits | BinaryCard::from_two(*hole)
+        });
+        assert_eq!(51, dealt.number_of_cards());
+        assert!(hand.board.is_valid());
+        assert_eq!(MAX_SEATS, hand.ranks.len());
+        assert!(!hand.winners.is_empty());
+        assert!(hand.equities.is_empty());
+        for winner in &hand.winners {
+            assert_eq!(hand.ranks.iter().max(), Some(&hand.ranks[*winner]));
+        }
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn deal__equity() {
+        let mut rng = SmallRng::seed_from_u64(42);
+
+        for street in [Street::Flop, Street::Turn, Street::River] {
+            let hand = Simulator::new(3).unwrap().equity_on(street).deal(&mut rng);
+
+            assert_eq!(3, hand.equities.len());
+            assert!((1.0 - hand.equities.iter().sum::<f64>()).abs() < 1e-9);
+            if street == Street::River {
+                for winner in &hand.winners {
+                    assert!((1.0 / hand.winners.len() as f64 - hand.equities[*winner]).abs() < 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn run() {
+        let mut rng = SmallRng::seed_from_u64(7);
+        let simulator = Simulator::new(4).unwrap().equity_on(Street::Turn);
+        let mut stats = SimStats::default();
+
+        simulator.run(500, &mut rng, &mut stats);
+
+        assert_eq!(500, stats.hands);
+        assert!((500.0 - stats.wins.iter().sum::<f64>()).abs() < 1e-9);
+        assert_eq!(500, stats.winning_names.iter().sum::<usize>());
+        assert!((1.0 - (0..4).map(|seat| stats.mean_equity(seat)).sum::<f64>()).abs() < 1e-9);
+        for seat in 0..4 {
+            // Everyone has the same chance, so each seat wins about a quarter of the time.
+            assert!((stats.win_rate(seat) - 0.25).abs() < 0.1);
+        }
+        assert!(stats.win_rate(4).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn run__closure() {
+        let mut rng = SmallRng::seed_from_u64(7);
+        let mut splits = 0;
+        let mut dealt = 0;
+
+        Simulator::new(9).unwrap().run(200, &mut rng, &mut |hand: &SimHand| {
+            dealt += 1;
+            if hand.winners.len() > 1 {
+                splits += 1;
+            }
+        });
+
+        assert_eq!(200, dealt);
+        assert!(splits < 200);
+    }
+
+    #[test]
+    fn run_cancellable() {
+        let mut rng = SmallRng::seed_from_u64(7);
+        let simulator = Simulator::new(2).unwrap();
+        let flag = AtomicBool::new(false);
+        let mut stats = SimStats::default();
+
+        let finished = simulator.run_cancellable(50, &mut rng, &mut stats, Cancellation::from(&flag));
+        let cancelled = simulator.run_cancellable(
+            100,
+            &mut rng,
+            &mut |hand: &SimHand| {
+                stats.visit(hand);
+                flag.store(stats.hands == 80, Ordering::Relaxed);
+            },
+            Cancellation::from(&flag),
+        );
+
+        assert_eq!(50, finished);
+        assert_eq!(30, cancelled);
+        assert_eq!(80, stats.hands);
+    }
+
+    #[test]
+    fn sim_hand__rotated() {
+        let mut rng = SmallRng::seed_from_u64(3);
+        let hand = Simulator::new(4).unwrap().equity_on(Street::River).deal(&mut rng);
+
+        let rotated = hand.rotated(1);
+
+        assert_eq!(hand.board, rotated.board);
+        for seat in 0..4 {
+            assert_eq!(hand.seats[seat], rotated.seats[(seat + 1) % 4]);
+            assert_eq!(hand.ranks[seat], rotated.ranks[(seat + 1) % 4]);
+            assert!((hand.equities[seat] - rotated.equities[(seat + 1) % 4]).abs() < f64::EPSILON);
+        }
+        for winner in &hand.winners {
+            assert!(rotated.winners.contains(&((winner + 1) % 4)));
+        }
+        assert_eq!(hand, hand.rotated(4));
+        assert_eq!(SimHand::default(), SimHand::default().rotated(2));
+    }
+
+    #[test]
+    fn duplicate_deals() {
+        let simulator = Simulator::new(6).unwrap();
+        let mut stats = SimStats::default();
+
+        for rotations in super::duplicate_deals::<SmallRng>(simulator, 9).take(100) {
+            assert_eq!(6, rotations.len());
+            for hand in &rotations {
+                stats.visit(hand);
+            }
+        }
+
+        // Every seat plays every set of cards once, so they all win exactly as often.
+        assert_eq!(600, stats.hands);
+        for seat in 0..6 {
+            assert!((stats.win_rate(seat) - stats.win_rate(0)).abs() < 1e-9);
+        }
+        assert_ne!(
+            super::duplicate_deals::<SmallRng>(simulator, 9).next(),
+            super::duplicate_deals::<SmallRng>(simulator, 10).next()
+        );
+    }
+
+    #[test]
+    fn starting_hand_frequencies() {
+        let mut rng = SmallRng::seed_from_u64(42);
+
+        let frequencies = super::starting_hand_frequencies(6, 2_000, &mut rng).unwrap();
+
+        assert_eq!(
+            12_000,
+            frequencies.iter().map(|(_, frequency)| frequency.dealt).sum::<usize>()
+        );
+        assert!((2_000.0 - frequencies.iter().map(|(_, frequency)| frequency.wins).sum::<f64>()).abs() < 1e-6);
+        let aces = frequencies[PreflopClass::try_from("AA").unwrap()];
+        let seven_deuce = frequencies[PreflopClass::try_from("72o").unwrap()];
+        assert!(aces.win_rate() > seven_deuce.win_rate());
+        assert_eq!(
+            HandError::NotEnoughCards,
+            super::starting_hand_frequencies(24, 1, &mut rng).unwrap_err()
+        );
+    }
+}
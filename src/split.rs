@@ -0,0 +1,315 @@
+//! Evaluators for split pot draw games, where the pot is shared between the best low hand and the
+//! best badugi.
+//!
+//! * Badeucey splits the pot between the best deuce to seven low and the best badugi, with aces
+//!   high in both halves.
+//! * Badacey splits the pot between the best ace to five low and the best badugi, with aces low
+//!   in both halves.
+//!
+//! Every value in this module follows the same rule as `HandRankValue`: lower is better, and zero
+//! means the hand couldn't be ranked.
+
+use crate::cards::five::Five;
+use crate::cards::four::Four;
+use crate::cards::{HandRanker, HandValidator};
+use crate::hand_rank::{HandRankClass, HandRankValue};
+use crate::{BinaryCard, CKCNumber, CardRank, PokerCard, BC64};
+
+/// The `HandRankValue` of 7-5-4-3-2, the weakest high hand.
+const WORST_HIGH_HAND: HandRankValue = 7462;
+
+/// The value of one low hand. Lower is better, and zero is an invalid hand.
+pub type LowValue = u32;
+
+/// Whether aces play as the highest or the lowest rank.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Aces {
+    High,
+    Low,
+}
+
+impl Aces {
+    /// The rank of the card from 0 for the lowest to 12 for the highest.
+    fn rank_of(self, card: CKCNumber) -> u32 {
+        match (self, card.get_card_rank()) {
+            (Aces::Low, CardRank::ACE) => 0,
+            (Aces::Low, rank) => rank as u32 - 1,
+            (Aces::High, rank) => rank as u32 - 2,
+        }
+    }
+}
+
+/// The rank of a badugi: the largest group of cards with no two of the same rank or suit, and
+/// then the lowest cards within it. Lower is better, so any four card badugi beats any three card
+/// one, and zero is an invalid hand.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct BadugiRank(u32);
+
+impl BadugiRank {
+    #[must_use]
+    pub fn value(self) -> u32 {
+        self.0
+    }
+
+    /// The number of cards that play, from one to four, or zero for an invalid hand.
+    #[must_use]
+    pub fn cards(self) -> usize {
+        match self.0 {
+            0 => 0,
+            value => 4 - ((value - 1) / 13u32.pow(4)) as usize,
+        }
+    }
+
+    #[must_use]
+    pub fn is_valid(self) -> bool {
+        self.0 != 0
+    }
+}
+
+/// The ranks of both halves of a split pot hand.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SplitRank {
+    pub low: LowValue,
+    pub badugi: BadugiRank,
+}
+
+/// Ranks the hand for deuce to seven lowball, where the worst high hand wins, so aces are high
+/// and straights and flushes count against the hand. 7-5-4-3-2 offsuit is 1.
+///
+/// Since the ace is only high, A-5-4-3-2 is an ace high hand and not a straight. It has no
+/// `HandRankValue` of its own, so the values go up in steps of two to leave a gap for it just
+/// under A-6-4-3-2.
+#[must_use]
+pub fn deuce_to_seven(five: Five) -> LowValue {
+    const WHEEL: HandRankValue = HandRankClass::FiveHighStraight.min_value();
+    const STEEL_WHEEL: HandRankValue = HandRankClass::FiveHighStraightFlush.min_value();
+
+    let low = |hrv: HandRankValue| LowValue::from(WORST_HIGH_HAND + 1 - hrv) * 2 - 1;
+    match five.hand_rank_value_validated() {
+        0 => 0,
+        WHEEL => low(HandRankClass::AceHigh.max_value()) - 1,
+        STEEL_WHEEL => low(HandRankClass::AceHighFlush.max_value()) - 1,
+        hrv => low(hrv),
+    }
+}
+
+/// Ranks the hand for ace to five lowball, where aces are low and straights and flushes don't
+/// count. 5-4-3-2-A is 1.
+#[must_use]
+pub fn ace_to_five(five: Five) -> LowValue {
+    if !five.is_valid() {
+        return 0;
+    }
+    let mut counts = [0u8; 13];
+    for card in five.iter() {
+        counts[Aces::Low.rank_of(*card) as usize] += 1;
+    }
+    // Groups of the same rank, biggest groups first and then highest ranks first.
+    let mut groups: [(u8, u32); 5] = [(0, 0); 5];
+    let mut len = 0;
+    for (rank, count) in (0u32..).zip(counts).filter(|(_, count)| *count > 0) {
+        groups[len] = (count, rank);
+        len += 1;
+    }
+    groups[..len].sort_unstable_by(|a, b| b.cmp(a));
+
+    let category = match (groups[0].0, groups[1].0) {
+        (1, _) => 0,
+        (2, 1) => 1,
+        (2, 2) => 2,
+        (3, 1) => 3,
+        (3, 2) => 4,
+        _ => 5,
+    };
+    let ranks = groups.iter().fold(
+        0,
+        |value, (count, rank)| if *count == 0 { value * 13 } else { value * 13 + rank },
+    );
+    // 5-4-3-2-A has the lowest ranks of any hand.
+    let nuts = (((4 * 13 + 3) * 13 + 2) * 13 + 1) * 13;
+    1 + category * 13u32.pow(5) + ranks - nuts
+}
+
+/// Ranks the best badugi out of the four cards.
+#[must_use]
+pub fn badugi(four: Four, aces: Aces) -> BadugiRank {
+    best_badugi(&four.to_arr(), aces)
+}
+
+/// Ranks the best badugi out of any four of the five cards.
+#[must_use]
+pub fn best_badugi_of_five(five: Five, aces: Aces) -> BadugiRank {
+    best_badugi(&five.to_arr(), aces)
+}
+
+/// Ranks a five card hand for Badeucey: deuce to seven low, and the best badugi with aces high.
+#[must_use]
+pub fn badeucey(five: Five) -> SplitRank {
+    SplitRank {
+        low: deuce_to_seven(five),
+        badugi: best_badugi_of_five(five, Aces::High),
+    }
+}
+
+/// Ranks a five card hand for Badacey: ace to five low, and the best badugi with aces low.
+#[must_use]
+pub fn badacey(five: Five) -> SplitRank {
+    SplitRank {
+        low: ace_to_five(five),
+        badugi: best_badugi_of_five(five, Aces::Low),
+    }
+}
+
+fn best_badugi(cards: &[CKCNumber], aces: Aces) -> BadugiRank {
+    let valid = cards
+        .iter()
+        .all(|card| BinaryCard::from_ckc(*card) != BinaryCard::BLANK)
+        && !(1..cards.len()).any(|i| cards[i..].contains(&cards[i - 1]));
+    if !valid {
+        return BadugiRank::default();
+    }
+
+    let mut best = BadugiRank::default();
+    for subset in 1u32..(1 << cards.len()) {
+        let size = subset.count_ones();
+        if size > 4 {
+            continue;
+        }
+        let mut ranks = 0u32;
+        let mut suits = 0u32;
+        let mut badugi = true;
+        for card in (0..cards.len()).filter(|i| subset & (1 << i) != 0).map(|i| cards[i]) {
+            let rank = 1 << aces.rank_of(card);
+            let suit = card.get_suit_bit();
+            badugi &= ranks & rank == 0 && suits & suit == 0;
+            ranks |= rank;
+            suits |= suit;
+        }
+        if !badugi {
+            continue;
+        }
+        // The ranks from highest to lowest, padded with zeros up to four digits.
+        let mut digits = 0;
+        for rank in (0..13).rev().filter(|rank| ranks & (1 << rank) != 0) {
+            digits = digits * 13 + rank;
+        }
+        digits *= 13u32.pow(4 - size);
+        let rank = BadugiRank(1 + (4 - size) * 13u32.pow(4) + digits);
+        if !best.is_valid() || rank < best {
+            best = rank;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod split_tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn deuce_to_seven__nuts() {
+        assert_eq!(1, deuce_to_seven(Five::try_from("7♠ 5♥ 4♦ 3♣ 2♠").unwrap()));
+        assert_eq!(0, deuce_to_seven(Five::default()));
+    }
+
+    #[rstest]
+    #[case("7♠ 5♥ 4♦ 3♣ 2♠", "7♠ 6♥ 4♦ 3♣ 2♠")]
+    #[case("8♠ 6♥ 4♦ 3♣ 2♠", "8♠ 7♥ 6♦ 5♣ 4♠")]
+    #[case("K♠ Q♥ J♦ 9♣ 8♠", "A♠ 5♥ 4♦ 3♣ 2♠")]
+    #[case("A♠ K♥ Q♦ J♣ 9♠", "2♠ 2♥ 4♦ 3♣ 5♠")]
+    #[case("8♠ 6♥ 4♦ 3♣ 2♣", "8♣ 6♣ 4♣ 3♣ 2♣")]
+    #[case("K♠ Q♥ J♦ T♣ 8♠", "A♠ 5♥ 4♦ 3♣ 2♠")]
+    #[case("A♠ 5♥ 4♦ 3♣ 2♠", "A♠ 6♥ 4♦ 3♣ 2♠")]
+    #[case("A♠ 6♥ 4♦ 3♣ 2♠", "2♠ 2♥ 5♦ 4♣ 3♠")]
+    #[case("K♠ Q♠ J♠ T♠ 8♠", "A♠ 5♠ 4♠ 3♠ 2♠")]
+    #[case("A♠ 5♠ 4♠ 3♠ 2♠", "A♠ 6♠ 4♠ 3♠ 2♠")]
+    #[case("A♠ 6♠ 4♠ 3♠ 2♠", "3♠ 3♥ 3♦ 2♣ 2♠")]
+    fn deuce_to_seven__beats(#[case] better: &'static str, #[case] worse: &'static str) {
+        let better = deuce_to_seven(Five::try_from(better).unwrap());
+        let worse = deuce_to_seven(Five::try_from(worse).unwrap());
+
+        assert!(better < worse);
+    }
+
+    #[test]
+    fn deuce_to_seven__wheel_is_ace_high() {
+        let wheel = deuce_to_seven(Five::try_from("A♠ 5♥ 4♦ 3♣ 2♠").unwrap());
+
+        assert_eq!(deuce_to_seven(Five::try_from("A♠ 6♥ 4♦ 3♣ 2♠").unwrap()) - 1, wheel);
+        assert_eq!(deuce_to_seven(Five::try_from("K♠ Q♥ J♦ T♣ 8♠").unwrap()) + 1, wheel);
+        assert!(deuce_to_seven(Five::try_from("6♠ 5♥ 4♦ 3♣ 2♠").unwrap()) > wheel);
+    }
+
+    #[test]
+    fn ace_to_five__nuts() {
+        assert_eq!(1, ace_to_five(Five::try_from("5♠ 4♥ 3♦ 2♣ A♠").unwrap()));
+        assert_eq!(1, ace_to_five(Five::try_from("5♠ 4♠ 3♠ 2♠ A♠").unwrap()));
+        assert_eq!(0, ace_to_five(Five::default()));
+    }
+
+    #[rstest]
+    #[case("5♠ 4♥ 3♦ 2♣ A♠", "6♠ 4♥ 3♦ 2♣ A♠")]
+    #[case("6♠ 5♥ 4♦ 2♣ A♠", "6♠ 5♥ 4♦ 3♣ 2♠")]
+    #[case("K♠ Q♥ J♦ T♣ 9♠", "A♠ A♥ 3♦ 2♣ 4♠")]
+    #[case("A♠ A♥ 4♦ 3♣ 2♠", "A♠ A♥ 5♦ 3♣ 2♠")]
+    #[case("2♠ 2♥ K♦ Q♣ J♠", "A♠ A♥ 2♦ 2♣ 3♠")]
+    #[case("A♠ A♥ 2♦ 2♣ 3♠", "A♠ A♥ A♦ 2♣ 3♠")]
+    #[case("K♠ K♥ K♦ Q♣ Q♠", "2♠ 2♥ 2♦ 2♣ 3♠")]
+    fn ace_to_five__beats(#[case] better: &'static str, #[case] worse: &'static str) {
+        let better = ace_to_five(Five::try_from(better).unwrap());
+        let worse = ace_to_five(Five::try_from(worse).unwrap());
+
+        assert!(better < worse);
+    }
+
+    #[rstest]
+    #[case("4♠ 3♥ 2♦ A♣", Aces::Low, 4)]
+    #[case("4♠ 3♥ 2♦ A♣", Aces::High, 4)]
+    #[case("4♠ 3♠ 2♦ A♣", Aces::Low, 3)]
+    #[case("4♠ 4♥ 2♦ 2♣", Aces::Low, 2)]
+    #[case("4♠ 3♠ 2♠ A♠", Aces::Low, 1)]
+    fn badugi__cards(#[case] index: &'static str, #[case] aces: Aces, #[case] expected: usize) {
+        assert_eq!(expected, badugi(Four::try_from(index).unwrap(), aces).cards());
+    }
+
+    #[rstest]
+    #[case("4♠ 3♥ 2♦ A♣", "5♠ 3♥ 2♦ A♣", Aces::Low)]
+    #[case("5♠ 4♥ 3♦ 2♣", "4♠ 3♥ 2♦ A♣", Aces::High)]
+    #[case("4♠ 3♥ 2♦ A♣", "5♠ 4♥ 3♦ 2♣", Aces::Low)]
+    #[case("K♠ Q♥ J♦ T♣", "2♠ 3♠ 4♦ 5♣", Aces::Low)]
+    #[case("3♠ 2♥ A♦ A♣", "4♠ 3♥ 2♦ 2♣", Aces::Low)]
+    #[case("2♠ 3♥ 4♦ 5♠", "2♠ 3♥ 6♦ 7♠", Aces::Low)]
+    fn badugi__beats(#[case] better: &'static str, #[case] worse: &'static str, #[case] aces: Aces) {
+        let better = badugi(Four::try_from(better).unwrap(), aces);
+        let worse = badugi(Four::try_from(worse).unwrap(), aces);
+
+        assert!(better < worse);
+    }
+
+    #[test]
+    fn badugi__invalid() {
+        assert!(!badugi(Four::default(), Aces::Low).is_valid());
+        assert!(!badugi(Four::try_from("4♠ 4♠ 2♦ A♣").unwrap(), Aces::Low).is_valid());
+        assert_eq!(0, BadugiRank::default().cards());
+    }
+
+    #[test]
+    fn badeucey() {
+        // Seven low and a seven badugi with the three of clubs left out.
+        let rank = super::badeucey(Five::try_from("7♠ 5♥ 4♦ 3♣ 2♣").unwrap());
+
+        assert_eq!(1, rank.low);
+        assert_eq!(4, rank.badugi.cards());
+        assert_eq!(badugi(Four::try_from("7♠ 5♥ 4♦ 2♣").unwrap(), Aces::High), rank.badugi);
+    }
+
+    #[test]
+    fn badacey() {
+        let rank = super::badacey(Five::try_from("5♠ 4♥ 3♦ 2♣ A♣").unwrap());
+
+        assert_eq!(1, rank.low);
+        assert_eq!(badugi(Four::try_from("4♥ 3♦ 5♠ A♣").unwrap(), Aces::Low), rank.badugi);
+    }
+}
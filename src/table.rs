@@ -0,0 +1,281 @@
+//! Seats at a poker table: the `Position` of each one, and the stakes of the game.
+
+use crate::showdown::Chips;
+use crate::HandError;
+
+/// A seat's position in a hand, in the order that they act preflop. Tables with fewer than nine
+/// seats drop positions from the front, so six max starts at the lojack. Heads up, the button
+/// posts the small blind and there's no `SmallBlind`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Position {
+    UnderTheGun,
+    UnderTheGun1,
+    UnderTheGun2,
+    Lojack,
+    Hijack,
+    Cutoff,
+    Button,
+    SmallBlind,
+    BigBlind,
+}
+
+impl Position {
+    pub const COUNT: usize = 9;
+
+    /// Every `Position` on a full ring table, from first to act preflop to last.
+    pub const ALL: [Position; Position::COUNT] = [
+        Position::UnderTheGun,
+        Position::UnderTheGun1,
+        Position::UnderTheGun2,
+        Position::Lojack,
+        Position::Hijack,
+        Position::Cutoff,
+        Position::Button,
+        Position::SmallBlind,
+        Position::BigBlind,
+    ];
+
+    /// The positions at a six max table, from first to act preflop to last.
+    pub const SIX_MAX: [Position; 6] = [
+        Position::Lojack,
+        Position::Hijack,
+        Position::Cutoff,
+        Position::Button,
+        Position::SmallBlind,
+        Position::BigBlind,
+    ];
+
+    /// The positions heads up, where the button is also the small blind.
+    pub const HEADS_UP: [Position; 2] = [Position::Button, Position::BigBlind];
+
+    /// Returns the positions at a table with the number of seats, from first to act preflop to
+    /// last, or `None` for fewer than two seats or more than nine.
+    #[must_use]
+    pub fn for_seats(seats: usize) -> Option<&'static [Position]> {
+        match seats {
+            2 => Some(&Position::HEADS_UP),
+            3..=Position::COUNT => Some(&Position::ALL[Position::COUNT - seats..]),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    #[must_use]
+    pub fn is_blind(self) -> bool {
+        matches!(self, Position::SmallBlind | Position::BigBlind)
+    }
+}
+
+/// The forced bets that start each hand.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Blinds {
+    pub small: Chips,
+    pub big: Chips,
+}
+
+impl Blinds {
+    #[must_use]
+    pub fn new(small: Chips, big: Chips) -> Self {
+        Blinds { small, big }
+    }
+}
+
+/// The number of seats at a table and the stakes, shared by anything that needs to know who
+/// sits where, such as push/fold charts or realization by `Position`.
+///
+/// Seats are numbered from 0 clockwise, and every method that takes the button's seat wraps it
+/// around the table.
+///
+/// ```
+/// use ckc_rs::table::{Blinds, Position, TableConfig};
+///
+/// let table = TableConfig::six_max(Blinds::new(1, 2)).with_ante(1);
+/// let preflop: Vec<(usize, Position)> = table.preflop_order(4).collect();
+///
+/// assert_eq!((1, Position::Lojack), preflop[0]);
+/// assert_eq!((0, Position::BigBlind), preflop[5]);
+/// assert_eq!(9, table.starting_pot());
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TableConfig {
+    seats: usize,
+    pub blinds: Blinds,
+    /// Posted by every seat.
+    pub ante: Chips,
+}
+
+impl TableConfig {
+    /// # Errors
+    ///
+    /// Returns `HandError::InvalidSeatCount` for fewer than two seats or more than nine, since
+    /// `Position` only covers a full ring table.
+    pub fn new(seats: usize, blinds: Blinds) -> Result<Self, HandError> {
+        match seats {
+            2..=Position::COUNT => Ok(TableConfig { seats, blinds, ante: 0 }),
+            _ => Err(HandError::InvalidSeatCount),
+        }
+    }
+
+    #[must_use]
+    pub fn heads_up(blinds: Blinds) -> Self {
+        TableConfig {
+            seats: 2,
+            blinds,
+            ante: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn six_max(blinds: Blinds) -> Self {
+        TableConfig {
+            seats: 6,
+            blinds,
+            ante: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn full_ring(blinds: Blinds) -> Self {
+        TableConfig {
+            seats: Position::COUNT,
+            blinds,
+            ante: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn with_ante(mut self, ante: Chips) -> Self {
+        self.ante = ante;
+        self
+    }
+
+    /// The number of seats, from two to nine.
+    #[must_use]
+    pub fn seats(&self) -> usize {
+        self.seats
+    }
+
+    /// The positions at the table, from first to act preflop to last.
+    #[must_use]
+    pub fn positions(&self) -> &'static [Position] {
+        Position::for_seats(self.seats).unwrap_or(&[])
+    }
+
+    /// The chips in the pot before anyone acts: the blinds and every seat's ante.
+    #[must_use]
+    pub fn starting_pot(&self) -> Chips {
+        self.blinds.small + self.blinds.big + self.ante * self.seats as Chips
+    }
+
+    /// Returns the `Position` of the seat, or `None` if there's no such seat.
+    #[must_use]
+    pub fn position_of(&self, seat: usize, button: usize) -> Option<Position> {
+        if seat >= self.seats {
+            return None;
+        }
+        let first = self.first_to_act(button);
+        self.positions().get((seat + self.seats - first) % self.seats).copied()
+    }
+
+    /// Every seat and its `Position`, in the order that they act preflop.
+    pub fn preflop_order(&self, button: usize) -> impl Iterator<Item = (usize, Position)> {
+        let seats = self.seats;
+        let first = self.first_to_act(button);
+        self.positions()
+            .iter()
+            .enumerate()
+            .map(move |(i, position)| ((first + i) % seats, *position))
+    }
+
+    /// Every seat and its `Position`, in the order that they act after the flop, starting from
+    /// the first seat after the button.
+    pub fn postflop_order(&self, button: usize) -> impl Iterator<Item = (usize, Position)> {
+        let table = *self;
+        (1..=self.seats).filter_map(move |i| {
+            let seat = (button + i) % table.seats;
+            table.position_of(seat, button).map(|position| (seat, position))
+        })
+    }
+
+    /// The seat that acts first preflop: the button heads up, and otherwise the seat after the
+    /// big blind.
+    fn first_to_act(&self, button: usize) -> usize {
+        match self.seats {
+            0 => 0,
+            2 => button % 2,
+            seats => (button + 3) % seats,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod table_tests {
+    use super::*;
+
+    #[test]
+    fn all() {
+        for (i, position) in Position::ALL.iter().enumerate() {
+            assert_eq!(i, position.index());
+        }
+        assert_eq!(2, Position::ALL.iter().filter(|position| position.is_blind()).count());
+    }
+
+    #[test]
+    fn for_seats() {
+        assert_eq!(Some(&Position::SIX_MAX[..]), Position::for_seats(6));
+        assert_eq!(Some(&Position::ALL[..]), Position::for_seats(9));
+        assert_eq!(
+            Some(&[Position::Button, Position::SmallBlind, Position::BigBlind][..]),
+            Position::for_seats(3)
+        );
+        assert_eq!(Some(&Position::HEADS_UP[..]), Position::for_seats(2));
+        assert_eq!(None, Position::for_seats(1));
+        assert_eq!(None, Position::for_seats(10));
+    }
+
+    #[test]
+    fn table_config__new() {
+        let blinds = Blinds::new(50, 100);
+
+        assert_eq!(TableConfig::six_max(blinds), TableConfig::new(6, blinds).unwrap());
+        assert_eq!(HandError::InvalidSeatCount, TableConfig::new(1, blinds).unwrap_err());
+        assert_eq!(HandError::InvalidSeatCount, TableConfig::new(10, blinds).unwrap_err());
+        assert_eq!(6, TableConfig::new(6, blinds).unwrap().seats());
+    }
+
+    #[test]
+    fn table_config__orders() {
+        let table = TableConfig::full_ring(Blinds::new(1, 2));
+
+        let preflop: Vec<(usize, Position)> = table.preflop_order(7).collect();
+        let postflop: Vec<(usize, Position)> = table.postflop_order(7).collect();
+
+        assert_eq!(9, preflop.len());
+        assert_eq!((1, Position::UnderTheGun), preflop[0]);
+        assert_eq!((7, Position::Button), preflop[6]);
+        assert_eq!((0, Position::BigBlind), preflop[8]);
+        assert_eq!((8, Position::SmallBlind), postflop[0]);
+        assert_eq!((7, Position::Button), postflop[8]);
+        for (seat, position) in preflop {
+            assert_eq!(Some(position), table.position_of(seat, 7));
+        }
+        assert_eq!(None, table.position_of(9, 7));
+    }
+
+    #[test]
+    fn table_config__heads_up() {
+        let table = TableConfig::heads_up(Blinds::new(1, 2));
+
+        let preflop: Vec<(usize, Position)> = table.preflop_order(1).collect();
+        let postflop: Vec<(usize, Position)> = table.postflop_order(1).collect();
+
+        assert_eq!(alloc::vec![(1, Position::Button), (0, Position::BigBlind)], preflop);
+        assert_eq!(alloc::vec![(0, Position::BigBlind), (1, Position::Button)], postflop);
+        assert_eq!(3, table.starting_pot());
+    }
+}